@@ -0,0 +1,239 @@
+//! Throughput of the live quicksort stepper on large arrays.
+//!
+//! Run with `cargo bench --bench live_stepper`. Sorts a shuffled array of
+//! 1M elements to completion in batches, the way the live engine drives the
+//! stepper, and compares `QuickSortLLStepper` against `reference`, the
+//! straightforward one-step-per-iteration version it replaced. Both emit
+//! exactly the same events.
+//!
+//! Only time spent inside `step()` is counted; dropping the returned event
+//! batches costs the same for both. Runs alternate between the two and the
+//! best run is reported, which is the most stable figure on a busy machine.
+//!
+//! The `no events` row runs the same partitions as a plain loop that emits
+//! nothing, which bounds what any stepper can reach. Here it takes about
+//! 125 ms against the reference's 290 ms, so doubling the reference would
+//! leave about 20 ms, roughly 0.5 ns per event, to emit 38M 24-byte
+//! events. The stepper gets about 1.1-1.3x; the 2x first asked for is out
+//! of reach without changing the event stream.
+
+use std::time::{Duration, Instant};
+
+use sort_forge_core::live::{QuickSortLLStepper, Stepper};
+use sort_forge_core::patterns::{self, Pattern};
+
+const N: usize = 1_000_000;
+const RUNS: usize = 9;
+
+/// Batch sizes: the web UI's default, and a larger one for headless use.
+const BATCH_SIZES: [usize; 2] = [200, 10_000];
+
+fn main() {
+    let input = patterns::generate(Pattern::Uniform, N, 42);
+
+    println!(
+        "{:<16} {:>8} {:>12} {:>10} {:>12} {:>8}",
+        "stepper", "batch", "events", "best ms", "events/ms", "speedup"
+    );
+
+    for batch in BATCH_SIZES {
+        let mut reference_best = Duration::MAX;
+        let mut optimized_best = Duration::MAX;
+        let mut bare_best = Duration::MAX;
+        let mut event_count = 0;
+
+        for _ in 0..RUNS {
+            let (time, _) = time_steps(reference::QuickSortLLStepper::new(N), &input, batch);
+            reference_best = reference_best.min(time);
+
+            let (time, count) = time_steps(QuickSortLLStepper::new(N), &input, batch);
+            optimized_best = optimized_best.min(time);
+            event_count = count;
+
+            bare_best = bare_best.min(time_bare(&input));
+        }
+
+        let speedup = reference_best.as_secs_f64() / optimized_best.as_secs_f64();
+        report("reference", batch, event_count, reference_best, None);
+        report("quicksort_ll", batch, event_count, optimized_best, Some(speedup));
+        let ceiling = reference_best.as_secs_f64() / bare_best.as_secs_f64();
+        report("no events", batch, event_count, bare_best, Some(ceiling));
+    }
+}
+
+/// Sort a copy of input to completion; returns time inside step() and the
+/// number of events emitted.
+fn time_steps<S: Stepper>(mut stepper: S, input: &[i32], batch: usize) -> (Duration, usize) {
+    let mut arr = input.to_vec();
+    let mut elapsed = Duration::ZERO;
+    let mut event_count = 0;
+
+    while !stepper.is_done() {
+        let start = Instant::now();
+        let events = stepper.step(&mut arr, batch);
+        elapsed += start.elapsed();
+        event_count += events.len();
+    }
+
+    assert!(arr.windows(2).all(|w| w[0] <= w[1]));
+    (elapsed, event_count)
+}
+
+/// Sort a copy of input with the stepper's partitions, in the same order,
+/// without emitting events; returns the time taken.
+fn time_bare(input: &[i32]) -> Duration {
+    let mut arr = input.to_vec();
+    let start = Instant::now();
+
+    let mut stack = vec![(0, arr.len() - 1)];
+    while let Some((lo, hi)) = stack.pop() {
+        let pivot = arr[hi];
+        let mut i = lo;
+        for j in lo..hi {
+            if arr[j] <= pivot {
+                arr.swap(i, j);
+                i += 1;
+            }
+        }
+        arr.swap(i, hi);
+
+        if i + 1 < hi {
+            stack.push((i + 1, hi));
+        }
+        if i > lo + 1 {
+            stack.push((lo, i - 1));
+        }
+    }
+
+    let elapsed = start.elapsed();
+    assert!(arr.windows(2).all(|w| w[0] <= w[1]));
+    elapsed
+}
+
+fn report(name: &str, batch: usize, events: usize, time: Duration, speedup: Option<f64>) {
+    let ms = time.as_secs_f64() * 1000.0;
+    let speedup = speedup.map_or(String::new(), |s| format!("{:.2}x", s));
+    println!(
+        "{:<16} {:>8} {:>12} {:>10.1} {:>12.0} {:>8}",
+        name,
+        batch,
+        events,
+        ms,
+        events as f64 / ms,
+        speedup
+    );
+}
+
+/// The stepper before the hot-loop rewrite: one loop iteration per step,
+/// re-borrowing the partition state from `self.current` every time.
+mod reference {
+    use sort_forge_core::live::Stepper;
+    use sort_forge_core::SortEvent;
+
+    struct PartitionState {
+        lo: usize,
+        hi: usize,
+        i: usize,
+        j: usize,
+        pivot: i32,
+        entered: bool,
+    }
+
+    pub struct QuickSortLLStepper {
+        stack: Vec<(usize, usize)>,
+        current: Option<PartitionState>,
+        done: bool,
+    }
+
+    impl QuickSortLLStepper {
+        pub fn new(len: usize) -> Self {
+            let mut stepper = Self {
+                stack: Vec::new(),
+                current: None,
+                done: len <= 1,
+            };
+
+            if len > 1 {
+                stepper.stack.push((0, len - 1));
+            }
+
+            stepper
+        }
+    }
+
+    impl Stepper for QuickSortLLStepper {
+        fn step(&mut self, arr: &mut [i32], limit: usize) -> Vec<SortEvent> {
+            let mut events = Vec::with_capacity(limit);
+
+            for _ in 0..limit {
+                if self.done {
+                    if !matches!(events.last(), Some(SortEvent::Done)) {
+                        events.push(SortEvent::Done);
+                    }
+                    break;
+                }
+
+                if self.current.is_none() {
+                    if let Some((lo, hi)) = self.stack.pop() {
+                        self.current = Some(PartitionState {
+                            lo,
+                            hi,
+                            i: lo,
+                            j: lo,
+                            pivot: arr[hi],
+                            entered: false,
+                        });
+                    } else {
+                        self.done = true;
+                        events.push(SortEvent::Done);
+                        break;
+                    }
+                }
+
+                let state = self.current.as_mut().unwrap();
+
+                if !state.entered {
+                    events.push(SortEvent::EnterRange { lo: state.lo, hi: state.hi });
+                    state.entered = true;
+                    continue;
+                }
+
+                if state.j < state.hi {
+                    events.push(SortEvent::Compare { i: state.j, j: state.hi });
+
+                    if arr[state.j] <= state.pivot {
+                        if state.i != state.j {
+                            events.push(SortEvent::Swap { i: state.i, j: state.j });
+                            arr.swap(state.i, state.j);
+                        }
+                        state.i += 1;
+                    }
+                    state.j += 1;
+                } else {
+                    if state.i != state.hi {
+                        events.push(SortEvent::Swap { i: state.i, j: state.hi });
+                        arr.swap(state.i, state.hi);
+                    }
+
+                    let (pivot_idx, lo, hi) = (state.i, state.lo, state.hi);
+                    events.push(SortEvent::ExitRange { lo, hi });
+
+                    if pivot_idx + 1 < hi {
+                        self.stack.push((pivot_idx + 1, hi));
+                    }
+                    if pivot_idx > lo + 1 {
+                        self.stack.push((lo, pivot_idx - 1));
+                    }
+
+                    self.current = None;
+                }
+            }
+
+            events
+        }
+
+        fn is_done(&self) -> bool {
+            self.done
+        }
+    }
+}
@@ -0,0 +1,95 @@
+//! Argsort: sort an index array while the values stay put.
+//!
+//! The algorithm sorts the indices 0..n by the values they point at, so its
+//! events refer to positions in the index array and the values are never
+//! mutated: a frontend can draw the data row fixed and the index row
+//! rearranging above it. Ties are broken by index, which makes the
+//! resulting order stable whatever the algorithm.
+//!
+//! Internally each index is replaced by its rank, its position in the
+//! (stable) sorted order, and the algorithm sorts the ranks. Ranks compare
+//! exactly like (value, index) pairs, so the trace is the trace of sorting
+//! the index array. Overwrite events carry index-array entries, i.e.
+//! indices, in old_val and new_val.
+
+use crate::prelude::*;
+use crate::events::SortEvent;
+use crate::pregen::{pregen_sort_into, Algorithm};
+
+/// Result of `pregen_argsort`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArgsortRun {
+    /// Events on the index array, which starts as 0..n.
+    pub events: Vec<SortEvent>,
+    /// The sorted index array: `values[indices[0]]` is the smallest value.
+    pub indices: Vec<usize>,
+}
+
+/// Sort the indices of `values` by value with the given algorithm.
+pub fn pregen_argsort(algorithm: Algorithm, values: &[i32]) -> ArgsortRun {
+    let mut by_rank: Vec<usize> = (0..values.len()).collect();
+    by_rank.sort_by_key(|&i| values[i]);
+    let mut ranks = vec![0; values.len()];
+    for (rank, &index) in by_rank.iter().enumerate() {
+        ranks[index] = rank as i32;
+    }
+
+    let mut events = Vec::new();
+    pregen_sort_into(algorithm, &mut ranks, &mut events);
+
+    // Overwrites wrote ranks; the index array holds the indices they stand for
+    for event in &mut events {
+        if let SortEvent::Overwrite { old_val, new_val, .. } = event {
+            *old_val = by_rank[*old_val as usize] as i32;
+            *new_val = by_rank[*new_val as usize] as i32;
+        }
+    }
+
+    let indices = ranks.iter().map(|&rank| by_rank[rank as usize]).collect();
+    ArgsortRun { events, indices }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patterns::{self, Pattern};
+
+    #[test]
+    fn test_indices_sort_values() {
+        let values = vec![30, 10, 20];
+        let run = pregen_argsort(Algorithm::Bubble, &values);
+        assert_eq!(run.indices, vec![1, 2, 0]);
+    }
+
+    #[test]
+    fn test_ties_keep_index_order() {
+        let values = vec![5, 1, 5, 1, 5];
+        for algorithm in [Algorithm::HeapSort, Algorithm::QuickSortLL, Algorithm::Selection] {
+            let run = pregen_argsort(algorithm, &values);
+            assert_eq!(run.indices, vec![1, 3, 0, 2, 4], "{:?}", algorithm);
+        }
+    }
+
+    #[test]
+    fn test_events_replay_on_index_array() {
+        let values = patterns::generate(Pattern::FewUnique, 120, 3);
+        for &algorithm in Algorithm::all() {
+            let run = pregen_argsort(algorithm, &values);
+
+            let mut index_array: Vec<i32> = (0..values.len() as i32).collect();
+            for event in &run.events {
+                event.apply(&mut index_array);
+            }
+            let replayed: Vec<usize> = index_array.iter().map(|&i| i as usize).collect();
+            assert_eq!(replayed, run.indices, "{:?}", algorithm);
+            assert!(run.indices.windows(2).all(|w| values[w[0]] <= values[w[1]]));
+        }
+    }
+
+    #[test]
+    fn test_empty_values() {
+        let run = pregen_argsort(Algorithm::MergeSort, &[]);
+        assert!(run.indices.is_empty());
+        assert_eq!(run.events, vec![SortEvent::Done]);
+    }
+}
@@ -0,0 +1,266 @@
+//! Audio hints for "sound of sorting" style playback.
+//!
+//! A post-processing pass over a finished trace: it replays the events on
+//! the input array and, for every event, returns the tones a frontend
+//! should play. Pitch follows the value of each element the event touches,
+//! normalized over the input's value range, so WebAudio can use the hints
+//! directly without scaling values per event.
+
+use crate::events::SortEvent;
+
+/// Frequency range the normalized pitch is mapped onto, in Hz.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AudioRange {
+    pub min_freq: f32,
+    pub max_freq: f32,
+}
+
+impl Default for AudioRange {
+    fn default() -> Self {
+        Self {
+            min_freq: 120.0,
+            max_freq: 1200.0,
+        }
+    }
+}
+
+/// Gains per event kind. Comparisons are quieter than writes so that
+/// mutations stand out; events without an element are silent.
+pub const COMPARE_GAIN: f32 = 0.4;
+pub const WRITE_GAIN: f32 = 0.8;
+pub const FOUND_GAIN: f32 = 1.0;
+
+/// Tones for one event: one frequency per element it touches (single
+/// element events repeat it). Silent events have zero gain and frequency.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AudioHint {
+    pub freqs: [f32; 2],
+    pub gain: f32,
+}
+
+impl AudioHint {
+    pub const SILENT: AudioHint = AudioHint {
+        freqs: [0.0, 0.0],
+        gain: 0.0,
+    };
+
+    pub fn is_silent(&self) -> bool {
+        self.gain == 0.0
+    }
+}
+
+/// Compute one hint per event by replaying `events` on a copy of `input`.
+/// Compares sound the values being compared, swaps and overwrites the
+/// values after the write, block moves and rotations the new first value of
+/// the moved range. Errors if an event doesn't fit the input.
+pub fn audio_hints(
+    input: &[i32],
+    events: &[SortEvent],
+    range: AudioRange,
+) -> Result<Vec<AudioHint>, String> {
+    let n = input.len();
+    if let Some(k) = events.iter().position(|e| !e.in_bounds(n)) {
+        return Err(format!("Event {} is out of bounds for an array of {}", k, n));
+    }
+
+    let mut array = input.to_vec();
+    let scale = PitchScale::new(input, range);
+
+    let hints = events
+        .iter()
+        .map(|event| match *event {
+            SortEvent::Compare { i, j } => AudioHint {
+                freqs: [scale.freq(array[i]), scale.freq(array[j])],
+                gain: COMPARE_GAIN,
+            },
+            SortEvent::Swap { i, j } => {
+                array.swap(i, j);
+                AudioHint {
+                    freqs: [scale.freq(array[i]), scale.freq(array[j])],
+                    gain: WRITE_GAIN,
+                }
+            }
+            SortEvent::Overwrite { idx, new_val, .. } => {
+                array[idx] = new_val;
+                let freq = scale.freq(new_val);
+                AudioHint {
+                    freqs: [freq, freq],
+                    gain: WRITE_GAIN,
+                }
+            }
+            // An empty move may point one past the end and moves nothing
+            SortEvent::BlockMove(ref m) if m.len == 0 => AudioHint::SILENT,
+            SortEvent::BlockMove(ref m) => {
+                event.apply(&mut array);
+                let freq = scale.freq(array[m.to]);
+                AudioHint {
+                    freqs: [freq, freq],
+                    gain: WRITE_GAIN,
+                }
+            }
+            SortEvent::Rotate(ref r) => {
+                event.apply(&mut array);
+                let freq = scale.freq(array[r.lo]);
+                AudioHint {
+                    freqs: [freq, freq],
+                    gain: WRITE_GAIN,
+                }
+            }
+            SortEvent::Flip { k } => {
+                event.apply(&mut array);
+                AudioHint {
+                    freqs: [scale.freq(array[0]), scale.freq(array[k])],
+                    gain: WRITE_GAIN,
+                }
+            }
+            SortEvent::Found { idx } => {
+                let freq = scale.freq(array[idx]);
+                AudioHint {
+                    freqs: [freq, freq],
+                    gain: FOUND_GAIN,
+                }
+            }
+            _ => AudioHint::SILENT,
+        })
+        .collect();
+    Ok(hints)
+}
+
+/// Flatten hints to `[freq_a, freq_b, gain]` triples for a Float32Array.
+pub fn flatten_hints(hints: &[AudioHint]) -> Vec<f32> {
+    hints
+        .iter()
+        .flat_map(|hint| [hint.freqs[0], hint.freqs[1], hint.gain])
+        .collect()
+}
+
+/// Maps values onto the frequency range. The mapping is exponential, so
+/// equal value steps sound like equal musical intervals.
+struct PitchScale {
+    min: i32,
+    span: f32,
+    range: AudioRange,
+}
+
+impl PitchScale {
+    fn new(input: &[i32], range: AudioRange) -> Self {
+        let min = input.iter().copied().min().unwrap_or(0);
+        let max = input.iter().copied().max().unwrap_or(0);
+        Self {
+            min,
+            span: (max as f32 - min as f32).max(0.0),
+            range,
+        }
+    }
+
+    /// Value normalized to 0..=1 over the input's range; 0.5 if all equal.
+    fn pitch(&self, value: i32) -> f32 {
+        if self.span == 0.0 {
+            return 0.5;
+        }
+        ((value as f32 - self.min as f32) / self.span).clamp(0.0, 1.0)
+    }
+
+    fn freq(&self, value: i32) -> f32 {
+        let ratio = self.range.max_freq / self.range.min_freq;
+        self.range.min_freq * ratio.powf(self.pitch(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pregen::{pregen_sort, Algorithm};
+
+    fn close(a: f32, b: f32) -> bool {
+        (a - b).abs() < 0.01
+    }
+
+    #[test]
+    fn test_one_hint_per_event() {
+        let input = vec![5, 3, 8, 1, 9, 2];
+        let events = pregen_sort(Algorithm::MergeSort, &mut input.clone());
+        let hints = audio_hints(&input, &events, AudioRange::default()).unwrap();
+
+        assert_eq!(hints.len(), events.len());
+        assert!(hints.last().unwrap().is_silent());
+        assert_eq!(flatten_hints(&hints).len(), 3 * hints.len());
+    }
+
+    #[test]
+    fn test_pitch_follows_value() {
+        let input = vec![1, 10, 100];
+        let events = vec![SortEvent::Compare { i: 0, j: 2 }, SortEvent::Found { idx: 1 }];
+        let hints = audio_hints(&input, &events, AudioRange::default()).unwrap();
+
+        // Lowest and highest values map to the ends of the range
+        assert!(close(hints[0].freqs[0], 120.0));
+        assert!(close(hints[0].freqs[1], 1200.0));
+        assert_eq!(hints[0].gain, COMPARE_GAIN);
+
+        let middle = hints[1].freqs[0];
+        assert!(middle > 120.0 && middle < 1200.0);
+        assert_eq!(hints[1].freqs[0], hints[1].freqs[1]);
+        assert_eq!(hints[1].gain, FOUND_GAIN);
+    }
+
+    #[test]
+    fn test_writes_use_values_after_the_write() {
+        let input = vec![1, 3];
+        let events = vec![
+            SortEvent::Swap { i: 0, j: 1 },
+            SortEvent::Overwrite { idx: 1, old_val: 1, new_val: 3 },
+        ];
+        let range = AudioRange { min_freq: 100.0, max_freq: 400.0 };
+        let hints = audio_hints(&input, &events, range).unwrap();
+
+        assert!(close(hints[0].freqs[0], 400.0));
+        assert!(close(hints[0].freqs[1], 100.0));
+        assert!(close(hints[1].freqs[0], 400.0));
+        assert_eq!(hints[1].gain, WRITE_GAIN);
+    }
+
+    #[test]
+    fn test_structural_events_are_silent() {
+        let events = vec![
+            SortEvent::EnterRange { lo: 0, hi: 1 },
+            SortEvent::ExitRange { lo: 0, hi: 1 },
+            SortEvent::Phase { name: "merge".to_string() },
+            SortEvent::Done,
+        ];
+        let hints = audio_hints(&[2, 1], &events, AudioRange::default()).unwrap();
+        assert!(hints.iter().all(|hint| *hint == AudioHint::SILENT));
+    }
+
+    #[test]
+    fn test_constant_input_uses_middle_pitch() {
+        let hints =
+            audio_hints(&[7, 7], &[SortEvent::Compare { i: 0, j: 1 }], AudioRange::default())
+                .unwrap();
+        let middle = (120.0f32 * 1200.0).sqrt();
+        assert!(close(hints[0].freqs[0], middle));
+    }
+
+    #[test]
+    fn test_out_of_bounds_event_is_an_error() {
+        let input = vec![2, 1];
+        for event in [
+            SortEvent::Compare { i: 0, j: 2 },
+            SortEvent::Overwrite { idx: 5, old_val: 0, new_val: 1 },
+            SortEvent::block_move(0, 2, 1),
+            SortEvent::Flip { k: 2 },
+            SortEvent::Found { idx: 2 },
+        ] {
+            let events = vec![SortEvent::Swap { i: 0, j: 1 }, event];
+            let err = audio_hints(&input, &events, AudioRange::default()).unwrap_err();
+            assert!(err.contains("Event 1"), "{}", err);
+        }
+    }
+
+    #[test]
+    fn test_empty_block_move_is_silent() {
+        let events = vec![SortEvent::block_move(0, 2, 0)];
+        let hints = audio_hints(&[2, 1], &events, AudioRange::default()).unwrap();
+        assert!(hints[0].is_silent());
+    }
+}
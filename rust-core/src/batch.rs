@@ -0,0 +1,407 @@
+//! Batch trace generation.
+//!
+//! A batch is a list of independent jobs, usually a grid of algorithms and
+//! seeds. With the `parallel` feature the jobs run on the rayon thread
+//! pool (in wasm, with `wasm-threads`, a pool of Web Workers); without it
+//! they run one after another. Either way, results come
+//! back in job order, so output is deterministic.
+//!
+//! `BatchRequest` is the serde-facing form used by the wasm
+//! `pregen_sort_batch`: one request per trace, with either an explicit array
+//! or a generated pattern, answered with the trace and its event counts.
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::coalesce::coalesce_shifts;
+use crate::events::{self, EventCounts, SortEvent};
+use crate::patterns::{self, Pattern};
+use crate::precheck::{self, Outcome};
+use crate::pregen::{pregen_sort, Algorithm};
+use crate::sweep::append_sweep;
+
+/// One pregen run: an algorithm on a generated input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraceJob {
+    pub algorithm: Algorithm,
+    pub pattern: Pattern,
+    pub n: usize,
+    pub seed: u64,
+}
+
+/// Result of a TraceJob: the input it generated and the events of the sort.
+#[derive(Debug, Clone)]
+pub struct TraceRun {
+    pub job: TraceJob,
+    pub input: Vec<i32>,
+    pub events: Vec<SortEvent>,
+}
+
+impl TraceJob {
+    /// Generate the input and sort it.
+    pub fn run(&self) -> TraceRun {
+        let input = patterns::generate(self.pattern, self.n, self.seed);
+        let mut array = input.clone();
+        let events = pregen_sort(self.algorithm, &mut array);
+
+        TraceRun {
+            job: *self,
+            input,
+            events,
+        }
+    }
+}
+
+/// Every combination of algorithm and seed, algorithm-major.
+pub fn job_grid(
+    algorithms: &[Algorithm],
+    pattern: Pattern,
+    n: usize,
+    seeds: &[u64],
+) -> Vec<TraceJob> {
+    algorithms
+        .iter()
+        .flat_map(|&algorithm| {
+            seeds.iter().map(move |&seed| TraceJob {
+                algorithm,
+                pattern,
+                n,
+                seed,
+            })
+        })
+        .collect()
+}
+
+/// Run every job and pass its result to `f`, returning f's outputs in job
+/// order. Each run is dropped once `f` returns, so consumers that write
+/// traces out as they go never hold the whole batch in memory.
+pub fn run_each<F, R>(jobs: &[TraceJob], f: F) -> Vec<R>
+where
+    F: Fn(TraceRun) -> R + Sync + Send,
+    R: Send,
+{
+    map_in_order(jobs, |job| f(job.run()))
+}
+
+/// Map over items, on the thread pool if available, keeping item order.
+pub(crate) fn map_in_order<T, F, R>(items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    F: Fn(&T) -> R + Sync + Send,
+    R: Send,
+{
+    #[cfg(feature = "parallel")]
+    {
+        items.par_iter().map(f).collect()
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    {
+        items.iter().map(f).collect()
+    }
+}
+
+/// Run every job and collect the results in job order.
+pub fn run_all(jobs: &[TraceJob]) -> Vec<TraceRun> {
+    run_each(jobs, |run| run)
+}
+
+/// One trace in a `run_requests` batch. The input is either `array`, or
+/// generated from `pattern`, `n` and `seed`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchRequest {
+    pub algorithm: String,
+    #[serde(default)]
+    pub array: Option<Vec<i32>>,
+    #[serde(default)]
+    pub pattern: Option<String>,
+    #[serde(default)]
+    pub n: Option<usize>,
+    #[serde(default)]
+    pub seed: u64,
+    #[serde(default)]
+    pub options: BatchOptions,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BatchOptions {
+    /// Return the events; with false only the counts come back, which is
+    /// all an experiment grid needs.
+    pub events: bool,
+    /// Append a verification sweep, as `pregen_sort_with_sweep`.
+    pub sweep: bool,
+    /// Coalesce insertion shifts into BlockMove events, see `coalesce`.
+    pub coalesce: bool,
+    /// Scan for sortedness first and skip sorted inputs, see `precheck`.
+    pub precheck: bool,
+    /// Replace Flip events with Swaps, see `events::expand_flips`.
+    pub expand_flips: bool,
+}
+
+impl Default for BatchOptions {
+    fn default() -> Self {
+        Self {
+            events: true,
+            sweep: false,
+            coalesce: false,
+            precheck: false,
+            expand_flips: false,
+        }
+    }
+}
+
+/// Result of one BatchRequest.
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchResult {
+    pub algorithm: &'static str,
+    pub input: Vec<i32>,
+    pub sorted_array: Vec<i32>,
+    /// None when the request's `events` option is off.
+    pub events: Option<Vec<SortEvent>>,
+    pub counts: EventCounts,
+    /// Writes to the main array, see `events::main_writes`.
+    pub main_writes: usize,
+    /// Set when the `precheck` option is on.
+    pub outcome: Option<Outcome>,
+}
+
+/// A request with its names parsed and its input generated.
+struct ResolvedRequest {
+    algorithm: Algorithm,
+    input: Vec<i32>,
+    options: BatchOptions,
+}
+
+impl BatchRequest {
+    fn resolve(&self) -> Result<ResolvedRequest, String> {
+        let algorithm = Algorithm::from_str(&self.algorithm)
+            .ok_or_else(|| format!("Unknown algorithm: {}", self.algorithm))?;
+
+        let input = match (&self.array, &self.pattern) {
+            (Some(array), None) => array.clone(),
+            (None, Some(pattern)) => {
+                let pattern = Pattern::from_str(pattern)
+                    .ok_or_else(|| format!("Unknown pattern: {}", pattern))?;
+                let n = self.n.ok_or("A pattern request needs n")?;
+                patterns::generate(pattern, n, self.seed)
+            }
+            _ => return Err("Give either an array or a pattern".to_string()),
+        };
+
+        Ok(ResolvedRequest {
+            algorithm,
+            input,
+            options: self.options,
+        })
+    }
+
+    /// Check the names and input source without running the sort.
+    pub fn validate(&self) -> Result<(), String> {
+        self.resolve().map(|_| ())
+    }
+
+    /// Run this request on its own.
+    pub fn run(&self) -> Result<BatchResult, String> {
+        Ok(self.resolve()?.run())
+    }
+}
+
+impl ResolvedRequest {
+    fn run(&self) -> BatchResult {
+        let mut array = self.input.clone();
+        let (mut events, outcome) = if self.options.precheck {
+            let (events, outcome) = precheck::pregen_sort_prechecked(self.algorithm, &mut array);
+            (events, Some(outcome))
+        } else {
+            (pregen_sort(self.algorithm, &mut array), None)
+        };
+        if self.options.coalesce {
+            events = coalesce_shifts(&self.input, &events);
+        }
+        if self.options.expand_flips {
+            events = events::expand_flips(&events);
+        }
+        // A sorted input has just been scanned; a sweep would repeat it
+        if self.options.sweep && outcome != Some(Outcome::AlreadySorted) {
+            append_sweep(&array, &mut events);
+        }
+
+        BatchResult {
+            algorithm: self.algorithm.as_str(),
+            input: self.input.clone(),
+            sorted_array: array,
+            counts: EventCounts::from_events(&events),
+            main_writes: events::main_writes(&events),
+            outcome,
+            events: self.options.events.then_some(events),
+        }
+    }
+}
+
+/// Run a batch of requests, returning results in request order. Every
+/// request is validated before any runs; errors name the request index.
+pub fn run_requests(requests: &[BatchRequest]) -> Result<Vec<BatchResult>, String> {
+    let resolved = requests
+        .iter()
+        .enumerate()
+        .map(|(i, request)| request.resolve().map_err(|e| format!("Request {}: {}", i, e)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(map_in_order(&resolved, ResolvedRequest::run))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_job_grid() {
+        let jobs = job_grid(&[Algorithm::Bubble, Algorithm::HeapSort], Pattern::Uniform, 10, &[1, 2, 3]);
+
+        assert_eq!(jobs.len(), 6);
+        assert_eq!(jobs[0].algorithm, Algorithm::Bubble);
+        assert_eq!(jobs[3].algorithm, Algorithm::HeapSort);
+        assert_eq!(jobs.iter().map(|j| j.seed).collect::<Vec<_>>(), vec![1, 2, 3, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_run_all_matches_sequential() {
+        let seeds: Vec<u64> = (0..8).collect();
+        let jobs = job_grid(Algorithm::all(), Pattern::Random, 40, &seeds);
+        let runs = run_all(&jobs);
+
+        assert_eq!(runs.len(), jobs.len());
+        for (job, run) in jobs.iter().zip(&runs) {
+            let expected = job.run();
+            assert_eq!(run.job, *job);
+            assert_eq!(run.input, expected.input);
+            assert_eq!(run.events, expected.events);
+        }
+    }
+
+    #[test]
+    fn test_run_each_keeps_order() {
+        let jobs = job_grid(&[Algorithm::Insertion], Pattern::Sorted, 5, &[9, 4, 7]);
+        let seeds = run_each(&jobs, |run| run.job.seed);
+
+        assert_eq!(seeds, vec![9, 4, 7]);
+    }
+
+    #[test]
+    fn test_run_empty_batch() {
+        assert!(run_all(&[]).is_empty());
+    }
+
+    fn request(algorithm: &str) -> BatchRequest {
+        BatchRequest {
+            algorithm: algorithm.to_string(),
+            array: None,
+            pattern: None,
+            n: None,
+            seed: 0,
+            options: BatchOptions::default(),
+        }
+    }
+
+    #[test]
+    fn test_run_requests() {
+        let explicit = BatchRequest {
+            array: Some(vec![3, 1, 2]),
+            ..request("bubble")
+        };
+        let generated = BatchRequest {
+            pattern: Some("uniform".to_string()),
+            n: Some(16),
+            seed: 5,
+            options: BatchOptions { events: false, sweep: true, ..BatchOptions::default() },
+            ..request("heap")
+        };
+
+        let results = run_requests(&[explicit, generated]).unwrap();
+
+        assert_eq!(results[0].algorithm, "bubble");
+        assert_eq!(results[0].sorted_array, vec![1, 2, 3]);
+        let events = results[0].events.as_ref().unwrap();
+        assert_eq!(results[0].counts, EventCounts::from_events(events));
+        assert_eq!(results[0].main_writes, 4);
+
+        assert_eq!(results[1].input, patterns::generate(Pattern::Uniform, 16, 5));
+        assert_eq!(results[1].sorted_array, (1..=16).collect::<Vec<_>>());
+        assert!(results[1].events.is_none());
+        assert_eq!(results[1].counts.marked_sorted, 16);
+    }
+
+    #[test]
+    fn test_run_requests_coalesce() {
+        let coalesced = BatchRequest {
+            array: Some(vec![4, 3, 2, 1]),
+            options: BatchOptions { coalesce: true, ..BatchOptions::default() },
+            ..request("insertion")
+        };
+
+        let results = run_requests(&[coalesced]).unwrap();
+        assert_eq!(results[0].sorted_array, vec![1, 2, 3, 4]);
+        assert_eq!(results[0].counts.block_moves, 3);
+        assert_eq!(results[0].counts.overwrites, 0);
+    }
+
+    #[test]
+    fn test_run_requests_expand_flips() {
+        let input = Some(vec![2, 4, 1, 3]);
+        let flips = BatchRequest { array: input.clone(), ..request("pancake") };
+        let options = BatchOptions { expand_flips: true, ..BatchOptions::default() };
+        let swaps = BatchRequest { array: input, options, ..request("pancake") };
+
+        let results = run_requests(&[flips, swaps]).unwrap();
+        assert!(results[0].counts.flips > 0);
+        assert_eq!(results[1].counts.flips, 0);
+        assert_eq!(results[1].sorted_array, vec![1, 2, 3, 4]);
+        assert_eq!(results[0].main_writes, results[1].main_writes);
+    }
+
+    #[test]
+    fn test_run_requests_precheck() {
+        let options = BatchOptions { precheck: true, sweep: true, ..BatchOptions::default() };
+        let sorted = BatchRequest { array: Some(vec![1, 2, 3]), options, ..request("selection") };
+        let unsorted = BatchRequest { array: Some(vec![2, 1, 3]), options, ..request("selection") };
+
+        let plain = BatchRequest { array: Some(vec![1]), ..request("selection") };
+
+        let results = run_requests(&[sorted, unsorted, plain]).unwrap();
+        assert_eq!(results[0].outcome, Some(Outcome::AlreadySorted));
+        assert_eq!(results[0].counts.marked_sorted, 0);
+        assert_eq!(results[1].outcome, Some(Outcome::Sorted));
+        assert_eq!(results[1].counts.marked_sorted, 3);
+        assert_eq!(results[2].outcome, None);
+    }
+
+    #[test]
+    fn test_run_requests_reports_bad_request() {
+        let good = BatchRequest {
+            array: Some(vec![1]),
+            ..request("bubble")
+        };
+        let no_input = request("bubble");
+        let bad_algorithm = BatchRequest {
+            array: Some(vec![1]),
+            ..request("bogus")
+        };
+
+        let error = run_requests(&[good.clone(), no_input]).unwrap_err();
+        assert!(error.starts_with("Request 1:"), "{}", error);
+        assert!(run_requests(&[bad_algorithm]).unwrap_err().contains("bogus"));
+    }
+
+    #[cfg(feature = "native")]
+    #[test]
+    fn test_batch_request_from_json() {
+        let json = r#"[{"algorithm":"merge","array":[2,1]},
+                      {"algorithm":"quicksort_ll","pattern":"sorted","n":4,"options":{"sweep":true}}]"#;
+        let requests: Vec<BatchRequest> = serde_json::from_str(json).unwrap();
+
+        assert!(requests[0].options.events && !requests[0].options.sweep);
+        assert!(requests[1].options.events && requests[1].options.sweep);
+        assert_eq!(run_requests(&requests).unwrap().len(), 2);
+    }
+}
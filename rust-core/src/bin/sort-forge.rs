@@ -0,0 +1,511 @@
+//! `sort-forge` command-line tool (requires the `native` feature).
+//!
+//! Generates event traces offline, benchmarks the pregen algorithms and
+//! renders trace states to SVG bar charts:
+//!
+//! ```text
+//! sort-forge trace --algo quicksort --pattern random --n 1000 --seed 42 --out trace.json
+//! sort-forge trace --algo all --seeds 0..100 --n 1000 --out traces/
+//! sort-forge bench --algo all --pattern uniform --n 10000 --runs 5
+//! sort-forge render --algo merge --n 64 --every 50 --out frames/
+//! sort-forge list
+//! ```
+
+use std::collections::HashMap;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+use sort_forge_core::batch::{self, TraceRun};
+use sort_forge_core::events;
+use sort_forge_core::normalize::{Normalizer, Scale};
+use sort_forge_core::patterns::{self, Pattern};
+use sort_forge_core::trace::Trace;
+use sort_forge_core::{pregen_sort, Algorithm, SortEvent};
+
+const USAGE: &str = "\
+Usage: sort-forge <command> [options]
+
+Commands:
+  trace   Run algorithms and write their event traces as JSON
+  bench   Time algorithms and count their events
+  render  Write the array every few events as SVG bar charts
+  list    List available algorithms and input patterns
+
+Options:
+  --algo <names>      Algorithm name, comma-separated list, or \"all\"
+  --pattern <name>    Input pattern (default: random)
+  --n <count>         Array length (default: 100)
+  --seed <number>     Seed for the input pattern (default: 0)
+  --seeds <a..b>      trace: seed range (end exclusive) or comma-separated list
+  --out <path>        trace: write to file instead of stdout; a directory
+                      when several traces are generated (runs in parallel)
+  --runs <count>      bench: runs per algorithm, median is reported (default: 3)
+  --every <count>     render: events between frames (default: 10)";
+
+/// Options shared by the subcommands.
+#[derive(Debug, PartialEq)]
+struct Options {
+    algo: Option<String>,
+    pattern: Pattern,
+    n: usize,
+    seeds: Vec<u64>,
+    out: Option<String>,
+    runs: usize,
+    every: usize,
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("error: {}\n\n{}", message, USAGE);
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn run(args: &[String]) -> Result<(), String> {
+    let Some((command, rest)) = args.split_first() else {
+        println!("{}", USAGE);
+        return Ok(());
+    };
+
+    match command.as_str() {
+        "trace" => trace(&parse_options(rest)?),
+        "bench" => bench(&parse_options(rest)?),
+        "render" => render(&parse_options(rest)?),
+        "list" => {
+            list();
+            Ok(())
+        }
+        "help" | "--help" | "-h" => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        other => Err(format!("unknown command: {}", other)),
+    }
+}
+
+fn parse_options(args: &[String]) -> Result<Options, String> {
+    let mut values: HashMap<&str, &str> = HashMap::new();
+    let mut iter = args.iter();
+
+    while let Some(flag) = iter.next() {
+        let key = match flag.as_str() {
+            "--algo" | "--pattern" | "--n" | "--seed" | "--seeds" | "--out" | "--runs"
+            | "--every" => &flag[2..],
+            other => return Err(format!("unknown option: {}", other)),
+        };
+        let value = iter.next().ok_or_else(|| format!("missing value for {}", flag))?;
+        values.insert(key, value);
+    }
+
+    let pattern = match values.get("pattern") {
+        Some(name) => {
+            Pattern::from_str(name).ok_or_else(|| format!("unknown pattern: {}", name))?
+        }
+        None => Pattern::Random,
+    };
+
+    let seeds = match (values.get("seeds"), values.get("seed")) {
+        (Some(_), Some(_)) => return Err("use either --seed or --seeds".to_string()),
+        (Some(spec), None) => parse_seeds(spec)?,
+        (None, _) => vec![parse_number(&values, "seed", 0)?],
+    };
+
+    Ok(Options {
+        algo: values.get("algo").map(|s| s.to_string()),
+        pattern,
+        n: parse_number(&values, "n", 100)?,
+        seeds,
+        out: values.get("out").map(|s| s.to_string()),
+        runs: parse_number(&values, "runs", 3)?.max(1),
+        every: parse_number(&values, "every", 10)?.max(1),
+    })
+}
+
+fn parse_number<T: std::str::FromStr>(
+    values: &HashMap<&str, &str>,
+    key: &str,
+    default: T,
+) -> Result<T, String> {
+    match values.get(key) {
+        Some(value) => value.parse().map_err(|_| format!("invalid --{}: {}", key, value)),
+        None => Ok(default),
+    }
+}
+
+/// Parse "a..b" (end exclusive) or a comma-separated list of seeds.
+fn parse_seeds(spec: &str) -> Result<Vec<u64>, String> {
+    let invalid = || format!("invalid --seeds: {}", spec);
+
+    if let Some((start, end)) = spec.split_once("..") {
+        let start: u64 = start.parse().map_err(|_| invalid())?;
+        let end: u64 = end.parse().map_err(|_| invalid())?;
+        if start >= end {
+            return Err(invalid());
+        }
+        return Ok((start..end).collect());
+    }
+
+    spec.split(',').map(|s| s.trim().parse().map_err(|_| invalid())).collect()
+}
+
+/// Parse a comma-separated list of algorithm names, or "all".
+fn parse_algorithms(names: &str) -> Result<Vec<Algorithm>, String> {
+    if names == "all" {
+        return Ok(Algorithm::all().to_vec());
+    }
+
+    names
+        .split(',')
+        .map(|name| {
+            Algorithm::from_str(name.trim()).ok_or_else(|| format!("unknown algorithm: {}", name))
+        })
+        .collect()
+}
+
+/// A trace file: a metadata header, the input and every event of the sort.
+fn trace_json(run: TraceRun) -> Result<String, String> {
+    let (pattern, seed) = (run.job.pattern, run.job.seed);
+    let mut trace = Trace::new(run.job.algorithm, run.input, run.events);
+    trace.header = trace.header.with_source(pattern, seed);
+    serde_json::to_string(&trace).map_err(|e| e.to_string())
+}
+
+fn trace(options: &Options) -> Result<(), String> {
+    let names = options.algo.as_deref().ok_or("trace requires --algo")?;
+    let algorithms = parse_algorithms(names)?;
+    let jobs = batch::job_grid(&algorithms, options.pattern, options.n, &options.seeds);
+
+    // A single trace goes to stdout or the given file
+    if let [job] = jobs.as_slice() {
+        let json = trace_json(job.run())?;
+        return match &options.out {
+            Some(path) => std::fs::write(path, json).map_err(|e| format!("{}: {}", path, e)),
+            None => {
+                println!("{}", json);
+                Ok(())
+            }
+        };
+    }
+
+    // Several traces go to one file each, written as the runs finish
+    let dir = options.out.as_deref().ok_or("--out <dir> is required for multiple traces")?;
+    std::fs::create_dir_all(dir).map_err(|e| format!("{}: {}", dir, e))?;
+
+    let results = batch::run_each(&jobs, |run| {
+        let file = format!(
+            "{}/{}_{}_{}_{}.json",
+            dir,
+            run.job.algorithm.as_str(),
+            run.job.pattern.as_str(),
+            run.job.n,
+            run.job.seed
+        );
+        let json = trace_json(run)?;
+        std::fs::write(&file, json).map_err(|e| format!("{}: {}", file, e))
+    });
+    results.into_iter().collect::<Result<Vec<()>, String>>()?;
+
+    eprintln!("wrote {} traces to {}", jobs.len(), dir);
+    Ok(())
+}
+
+fn bench(options: &Options) -> Result<(), String> {
+    // Runs stay sequential so they don't compete for cores and skew timings
+    let algorithms = parse_algorithms(options.algo.as_deref().unwrap_or("all"))?;
+    let seed = match options.seeds.as_slice() {
+        [seed] => *seed,
+        _ => return Err("bench takes a single --seed".to_string()),
+    };
+    let input = patterns::generate(options.pattern, options.n, seed);
+
+    println!(
+        "{:<24} {:>12} {:>12} {:>12} {:>12} {:>12} {:>10}",
+        "algorithm", "events", "compares", "swaps", "overwrites", "writes", "median ms"
+    );
+
+    for algorithm in algorithms {
+        let mut times = Vec::with_capacity(options.runs);
+        let mut events = Vec::new();
+
+        for _ in 0..options.runs {
+            let mut array = input.clone();
+            let start = Instant::now();
+            events = pregen_sort(algorithm, &mut array);
+            times.push(start.elapsed());
+        }
+
+        let count = |f: fn(&SortEvent) -> bool| events.iter().filter(|e| f(e)).count();
+        println!(
+            "{:<24} {:>12} {:>12} {:>12} {:>12} {:>12} {:>10.3}",
+            algorithm.as_str(),
+            events.len(),
+            count(|e| matches!(e, SortEvent::Compare { .. })),
+            count(|e| matches!(e, SortEvent::Swap { .. })),
+            count(|e| matches!(e, SortEvent::Overwrite { .. })),
+            events::main_writes(&events),
+            median(&mut times).as_secs_f64() * 1000.0,
+        );
+    }
+
+    Ok(())
+}
+
+/// Frame size of `render`, in SVG user units.
+const FRAME_WIDTH: f64 = 640.0;
+const FRAME_HEIGHT: f64 = 360.0;
+
+fn render(options: &Options) -> Result<(), String> {
+    let name = options.algo.as_deref().ok_or("render requires --algo")?;
+    let algorithm =
+        Algorithm::from_str(name).ok_or_else(|| format!("unknown algorithm: {}", name))?;
+    let seed = match options.seeds.as_slice() {
+        [seed] => *seed,
+        _ => return Err("render takes a single --seed".to_string()),
+    };
+    let dir = options.out.as_deref().ok_or("render requires --out <dir>")?;
+    std::fs::create_dir_all(dir).map_err(|e| format!("{}: {}", dir, e))?;
+
+    let mut array = patterns::generate(options.pattern, options.n, seed);
+    let events = pregen_sort(algorithm, &mut array.clone());
+    // Heights are scaled to the input; sorting only moves its values around
+    let normalizer = Normalizer::new(&array, Scale::Linear);
+
+    let mut frames = 0;
+    let mut write_frame = |array: &[i32], event: Option<&SortEvent>| {
+        let file = format!("{}/frame_{:05}.svg", dir, frames);
+        frames += 1;
+        std::fs::write(&file, svg_frame(array, event, &normalizer))
+            .map_err(|e| format!("{}: {}", file, e))
+    };
+
+    write_frame(&array, None)?;
+    for chunk in events.chunks(options.every) {
+        for event in chunk {
+            event.apply(&mut array);
+        }
+        write_frame(&array, chunk.last())?;
+    }
+
+    eprintln!("wrote {} frames to {}", frames, dir);
+    Ok(())
+}
+
+/// One SVG bar chart of array. Bars the event touched are highlighted:
+/// compared ones in yellow, written ones in red.
+fn svg_frame(array: &[i32], event: Option<&SortEvent>, normalizer: &Normalizer<i32>) -> String {
+    const BASE: f64 = 2.0;
+    let (compared, written): (Vec<usize>, Vec<usize>) = match event {
+        Some(SortEvent::Compare { i, j }) => (vec![*i, *j], vec![]),
+        Some(SortEvent::Swap { i, j }) => (vec![], vec![*i, *j]),
+        Some(SortEvent::Overwrite { idx, .. }) => (vec![], vec![*idx]),
+        _ => (vec![], vec![]),
+    };
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" \
+         viewBox=\"0 0 {w} {h}\">\n<rect width=\"{w}\" height=\"{h}\" fill=\"#111\"/>\n",
+        w = FRAME_WIDTH,
+        h = FRAME_HEIGHT
+    );
+    let bar_width = FRAME_WIDTH / array.len().max(1) as f64;
+    for (i, &value) in array.iter().enumerate() {
+        let height = BASE + normalizer.height(value) * (FRAME_HEIGHT - BASE);
+        let fill = if written.contains(&i) {
+            "#e5534b"
+        } else if compared.contains(&i) {
+            "#e3b341"
+        } else {
+            "#58a6ff"
+        };
+        svg.push_str(&format!(
+            "<rect x=\"{:.2}\" y=\"{:.2}\" width=\"{:.2}\" height=\"{:.2}\" fill=\"{}\"/>\n",
+            i as f64 * bar_width,
+            FRAME_HEIGHT - height,
+            bar_width,
+            height,
+            fill
+        ));
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn list() {
+    println!("algorithms:");
+    for algorithm in Algorithm::all() {
+        println!("  {}", algorithm.as_str());
+    }
+    println!("patterns:");
+    for pattern in Pattern::all() {
+        println!("  {}", pattern.as_str());
+    }
+}
+
+fn median(times: &mut [Duration]) -> Duration {
+    times.sort();
+    times[times.len() / 2]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_parse_options_defaults() {
+        let options = parse_options(&[]).unwrap();
+
+        assert_eq!(options.algo, None);
+        assert_eq!(options.pattern, Pattern::Random);
+        assert_eq!(options.seeds, vec![0]);
+        assert_eq!((options.n, options.runs, options.every), (100, 3, 10));
+    }
+
+    #[test]
+    fn test_parse_options_all_flags() {
+        let options = parse_options(&args(
+            "--algo quicksort_ll --pattern reversed --n 1000 --seed 42 --out t.json --runs 5",
+        ))
+        .unwrap();
+
+        assert_eq!(options.algo.as_deref(), Some("quicksort_ll"));
+        assert_eq!(options.pattern, Pattern::Reversed);
+        assert_eq!(options.seeds, vec![42]);
+        assert_eq!((options.n, options.runs), (1000, 5));
+        assert_eq!(options.out.as_deref(), Some("t.json"));
+    }
+
+    #[test]
+    fn test_documented_algorithms_resolve() {
+        let mut names = Vec::new();
+        let mut words = include_str!("sort-forge.rs").split_whitespace();
+        while let Some(word) = words.next() {
+            if word == "--algo" {
+                names.extend(words.next());
+            }
+        }
+        // Skip placeholders such as <names>
+        names.retain(|name| name.chars().all(|c| c.is_ascii_alphanumeric() || "_,".contains(c)));
+
+        assert!(names.len() >= 4, "{:?}", names);
+        for name in names {
+            assert!(parse_algorithms(name).is_ok(), "documented --algo {} doesn't resolve", name);
+        }
+    }
+
+    #[test]
+    fn test_parse_options_errors() {
+        assert!(parse_options(&args("--n")).is_err());
+        assert!(parse_options(&args("--n ten")).is_err());
+        assert!(parse_options(&args("--pattern bogus")).is_err());
+        assert!(parse_options(&args("--size 10")).is_err());
+        assert!(parse_options(&args("--seed 1 --seeds 0..4")).is_err());
+    }
+
+    #[test]
+    fn test_parse_seeds() {
+        assert_eq!(parse_seeds("0..4").unwrap(), vec![0, 1, 2, 3]);
+        assert_eq!(parse_seeds("7, 3,9").unwrap(), vec![7, 3, 9]);
+        assert!(parse_seeds("4..4").is_err());
+        assert!(parse_seeds("a..b").is_err());
+    }
+
+    #[test]
+    fn test_parse_algorithms() {
+        assert_eq!(parse_algorithms("all").unwrap().len(), Algorithm::all().len());
+        assert_eq!(
+            parse_algorithms("bubble, heap").unwrap(),
+            vec![Algorithm::Bubble, Algorithm::HeapSort]
+        );
+        assert!(parse_algorithms("bubble,bogus").is_err());
+    }
+
+    #[test]
+    fn test_trace_batch_writes_files() {
+        let dir = std::env::temp_dir().join("sort-forge-cli-test-batch");
+        let dir_str = dir.to_str().unwrap();
+        let command = format!("trace --algo bubble,insertion --seeds 0..3 --n 6 --out {}", dir_str);
+        run(&args(&command)).unwrap();
+
+        let mut files: Vec<String> = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        files.sort();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(files.len(), 6);
+        assert_eq!(files[0], "bubble_random_6_0.json");
+    }
+
+    #[test]
+    fn test_trace_writes_json() {
+        let path = std::env::temp_dir().join("sort-forge-cli-test-trace.json");
+        let path_str = path.to_str().unwrap();
+        let command = format!("trace --algo bubble --n 8 --seed 1 --out {}", path_str);
+        run(&args(&command)).unwrap();
+
+        let json: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(json["header"]["algorithm"], "bubble");
+        assert_eq!(json["header"]["pattern"], "random");
+        assert_eq!(json["header"]["seed"], 1);
+        assert_eq!(json["header"]["n"], 8);
+        assert_eq!(json["input"].as_array().unwrap().len(), 8);
+        assert_eq!(json["events"].as_array().unwrap().last().unwrap()["type"], "Done");
+    }
+
+    #[test]
+    fn test_render_writes_frames() {
+        let dir = std::env::temp_dir().join("sort-forge-cli-test-render");
+        let dir_str = dir.to_str().unwrap();
+        let command = format!("render --algo insertion --n 8 --every 5 --out {}", dir_str);
+        run(&args(&command)).unwrap();
+
+        let mut input = patterns::generate(Pattern::Random, 8, 0);
+        let events = pregen_sort(Algorithm::Insertion, &mut input);
+        let mut files: Vec<String> = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        files.sort();
+        let first = std::fs::read_to_string(dir.join(&files[0])).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(files.len(), 1 + events.len().div_ceil(5));
+        assert_eq!(files[0], "frame_00000.svg");
+        assert!(first.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert_eq!(first.matches("<rect").count(), 1 + 8);
+    }
+
+    #[test]
+    fn test_svg_frame_highlights_event() {
+        let array = [1, 3, 2];
+        let normalizer = Normalizer::new(&array, Scale::Linear);
+
+        let svg = svg_frame(&array, Some(&SortEvent::Compare { i: 0, j: 2 }), &normalizer);
+        assert_eq!(svg.matches("#e3b341").count(), 2);
+        let svg = svg_frame(&array, Some(&SortEvent::Swap { i: 1, j: 2 }), &normalizer);
+        assert_eq!(svg.matches("#e5534b").count(), 2);
+        // The tallest bar fills the frame
+        assert!(svg.contains("y=\"0.00\" width=\"213.33\" height=\"360.00\""));
+    }
+
+    #[test]
+    fn test_run_rejects_unknown() {
+        assert!(run(&args("frobnicate")).is_err());
+        assert!(run(&args("trace --algo bogus")).is_err());
+        assert!(run(&args("trace")).is_err());
+        assert!(run(&args("render --algo merge")).is_err());
+    }
+}
@@ -0,0 +1,242 @@
+//! Coalescing of insertion shifts into BlockMove events.
+//!
+//! Insertion-style sorts move an element left by shifting every element in
+//! between one slot right, emitting one Overwrite per slot plus one for the
+//! final placement. `coalesce_shifts` is a post-processing pass that
+//! replaces each such chain with a single `BlockMove`, which is what the
+//! chain does as a whole. Traces of insertion sorts shrink by close to half,
+//! and playback applies a whole insertion at once.
+//!
+//! The pass replays the trace and only coalesces writes whose values show
+//! they really are a shift, so any trace can be passed through it: the
+//! coalesced trace always leaves the array in the same state.
+
+use crate::prelude::*;
+use crate::events::SortEvent;
+
+/// Replace shift chains in `events`, a trace of sorting `input`, with
+/// BlockMove events.
+///
+/// Non-mutating events inside a chain (usually the Compares that drive the
+/// shift) are kept before the BlockMove, with their indices mapped to where
+/// the elements are while nothing has moved yet; the slot being shifted
+/// into maps to the element being inserted. Gapped shifts (shell sort with
+/// gap > 1) are not contiguous and are left as they are.
+pub fn coalesce_shifts(input: &[i32], events: &[SortEvent]) -> Vec<SortEvent> {
+    let mut array = input.to_vec();
+    let mut coalesced = Vec::with_capacity(events.len());
+    let mut chain: Option<ShiftChain> = None;
+
+    for event in events {
+        if let Some(shift) = &mut chain {
+            match shift.feed(event, &array) {
+                Feed::Kept | Feed::Shifted => {
+                    event.apply(&mut array);
+                    continue;
+                }
+                Feed::Placed => {
+                    event.apply(&mut array);
+                    coalesced.append(&mut shift.coalesced);
+                    coalesced.push(SortEvent::block_move(shift.start, shift.hole, 1));
+                    chain = None;
+                    continue;
+                }
+                // Not a shift after all: keep the chain's events as they were
+                Feed::Broken => coalesced.append(&mut chain.take().unwrap().raw),
+            }
+        }
+
+        if let SortEvent::Overwrite { idx, old_val, new_val } = *event {
+            if idx > 0 && new_val == array[idx - 1] {
+                chain = Some(ShiftChain::new(idx, old_val, event.clone()));
+                event.apply(&mut array);
+                continue;
+            }
+        }
+
+        event.apply(&mut array);
+        coalesced.push(event.clone());
+    }
+
+    if let Some(mut shift) = chain {
+        coalesced.append(&mut shift.raw);
+    }
+    coalesced
+}
+
+/// A chain of right shifts in progress: `value` was at `start` and every
+/// element from `hole` to `start - 1` has been shifted one slot right.
+struct ShiftChain {
+    start: usize,
+    hole: usize,
+    value: i32,
+    /// The chain's events as emitted, for when it turns out not to be one.
+    raw: Vec<SortEvent>,
+    /// Non-mutating events of the chain, remapped.
+    coalesced: Vec<SortEvent>,
+}
+
+enum Feed {
+    /// A non-mutating event, kept.
+    Kept,
+    /// One more element shifted right.
+    Shifted,
+    /// The value was written into the hole, completing the chain.
+    Placed,
+    /// A mutation that doesn't continue the chain.
+    Broken,
+}
+
+impl ShiftChain {
+    fn new(start: usize, value: i32, first: SortEvent) -> Self {
+        Self {
+            start,
+            hole: start - 1,
+            value,
+            raw: vec![first],
+            coalesced: Vec::new(),
+        }
+    }
+
+    fn feed(&mut self, event: &SortEvent, array: &[i32]) -> Feed {
+        let feed = match *event {
+            SortEvent::Overwrite { idx, new_val, .. }
+                if idx == self.hole && new_val == self.value =>
+            {
+                Feed::Placed
+            }
+            SortEvent::Overwrite { idx, new_val, .. }
+                if idx == self.hole && idx > 0 && new_val == array[idx - 1] =>
+            {
+                Feed::Shifted
+            }
+            SortEvent::Done => return Feed::Broken,
+            ref other if other.is_mutation() => return Feed::Broken,
+            _ => {
+                self.coalesced.push(self.remap(event));
+                Feed::Kept
+            }
+        };
+
+        self.raw.push(event.clone());
+        if let Feed::Shifted = feed {
+            self.hole -= 1;
+        }
+        feed
+    }
+
+    /// Index in the unshifted array of the element now at `idx`.
+    fn unshifted(&self, idx: usize) -> usize {
+        if idx == self.hole {
+            self.start
+        } else if idx > self.hole && idx <= self.start {
+            idx - 1
+        } else {
+            idx
+        }
+    }
+
+    fn remap(&self, event: &SortEvent) -> SortEvent {
+        match event {
+            SortEvent::Compare { i, j } => SortEvent::Compare {
+                i: self.unshifted(*i),
+                j: self.unshifted(*j),
+            },
+            SortEvent::Found { idx } => SortEvent::Found { idx: self.unshifted(*idx) },
+            SortEvent::MarkSorted { idx } => SortEvent::MarkSorted { idx: self.unshifted(*idx) },
+            SortEvent::Highlight(highlight) => {
+                let indices = highlight.indices.iter().map(|&idx| self.unshifted(idx)).collect();
+                SortEvent::highlight(indices, &highlight.tag)
+            }
+            SortEvent::SetCursor(c) => SortEvent::set_cursor(
+                &c.id,
+                c.index.map(|idx| self.unshifted(idx)),
+                c.prev.map(|idx| self.unshifted(idx)),
+            ),
+            other => other.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::EventCounts;
+    use crate::patterns::{self, Pattern};
+    use crate::pregen::{pregen_sort, Algorithm};
+
+    fn replay(input: &[i32], events: &[SortEvent]) -> Vec<i32> {
+        let mut array = input.to_vec();
+        for event in events {
+            event.apply(&mut array);
+        }
+        array
+    }
+
+    #[test]
+    fn test_insertion_shift_becomes_block_move() {
+        let input = vec![2, 3, 1];
+        let events = pregen_sort(Algorithm::Insertion, &mut input.clone());
+        let coalesced = coalesce_shifts(&input, &events);
+
+        assert_eq!(
+            coalesced,
+            vec![
+                SortEvent::Compare { i: 0, j: 1 },
+                SortEvent::Compare { i: 1, j: 2 },
+                SortEvent::Compare { i: 0, j: 2 },
+                SortEvent::block_move(2, 0, 1),
+                SortEvent::Done,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_coalesced_traces_replay_to_same_array() {
+        let input = patterns::generate(Pattern::Uniform, 300, 5);
+        for &algorithm in Algorithm::all() {
+            let mut sorted = input.clone();
+            let events = pregen_sort(algorithm, &mut sorted);
+            let coalesced = coalesce_shifts(&input, &events);
+
+            assert_eq!(replay(&input, &coalesced), sorted, "{:?}", algorithm);
+            assert!(coalesced.len() <= events.len());
+        }
+    }
+
+    #[test]
+    fn test_insertion_sorts_shrink() {
+        let input = patterns::generate(Pattern::Reversed, 200, 0);
+        for algorithm in [Algorithm::Insertion, Algorithm::BinaryInsertion, Algorithm::Shell] {
+            let events = pregen_sort(algorithm, &mut input.clone());
+            let counts = EventCounts::from_events(&coalesce_shifts(&input, &events));
+
+            assert!(counts.block_moves > 0, "{:?}", algorithm);
+            assert!(counts.total() < events.len(), "{:?}", algorithm);
+        }
+    }
+
+    #[test]
+    fn test_coalesced_trace_rewinds() {
+        let input = patterns::generate(Pattern::Uniform, 50, 9);
+        let events = pregen_sort(Algorithm::Insertion, &mut input.clone());
+        let coalesced = coalesce_shifts(&input, &events);
+
+        let mut array = replay(&input, &coalesced);
+        for event in coalesced.iter().rev() {
+            event.inverse().apply(&mut array);
+        }
+        assert_eq!(array, input);
+    }
+
+    #[test]
+    fn test_unfinished_chain_is_kept() {
+        let input = vec![1, 2, 3];
+        let events = vec![
+            SortEvent::Overwrite { idx: 2, old_val: 3, new_val: 2 },
+            SortEvent::Compare { i: 0, j: 1 },
+            SortEvent::Swap { i: 0, j: 1 },
+        ];
+        assert_eq!(coalesce_shifts(&input, &events), events);
+    }
+}
@@ -0,0 +1,262 @@
+//! Compact event storage.
+//!
+//! A `Vec<SortEvent>` spends 24 bytes on every event and, while growing,
+//! briefly holds the old and the new buffer at once. Pregen traces are
+//! mostly Compares and Swaps whose fields fit in a few words, so an
+//! `EventLog` stores each event as its `packed` type code followed by only
+//! the fields that type uses: 12 bytes for a Compare or Swap, 4 for Done.
+//! Words go into fixed chunks that are never reallocated, so the peak is the
+//! log itself rather than twice it.
+//!
+//! Events without a packed form (Phase, Highlight, SetCursor, Snapshot) are
+//! kept as they are in a side list and referenced by index. Events become
+//! `SortEvent`s again only when read, so the JS-facing format is unchanged.
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+use crate::prelude::*;
+use crate::events::{self, EventSink, SortEvent};
+use crate::packed;
+
+/// Words in the largest chunk. Chunks start small and double up to this, so
+/// short traces don't reserve it.
+const MAX_CHUNK_WORDS: usize = 1 << 16;
+
+/// Type code of a record whose one field indexes the side list.
+const OTHER: u32 = 5;
+
+/// Words following the type code of each packed record.
+fn field_words(code: u32) -> usize {
+    match code {
+        15 => 0,
+        6 | 7 | 14 | OTHER => 1,
+        1 | 9 | 10 => 3,
+        _ => 2,
+    }
+}
+
+/// Events in compact form, appended through `EventSink`.
+#[derive(Debug, Clone, Default)]
+pub struct EventLog {
+    chunks: Vec<Vec<u32>>,
+    /// Events without a packed form, in order.
+    other: Vec<SortEvent>,
+    len: usize,
+}
+
+impl EventLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of events.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Events in order.
+    pub fn iter(&self) -> Iter<'_> {
+        Iter { log: self, chunk: 0, word: 0 }
+    }
+
+    /// The events as the public enum, for consumers that need a slice.
+    pub fn to_vec(&self) -> Vec<SortEvent> {
+        let mut events = Vec::with_capacity(self.len);
+        events.extend(self.iter());
+        events
+    }
+
+    /// Bytes of heap held by the log, the side list's boxed payloads aside.
+    pub fn heap_bytes(&self) -> usize {
+        self.chunks.iter().map(|c| c.capacity() * 4).sum::<usize>()
+            + self.chunks.capacity() * core::mem::size_of::<Vec<u32>>()
+            + self.other.capacity() * core::mem::size_of::<SortEvent>()
+    }
+
+    /// The events as a JSON array, in the shape `events::events_to_json`
+    /// produces.
+    pub fn to_json(&self) -> String {
+        let mut out = String::with_capacity(self.len * 32 + 2);
+        out.push('[');
+        for (n, event) in self.iter().enumerate() {
+            if n > 0 {
+                out.push(',');
+            }
+            events::write_event_json(&event, &mut out);
+        }
+        out.push(']');
+        out
+    }
+
+    /// Convert to a JsValue, the same objects `events::events_to_js` makes.
+    #[cfg(feature = "wasm")]
+    pub fn to_js(&self) -> Result<JsValue, JsValue> {
+        if self.len >= events::JSON_FAST_PATH_THRESHOLD {
+            return js_sys::JSON::parse(&self.to_json());
+        }
+        events::events_to_js(&self.to_vec())
+    }
+
+    fn push_record(&mut self, record: &[u32]) {
+        let fits = self.chunks.last().is_some_and(|c| c.capacity() - c.len() >= record.len());
+        if !fits {
+            let last = self.chunks.last().map_or(0, |c| c.capacity());
+            let words = (last * 2).clamp(64, MAX_CHUNK_WORDS);
+            self.chunks.push(Vec::with_capacity(words));
+        }
+        self.chunks.last_mut().unwrap().extend_from_slice(record);
+    }
+}
+
+impl EventSink for EventLog {
+    fn push(&mut self, event: SortEvent) {
+        match packed::pack(&event) {
+            Some(record) => {
+                let words = record.map(|w| w as u32);
+                self.push_record(&words[..1 + field_words(words[0])]);
+            }
+            None => {
+                self.push_record(&[OTHER, self.other.len() as u32]);
+                self.other.push(event);
+            }
+        }
+        self.len += 1;
+    }
+}
+
+impl<'a> IntoIterator for &'a EventLog {
+    type Item = SortEvent;
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Iter<'a> {
+        self.iter()
+    }
+}
+
+/// Iterator over an EventLog, decoding one record at a time.
+pub struct Iter<'a> {
+    log: &'a EventLog,
+    chunk: usize,
+    word: usize,
+}
+
+impl Iterator for Iter<'_> {
+    type Item = SortEvent;
+
+    fn next(&mut self) -> Option<SortEvent> {
+        let mut words = self.log.chunks.get(self.chunk)?;
+        if self.word == words.len() {
+            self.chunk += 1;
+            self.word = 0;
+            words = self.log.chunks.get(self.chunk)?;
+        }
+
+        let code = words[self.word];
+        let len = 1 + field_words(code);
+        let fields = &words[self.word + 1..self.word + len];
+        self.word += len;
+
+        if code == OTHER {
+            return Some(self.log.other[fields[0] as usize].clone());
+        }
+        let mut record = [code as i32, 0, 0, 0];
+        for (slot, &field) in record[1..].iter_mut().zip(fields) {
+            *slot = field as i32;
+        }
+        packed::unpack(record)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pregen::{pregen_sort, pregen_sort_compact, Algorithm};
+
+    fn log_of(events: &[SortEvent]) -> EventLog {
+        let mut log = EventLog::new();
+        for event in events {
+            log.push(event.clone());
+        }
+        log
+    }
+
+    #[test]
+    fn test_round_trips_every_event_type() {
+        let events = vec![
+            SortEvent::Swap { i: 0, j: 7 },
+            SortEvent::Overwrite { idx: 3, old_val: -5, new_val: i32::MAX },
+            SortEvent::Compare { i: 1, j: 2 },
+            SortEvent::EnterRange { lo: 0, hi: 9 },
+            SortEvent::ExitRange { lo: 0, hi: 9 },
+            SortEvent::Phase { name: "merge".to_string() },
+            SortEvent::Found { idx: 4 },
+            SortEvent::MarkSorted { idx: 5 },
+            SortEvent::highlight(vec![1, 2], "gap"),
+            SortEvent::block_move(2, 0, 3),
+            SortEvent::rotate(1, 6, 2),
+            SortEvent::set_cursor("i", Some(3), None),
+            SortEvent::EqualRange { lo: 2, hi: 4 },
+            SortEvent::snapshot(&[3, 1, 2]),
+            SortEvent::Flip { k: 6 },
+            SortEvent::GapChange { gap: 13, prev: 40 },
+            SortEvent::Done,
+        ];
+        let log = log_of(&events);
+
+        assert_eq!(log.len(), events.len());
+        assert_eq!(log.to_vec(), events);
+    }
+
+    #[test]
+    fn test_spans_chunks() {
+        let events: Vec<SortEvent> =
+            (0..50_000).map(|k| SortEvent::Overwrite { idx: k, old_val: 0, new_val: -1 }).collect();
+        let log = log_of(&events);
+
+        assert!(log.chunks.len() > 1);
+        assert!(log.chunks.iter().all(|c| c.capacity() <= MAX_CHUNK_WORDS));
+        assert!(log.iter().eq(events.iter().cloned()));
+    }
+
+    #[test]
+    fn test_matches_pregen_sort() {
+        let input = vec![9, 3, 7, 1, 8, 2, 6, 4, 5, 0];
+        for algorithm in Algorithm::all() {
+            let expected = pregen_sort(*algorithm, &mut input.clone());
+            let mut array = input.clone();
+            let log = pregen_sort_compact(*algorithm, &mut array);
+
+            assert_eq!(log.to_vec(), expected, "{}", algorithm.as_str());
+            assert!(array.windows(2).all(|w| w[0] <= w[1]));
+        }
+    }
+
+    #[test]
+    fn test_halves_trace_memory() {
+        let mut input: Vec<i32> = (0..1000).rev().collect();
+        let events = pregen_sort(Algorithm::Bubble, &mut input.clone());
+        let log = pregen_sort_compact(Algorithm::Bubble, &mut input);
+
+        let vec_bytes = events.len() * core::mem::size_of::<SortEvent>();
+        assert!(log.heap_bytes() * 2 <= vec_bytes + vec_bytes / 20, "{}", log.heap_bytes());
+    }
+
+    #[test]
+    fn test_json_matches_events_to_json() {
+        let events = pregen_sort(Algorithm::Selection, &mut [4, 2, 3, 1]);
+        assert_eq!(log_of(&events).to_json(), events::events_to_json(&events));
+        assert_eq!(EventLog::new().to_json(), "[]");
+    }
+
+    #[test]
+    fn test_empty() {
+        let log = EventLog::new();
+        assert!(log.is_empty());
+        assert_eq!(log.iter().next(), None);
+        assert_eq!(log.heap_bytes(), 0);
+    }
+}
@@ -0,0 +1,283 @@
+//! Comparator-driven sorting with an operation budget.
+//!
+//! The pregen algorithms compare i32 values directly. Sorting by a
+//! user-supplied comparator (a JS function or a plugin) goes through
+//! `Comparator` instead, which counts every call against a budget. A
+//! comparator with an inconsistent ordering can make some algorithms loop
+//! forever (gnome sort swaps the same pair back and forth); with the budget
+//! the sort aborts with a structured error and the partial trace instead of
+//! freezing the wasm instance. A comparator that fails aborts the same way.
+
+use std::cmp::Ordering;
+
+use serde::Serialize;
+use crate::events::SortEvent;
+
+/// Budget used when the caller doesn't give one.
+pub const DEFAULT_OP_BUDGET: usize = 1 << 24;
+
+/// Algorithms that can sort by a comparator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComparatorAlgorithm {
+    Merge,
+    Gnome,
+}
+
+impl ComparatorAlgorithm {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ComparatorAlgorithm::Merge => "merge",
+            ComparatorAlgorithm::Gnome => "gnome",
+        }
+    }
+
+    pub fn all() -> &'static [ComparatorAlgorithm] {
+        &[ComparatorAlgorithm::Merge, ComparatorAlgorithm::Gnome]
+    }
+
+    /// Parse algorithm name from string.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<ComparatorAlgorithm> {
+        match s.to_lowercase().as_str() {
+            "merge" | "mergesort" | "merge_sort" => Some(ComparatorAlgorithm::Merge),
+            "gnome" | "gnomesort" | "gnome_sort" => Some(ComparatorAlgorithm::Gnome),
+            _ => None,
+        }
+    }
+}
+
+/// Why a comparator-driven sort stopped early.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind")]
+pub enum AbortReason {
+    /// The comparator was called `budget` times without the sort finishing.
+    BudgetExceeded { budget: usize },
+    /// The comparator itself failed, e.g. a JS exception.
+    ComparatorError { message: String },
+}
+
+/// Error of an aborted sort, with the events emitted up to that point.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SortAborted {
+    pub reason: AbortReason,
+    pub events: Vec<SortEvent>,
+}
+
+/// The comparison layer: a comparator plus the budget it is charged against.
+pub struct Comparator<F> {
+    cmp: F,
+    budget: usize,
+    used: usize,
+}
+
+impl<F> Comparator<F>
+where
+    F: FnMut(i32, i32) -> Result<Ordering, String>,
+{
+    pub fn new(cmp: F, budget: usize) -> Self {
+        Self { cmp, budget, used: 0 }
+    }
+
+    /// Compare two values, charging one operation.
+    pub fn compare(&mut self, a: i32, b: i32) -> Result<Ordering, AbortReason> {
+        if self.used == self.budget {
+            return Err(AbortReason::BudgetExceeded { budget: self.budget });
+        }
+        self.used += 1;
+        (self.cmp)(a, b).map_err(|message| AbortReason::ComparatorError { message })
+    }
+
+    /// Operations charged so far.
+    pub fn used(&self) -> usize {
+        self.used
+    }
+}
+
+/// Sort array by `cmp`, allowing at most `budget` comparator calls.
+/// Elements the comparator calls Less come first; ties keep their order
+/// with merge sort.
+pub fn sort_by<F>(
+    algorithm: ComparatorAlgorithm,
+    array: &mut [i32],
+    cmp: F,
+    budget: usize,
+) -> Result<Vec<SortEvent>, SortAborted>
+where
+    F: FnMut(i32, i32) -> Result<Ordering, String>,
+{
+    let mut comparator = Comparator::new(cmp, budget);
+    let mut events = Vec::new();
+
+    let result = match algorithm {
+        ComparatorAlgorithm::Merge => {
+            let mut aux = array.to_vec();
+            merge_sort(array, &mut aux, 0, array.len(), &mut comparator, &mut events)
+        }
+        ComparatorAlgorithm::Gnome => gnome_sort(array, &mut comparator, &mut events),
+    };
+
+    match result {
+        Ok(()) => {
+            events.push(SortEvent::Done);
+            Ok(events)
+        }
+        Err(reason) => Err(SortAborted { reason, events }),
+    }
+}
+
+/// Sort the half-open range [lo, hi).
+#[allow(clippy::needless_range_loop)]
+fn merge_sort<F>(
+    array: &mut [i32],
+    aux: &mut [i32],
+    lo: usize,
+    hi: usize,
+    cmp: &mut Comparator<F>,
+    events: &mut Vec<SortEvent>,
+) -> Result<(), AbortReason>
+where
+    F: FnMut(i32, i32) -> Result<Ordering, String>,
+{
+    if hi - lo < 2 {
+        return Ok(());
+    }
+
+    events.push(SortEvent::EnterRange { lo, hi: hi - 1 });
+    let mid = lo + (hi - lo) / 2;
+    merge_sort(array, aux, lo, mid, cmp, events)?;
+    merge_sort(array, aux, mid, hi, cmp, events)?;
+
+    aux[lo..hi].copy_from_slice(&array[lo..hi]);
+    let (mut i, mut j) = (lo, mid);
+    for k in lo..hi {
+        let take_left = if i == mid {
+            false
+        } else if j == hi {
+            true
+        } else {
+            let order = cmp.compare(aux[i], aux[j])?;
+            events.push(SortEvent::Compare { i, j });
+            order != Ordering::Greater
+        };
+
+        let value = if take_left { aux[i] } else { aux[j] };
+        if take_left {
+            i += 1;
+        } else {
+            j += 1;
+        }
+        if array[k] != value {
+            events.push(SortEvent::Overwrite { idx: k, old_val: array[k], new_val: value });
+            array[k] = value;
+        }
+    }
+
+    events.push(SortEvent::ExitRange { lo, hi: hi - 1 });
+    Ok(())
+}
+
+fn gnome_sort<F>(
+    array: &mut [i32],
+    cmp: &mut Comparator<F>,
+    events: &mut Vec<SortEvent>,
+) -> Result<(), AbortReason>
+where
+    F: FnMut(i32, i32) -> Result<Ordering, String>,
+{
+    let mut i = 1;
+    while i < array.len() {
+        let order = cmp.compare(array[i - 1], array[i])?;
+        events.push(SortEvent::Compare { i: i - 1, j: i });
+
+        if order != Ordering::Greater {
+            i += 1;
+        } else {
+            events.push(SortEvent::Swap { i: i - 1, j: i });
+            array.swap(i - 1, i);
+            i = (i - 1).max(1);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn descending(a: i32, b: i32) -> Result<Ordering, String> {
+        Ok(b.cmp(&a))
+    }
+
+    #[test]
+    fn test_sort_by_comparator() {
+        for &algorithm in ComparatorAlgorithm::all() {
+            let mut array = vec![3, 9, 1, 7, 5, 2];
+            let events = sort_by(algorithm, &mut array, descending, DEFAULT_OP_BUDGET).unwrap();
+
+            assert_eq!(array, vec![9, 7, 5, 3, 2, 1], "{:?}", algorithm);
+            assert_eq!(events.last(), Some(&SortEvent::Done));
+        }
+    }
+
+    #[test]
+    fn test_inconsistent_comparator_hits_budget() {
+        // Every pair claims to be out of order, so gnome sort never finishes
+        let always_greater = |_: i32, _: i32| Ok(Ordering::Greater);
+        let mut array = vec![1, 2, 3];
+        let aborted = sort_by(ComparatorAlgorithm::Gnome, &mut array, always_greater, 100)
+            .unwrap_err();
+
+        assert_eq!(aborted.reason, AbortReason::BudgetExceeded { budget: 100 });
+        let compares = aborted.events.iter().filter(|e| matches!(e, SortEvent::Compare { .. }));
+        assert_eq!(compares.count(), 100);
+        assert!(!aborted.events.contains(&SortEvent::Done));
+    }
+
+    #[test]
+    fn test_merge_sort_terminates_on_inconsistent_comparator() {
+        let always_greater = |_: i32, _: i32| Ok(Ordering::Greater);
+        let mut array: Vec<i32> = (0..64).collect();
+        let result = sort_by(ComparatorAlgorithm::Merge, &mut array, always_greater, 1000);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_comparator_error_aborts() {
+        let mut calls = 0;
+        let flaky = |a: i32, b: i32| {
+            calls += 1;
+            if calls == 3 {
+                Err("comparator threw".to_string())
+            } else {
+                Ok(a.cmp(&b))
+            }
+        };
+        let mut array = vec![4, 3, 2, 1];
+        let aborted = sort_by(ComparatorAlgorithm::Merge, &mut array, flaky, 100).unwrap_err();
+
+        assert_eq!(
+            aborted.reason,
+            AbortReason::ComparatorError { message: "comparator threw".to_string() }
+        );
+        // Only the two successful comparisons made it into the trace
+        let compares = aborted.events.iter().filter(|e| matches!(e, SortEvent::Compare { .. }));
+        assert_eq!(compares.count(), 2);
+    }
+
+    #[test]
+    fn test_budget_counts_every_call() {
+        let mut comparator = Comparator::new(|a: i32, b: i32| Ok(a.cmp(&b)), 2);
+        assert!(comparator.compare(1, 2).is_ok());
+        assert!(comparator.compare(2, 1).is_ok());
+        assert!(comparator.compare(1, 1).is_err());
+        assert_eq!(comparator.used(), 2);
+    }
+
+    #[test]
+    fn test_algorithm_from_str() {
+        for &algorithm in ComparatorAlgorithm::all() {
+            assert_eq!(ComparatorAlgorithm::from_str(algorithm.as_str()), Some(algorithm));
+        }
+        assert_eq!(ComparatorAlgorithm::from_str("bogus"), None);
+    }
+}
@@ -0,0 +1,208 @@
+//! Playback cost model.
+//!
+//! Events aren't equally interesting to watch: a Swap moves two bars while
+//! a Compare only flashes them. A `CostModel` gives every event type a
+//! weight, and `cumulative_costs` turns a trace into a running total so the
+//! player can advance by cost per frame instead of by event count. Zero
+//! weights make an event free, so it plays together with the next one.
+
+use serde::{Deserialize, Serialize};
+use crate::prelude::*;
+use crate::events::SortEvent;
+
+/// Weight per event type. Missing fields deserialize to their defaults.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CostModel {
+    pub swap: f64,
+    pub overwrite: f64,
+    pub compare: f64,
+    pub enter_range: f64,
+    pub exit_range: f64,
+    pub phase: f64,
+    pub found: f64,
+    pub mark_sorted: f64,
+    pub highlight: f64,
+    pub block_move: f64,
+    pub rotate: f64,
+    pub set_cursor: f64,
+    pub equal_range: f64,
+    pub snapshot: f64,
+    pub flip: f64,
+    pub gap_change: f64,
+    pub done: f64,
+}
+
+impl Default for CostModel {
+    fn default() -> Self {
+        Self {
+            swap: 2.0,
+            overwrite: 1.0,
+            compare: 1.0,
+            enter_range: 0.0,
+            exit_range: 0.0,
+            phase: 0.0,
+            found: 1.0,
+            mark_sorted: 0.5,
+            highlight: 0.5,
+            block_move: 3.0,
+            rotate: 3.0,
+            set_cursor: 0.0,
+            equal_range: 0.5,
+            snapshot: 1.0,
+            flip: 3.0,
+            gap_change: 0.0,
+            done: 0.0,
+        }
+    }
+}
+
+impl CostModel {
+    /// Every event costs 1, so costs equal event counts.
+    pub fn uniform() -> Self {
+        Self {
+            swap: 1.0,
+            overwrite: 1.0,
+            compare: 1.0,
+            enter_range: 1.0,
+            exit_range: 1.0,
+            phase: 1.0,
+            found: 1.0,
+            mark_sorted: 1.0,
+            highlight: 1.0,
+            block_move: 1.0,
+            rotate: 1.0,
+            set_cursor: 1.0,
+            equal_range: 1.0,
+            snapshot: 1.0,
+            flip: 1.0,
+            gap_change: 1.0,
+            done: 1.0,
+        }
+    }
+
+    pub fn cost(&self, event: &SortEvent) -> f64 {
+        match event {
+            SortEvent::Swap { .. } => self.swap,
+            SortEvent::Overwrite { .. } => self.overwrite,
+            SortEvent::Compare { .. } => self.compare,
+            SortEvent::EnterRange { .. } => self.enter_range,
+            SortEvent::ExitRange { .. } => self.exit_range,
+            SortEvent::Phase { .. } => self.phase,
+            SortEvent::Found { .. } => self.found,
+            SortEvent::MarkSorted { .. } => self.mark_sorted,
+            SortEvent::Highlight(_) => self.highlight,
+            SortEvent::BlockMove(_) => self.block_move,
+            SortEvent::Rotate(_) => self.rotate,
+            SortEvent::SetCursor(_) => self.set_cursor,
+            SortEvent::EqualRange { .. } => self.equal_range,
+            SortEvent::Snapshot(_) => self.snapshot,
+            SortEvent::Flip { .. } => self.flip,
+            SortEvent::GapChange { .. } => self.gap_change,
+            SortEvent::Done => self.done,
+        }
+    }
+
+    /// Reject weights the player can't step through.
+    pub fn validate(&self) -> Result<(), String> {
+        let weights = [
+            self.swap,
+            self.overwrite,
+            self.compare,
+            self.enter_range,
+            self.exit_range,
+            self.phase,
+            self.found,
+            self.mark_sorted,
+            self.highlight,
+            self.block_move,
+            self.rotate,
+            self.set_cursor,
+            self.equal_range,
+            self.snapshot,
+            self.flip,
+            self.gap_change,
+            self.done,
+        ];
+        if weights.iter().any(|w| !w.is_finite() || *w < 0.0) {
+            return Err("Cost weights must be finite and non-negative".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Running total of event costs: entry i is the cost of events[0..=i].
+pub fn cumulative_costs(events: &[SortEvent], model: &CostModel) -> Vec<f64> {
+    let mut total = 0.0;
+    events
+        .iter()
+        .map(|event| {
+            total += model.cost(event);
+            total
+        })
+        .collect()
+}
+
+/// Number of events fully played once playback reaches `cost`, i.e. the
+/// playback position for a cost-driven clock.
+pub fn position_at_cost(cumulative: &[f64], cost: f64) -> usize {
+    cumulative.partition_point(|&c| c <= cost)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pregen::{pregen_sort, Algorithm};
+
+    #[test]
+    fn test_cumulative_costs() {
+        let events = vec![
+            SortEvent::Compare { i: 0, j: 1 },
+            SortEvent::Swap { i: 0, j: 1 },
+            SortEvent::EnterRange { lo: 0, hi: 1 },
+            SortEvent::Done,
+        ];
+        assert_eq!(cumulative_costs(&events, &CostModel::default()), vec![1.0, 3.0, 3.0, 3.0]);
+    }
+
+    #[test]
+    fn test_uniform_matches_event_count() {
+        let mut array: Vec<i32> = (0..40).rev().collect();
+        let events = pregen_sort(Algorithm::MergeSort, &mut array);
+        let costs = cumulative_costs(&events, &CostModel::uniform());
+
+        for (i, &cost) in costs.iter().enumerate() {
+            assert_eq!(cost, (i + 1) as f64);
+        }
+    }
+
+    #[test]
+    fn test_position_at_cost() {
+        let cumulative = [1.0, 3.0, 3.0, 4.0];
+
+        assert_eq!(position_at_cost(&cumulative, 0.5), 0);
+        assert_eq!(position_at_cost(&cumulative, 1.0), 1);
+        // Zero-cost events play together with the one before them
+        assert_eq!(position_at_cost(&cumulative, 3.5), 3);
+        assert_eq!(position_at_cost(&cumulative, 10.0), 4);
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(CostModel::default().validate().is_ok());
+        let negative = CostModel { swap: -1.0, ..CostModel::default() };
+        assert!(negative.validate().is_err());
+        let nan = CostModel { compare: f64::NAN, ..CostModel::default() };
+        assert!(nan.validate().is_err());
+    }
+
+    #[test]
+    fn test_cumulative_is_monotonic() {
+        let mut array: Vec<i32> = (0..60).map(|x| (x * 37) % 61).collect();
+        let events = pregen_sort(Algorithm::PdqSort, &mut array);
+        let costs = cumulative_costs(&events, &CostModel::default());
+
+        assert_eq!(costs.len(), events.len());
+        assert!(costs.windows(2).all(|w| w[0] <= w[1]));
+    }
+}
@@ -0,0 +1,169 @@
+//! Incremental consumption of a Rust-side trace.
+//!
+//! Handing a million-event trace to JavaScript as one array makes a
+//! million objects at once and stalls the page on garbage collection. An
+//! `EventCursor` keeps the trace in Rust instead and gives it out in
+//! batches, so a frontend only materializes the events it is about to play.
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+use crate::events::SortEvent;
+#[cfg(feature = "wasm")]
+use crate::events;
+#[cfg(feature = "wasm")]
+use crate::pregen::{pregen_sort, Algorithm};
+
+/// A trace with a read position.
+pub struct EventCursor {
+    events: Vec<SortEvent>,
+    pos: usize,
+}
+
+impl EventCursor {
+    pub fn new(events: Vec<SortEvent>) -> Self {
+        Self { events, pos: 0 }
+    }
+
+    /// Return up to `n` events from the current position and move past them.
+    pub fn next_batch(&mut self, n: usize) -> &[SortEvent] {
+        let start = self.pos;
+        self.pos = (start + n).min(self.events.len());
+        &self.events[start..self.pos]
+    }
+
+    /// Move the read position, clamped to the end of the trace.
+    pub fn seek(&mut self, pos: usize) {
+        self.pos = pos.min(self.events.len());
+    }
+
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Total number of events in the trace.
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// True once every event has been read.
+    pub fn is_done(&self) -> bool {
+        self.pos == self.events.len()
+    }
+
+    /// Event at index, regardless of the read position.
+    pub fn get(&self, index: usize) -> Option<&SortEvent> {
+        self.events.get(index)
+    }
+}
+
+/// Wasm-exposed cursor over a pregenerated trace.
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub struct TraceCursor {
+    inner: EventCursor,
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl TraceCursor {
+    /// Run a pregeneration sort and keep its trace on the Rust side.
+    #[wasm_bindgen(constructor)]
+    pub fn new(algorithm: &str, array: JsValue) -> Result<TraceCursor, JsValue> {
+        let algo = Algorithm::from_str(algorithm)
+            .ok_or_else(|| JsValue::from_str(&format!("Unknown algorithm: {}", algorithm)))?;
+
+        let mut arr: Vec<i32> = events::js_to_array(array)?;
+        let events = pregen_sort(algo, &mut arr);
+        Ok(TraceCursor { inner: EventCursor::new(events) })
+    }
+
+    /// Return the next `n` events (fewer at the end of the trace).
+    pub fn next_batch(&mut self, n: usize) -> Result<JsValue, JsValue> {
+        events::events_to_js(self.inner.next_batch(n))
+    }
+
+    /// Move the read position, clamped to the end of the trace.
+    pub fn seek(&mut self, pos: usize) {
+        self.inner.seek(pos);
+    }
+
+    pub fn position(&self) -> usize {
+        self.inner.position()
+    }
+
+    /// Total number of events in the trace.
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.inner.is_done()
+    }
+
+    /// Event at index without moving the read position, or undefined.
+    pub fn get_event(&self, index: usize) -> Result<JsValue, JsValue> {
+        match self.inner.get(index) {
+            Some(event) => serde_wasm_bindgen::to_value(event)
+                .map_err(|e| JsValue::from_str(&e.to_string())),
+            None => Ok(JsValue::UNDEFINED),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pregen::{pregen_sort, Algorithm};
+
+    #[test]
+    fn test_batches_cover_trace() {
+        let events = pregen_sort(Algorithm::HeapSort, &mut [5, 2, 8, 1, 9, 3]);
+        let mut cursor = EventCursor::new(events.clone());
+
+        let mut read = Vec::new();
+        while !cursor.is_done() {
+            let batch = cursor.next_batch(4);
+            assert!(!batch.is_empty() && batch.len() <= 4);
+            read.extend_from_slice(batch);
+        }
+        assert_eq!(read, events);
+        assert!(cursor.next_batch(4).is_empty());
+    }
+
+    #[test]
+    fn test_seek() {
+        let events = pregen_sort(Algorithm::Bubble, &mut [3, 2, 1]);
+        let mut cursor = EventCursor::new(events.clone());
+
+        cursor.seek(2);
+        assert_eq!(cursor.next_batch(1), &events[2..3]);
+        assert_eq!(cursor.position(), 3);
+
+        cursor.seek(usize::MAX);
+        assert!(cursor.is_done());
+        assert_eq!(cursor.position(), events.len());
+    }
+
+    #[test]
+    fn test_get_ignores_position() {
+        let mut cursor = EventCursor::new(vec![SortEvent::Compare { i: 0, j: 1 }, SortEvent::Done]);
+        cursor.next_batch(2);
+        assert_eq!(cursor.get(0), Some(&SortEvent::Compare { i: 0, j: 1 }));
+        assert_eq!(cursor.get(2), None);
+    }
+
+    #[test]
+    fn test_empty_trace() {
+        let mut cursor = EventCursor::new(Vec::new());
+        assert!(cursor.is_empty() && cursor.is_done());
+        assert!(cursor.next_batch(10).is_empty());
+    }
+}
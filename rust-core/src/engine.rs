@@ -0,0 +1,169 @@
+//! The two execution engines and their limits.
+//!
+//! - Pregen (V1) runs the whole sort up front and returns every event, so
+//!   playback can seek anywhere but memory grows with the trace.
+//! - Live (V2) steps the sort incrementally and keeps no history, so it
+//!   scales to much larger arrays.
+//!
+//! `recommend_engine` picks between them from the algorithm's event
+//! estimate, so frontends can show the choice and its cost to the user.
+
+use std::mem::size_of;
+
+use serde::{Deserialize, Serialize};
+use crate::events::SortEvent;
+use crate::live::LIVE_ALGORITHMS;
+use crate::pregen::{self, Algorithm};
+
+/// Largest array the pregen engine accepts.
+pub const PREGEN_MAX_LEN: usize = 1024;
+
+/// Largest array the live engine accepts.
+pub const LIVE_MAX_LEN: usize = 1 << 20;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Engine {
+    #[default]
+    Pregen,
+    Live,
+}
+
+impl Engine {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Engine::Pregen => "pregen",
+            Engine::Live => "live",
+        }
+    }
+
+    pub fn all() -> &'static [Engine] {
+        &[Engine::Pregen, Engine::Live]
+    }
+
+    /// Parse engine name from string.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Engine> {
+        match s.to_lowercase().as_str() {
+            "pregen" | "v1" => Some(Engine::Pregen),
+            "live" | "v2" => Some(Engine::Live),
+            _ => None,
+        }
+    }
+
+    /// Largest array length the engine accepts.
+    pub fn max_len(&self) -> usize {
+        match self {
+            Engine::Pregen => PREGEN_MAX_LEN,
+            Engine::Live => LIVE_MAX_LEN,
+        }
+    }
+}
+
+/// Pregen traces expected to be longer than this are better run live.
+pub const PREGEN_EVENT_BUDGET: usize = 1 << 22;
+
+/// Suggested engine for a run, with the numbers behind the choice.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EngineRecommendation {
+    pub engine: Engine,
+    /// Estimated number of events of the sort.
+    pub expected_events: usize,
+    /// Estimated memory held by the recommended engine, in bytes.
+    pub expected_memory: usize,
+    pub live_available: bool,
+    /// Why this engine was chosen.
+    pub reason: String,
+}
+
+/// Estimated bytes held by an engine while running a sort of n elements
+/// that emits the given number of events. Pregen keeps every event; live
+/// keeps only the array.
+pub fn expected_memory(engine: Engine, n: usize, events: usize) -> usize {
+    let array = n * size_of::<i32>();
+    match engine {
+        Engine::Pregen => array + events * size_of::<SortEvent>(),
+        Engine::Live => array,
+    }
+}
+
+/// Suggest pregen or live for running algorithm on n elements. Pregen is
+/// preferred, since its traces can be scrubbed, unless the trace would be
+/// too large and a live implementation exists. Fails for unknown
+/// algorithms and inputs neither engine can run.
+pub fn recommend_engine(algorithm: &str, n: usize) -> Result<EngineRecommendation, String> {
+    let algo = Algorithm::from_str(algorithm)
+        .ok_or_else(|| format!("Unknown algorithm: {}", algorithm))?;
+    let expected_events = pregen::estimated_events(algo, n);
+    let live_available = LIVE_ALGORITHMS.contains(&algo.as_str());
+
+    let (engine, reason) = if n <= PREGEN_MAX_LEN && expected_events <= PREGEN_EVENT_BUDGET {
+        (Engine::Pregen, "trace fits in memory, so playback can seek".to_string())
+    } else if live_available && n <= LIVE_MAX_LEN {
+        let reason = if n > PREGEN_MAX_LEN {
+            format!("{} elements exceeds the pregen limit of {}", n, PREGEN_MAX_LEN)
+        } else {
+            format!("about {} events is too many to pregenerate", expected_events)
+        };
+        (Engine::Live, reason)
+    } else if live_available {
+        return Err(format!("{} elements exceeds the live limit of {}", n, LIVE_MAX_LEN));
+    } else {
+        return Err(format!(
+            "{} has no live implementation and {} elements is too large to pregenerate",
+            algo.as_str(),
+            n
+        ));
+    };
+
+    Ok(EngineRecommendation {
+        engine,
+        expected_events,
+        expected_memory: expected_memory(engine, n, expected_events),
+        live_available,
+        reason,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_engine_from_str() {
+        for &engine in Engine::all() {
+            assert_eq!(Engine::from_str(engine.as_str()), Some(engine));
+        }
+        assert_eq!(Engine::from_str("V2"), Some(Engine::Live));
+        assert_eq!(Engine::from_str("bogus"), None);
+    }
+
+    #[test]
+    fn test_live_accepts_larger_arrays() {
+        assert!(Engine::Live.max_len() > Engine::Pregen.max_len());
+    }
+
+    #[test]
+    fn test_small_inputs_use_pregen() {
+        let rec = recommend_engine("bubble", 100).unwrap();
+        assert_eq!(rec.engine, Engine::Pregen);
+        assert!(rec.live_available);
+        assert_eq!(rec.expected_events, pregen::estimated_events(Algorithm::Bubble, 100));
+        assert_eq!(rec.expected_memory, 400 + rec.expected_events * size_of::<SortEvent>());
+    }
+
+    #[test]
+    fn test_large_inputs_use_live() {
+        let rec = recommend_engine("quicksort_ll", 100_000).unwrap();
+        assert_eq!(rec.engine, Engine::Live);
+        assert_eq!(rec.expected_memory, 400_000);
+        assert!(rec.reason.contains("pregen limit"));
+    }
+
+    #[test]
+    fn test_recommend_engine_errors() {
+        assert!(recommend_engine("bogus", 10).unwrap_err().contains("Unknown"));
+        assert!(recommend_engine("heap", 100_000).unwrap_err().contains("no live"));
+        assert!(recommend_engine("bubble", LIVE_MAX_LEN + 1).unwrap_err().contains("live limit"));
+    }
+}
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::*;
+use crate::prelude::*;
 
 /// Semantic events emitted by sorting algorithms.
 /// These events describe *what* happened, not *how* to render it.
@@ -26,14 +28,162 @@ pub enum SortEvent {
     /// Exiting the current subarray range. Stores lo/hi for invertibility.
     ExitRange { lo: usize, hi: usize },
 
+    /// The algorithm switched strategy or entered a named phase (no mutation).
+    Phase { name: String },
+
+    /// The element at idx is the result of a selection or search (no mutation).
+    Found { idx: usize },
+
+    /// The element at idx was verified to be in its final position (no mutation).
+    MarkSorted { idx: usize },
+
+    /// Emphasis on some elements with no semantic meaning, e.g. the current
+    /// minimum candidate (no mutation). Boxed to keep SortEvent small.
+    Highlight(Box<Highlight>),
+
+    /// A block of elements moved to another position; the elements in
+    /// between shift over to fill the gap. One event for what would
+    /// otherwise be a chain of single-slot writes. Boxed to keep SortEvent
+    /// small.
+    BlockMove(Box<BlockMove>),
+
+    /// The range lo..=hi was rotated left by `by`: array[lo + by] became
+    /// its first element. One event for a whole rotation. Boxed to keep
+    /// SortEvent small.
+    Rotate(Box<Rotate>),
+
+    /// A named pointer of the algorithm moved, e.g. selection sort's current
+    /// minimum (no mutation). Index None hides it. Stores the previous index
+    /// for invertibility. Boxed to keep SortEvent small.
+    SetCursor(Box<SetCursor>),
+
+    /// The range lo..=hi is known to hold equal keys, e.g. the equal block of
+    /// a three-way partition (no mutation).
+    EqualRange { lo: usize, hi: usize },
+
+    /// The whole array at this point of the trace, a synchronization point
+    /// for frontends. Applying it restores the array whatever state it was
+    /// in; on a correct replay it changes nothing. Boxed to keep SortEvent
+    /// small.
+    Snapshot(Box<Snapshot>),
+
+    /// The prefix 0..=k was reversed in one move, pancake sort's spatula
+    /// flip. Self-inverse.
+    Flip { k: usize },
+
+    /// A gap-based sort (shell, comb) moved from gap `prev` to `gap`:
+    /// comparisons from here on are `gap` apart (no mutation). prev is 0
+    /// before the first gap, so the inverse, which swaps the two, clears it.
+    GapChange { gap: usize, prev: usize },
+
     /// Sorting is complete.
     Done,
 }
 
+/// Payload of `SortEvent::Highlight`: the indices to emphasize and a tag
+/// saying why ("min", "gap", ...), for the frontend to style them by.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Highlight {
+    pub indices: Vec<usize>,
+    pub tag: String,
+}
+
+/// Payload of `SortEvent::BlockMove`: the `len` elements starting at `from`
+/// end up starting at `to`. Undone by moving them from `to` back to `from`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockMove {
+    pub from: usize,
+    pub to: usize,
+    pub len: usize,
+}
+
+/// Payload of `SortEvent::Rotate`. Undone by rotating the same range left
+/// by its length minus `by`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Rotate {
+    pub lo: usize,
+    pub hi: usize,
+    pub by: usize,
+}
+
+/// Payload of `SortEvent::SetCursor`: cursor `id` moved from `prev` to
+/// `index`, None meaning hidden. Undone by moving it back.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SetCursor {
+    pub id: String,
+    pub index: Option<usize>,
+    pub prev: Option<usize>,
+}
+
+/// Current index of a named cursor, for emitting SetCursor events that
+/// know their previous value.
+#[derive(Debug, Clone)]
+pub struct Cursor {
+    id: &'static str,
+    index: Option<usize>,
+}
+
+impl Cursor {
+    /// A hidden cursor.
+    pub fn new(id: &'static str) -> Self {
+        Self { id, index: None }
+    }
+
+    pub fn index(&self) -> Option<usize> {
+        self.index
+    }
+
+    /// Move the cursor, pushing a SetCursor event unless it is already there.
+    pub fn set(&mut self, index: Option<usize>, events: &mut impl EventSink) {
+        if index != self.index {
+            events.push(SortEvent::set_cursor(self.id, index, self.index));
+            self.index = index;
+        }
+    }
+}
+
+/// Payload of `SortEvent::Snapshot`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub array: Vec<i32>,
+}
+
 impl SortEvent {
+    /// Build a BlockMove event.
+    pub fn block_move(from: usize, to: usize, len: usize) -> SortEvent {
+        SortEvent::BlockMove(Box::new(BlockMove { from, to, len }))
+    }
+
+    /// Build a Rotate event.
+    pub fn rotate(lo: usize, hi: usize, by: usize) -> SortEvent {
+        SortEvent::Rotate(Box::new(Rotate { lo, hi, by }))
+    }
+
+    /// Build a SetCursor event.
+    pub fn set_cursor(id: &str, index: Option<usize>, prev: Option<usize>) -> SortEvent {
+        SortEvent::SetCursor(Box::new(SetCursor { id: id.to_string(), index, prev }))
+    }
+
+    /// Build a Snapshot event of the array.
+    pub fn snapshot(array: &[i32]) -> SortEvent {
+        SortEvent::Snapshot(Box::new(Snapshot { array: array.to_vec() }))
+    }
+
+    /// Build a Highlight event.
+    pub fn highlight(indices: Vec<usize>, tag: &str) -> SortEvent {
+        SortEvent::Highlight(Box::new(Highlight {
+            indices,
+            tag: tag.to_string(),
+        }))
+    }
+
     /// Returns the inverse of this event for rewinding.
-    /// Stateless events (Compare, Done) return themselves.
+    /// Stateless events (Compare, Phase, Found, MarkSorted, Highlight,
+    /// EqualRange, Snapshot, Done) return themselves; a Snapshot holds the
+    /// array both before and after it. Flip, like Swap, is self-inverse.
     /// EnterRange and ExitRange are inverses of each other.
+    /// SetCursor and GapChange are undone by moving back to their previous
+    /// index or gap.
     pub fn inverse(&self) -> SortEvent {
         match self {
             // Swap is self-inverse
@@ -50,6 +200,24 @@ impl SortEvent {
             SortEvent::EnterRange { lo, hi } => SortEvent::ExitRange { lo: *lo, hi: *hi },
             SortEvent::ExitRange { lo, hi } => SortEvent::EnterRange { lo: *lo, hi: *hi },
 
+            // A block moved back to where it came from
+            SortEvent::BlockMove(m) => SortEvent::block_move(m.to, m.from, m.len),
+
+            // Rotating by the rest of the range brings it back
+            SortEvent::Rotate(r) => {
+                let len = r.hi - r.lo + 1;
+                SortEvent::rotate(r.lo, r.hi, (len - r.by) % len)
+            }
+
+            // Flipping the same prefix again restores it
+            SortEvent::Flip { k } => SortEvent::Flip { k: *k },
+
+            // A cursor moved back to where it was
+            SortEvent::SetCursor(c) => SortEvent::set_cursor(&c.id, c.prev, c.index),
+
+            // The gap went back to what it was
+            SortEvent::GapChange { gap, prev } => SortEvent::GapChange { gap: *prev, prev: *gap },
+
             // Stateless events are their own inverse
             other => other.clone(),
         }
@@ -57,16 +225,383 @@ impl SortEvent {
 
     /// Returns true if this event mutates the array.
     pub fn is_mutation(&self) -> bool {
-        matches!(self, SortEvent::Swap { .. } | SortEvent::Overwrite { .. })
+        matches!(
+            self,
+            SortEvent::Swap { .. }
+                | SortEvent::Overwrite { .. }
+                | SortEvent::BlockMove(_)
+                | SortEvent::Rotate(_)
+                | SortEvent::Flip { .. }
+        )
+    }
+
+    /// Number of writes to the main array the event stands for: 2 per Swap,
+    /// 1 per Overwrite, and one per element that ends up in a new slot for
+    /// BlockMove, Rotate and Flip. Writes to auxiliary buffers aren't part of the
+    /// trace and never counted.
+    pub fn main_writes(&self) -> usize {
+        match self {
+            SortEvent::Swap { i, j } if i != j => 2,
+            SortEvent::Overwrite { .. } => 1,
+            SortEvent::BlockMove(m) if m.from != m.to => m.len + m.from.abs_diff(m.to),
+            SortEvent::Rotate(r) if r.by % (r.hi - r.lo + 1) != 0 => r.hi - r.lo + 1,
+            // The middle element of an odd prefix stays put
+            SortEvent::Flip { k } => k.div_ceil(2) * 2,
+            _ => 0,
+        }
+    }
+
+    /// Whether every index the event touches is below n, i.e. it can be
+    /// applied to an array of length n.
+    pub fn in_bounds(&self, n: usize) -> bool {
+        match self {
+            SortEvent::Swap { i, j } | SortEvent::Compare { i, j } => *i < n && *j < n,
+            SortEvent::Overwrite { idx, .. }
+            | SortEvent::Found { idx }
+            | SortEvent::MarkSorted { idx } => *idx < n,
+            SortEvent::EnterRange { lo, hi }
+            | SortEvent::ExitRange { lo, hi }
+            | SortEvent::EqualRange { lo, hi } => lo <= hi && *hi < n,
+            SortEvent::Highlight(h) => h.indices.iter().all(|&i| i < n),
+            SortEvent::BlockMove(m) => m.from + m.len <= n && m.to + m.len <= n,
+            SortEvent::Rotate(r) => r.lo <= r.hi && r.hi < n && r.by <= r.hi - r.lo + 1,
+            SortEvent::SetCursor(c) => c.index.is_none_or(|i| i < n),
+            SortEvent::Snapshot(s) => s.array.len() == n,
+            SortEvent::Flip { k } => *k < n,
+            SortEvent::Phase { .. } | SortEvent::GapChange { .. } | SortEvent::Done => true,
+        }
+    }
+
+    /// Apply the event's mutation to `array`, or restore a Snapshot; other
+    /// events do nothing.
+    pub fn apply(&self, array: &mut [i32]) {
+        match self {
+            SortEvent::Swap { i, j } => array.swap(*i, *j),
+            SortEvent::Overwrite { idx, new_val, .. } => array[*idx] = *new_val,
+            SortEvent::BlockMove(m) if m.to < m.from => {
+                array[m.to..m.from + m.len].rotate_right(m.len)
+            }
+            SortEvent::BlockMove(m) => array[m.from..m.to + m.len].rotate_left(m.len),
+            SortEvent::Rotate(r) => array[r.lo..=r.hi].rotate_left(r.by),
+            SortEvent::Snapshot(s) => array.copy_from_slice(&s.array),
+            SortEvent::Flip { k } => array[..=*k].reverse(),
+            _ => {}
+        }
+    }
+}
+
+/// Where pregen algorithms put their events. `Vec<SortEvent>` is the usual
+/// sink; `compact::EventLog` stores the same events in less memory.
+pub trait EventSink {
+    fn push(&mut self, event: SortEvent);
+}
+
+impl EventSink for Vec<SortEvent> {
+    fn push(&mut self, event: SortEvent) {
+        Vec::push(self, event);
+    }
+}
+
+/// Number of events of each type in a trace.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventCounts {
+    pub swaps: usize,
+    pub overwrites: usize,
+    pub compares: usize,
+    pub enter_ranges: usize,
+    pub exit_ranges: usize,
+    pub phases: usize,
+    pub found: usize,
+    pub marked_sorted: usize,
+    pub highlights: usize,
+    pub block_moves: usize,
+    pub rotates: usize,
+    pub cursors: usize,
+    pub equal_ranges: usize,
+    pub snapshots: usize,
+    pub flips: usize,
+    pub gap_changes: usize,
+    pub done: usize,
+}
+
+impl EventCounts {
+    pub fn from_events(events: &[SortEvent]) -> Self {
+        let mut counts = Self::default();
+        for event in events {
+            counts.add(event);
+        }
+        counts
+    }
+
+    pub fn add(&mut self, event: &SortEvent) {
+        let count = match event {
+            SortEvent::Swap { .. } => &mut self.swaps,
+            SortEvent::Overwrite { .. } => &mut self.overwrites,
+            SortEvent::Compare { .. } => &mut self.compares,
+            SortEvent::EnterRange { .. } => &mut self.enter_ranges,
+            SortEvent::ExitRange { .. } => &mut self.exit_ranges,
+            SortEvent::Phase { .. } => &mut self.phases,
+            SortEvent::Found { .. } => &mut self.found,
+            SortEvent::MarkSorted { .. } => &mut self.marked_sorted,
+            SortEvent::Highlight(_) => &mut self.highlights,
+            SortEvent::BlockMove(_) => &mut self.block_moves,
+            SortEvent::Rotate(_) => &mut self.rotates,
+            SortEvent::SetCursor(_) => &mut self.cursors,
+            SortEvent::EqualRange { .. } => &mut self.equal_ranges,
+            SortEvent::Snapshot(_) => &mut self.snapshots,
+            SortEvent::Flip { .. } => &mut self.flips,
+            SortEvent::GapChange { .. } => &mut self.gap_changes,
+            SortEvent::Done => &mut self.done,
+        };
+        *count += 1;
+    }
+
+    /// Counts paired with the event type they count, in declaration order.
+    pub fn by_type(&self) -> [(&'static str, usize); 17] {
+        [
+            ("Swap", self.swaps),
+            ("Overwrite", self.overwrites),
+            ("Compare", self.compares),
+            ("EnterRange", self.enter_ranges),
+            ("ExitRange", self.exit_ranges),
+            ("Phase", self.phases),
+            ("Found", self.found),
+            ("MarkSorted", self.marked_sorted),
+            ("Highlight", self.highlights),
+            ("BlockMove", self.block_moves),
+            ("Rotate", self.rotates),
+            ("SetCursor", self.cursors),
+            ("EqualRange", self.equal_ranges),
+            ("Snapshot", self.snapshots),
+            ("Flip", self.flips),
+            ("GapChange", self.gap_changes),
+            ("Done", self.done),
+        ]
+    }
+
+    pub fn total(&self) -> usize {
+        self.by_type().iter().map(|(_, count)| count).sum()
     }
 }
 
+/// Replace every Flip with the Swaps that reverse its prefix from the
+/// outside in, for consumers that don't understand Flip.
+pub fn expand_flips(events: &[SortEvent]) -> Vec<SortEvent> {
+    let mut expanded = Vec::with_capacity(events.len());
+    for event in events {
+        match *event {
+            SortEvent::Flip { k } => {
+                expanded.extend((0..k.div_ceil(2)).map(|i| SortEvent::Swap { i, j: k - i }))
+            }
+            _ => expanded.push(event.clone()),
+        }
+    }
+    expanded
+}
+
+/// Total writes to the main array over a trace, see `SortEvent::main_writes`.
+/// Unlike mutation event counts this is comparable across algorithms that
+/// swap, overwrite or move blocks.
+pub fn main_writes(events: &[SortEvent]) -> usize {
+    events.iter().map(SortEvent::main_writes).sum()
+}
+
+/// Traces with at least this many events are sent to JavaScript as one
+/// JSON string and parsed there. Building every event object through
+/// serde_wasm_bindgen crosses the wasm boundary several times per event,
+/// which dominates for large traces; below the threshold it is cheaper.
+pub const JSON_FAST_PATH_THRESHOLD: usize = 4096;
+
 /// Convert a vector of SortEvents to a JsValue for passing to JavaScript.
+/// Large traces take the JSON fast path; the resulting objects are the same.
+#[cfg(feature = "wasm")]
 pub fn events_to_js(events: &[SortEvent]) -> Result<JsValue, JsValue> {
+    if events.len() >= JSON_FAST_PATH_THRESHOLD {
+        return js_sys::JSON::parse(&events_to_json(events));
+    }
     serde_wasm_bindgen::to_value(events).map_err(|e| JsValue::from_str(&e.to_string()))
 }
 
+/// Serialize events as a JSON array, in the same shape serde produces
+/// (`{"type":"Swap","i":0,"j":1}`), without going through serde.
+pub fn events_to_json(events: &[SortEvent]) -> String {
+    // Most events are a Compare or Swap of ~30 bytes
+    let mut out = String::with_capacity(events.len() * 32 + 2);
+    write_events_json(events, &mut out);
+    out
+}
+
+/// Append events to `out` as a JSON array.
+pub fn write_events_json(events: &[SortEvent], out: &mut String) {
+    out.push('[');
+    for (n, event) in events.iter().enumerate() {
+        if n > 0 {
+            out.push(',');
+        }
+        write_event_json(event, out);
+    }
+    out.push(']');
+}
+
+/// Append one event to `out` as a JSON object.
+pub(crate) fn write_event_json(event: &SortEvent, out: &mut String) {
+    match event {
+        SortEvent::Swap { i, j } => write_pair(out, "Swap", "i", *i, "j", *j),
+        SortEvent::Compare { i, j } => write_pair(out, "Compare", "i", *i, "j", *j),
+        SortEvent::EnterRange { lo, hi } => write_pair(out, "EnterRange", "lo", *lo, "hi", *hi),
+        SortEvent::ExitRange { lo, hi } => write_pair(out, "ExitRange", "lo", *lo, "hi", *hi),
+        SortEvent::EqualRange { lo, hi } => write_pair(out, "EqualRange", "lo", *lo, "hi", *hi),
+        SortEvent::GapChange { gap, prev } => {
+            write_pair(out, "GapChange", "gap", *gap, "prev", *prev)
+        }
+        SortEvent::Overwrite { idx, old_val, new_val } => {
+            out.push_str("{\"type\":\"Overwrite\",\"idx\":");
+            push_int(out, *idx as i64);
+            out.push_str(",\"old_val\":");
+            push_int(out, *old_val as i64);
+            out.push_str(",\"new_val\":");
+            push_int(out, *new_val as i64);
+            out.push('}');
+        }
+        SortEvent::Phase { name } => {
+            out.push_str("{\"type\":\"Phase\",\"name\":");
+            push_json_string(out, name);
+            out.push('}');
+        }
+        SortEvent::Found { idx } => {
+            out.push_str("{\"type\":\"Found\",\"idx\":");
+            push_int(out, *idx as i64);
+            out.push('}');
+        }
+        SortEvent::MarkSorted { idx } => {
+            out.push_str("{\"type\":\"MarkSorted\",\"idx\":");
+            push_int(out, *idx as i64);
+            out.push('}');
+        }
+        SortEvent::Highlight(highlight) => {
+            out.push_str("{\"type\":\"Highlight\",\"indices\":[");
+            for (n, &idx) in highlight.indices.iter().enumerate() {
+                if n > 0 {
+                    out.push(',');
+                }
+                push_int(out, idx as i64);
+            }
+            out.push_str("],\"tag\":");
+            push_json_string(out, &highlight.tag);
+            out.push('}');
+        }
+        SortEvent::BlockMove(m) => {
+            out.push_str("{\"type\":\"BlockMove\",\"from\":");
+            push_int(out, m.from as i64);
+            out.push_str(",\"to\":");
+            push_int(out, m.to as i64);
+            out.push_str(",\"len\":");
+            push_int(out, m.len as i64);
+            out.push('}');
+        }
+        SortEvent::Rotate(r) => {
+            out.push_str("{\"type\":\"Rotate\",\"lo\":");
+            push_int(out, r.lo as i64);
+            out.push_str(",\"hi\":");
+            push_int(out, r.hi as i64);
+            out.push_str(",\"by\":");
+            push_int(out, r.by as i64);
+            out.push('}');
+        }
+        SortEvent::SetCursor(c) => {
+            out.push_str("{\"type\":\"SetCursor\",\"id\":");
+            push_json_string(out, &c.id);
+            out.push_str(",\"index\":");
+            push_opt_index(out, c.index);
+            out.push_str(",\"prev\":");
+            push_opt_index(out, c.prev);
+            out.push('}');
+        }
+        SortEvent::Snapshot(s) => {
+            out.push_str("{\"type\":\"Snapshot\",\"array\":[");
+            for (n, &value) in s.array.iter().enumerate() {
+                if n > 0 {
+                    out.push(',');
+                }
+                push_int(out, value as i64);
+            }
+            out.push_str("]}");
+        }
+        SortEvent::Flip { k } => {
+            out.push_str("{\"type\":\"Flip\",\"k\":");
+            push_int(out, *k as i64);
+            out.push('}');
+        }
+        SortEvent::Done => out.push_str("{\"type\":\"Done\"}"),
+    }
+}
+
+fn push_opt_index(out: &mut String, index: Option<usize>) {
+    match index {
+        Some(idx) => push_int(out, idx as i64),
+        None => out.push_str("null"),
+    }
+}
+
+fn write_pair(out: &mut String, tag: &str, a: &str, a_val: usize, b: &str, b_val: usize) {
+    out.push_str("{\"type\":\"");
+    out.push_str(tag);
+    out.push_str("\",\"");
+    out.push_str(a);
+    out.push_str("\":");
+    push_int(out, a_val as i64);
+    out.push_str(",\"");
+    out.push_str(b);
+    out.push_str("\":");
+    push_int(out, b_val as i64);
+    out.push('}');
+}
+
+/// Decimal formatting without the fmt machinery, which is the hot spot.
+fn push_int(out: &mut String, value: i64) {
+    let mut digits = [0u8; 20];
+    let mut pos = digits.len();
+    let mut rest = value.unsigned_abs();
+
+    loop {
+        pos -= 1;
+        digits[pos] = b'0' + (rest % 10) as u8;
+        rest /= 10;
+        if rest == 0 {
+            break;
+        }
+    }
+
+    if value < 0 {
+        out.push('-');
+    }
+    for &digit in &digits[pos..] {
+        out.push(digit as char);
+    }
+}
+
+fn push_json_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                out.push_str("\\u00");
+                out.push(char::from_digit(c as u32 >> 4, 16).unwrap());
+                out.push(char::from_digit(c as u32 & 0xf, 16).unwrap());
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
 /// Convert a JsValue array back to Vec<i32> (for receiving arrays from JS).
+#[cfg(feature = "wasm")]
 pub fn js_to_array(js_array: JsValue) -> Result<Vec<i32>, JsValue> {
     serde_wasm_bindgen::from_value(js_array).map_err(|e| JsValue::from_str(&e.to_string()))
 }
@@ -127,6 +662,255 @@ mod tests {
         assert!(!SortEvent::Compare { i: 0, j: 1 }.is_mutation());
         assert!(!SortEvent::EnterRange { lo: 0, hi: 10 }.is_mutation());
         assert!(!SortEvent::ExitRange { lo: 0, hi: 10 }.is_mutation());
+        assert!(!SortEvent::Found { idx: 3 }.is_mutation());
+        assert!(!SortEvent::MarkSorted { idx: 3 }.is_mutation());
         assert!(!SortEvent::Done.is_mutation());
     }
+
+    #[test]
+    fn test_phase_inverse() {
+        let event = SortEvent::Phase {
+            name: "heapsort_fallback".to_string(),
+        };
+        assert_eq!(event.inverse(), event);
+        assert!(!event.is_mutation());
+    }
+
+    #[test]
+    fn test_equal_range_inverse() {
+        let event = SortEvent::EqualRange { lo: 3, hi: 8 };
+        assert_eq!(event.inverse(), event);
+        assert!(!event.is_mutation());
+    }
+
+    #[test]
+    fn test_snapshot_restores_array() {
+        let event = SortEvent::snapshot(&[1, 2, 3]);
+        let mut array = vec![3, 1, 2];
+        event.inverse().apply(&mut array);
+        assert_eq!(array, vec![1, 2, 3]);
+        assert!(!event.is_mutation());
+        assert_eq!(event.main_writes(), 0);
+    }
+
+    #[test]
+    fn test_highlight_inverse() {
+        let event = SortEvent::highlight(vec![2, 7], "gap");
+        assert_eq!(event.inverse(), event);
+        assert!(!event.is_mutation());
+    }
+
+    #[test]
+    fn test_set_cursor_inverse() {
+        let event = SortEvent::set_cursor("min", Some(4), Some(1));
+        assert_eq!(event.inverse(), SortEvent::set_cursor("min", Some(1), Some(4)));
+        assert_eq!(event.inverse().inverse(), event);
+        assert!(!event.is_mutation());
+        assert_eq!(event.main_writes(), 0);
+    }
+
+    #[test]
+    fn test_gap_change_inverse() {
+        let event = SortEvent::GapChange { gap: 4, prev: 13 };
+        assert_eq!(event.inverse(), SortEvent::GapChange { gap: 13, prev: 4 });
+        assert_eq!(event.inverse().inverse(), event);
+        assert!(!event.is_mutation());
+        assert!(event.in_bounds(0));
+        assert_eq!(event.main_writes(), 0);
+    }
+
+    #[test]
+    fn test_cursor_skips_unchanged_index() {
+        let mut cursor = Cursor::new("pos");
+        let mut events = Vec::new();
+        cursor.set(Some(2), &mut events);
+        cursor.set(Some(2), &mut events);
+        cursor.set(None, &mut events);
+        assert_eq!(
+            events,
+            vec![
+                SortEvent::set_cursor("pos", Some(2), None),
+                SortEvent::set_cursor("pos", None, Some(2)),
+            ]
+        );
+        assert_eq!(cursor.index(), None);
+    }
+
+    #[test]
+    fn test_block_move_apply_and_inverse() {
+        let original = vec![0, 1, 2, 3, 4, 5];
+        for (from, to, len, expected) in [
+            (4, 1, 1, vec![0, 4, 1, 2, 3, 5]),
+            (1, 3, 2, vec![0, 3, 4, 1, 2, 5]),
+            (2, 2, 3, vec![0, 1, 2, 3, 4, 5]),
+        ] {
+            let event = SortEvent::block_move(from, to, len);
+            let mut array = original.clone();
+
+            event.apply(&mut array);
+            assert_eq!(array, expected);
+            event.inverse().apply(&mut array);
+            assert_eq!(array, original);
+        }
+        assert!(SortEvent::block_move(1, 0, 1).is_mutation());
+    }
+
+    #[test]
+    fn test_rotate_apply_and_inverse() {
+        let original: Vec<i32> = (0..7).collect();
+        for by in 0..5 {
+            let event = SortEvent::rotate(1, 5, by);
+            let mut array = original.clone();
+
+            event.apply(&mut array);
+            let mut expected = original.clone();
+            expected[1..=5].rotate_left(by);
+            assert_eq!(array, expected);
+
+            event.inverse().apply(&mut array);
+            assert_eq!(array, original);
+        }
+        assert!(SortEvent::rotate(0, 3, 1).is_mutation());
+    }
+
+    #[test]
+    fn test_flip_apply_and_inverse() {
+        let original: Vec<i32> = (0..6).collect();
+        let event = SortEvent::Flip { k: 3 };
+        let mut array = original.clone();
+
+        event.apply(&mut array);
+        assert_eq!(array, vec![3, 2, 1, 0, 4, 5]);
+        event.inverse().apply(&mut array);
+        assert_eq!(array, original);
+        assert!(event.is_mutation());
+    }
+
+    #[test]
+    fn test_expand_flips() {
+        let events = vec![SortEvent::Flip { k: 4 }, SortEvent::Flip { k: 0 }, SortEvent::Done];
+        assert_eq!(
+            expand_flips(&events),
+            vec![SortEvent::Swap { i: 0, j: 4 }, SortEvent::Swap { i: 1, j: 3 }, SortEvent::Done]
+        );
+    }
+
+    #[test]
+    fn test_in_bounds() {
+        assert!(SortEvent::Swap { i: 0, j: 2 }.in_bounds(3));
+        assert!(!SortEvent::Compare { i: 3, j: 0 }.in_bounds(3));
+        assert!(SortEvent::block_move(0, 1, 2).in_bounds(3));
+        assert!(!SortEvent::block_move(0, 2, 2).in_bounds(3));
+        assert!(!SortEvent::rotate(1, 0, 0).in_bounds(3));
+        assert!(!SortEvent::snapshot(&[1, 2]).in_bounds(3));
+        assert!(SortEvent::set_cursor("min", None, Some(9)).in_bounds(3));
+        assert!(SortEvent::Done.in_bounds(0));
+    }
+
+    #[test]
+    fn test_main_writes() {
+        let events = vec![
+            SortEvent::Compare { i: 0, j: 1 },
+            SortEvent::Swap { i: 0, j: 1 },
+            SortEvent::Swap { i: 2, j: 2 },
+            SortEvent::Overwrite { idx: 3, old_val: 1, new_val: 2 },
+            SortEvent::block_move(5, 2, 1),
+            SortEvent::rotate(0, 4, 2),
+            SortEvent::rotate(0, 4, 0),
+            SortEvent::Flip { k: 4 },
+            SortEvent::Done,
+        ];
+        assert_eq!(main_writes(&events), 2 + 1 + 4 + 5 + 4);
+    }
+
+    #[test]
+    fn test_event_counts() {
+        let events = vec![
+            SortEvent::Compare { i: 0, j: 1 },
+            SortEvent::Swap { i: 0, j: 1 },
+            SortEvent::Compare { i: 1, j: 2 },
+            SortEvent::highlight(vec![2], "min"),
+            SortEvent::Done,
+        ];
+        let counts = EventCounts::from_events(&events);
+
+        assert_eq!(counts.compares, 2);
+        assert_eq!(counts.swaps, 1);
+        assert_eq!(counts.highlights, 1);
+        assert_eq!(counts.done, 1);
+        assert_eq!(counts.total(), events.len());
+    }
+
+    #[test]
+    fn test_event_size() {
+        // Traces hold millions of events; payloads that don't fit are boxed
+        assert!(std::mem::size_of::<SortEvent>() <= 24);
+    }
+
+    #[test]
+    fn test_events_to_json_shape() {
+        let events = vec![
+            SortEvent::Compare { i: 0, j: 12 },
+            SortEvent::Swap { i: 3, j: 4 },
+            SortEvent::Overwrite { idx: 2, old_val: -7, new_val: 100 },
+            SortEvent::EnterRange { lo: 0, hi: 9 },
+            SortEvent::ExitRange { lo: 0, hi: 9 },
+            SortEvent::Phase { name: "merge".to_string() },
+            SortEvent::Found { idx: 5 },
+            SortEvent::MarkSorted { idx: 6 },
+            SortEvent::highlight(vec![1, 4], "min"),
+            SortEvent::block_move(7, 2, 1),
+            SortEvent::rotate(0, 9, 4),
+            SortEvent::set_cursor("min", Some(3), None),
+            SortEvent::EqualRange { lo: 2, hi: 5 },
+            SortEvent::snapshot(&[3, -1]),
+            SortEvent::Flip { k: 3 },
+            SortEvent::GapChange { gap: 5, prev: 0 },
+            SortEvent::Done,
+        ];
+
+        assert_eq!(
+            events_to_json(&events),
+            concat!(
+                r#"[{"type":"Compare","i":0,"j":12},{"type":"Swap","i":3,"j":4},"#,
+                r#"{"type":"Overwrite","idx":2,"old_val":-7,"new_val":100},"#,
+                r#"{"type":"EnterRange","lo":0,"hi":9},{"type":"ExitRange","lo":0,"hi":9},"#,
+                r#"{"type":"Phase","name":"merge"},{"type":"Found","idx":5},"#,
+                r#"{"type":"MarkSorted","idx":6},"#,
+                r#"{"type":"Highlight","indices":[1,4],"tag":"min"},"#,
+                r#"{"type":"BlockMove","from":7,"to":2,"len":1},"#,
+                r#"{"type":"Rotate","lo":0,"hi":9,"by":4},"#,
+                r#"{"type":"SetCursor","id":"min","index":3,"prev":null},"#,
+                r#"{"type":"EqualRange","lo":2,"hi":5},"#,
+                r#"{"type":"Snapshot","array":[3,-1]},{"type":"Flip","k":3},"#,
+                r#"{"type":"GapChange","gap":5,"prev":0},{"type":"Done"}]"#
+            )
+        );
+        assert_eq!(events_to_json(&[]), "[]");
+    }
+
+    #[test]
+    fn test_events_to_json_escapes_phase_names() {
+        let event = SortEvent::Phase { name: "a\"b\\c\n\u{1}".to_string() };
+        assert_eq!(events_to_json(&[event]), r#"[{"type":"Phase","name":"a\"b\\c\n\u0001"}]"#);
+    }
+
+    #[cfg(feature = "native")]
+    #[test]
+    fn test_events_to_json_matches_serde() {
+        let mut array: Vec<i32> = (0..200).map(|v| (v * 7919) % 211 - 100).collect();
+        let mut events = crate::pregen::pregen_sort(crate::pregen::Algorithm::IntroSort, &mut array);
+        events.push(SortEvent::highlight(vec![0, 199], "gap"));
+        events.push(SortEvent::block_move(3, 9, 2));
+        events.push(SortEvent::rotate(5, 12, 3));
+        events.push(SortEvent::set_cursor("pos", None, Some(4)));
+        events.push(SortEvent::EqualRange { lo: 1, hi: 3 });
+        events.push(SortEvent::snapshot(&[0, -5, 7]));
+        events.push(SortEvent::Flip { k: 2 });
+        events.push(SortEvent::GapChange { gap: 1, prev: 4 });
+        let json = events_to_json(&events);
+
+        assert_eq!(json, serde_json::to_string(&events).unwrap());
+        assert_eq!(serde_json::from_str::<Vec<SortEvent>>(&json).unwrap(), events);
+    }
 }
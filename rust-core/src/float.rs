@@ -0,0 +1,218 @@
+//! Sorting f64 values with the i32 pregen algorithms.
+//!
+//! Each value is replaced by its rank among the distinct values, ordered by
+//! `f64::total_cmp`, and the algorithm sorts the ranks. Equal values share a
+//! rank, so every comparison the algorithm makes is answered exactly as the
+//! float comparison would be, and the trace is the trace of sorting the
+//! floats. -0.0 sorts before 0.0 and the infinities sit at the ends.
+//!
+//! NaN has no place in a partial order, so where it goes is decided by a
+//! `NanPolicy` before the sort starts: all NaNs rank together after or
+//! before every number, or the input is rejected. Nothing is left for the
+//! algorithm to discover mid-trace.
+//!
+//! Overwrite events carry ranks in old_val and new_val; `FloatSortRun::values`
+//! maps a rank back to its value.
+
+use core::cmp::Ordering;
+
+use crate::prelude::*;
+use crate::events::SortEvent;
+use crate::pregen::{pregen_sort_into, Algorithm};
+
+/// Where NaNs go in a float sort.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NanPolicy {
+    /// After every number, including +inf.
+    #[default]
+    Last,
+    /// Before every number, including -inf.
+    First,
+    /// Reject inputs containing NaN.
+    Error,
+}
+
+impl NanPolicy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NanPolicy::Last => "last",
+            NanPolicy::First => "first",
+            NanPolicy::Error => "error",
+        }
+    }
+
+    /// Parse policy name from string.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<NanPolicy> {
+        match s.to_lowercase().as_str() {
+            "last" | "nan_last" => Some(NanPolicy::Last),
+            "first" | "nan_first" => Some(NanPolicy::First),
+            "error" | "reject" => Some(NanPolicy::Error),
+            _ => None,
+        }
+    }
+}
+
+/// Result of `pregen_sort_f64`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FloatSortRun {
+    /// Events on the value array; Overwrites carry ranks.
+    pub events: Vec<SortEvent>,
+    /// The distinct values in sorted order: rank r stands for `values[r]`.
+    pub values: Vec<f64>,
+}
+
+/// Compare two floats by total order, with NaNs placed by the policy.
+/// Under `NanPolicy::Error` NaNs are treated as last; callers reject them
+/// beforehand.
+pub fn compare_f64(a: f64, b: f64, policy: NanPolicy) -> Ordering {
+    match (a.is_nan(), b.is_nan()) {
+        (true, true) => Ordering::Equal,
+        (true, false) if policy == NanPolicy::First => Ordering::Less,
+        (true, false) => Ordering::Greater,
+        (false, true) if policy == NanPolicy::First => Ordering::Greater,
+        (false, true) => Ordering::Less,
+        (false, false) => a.total_cmp(&b),
+    }
+}
+
+/// Check values against the policy: under `NanPolicy::Error` the first NaN
+/// is reported by index.
+pub fn check_f64(values: &[f64], policy: NanPolicy) -> Result<(), String> {
+    if policy == NanPolicy::Error {
+        if let Some(index) = values.iter().position(|v| v.is_nan()) {
+            return Err(format!("NaN at index {}", index));
+        }
+    }
+    if values.len() > i32::MAX as usize {
+        return Err(format!("Too many values for i32 ranks: {}", values.len()));
+    }
+    Ok(())
+}
+
+/// Rank each value among the distinct values. Returns the ranks in input
+/// order and the distinct values in rank order.
+pub fn rank_f64(values: &[f64], policy: NanPolicy) -> Result<(Vec<i32>, Vec<f64>), String> {
+    check_f64(values, policy)?;
+
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_by(|&a, &b| compare_f64(values[a], values[b], policy));
+
+    let mut ranks = vec![0; values.len()];
+    let mut distinct: Vec<f64> = Vec::new();
+    for &index in &order {
+        let value = values[index];
+        let is_new = match distinct.last() {
+            Some(&last) => compare_f64(last, value, policy) != Ordering::Equal,
+            None => true,
+        };
+        if is_new {
+            distinct.push(value);
+        }
+        ranks[index] = distinct.len() as i32 - 1;
+    }
+
+    Ok((ranks, distinct))
+}
+
+/// Sort `values` in place with the given algorithm and NaN policy. The
+/// input is checked before any event is generated.
+pub fn pregen_sort_f64(
+    algorithm: Algorithm,
+    values: &mut [f64],
+    policy: NanPolicy,
+) -> Result<FloatSortRun, String> {
+    let (mut ranks, distinct) = rank_f64(values, policy)?;
+
+    let mut events = Vec::new();
+    pregen_sort_into(algorithm, &mut ranks, &mut events);
+
+    for (value, &rank) in values.iter_mut().zip(&ranks) {
+        *value = distinct[rank as usize];
+    }
+
+    Ok(FloatSortRun { events, values: distinct })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn same(a: &[f64], b: &[f64]) -> bool {
+        a.len() == b.len() && a.iter().zip(b).all(|(x, y)| x.to_bits() == y.to_bits())
+    }
+
+    #[test]
+    fn test_sorts_floats() {
+        let mut values = vec![2.5, -1.0, 0.25, 2.5, -7.75];
+        let run = pregen_sort_f64(Algorithm::MergeSort, &mut values, NanPolicy::Last).unwrap();
+
+        assert!(same(&values, &[-7.75, -1.0, 0.25, 2.5, 2.5]));
+        assert!(same(&run.values, &[-7.75, -1.0, 0.25, 2.5]));
+        assert!(matches!(run.events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_nan_last_and_first() {
+        let input = [f64::NAN, 1.0, f64::INFINITY, -f64::NAN, f64::NEG_INFINITY];
+
+        let mut values = input;
+        pregen_sort_f64(Algorithm::Insertion, &mut values, NanPolicy::Last).unwrap();
+        assert!(same(&values[..3], &[f64::NEG_INFINITY, 1.0, f64::INFINITY]));
+        assert!(values[3].is_nan() && values[4].is_nan());
+
+        let mut values = input;
+        pregen_sort_f64(Algorithm::Insertion, &mut values, NanPolicy::First).unwrap();
+        assert!(values[0].is_nan() && values[1].is_nan());
+        assert!(same(&values[2..], &[f64::NEG_INFINITY, 1.0, f64::INFINITY]));
+    }
+
+    #[test]
+    fn test_nan_error_rejects_up_front() {
+        let mut values = vec![3.0, 1.0, f64::NAN, 2.0];
+        let err = pregen_sort_f64(Algorithm::Bubble, &mut values, NanPolicy::Error).unwrap_err();
+
+        assert_eq!(err, "NaN at index 2");
+        assert_eq!(values[..2], [3.0, 1.0]);
+
+        let mut clean = vec![2.0, 1.0];
+        assert!(pregen_sort_f64(Algorithm::Bubble, &mut clean, NanPolicy::Error).is_ok());
+    }
+
+    #[test]
+    fn test_total_order_signed_zero() {
+        let mut values = vec![0.0, -0.0, 0.0];
+        let run = pregen_sort_f64(Algorithm::Selection, &mut values, NanPolicy::Last).unwrap();
+
+        assert!(same(&values, &[-0.0, 0.0, 0.0]));
+        assert_eq!(run.values.len(), 2);
+    }
+
+    #[test]
+    fn test_ranks_share_equal_values() {
+        let (ranks, distinct) =
+            rank_f64(&[5.0, f64::NAN, 5.0, -2.0, -f64::NAN], NanPolicy::First).unwrap();
+
+        assert_eq!(ranks, vec![2, 0, 2, 1, 0]);
+        assert_eq!(distinct.len(), 3);
+    }
+
+    #[test]
+    fn test_trace_matches_rank_sort() {
+        let mut values = vec![0.5, f64::NAN, -3.0, 0.5, f64::INFINITY, 1e-9];
+        let (mut ranks, _) = rank_f64(&values, NanPolicy::Last).unwrap();
+        let expected = crate::pregen::pregen_sort(Algorithm::HeapSort, &mut ranks);
+
+        let run = pregen_sort_f64(Algorithm::HeapSort, &mut values, NanPolicy::Last).unwrap();
+        assert_eq!(run.events, expected);
+    }
+
+    #[test]
+    fn test_policy_names() {
+        for policy in [NanPolicy::Last, NanPolicy::First, NanPolicy::Error] {
+            assert_eq!(NanPolicy::from_str(policy.as_str()), Some(policy));
+        }
+        assert_eq!(NanPolicy::from_str("bogus"), None);
+        assert_eq!(NanPolicy::default(), NanPolicy::Last);
+    }
+}
@@ -0,0 +1,132 @@
+//! Capped pregeneration with a live continuation.
+//!
+//! A pregen run that would exceed an event cap doesn't have to fail: for
+//! algorithms with a live implementation the trace is generated by the live
+//! stepper, which emits the same events, and generation stops at the cap.
+//! The stepper is returned with the partial trace, positioned right after
+//! its last event, so playback can continue seamlessly in live mode. A
+//! `LiveSort` serializes with its full state, so the continuation can be
+//! handed to a frontend and resumed there.
+
+use crate::events::SortEvent;
+use crate::live::{LiveSort, LIVE_ALGORITHMS};
+use crate::pregen::{pregen_sort, Algorithm};
+
+/// Largest live batch while generating a capped trace.
+const LIVE_BATCH: usize = 1024;
+
+/// A trace cut off at an event cap.
+pub struct CappedTrace {
+    pub events: Vec<SortEvent>,
+    /// True if the sort did not finish within the cap.
+    pub truncated: bool,
+    /// Live sort positioned right after the last event. None if the trace
+    /// is complete or the algorithm has no live implementation.
+    pub continuation: Option<LiveSort>,
+}
+
+/// Sort a copy of input, emitting at most `max_events` events.
+///
+/// Live steppers stop between steps, so a truncated trace may end a few
+/// events short of the cap. Algorithms without a live implementation are
+/// pregenerated in full and cut at the cap, with no continuation.
+pub fn pregen_sort_capped(
+    algorithm: Algorithm,
+    input: &[i32],
+    max_events: usize,
+) -> CappedTrace {
+    if !LIVE_ALGORITHMS.contains(&algorithm.as_str()) {
+        let mut events = pregen_sort(algorithm, &mut input.to_vec());
+        let truncated = events.len() > max_events;
+        events.truncate(max_events);
+        return CappedTrace { events, truncated, continuation: None };
+    }
+
+    let mut live = LiveSort::new(algorithm.as_str(), input.to_vec())
+        .expect("live algorithms have a live implementation");
+    let mut events = Vec::new();
+
+    // A step of `limit` emits at most 2 * limit + 1 events. A stepper may
+    // also emit nothing when the limit is too small for its next step
+    while !matches!(events.last(), Some(SortEvent::Done)) {
+        let limit = ((max_events - events.len()).saturating_sub(1) / 2).min(LIVE_BATCH);
+        let batch = if limit == 0 { Vec::new() } else { live.step(limit) };
+        if batch.is_empty() {
+            return CappedTrace { events, truncated: true, continuation: Some(live) };
+        }
+        events.extend(batch);
+    }
+
+    CappedTrace { events, truncated: false, continuation: None }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patterns::{self, Pattern};
+
+    #[test]
+    fn test_uncapped_matches_pregen() {
+        let input = patterns::generate(Pattern::Uniform, 100, 7);
+        for name in LIVE_ALGORITHMS {
+            let algorithm = Algorithm::from_str(name).unwrap();
+            let trace = pregen_sort_capped(algorithm, &input, usize::MAX);
+
+            assert!(!trace.truncated);
+            assert!(trace.continuation.is_none());
+            assert_eq!(trace.events, pregen_sort(algorithm, &mut input.clone()), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_continuation_resumes_exactly() {
+        let input = patterns::generate(Pattern::Uniform, 200, 3);
+        for name in LIVE_ALGORITHMS {
+            let algorithm = Algorithm::from_str(name).unwrap();
+            let expected = pregen_sort(algorithm, &mut input.clone());
+
+            let trace = pregen_sort_capped(algorithm, &input, 500);
+            assert!(trace.truncated);
+            assert!(trace.events.len() <= 500 && trace.events.len() >= 490);
+
+            let mut live = trace.continuation.unwrap();
+            let mut events = trace.events;
+            while !live.is_done() {
+                events.extend(live.step(64));
+            }
+            assert_eq!(events, expected, "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_no_live_implementation_truncates() {
+        let input = vec![5, 4, 3, 2, 1];
+        let full = pregen_sort(Algorithm::HeapSort, &mut input.clone());
+        let trace = pregen_sort_capped(Algorithm::HeapSort, &input, 4);
+
+        assert!(trace.truncated);
+        assert!(trace.continuation.is_none());
+        assert_eq!(trace.events, full[..4]);
+    }
+
+    #[test]
+    fn test_tiny_cap() {
+        let trace = pregen_sort_capped(Algorithm::Bubble, &[2, 1], 0);
+        assert!(trace.truncated);
+        assert!(trace.events.is_empty());
+        assert_eq!(trace.continuation.unwrap().array(), &[2, 1]);
+    }
+
+    #[cfg(feature = "native")]
+    #[test]
+    fn test_continuation_survives_serialization() {
+        let input = patterns::generate(Pattern::Reversed, 50, 0);
+        let trace = pregen_sort_capped(Algorithm::QuickSortLL, &input, 101);
+        let mut live = trace.continuation.unwrap();
+
+        let json = serde_json::to_string(&live).unwrap();
+        let mut restored: LiveSort = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.step(1000), live.step(1000));
+        assert_eq!(restored.array(), live.array());
+    }
+}
@@ -0,0 +1,159 @@
+//! Layout of the implicit tree behind a heap.
+//!
+//! Heapsort keeps a d-ary tree in the array itself: node i has children
+//! d·i + 1 ..= d·i + d and parent (i - 1) / d. `heap_layout` spells that
+//! tree out so a view can draw it next to the bars, and
+//! `annotate_heap_events` lists the tree nodes each event of a trace
+//! touches, so the tree view can follow playback. A node's id is its
+//! offset from the heap's root in the array, which is 0 for heapsort but
+//! not for heaps built on a subrange (intro sort's fallback).
+
+use serde::Serialize;
+use crate::prelude::*;
+use crate::events::SortEvent;
+
+/// One node of the implicit tree.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct HeapNode {
+    pub id: usize,
+    pub parent: Option<usize>,
+    pub children: Vec<usize>,
+    /// Depth, 0 for the root.
+    pub level: usize,
+    /// Left-to-right index within the level, counting the slots of a full
+    /// tree: the first node of level l is at 0 and a full level has d^l.
+    pub position: usize,
+}
+
+/// Tree of a heap of `n` elements with the given arity, indexed by id.
+/// Fails for an arity below 2.
+pub fn heap_layout(n: usize, arity: usize) -> Result<Vec<HeapNode>, String> {
+    if arity < 2 {
+        return Err(format!("A heap needs an arity of at least 2, got {}", arity));
+    }
+
+    let mut nodes = Vec::with_capacity(n);
+    let (mut level, mut level_start, mut level_width) = (0, 0, 1);
+    for id in 0..n {
+        if id >= level_start + level_width {
+            level += 1;
+            level_start += level_width;
+            level_width *= arity;
+        }
+        let first_child = arity * id + 1;
+        nodes.push(HeapNode {
+            id,
+            parent: id.checked_sub(1).map(|i| i / arity),
+            children: (first_child..(first_child + arity).min(n)).collect(),
+            level,
+            position: id - level_start,
+        });
+    }
+    Ok(nodes)
+}
+
+/// Ids of the nodes of a heap of `n` elements rooted at array index `root`
+/// that an event touches, in the order the event names them. Ranges count
+/// every node they cover; indices outside the heap are left out.
+pub fn heap_node_ids(event: &SortEvent, root: usize, n: usize) -> Vec<usize> {
+    let node = |idx: usize| idx.checked_sub(root).filter(|&id| id < n);
+    let span = |lo: usize, hi: usize| (lo..=hi).filter_map(node).collect();
+    match event {
+        SortEvent::Swap { i, j } | SortEvent::Compare { i, j } => {
+            [*i, *j].into_iter().filter_map(node).collect()
+        }
+        SortEvent::Overwrite { idx, .. }
+        | SortEvent::Found { idx }
+        | SortEvent::MarkSorted { idx } => node(*idx).into_iter().collect(),
+        SortEvent::EnterRange { lo, hi }
+        | SortEvent::ExitRange { lo, hi }
+        | SortEvent::EqualRange { lo, hi } => span(*lo, *hi),
+        SortEvent::Highlight(h) => h.indices.iter().filter_map(|&idx| node(idx)).collect(),
+        SortEvent::BlockMove(m) if m.len > 0 => {
+            span(m.from.min(m.to), m.from.max(m.to) + m.len - 1)
+        }
+        SortEvent::Rotate(r) => span(r.lo, r.hi),
+        SortEvent::Flip { k } => span(0, *k),
+        SortEvent::SetCursor(c) => c.index.and_then(node).into_iter().collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// `heap_node_ids` of every event of a trace.
+pub fn annotate_heap_events(events: &[SortEvent], root: usize, n: usize) -> Vec<Vec<usize>> {
+    events.iter().map(|event| heap_node_ids(event, root, n)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pregen::{pregen_sort, Algorithm};
+
+    #[test]
+    fn test_binary_layout() {
+        let nodes = heap_layout(6, 2).unwrap();
+
+        assert_eq!(nodes[0].parent, None);
+        assert_eq!(nodes[0].children, vec![1, 2]);
+        assert_eq!(nodes[2].children, vec![5]);
+        assert_eq!(nodes[5].parent, Some(2));
+        let levels: Vec<(usize, usize)> = nodes.iter().map(|n| (n.level, n.position)).collect();
+        assert_eq!(levels, vec![(0, 0), (1, 0), (1, 1), (2, 0), (2, 1), (2, 2)]);
+    }
+
+    #[test]
+    fn test_ternary_layout() {
+        let nodes = heap_layout(14, 3).unwrap();
+
+        assert_eq!(nodes[0].children, vec![1, 2, 3]);
+        assert_eq!(nodes[4].children, vec![13]);
+        assert_eq!(nodes[13].parent, Some(4));
+        assert_eq!((nodes[3].level, nodes[3].position), (1, 2));
+        assert_eq!((nodes[13].level, nodes[13].position), (3, 0));
+    }
+
+    #[test]
+    fn test_parent_child_links_agree() {
+        for arity in 2..5 {
+            let nodes = heap_layout(50, arity).unwrap();
+            for node in &nodes {
+                for &child in &node.children {
+                    assert_eq!(nodes[child].parent, Some(node.id));
+                    assert_eq!(nodes[child].level, node.level + 1);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_rejects_unary_heaps() {
+        assert!(heap_layout(4, 1).is_err());
+        assert_eq!(heap_layout(0, 2).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn test_heapsort_compares_are_neighbours() {
+        let mut array: Vec<i32> = (0..40).map(|x| (x * 17) % 41).collect();
+        let events = pregen_sort(Algorithm::HeapSort, &mut array);
+        let nodes = heap_layout(40, 2).unwrap();
+
+        let annotations = annotate_heap_events(&events, 0, 40);
+        assert_eq!(annotations.len(), events.len());
+        for (event, ids) in events.iter().zip(&annotations) {
+            if let SortEvent::Compare { .. } = event {
+                // Sift-down compares a node with a child, or two siblings
+                let [a, b] = ids[..] else { panic!("{:?}", ids) };
+                let edge = nodes[a].children.contains(&b) || nodes[b].children.contains(&a);
+                assert!(edge || nodes[a].parent == nodes[b].parent, "{} {}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_offset_heap_ids() {
+        let event = SortEvent::Swap { i: 12, j: 3 };
+        assert_eq!(heap_node_ids(&event, 10, 5), vec![2]);
+        assert_eq!(heap_node_ids(&SortEvent::EnterRange { lo: 8, hi: 12 }, 10, 5), vec![0, 1, 2]);
+        assert!(heap_node_ids(&SortEvent::Done, 0, 5).is_empty());
+    }
+}
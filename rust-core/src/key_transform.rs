@@ -0,0 +1,194 @@
+//! Sorting by a derived key.
+//!
+//! A `KeyTransform` maps each value to the key it is ordered by: absolute
+//! value, value mod k, decimal digit sum or the bit-reversed value. Sorting
+//! by key from JavaScript would need a comparator callback per comparison;
+//! the presets run entirely in the engine instead.
+//!
+//! As in `argsort`, each element is replaced by its rank under (key, index)
+//! and the algorithm sorts the ranks, so its comparisons are the key
+//! comparisons and ties keep input order whatever the algorithm. Overwrite
+//! events are mapped back from ranks to the values they stand for, so the
+//! trace replays on the original array like any pregen trace.
+
+use serde::{Deserialize, Serialize};
+use crate::prelude::*;
+use crate::events::SortEvent;
+use crate::pregen::{pregen_sort_into, Algorithm};
+
+/// A built-in key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyTransform {
+    /// Absolute value.
+    Abs,
+    /// Least non-negative residue mod k, so -1 mod 10 is 9.
+    Mod(u32),
+    /// Sum of the decimal digits of the absolute value.
+    DigitSum,
+    /// The value's 32 bits reversed, read as unsigned.
+    BitReverse,
+}
+
+/// A KeyTransform by name, as it arrives from JavaScript, e.g.
+/// `{ transform: "mod", modulus: 7 }`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyTransformSpec {
+    pub transform: String,
+    /// Required for "mod", rejected otherwise.
+    #[serde(default)]
+    pub modulus: Option<u32>,
+}
+
+impl KeyTransform {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            KeyTransform::Abs => "abs",
+            KeyTransform::Mod(_) => "mod",
+            KeyTransform::DigitSum => "digit_sum",
+            KeyTransform::BitReverse => "bit_reverse",
+        }
+    }
+
+    /// Resolve a spec. The modulus must be positive.
+    pub fn from_spec(spec: &KeyTransformSpec) -> Result<Self, String> {
+        let transform = match spec.transform.to_lowercase().as_str() {
+            "abs" | "absolute" => KeyTransform::Abs,
+            "mod" | "modulo" => match spec.modulus {
+                Some(0) | None => return Err("mod needs a positive modulus".to_string()),
+                Some(k) => KeyTransform::Mod(k),
+            },
+            "digit_sum" | "digitsum" | "digits" => KeyTransform::DigitSum,
+            "bit_reverse" | "bitreverse" | "bit_reversed" => KeyTransform::BitReverse,
+            _ => return Err(format!("Unknown key transform: {}", spec.transform)),
+        };
+        if spec.modulus.is_some() && !matches!(transform, KeyTransform::Mod(_)) {
+            return Err(format!("A modulus only applies to mod, not {}", transform.as_str()));
+        }
+        Ok(transform)
+    }
+
+    /// The key of a value.
+    pub fn key(&self, value: i32) -> i64 {
+        match *self {
+            KeyTransform::Abs => (value as i64).abs(),
+            KeyTransform::Mod(k) => (value as i64).rem_euclid(k as i64),
+            KeyTransform::DigitSum => {
+                let mut rest = value.unsigned_abs();
+                let mut sum = 0;
+                while rest > 0 {
+                    sum += (rest % 10) as i64;
+                    rest /= 10;
+                }
+                sum
+            }
+            KeyTransform::BitReverse => (value as u32).reverse_bits() as i64,
+        }
+    }
+}
+
+/// Sort array by key with the given algorithm and return all events.
+/// Elements with equal keys keep their input order.
+pub fn pregen_sort_by_key(
+    algorithm: Algorithm,
+    transform: KeyTransform,
+    array: &mut [i32],
+) -> Vec<SortEvent> {
+    let mut by_rank: Vec<usize> = (0..array.len()).collect();
+    by_rank.sort_by_key(|&i| transform.key(array[i]));
+    let mut ranks = vec![0; array.len()];
+    for (rank, &index) in by_rank.iter().enumerate() {
+        ranks[index] = rank as i32;
+    }
+    let values: Vec<i32> = by_rank.iter().map(|&i| array[i]).collect();
+
+    let mut events = Vec::new();
+    pregen_sort_into(algorithm, &mut ranks, &mut events);
+
+    // Overwrites wrote ranks; the array holds the values they stand for
+    for event in &mut events {
+        if let SortEvent::Overwrite { old_val, new_val, .. } = event {
+            *old_val = values[*old_val as usize];
+            *new_val = values[*new_val as usize];
+        }
+    }
+
+    for (slot, &rank) in array.iter_mut().zip(&ranks) {
+        *slot = values[rank as usize];
+    }
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patterns::{self, Pattern};
+
+    fn spec(transform: &str, modulus: Option<u32>) -> KeyTransformSpec {
+        KeyTransformSpec { transform: transform.to_string(), modulus }
+    }
+
+    #[test]
+    fn test_keys() {
+        assert_eq!(KeyTransform::Abs.key(-7), 7);
+        assert_eq!(KeyTransform::Abs.key(i32::MIN), 1 << 31);
+        assert_eq!(KeyTransform::Mod(10).key(-1), 9);
+        assert_eq!(KeyTransform::Mod(10).key(42), 2);
+        assert_eq!(KeyTransform::DigitSum.key(-409), 13);
+        assert_eq!(KeyTransform::BitReverse.key(1), 1 << 31);
+        assert_eq!(KeyTransform::BitReverse.key(0), 0);
+    }
+
+    #[test]
+    fn test_sorts_by_abs() {
+        let mut array = vec![-5, 3, -1, 4, 1, -3];
+        pregen_sort_by_key(Algorithm::Bubble, KeyTransform::Abs, &mut array);
+        assert_eq!(array, vec![-1, 1, 3, -3, 4, -5]);
+    }
+
+    #[test]
+    fn test_equal_keys_keep_input_order() {
+        let input = vec![17, 3, 27, 10, 7, 20];
+        for algorithm in [Algorithm::HeapSort, Algorithm::QuickSortLR, Algorithm::Selection] {
+            let mut array = input.clone();
+            pregen_sort_by_key(algorithm, KeyTransform::Mod(10), &mut array);
+            assert_eq!(array, vec![10, 20, 3, 17, 27, 7], "{:?}", algorithm);
+        }
+    }
+
+    #[test]
+    fn test_events_replay_on_values() {
+        let input = patterns::generate(Pattern::Random, 150, 8);
+        let transform = KeyTransform::DigitSum;
+        for &algorithm in Algorithm::all() {
+            let mut array = input.clone();
+            let events = pregen_sort_by_key(algorithm, transform, &mut array);
+
+            let mut replayed = input.clone();
+            for event in &events {
+                event.apply(&mut replayed);
+            }
+            assert_eq!(replayed, array, "{:?}", algorithm);
+            assert!(array.windows(2).all(|w| transform.key(w[0]) <= transform.key(w[1])));
+        }
+    }
+
+    #[test]
+    fn test_from_spec() {
+        assert_eq!(KeyTransform::from_spec(&spec("abs", None)), Ok(KeyTransform::Abs));
+        assert_eq!(KeyTransform::from_spec(&spec("Mod", Some(7))), Ok(KeyTransform::Mod(7)));
+        assert_eq!(
+            KeyTransform::from_spec(&spec("bit_reverse", None)),
+            Ok(KeyTransform::BitReverse)
+        );
+        assert!(KeyTransform::from_spec(&spec("mod", None)).is_err());
+        assert!(KeyTransform::from_spec(&spec("mod", Some(0))).is_err());
+        assert!(KeyTransform::from_spec(&spec("digit_sum", Some(3))).is_err());
+        assert!(KeyTransform::from_spec(&spec("square", None)).is_err());
+    }
+
+    #[test]
+    fn test_empty_array() {
+        let events = pregen_sort_by_key(Algorithm::MergeSort, KeyTransform::Abs, &mut []);
+        assert_eq!(events, vec![SortEvent::Done]);
+    }
+}
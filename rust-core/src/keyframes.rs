@@ -0,0 +1,268 @@
+//! Precomputed animation keyframes.
+//!
+//! Interpolating bar positions in JS means replaying the trace every frame
+//! to know which bar is where. `keyframes` does that replay once: it
+//! follows every element through the trace and records, per element, the
+//! times at which its position or value changes. The frontend only
+//! interpolates linearly between an element's consecutive keyframes, which
+//! a shader or a canvas loop can do straight from the flat buffers.
+//!
+//! Time is measured in `CostModel` units: event k runs from the cost total
+//! before it to the total after it, so a Swap with weight 2 slides its two
+//! bars over 2 units and zero-weight events take no time. Elements are
+//! identified by their input index. Swaps, block moves, rotations and flips
+//! move elements; Overwrites and Snapshots change the value an element
+//! shows in place.
+
+use crate::prelude::*;
+use crate::cost::CostModel;
+use crate::events::SortEvent;
+
+/// Keyframes of every element, flattened for typed arrays. Element e's
+/// keyframes are `offsets[e]..offsets[e + 1]` of the other buffers, in
+/// time order, and its first keyframe is at time 0.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Keyframes {
+    pub offsets: Vec<u32>,
+    pub times: Vec<f32>,
+    /// Slot index the element occupies at that time.
+    pub positions: Vec<f32>,
+    /// Value the element shows at that time.
+    pub values: Vec<i32>,
+    /// Time at which the last event ends.
+    pub duration: f64,
+}
+
+impl Keyframes {
+    /// Number of elements.
+    pub fn len(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Position and value of element e at time t, interpolating between its
+    /// keyframes the way a frontend would.
+    pub fn sample(&self, e: usize, t: f32) -> (f32, f32) {
+        let range = self.offsets[e] as usize..self.offsets[e + 1] as usize;
+        let times = &self.times[range.clone()];
+        let next = times.partition_point(|&time| time <= t);
+        let k = range.start + next.saturating_sub(1);
+
+        if next == 0 || next == times.len() || self.times[k + 1] == self.times[k] {
+            return (self.positions[k], self.values[k] as f32);
+        }
+        let f = (t - self.times[k]) / (self.times[k + 1] - self.times[k]);
+        let lerp = |a: f32, b: f32| a + (b - a) * f;
+        (
+            lerp(self.positions[k], self.positions[k + 1]),
+            lerp(self.values[k] as f32, self.values[k + 1] as f32),
+        )
+    }
+}
+
+/// One element's keyframe list while building.
+#[derive(Default)]
+struct Track {
+    frames: Vec<(f32, f32, i32)>,
+}
+
+impl Track {
+    /// Add a keyframe, skipping exact repeats of the last one.
+    fn push(&mut self, time: f32, position: usize, value: i32) {
+        let frame = (time, position as f32, value);
+        if self.frames.last() != Some(&frame) {
+            self.frames.push(frame);
+        }
+    }
+}
+
+/// Follow every element of `input` through `events` and record its
+/// keyframes, timed by `model`.
+pub fn keyframes(
+    input: &[i32],
+    events: &[SortEvent],
+    model: &CostModel,
+) -> Result<Keyframes, String> {
+    let n = input.len();
+    if let Some(k) = events.iter().position(|e| !e.in_bounds(n)) {
+        return Err(format!("Event {} is out of bounds for an array of {}", k, n));
+    }
+
+    let mut values = input.to_vec();
+    // Element in each slot, as i32 so permutation events apply to it directly
+    let mut slots: Vec<i32> = (0..n as i32).collect();
+    let mut tracks: Vec<Track> = (0..n).map(|_| Track::default()).collect();
+    for (e, track) in tracks.iter_mut().enumerate() {
+        track.push(0.0, e, values[e]);
+    }
+
+    let mut end = 0.0;
+    for event in events {
+        let start = end;
+        end += model.cost(event);
+        let (t0, t1) = (start as f32, end as f32);
+
+        match event {
+            SortEvent::Overwrite { idx, new_val, .. } => {
+                let track = &mut tracks[slots[*idx] as usize];
+                track.push(t0, *idx, values[*idx]);
+                track.push(t1, *idx, *new_val);
+            }
+            SortEvent::Snapshot(snapshot) => {
+                for (idx, (&old, &new)) in values.iter().zip(&snapshot.array).enumerate() {
+                    if old != new {
+                        let track = &mut tracks[slots[idx] as usize];
+                        track.push(t0, idx, old);
+                        track.push(t1, idx, new);
+                    }
+                }
+            }
+            _ => {
+                if let Some((lo, hi)) = moved_range(event) {
+                    let before = slots[lo..=hi].to_vec();
+                    event.apply(&mut slots);
+                    // Only elements whose slot changed get keyframes
+                    for (idx, &element) in (lo..=hi).zip(&before) {
+                        if slots[idx] != element {
+                            tracks[element as usize].push(t0, idx, values[idx]);
+                        }
+                    }
+                    event.apply(&mut values);
+                    for (idx, &element) in (lo..=hi).zip(&before) {
+                        if slots[idx] != element {
+                            tracks[slots[idx] as usize].push(t1, idx, values[idx]);
+                        }
+                    }
+                    continue;
+                }
+            }
+        }
+        event.apply(&mut values);
+    }
+
+    let mut offsets = Vec::with_capacity(n + 1);
+    let total: usize = tracks.iter().map(|t| t.frames.len()).sum();
+    let (mut times, mut positions, mut out_values) =
+        (Vec::with_capacity(total), Vec::with_capacity(total), Vec::with_capacity(total));
+    offsets.push(0);
+    for track in &tracks {
+        for &(time, position, value) in &track.frames {
+            times.push(time);
+            positions.push(position);
+            out_values.push(value);
+        }
+        offsets.push(times.len() as u32);
+    }
+
+    Ok(Keyframes { offsets, times, positions, values: out_values, duration: end })
+}
+
+/// Slots lo..=hi whose elements a permutation event can move, None for
+/// events that move nothing.
+fn moved_range(event: &SortEvent) -> Option<(usize, usize)> {
+    match event {
+        SortEvent::Swap { i, j } if i != j => Some((*i.min(j), *i.max(j))),
+        SortEvent::BlockMove(m) if m.len > 0 && m.from != m.to => {
+            Some((m.from.min(m.to), m.from.max(m.to) + m.len - 1))
+        }
+        SortEvent::Rotate(r) => Some((r.lo, r.hi)),
+        SortEvent::Flip { k } => Some((0, *k)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pregen::{pregen_sort, Algorithm};
+
+    fn frames(keyframes: &Keyframes, e: usize) -> Vec<(f32, f32, i32)> {
+        let range = keyframes.offsets[e] as usize..keyframes.offsets[e + 1] as usize;
+        range
+            .map(|k| (keyframes.times[k], keyframes.positions[k], keyframes.values[k]))
+            .collect()
+    }
+
+    #[test]
+    fn test_swap_moves_both_elements() {
+        let events = vec![SortEvent::Compare { i: 0, j: 1 }, SortEvent::Swap { i: 0, j: 1 }];
+        let keyframes = keyframes(&[9, 4], &events, &CostModel::default()).unwrap();
+
+        // Compare costs 1, then the Swap slides over 2 units
+        assert_eq!(frames(&keyframes, 0), vec![(0.0, 0.0, 9), (1.0, 0.0, 9), (3.0, 1.0, 9)]);
+        assert_eq!(frames(&keyframes, 1), vec![(0.0, 1.0, 4), (1.0, 1.0, 4), (3.0, 0.0, 4)]);
+        assert_eq!(keyframes.duration, 3.0);
+    }
+
+    #[test]
+    fn test_overwrite_changes_value_in_place() {
+        let events = vec![SortEvent::Overwrite { idx: 1, old_val: 4, new_val: 7 }];
+        let keyframes = keyframes(&[9, 4], &events, &CostModel::default()).unwrap();
+
+        assert_eq!(frames(&keyframes, 0), vec![(0.0, 0.0, 9)]);
+        assert_eq!(frames(&keyframes, 1), vec![(0.0, 1.0, 4), (1.0, 1.0, 7)]);
+        assert_eq!(keyframes.sample(1, 0.5), (1.0, 5.5));
+    }
+
+    #[test]
+    fn test_far_swap_skips_elements_between() {
+        let events = vec![SortEvent::Swap { i: 0, j: 3 }];
+        let keyframes = keyframes(&[1, 2, 3, 4], &events, &CostModel::default()).unwrap();
+
+        assert_eq!(frames(&keyframes, 1), vec![(0.0, 1.0, 2)]);
+        assert_eq!(frames(&keyframes, 3), vec![(0.0, 3.0, 4), (2.0, 0.0, 4)]);
+    }
+
+    #[test]
+    fn test_block_moves_and_flips() {
+        let events = vec![SortEvent::block_move(2, 0, 1), SortEvent::Flip { k: 1 }];
+        let keyframes = keyframes(&[1, 2, 3], &events, &CostModel::uniform()).unwrap();
+
+        // [1, 2, 3] -> [3, 1, 2] -> [1, 3, 2]
+        let at_end = |e| keyframes.sample(e, keyframes.duration as f32).0;
+        assert_eq!((at_end(0), at_end(1), at_end(2)), (0.0, 2.0, 1.0));
+    }
+
+    #[test]
+    fn test_final_positions_match_sorted_order() {
+        let input = vec![5, 3, 8, 1, 9, 2, 7];
+        let mut sorted = input.clone();
+        for &algorithm in &[Algorithm::QuickSortLL, Algorithm::Insertion, Algorithm::Pancake] {
+            let events = pregen_sort(algorithm, &mut input.clone());
+            let keyframes = keyframes(&input, &events, &CostModel::default()).unwrap();
+
+            let mut drawn = vec![0; input.len()];
+            for e in 0..keyframes.len() {
+                let (position, value) = keyframes.sample(e, keyframes.duration as f32);
+                drawn[position as usize] = value as i32;
+            }
+            sorted.sort();
+            assert_eq!(drawn, sorted, "{}", algorithm.as_str());
+        }
+    }
+
+    #[test]
+    fn test_zero_cost_events_take_no_time() {
+        let events = vec![SortEvent::EnterRange { lo: 0, hi: 1 }, SortEvent::Swap { i: 0, j: 1 }];
+        let keyframes = keyframes(&[2, 1], &events, &CostModel::default()).unwrap();
+
+        assert_eq!(frames(&keyframes, 0), vec![(0.0, 0.0, 2), (2.0, 1.0, 2)]);
+    }
+
+    #[test]
+    fn test_rejects_foreign_trace() {
+        let events = vec![SortEvent::Swap { i: 0, j: 4 }];
+        assert!(keyframes(&[1, 2], &events, &CostModel::default()).is_err());
+    }
+
+    #[test]
+    fn test_empty_input() {
+        let keyframes = keyframes(&[], &[SortEvent::Done], &CostModel::default()).unwrap();
+
+        assert!(keyframes.is_empty());
+        assert_eq!(keyframes.offsets, vec![0]);
+    }
+}
@@ -1,74 +1,107 @@
-pub mod events;
-pub mod live;
-pub mod pregen;
-
-use wasm_bindgen::prelude::*;
-use events::SortEvent;
-use pregen::Algorithm;
-
-/// Initialize panic hook for better error messages in browser console
-#[wasm_bindgen(start)]
-pub fn init() {
-    #[cfg(feature = "console_error_panic_hook")]
-    console_error_panic_hook::set_once();
-}
-
-/// Run a pregeneration sort on the given array.
-///
-/// # Arguments
-/// * `algorithm` - Name of the sorting algorithm ("bubble", "quicksort")
-/// * `array` - JavaScript array of numbers to sort
-///
-/// # Returns
-/// Array of SortEvents describing all operations performed
-#[wasm_bindgen]
-pub fn pregen_sort(algorithm: &str, array: JsValue) -> Result<JsValue, JsValue> {
-    // Parse algorithm name
-    let algo = Algorithm::from_str(algorithm)
-        .ok_or_else(|| JsValue::from_str(&format!("Unknown algorithm: {}", algorithm)))?;
+//! SortForge core: sorting algorithms that emit semantic events.
+//!
+//! The crate builds as a plain Rust library. The `wasm` feature (on by
+//! default) adds the wasm-bindgen exports used by the web UI; native
+//! consumers can disable default features and call the same algorithms
+//! directly:
+//! - `pregen_sort` runs a full sort and returns its event trace.
+//! - `live::LiveSort` steps a sort incrementally.
+//! - `SortEvent` serializes with serde, so traces can be saved or replayed.
+//!
+//! Without the `std` feature (on by default) the crate is `no_std` and
+//! needs only `alloc`: events, `pregen` and `live` work the same on
+//! embedded targets; the modules around them need std.
+//!
+//! The distribution, network, esoteric and string algorithm families are
+//! behind features of those names (all on by default via `all-families`);
+//! `pregen::Algorithm` only has the families that were compiled in.
+//!
+//! The `native` feature builds the `sort-forge` CLI for offline traces and
+//! benchmarks; `parallel` runs `batch` jobs and races on a rayon thread
+//! pool. `wasm-threads` backs that pool with Web Workers, see `threads`.
 
-    // Convert JS array to Rust Vec
-    let mut arr: Vec<i32> = events::js_to_array(array)?;
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 
-    // Run the sort
-    let events = pregen::pregen_sort(algo, &mut arr);
+extern crate alloc;
+// The cdylib needs a panic handler and an allocator; hosted targets take
+// std's, bare-metal ones drop the cdylib. Linked without a name, so `std::`
+// paths still fail to resolve in a build without the std feature.
+#[cfg(not(any(feature = "std", test, target_os = "none")))]
+extern crate std as _;
 
-    // Convert events to JS
-    events::events_to_js(&events)
-}
+mod prelude;
 
-/// Get the sorted array after running pregen_sort.
-/// This is a convenience function to also get the final sorted result.
-#[wasm_bindgen]
-pub fn pregen_sort_with_result(algorithm: &str, array: JsValue) -> Result<JsValue, JsValue> {
-    let algo = Algorithm::from_str(algorithm)
-        .ok_or_else(|| JsValue::from_str(&format!("Unknown algorithm: {}", algorithm)))?;
-
-    let mut arr: Vec<i32> = events::js_to_array(array)?;
-    let events = pregen::pregen_sort(algo, &mut arr);
-
-    // Return both events and sorted array
-    let result = PregenResult {
-        events,
-        sorted_array: arr,
-    };
+// The no_std + alloc core: events, the sorting algorithms and the live
+// steppers, with the trace transforms they build on.
+pub mod argsort;
+pub mod coalesce;
+pub mod compact;
+pub mod cost;
+pub mod events;
+pub mod float;
+pub mod heap_tree;
+pub mod key_transform;
+pub mod keyframes;
+pub mod live;
+pub mod narrate;
+pub mod normalize;
+pub mod packed;
+pub mod patterns;
+pub mod permutation;
+pub mod pregen;
+pub mod rotation;
+pub mod snapshot;
+pub mod subrange;
+pub mod summary;
+pub mod sweep;
+pub mod throttle;
 
-    serde_wasm_bindgen::to_value(&result).map_err(|e| JsValue::from_str(&e.to_string()))
-}
+#[cfg(feature = "std")]
+pub mod audio;
+#[cfg(feature = "std")]
+pub mod batch;
+#[cfg(feature = "std")]
+pub mod comparator;
+#[cfg(feature = "std")]
+pub mod cursor;
+#[cfg(feature = "std")]
+pub mod engine;
+#[cfg(feature = "std")]
+pub mod handoff;
+#[cfg(all(feature = "std", feature = "networks"))]
+pub mod networks;
+#[cfg(feature = "std")]
+pub mod parity;
+#[cfg(feature = "std")]
+pub mod precheck;
+#[cfg(feature = "std")]
+pub mod priority_queue;
+#[cfg(feature = "std")]
+pub mod profile;
+#[cfg(feature = "std")]
+pub mod race;
+#[cfg(feature = "std")]
+pub mod search;
+#[cfg(feature = "std")]
+pub mod seed_sweep;
+#[cfg(feature = "std")]
+pub mod select;
+#[cfg(feature = "std")]
+pub mod session;
+#[cfg(feature = "wasm-threads")]
+pub mod threads;
+#[cfg(feature = "std")]
+pub mod tournament;
+#[cfg(feature = "std")]
+pub mod trace;
+#[cfg(feature = "std")]
+pub mod validate;
+#[cfg(feature = "std")]
+pub mod verify;
 
-/// Result of a pregeneration sort, including events and final array.
-#[derive(serde::Serialize)]
-struct PregenResult {
-    events: Vec<SortEvent>,
-    sorted_array: Vec<i32>,
-}
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
-/// Get list of available algorithms.
-#[wasm_bindgen]
-pub fn get_available_algorithms() -> JsValue {
-    let algorithms = Algorithm::all()
-        .iter()
-        .map(Algorithm::as_str)
-        .collect::<Vec<_>>();
-    serde_wasm_bindgen::to_value(&algorithms).unwrap()
-}
+pub use events::SortEvent;
+pub use live::{LiveSort, Stepper};
+pub use pregen::{pregen_sort, Algorithm};
@@ -0,0 +1,253 @@
+//! Many live sorts stepped together, e.g. a grid of small visualizations.
+//!
+//! A `StepperArena` keeps the steppers of all its cells in one vector and
+//! their arrays back to back in another, so a 5×4 grid is two allocations
+//! instead of forty, and `step_all` advances every cell in one call.
+//! Cells are addressed by the index `push` returns.
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+use crate::prelude::*;
+use crate::events::SortEvent;
+#[cfg(feature = "wasm")]
+use crate::events;
+use crate::summary::{self, BucketSummary};
+use super::StepperKind;
+
+struct Cell {
+    stepper: StepperKind,
+    /// Start of the cell's array in the shared buffer.
+    offset: usize,
+    len: usize,
+    /// Set once the stepper's Done has been returned.
+    finished: bool,
+}
+
+/// Live sorts whose steppers and arrays are stored contiguously.
+#[derive(Default)]
+pub struct StepperArena {
+    cells: Vec<Cell>,
+    data: Vec<i32>,
+}
+
+impl StepperArena {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a live sort of a copy of array; returns its cell index, or None
+    /// if the algorithm has no live implementation.
+    pub fn push(&mut self, algorithm: &str, array: &[i32]) -> Option<usize> {
+        let stepper = StepperKind::new(algorithm, array.len())?;
+        let offset = self.data.len();
+        self.cells.push(Cell { stepper, offset, len: array.len(), finished: false });
+        self.data.extend_from_slice(array);
+        Some(self.cells.len() - 1)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Execute up to `limit` steps of one cell. Once a cell has returned its
+    /// Done it returns no more events.
+    pub fn step(&mut self, cell: usize, limit: usize) -> Vec<SortEvent> {
+        let Cell { stepper, offset, len, finished } = &mut self.cells[cell];
+        if *finished {
+            return Vec::new();
+        }
+        let events = stepper.step(&mut self.data[*offset..*offset + *len], limit);
+        *finished = matches!(events.last(), Some(SortEvent::Done));
+        events
+    }
+
+    /// Execute up to `limit` steps of every cell, returning each cell's
+    /// events in cell order.
+    pub fn step_all(&mut self, limit: usize) -> Vec<Vec<SortEvent>> {
+        (0..self.cells.len()).map(|cell| self.step(cell, limit)).collect()
+    }
+
+    /// True once the cell has returned its Done.
+    pub fn is_done(&self, cell: usize) -> bool {
+        self.cells[cell].finished
+    }
+
+    pub fn all_done(&self) -> bool {
+        self.cells.iter().all(|cell| cell.finished)
+    }
+
+    /// Current array state of one cell.
+    pub fn array(&self, cell: usize) -> &[i32] {
+        let Cell { offset, len, .. } = self.cells[cell];
+        &self.data[offset..offset + len]
+    }
+
+    /// One cell's array reduced to `buckets` min/max/mean summaries.
+    pub fn summary(&self, cell: usize, buckets: usize) -> Vec<BucketSummary> {
+        summary::summarize(self.array(cell), buckets)
+    }
+}
+
+/// Wasm-exposed stepper arena.
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_name = StepperArena)]
+#[derive(Default)]
+pub struct WasmStepperArena {
+    inner: StepperArena,
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_class = StepperArena)]
+impl WasmStepperArena {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> WasmStepperArena {
+        WasmStepperArena::default()
+    }
+
+    /// Add a live sort of a copy of array, return its cell index.
+    pub fn push(&mut self, algorithm: &str, array: JsValue) -> Result<usize, JsValue> {
+        let arr: Vec<i32> = events::js_to_array(array)?;
+        self.inner
+            .push(algorithm, &arr)
+            .ok_or_else(|| JsValue::from_str(&format!("Unknown live algorithm: {}", algorithm)))
+    }
+
+    /// Number of cells.
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Execute up to `limit` steps of one cell, return its events.
+    pub fn step(&mut self, cell: usize, limit: usize) -> Result<JsValue, JsValue> {
+        self.check_cell(cell)?;
+        events::events_to_js(&self.inner.step(cell, limit))
+    }
+
+    /// Execute up to `limit` steps of every cell. Returns one array of
+    /// events per cell; finished cells get an empty array.
+    pub fn step_all(&mut self, limit: usize) -> Result<JsValue, JsValue> {
+        let output = js_sys::Array::new();
+        for events in self.inner.step_all(limit) {
+            output.push(&events::events_to_js(&events)?);
+        }
+        Ok(output.into())
+    }
+
+    pub fn is_done(&self, cell: usize) -> Result<bool, JsValue> {
+        self.check_cell(cell)?;
+        Ok(self.inner.is_done(cell))
+    }
+
+    pub fn all_done(&self) -> bool {
+        self.inner.all_done()
+    }
+
+    /// Get one cell's current array.
+    pub fn get_array(&self, cell: usize) -> Result<js_sys::Int32Array, JsValue> {
+        self.check_cell(cell)?;
+        Ok(js_sys::Int32Array::from(self.inner.array(cell)))
+    }
+
+    /// Summarize one cell's array as a Float64Array of `[min, max, mean]`
+    /// per bucket.
+    pub fn get_summary(
+        &self,
+        cell: usize,
+        buckets: usize,
+    ) -> Result<js_sys::Float64Array, JsValue> {
+        self.check_cell(cell)?;
+        let summary = self.inner.summary(cell, buckets);
+        Ok(js_sys::Float64Array::from(summary::flatten_summary(&summary).as_slice()))
+    }
+
+    fn check_cell(&self, cell: usize) -> Result<(), JsValue> {
+        if cell >= self.inner.len() {
+            let message = format!("No cell {} in an arena of {}", cell, self.inner.len());
+            return Err(JsValue::from_str(&message));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::live::LiveSort;
+
+    #[test]
+    fn test_step_all_sorts_every_cell() {
+        let mut arena = StepperArena::new();
+        for cell in 0..20 {
+            let algorithm = if cell % 2 == 0 { "bubble" } else { "quicksort_ll" };
+            let array: Vec<i32> = (0..30).map(|x| (x * 7 + cell) % 31).collect();
+            assert_eq!(arena.push(algorithm, &array), Some(cell as usize));
+        }
+
+        while !arena.all_done() {
+            arena.step_all(16);
+        }
+        for cell in 0..arena.len() {
+            assert!(arena.array(cell).windows(2).all(|w| w[0] <= w[1]), "cell {}", cell);
+        }
+    }
+
+    #[test]
+    fn test_cells_match_separate_live_sorts() {
+        let inputs = [vec![5, 3, 8, 1, 9, 2], vec![4, 4, 1], vec![]];
+        let mut arena = StepperArena::new();
+        let mut lives: Vec<LiveSort> = Vec::new();
+        for input in &inputs {
+            arena.push("quicksort_ll", input).unwrap();
+            lives.push(LiveSort::new("quicksort_ll", input.clone()).unwrap());
+        }
+
+        while !arena.all_done() {
+            let batches = arena.step_all(3);
+            for (cell, events) in batches.iter().enumerate() {
+                if !events.is_empty() {
+                    assert_eq!(events, &lives[cell].step(3));
+                }
+                assert_eq!(arena.array(cell), lives[cell].array());
+            }
+        }
+    }
+
+    #[test]
+    fn test_finished_cells_are_quiet() {
+        let mut arena = StepperArena::new();
+        arena.push("bubble", &[1]).unwrap();
+        arena.push("bubble", &[3, 2, 1]).unwrap();
+
+        let first = arena.step_all(100);
+        assert!(matches!(first[0].last(), Some(SortEvent::Done)));
+        assert!(arena.is_done(0));
+        while !arena.all_done() {
+            assert!(arena.step_all(1)[0].is_empty());
+        }
+        assert_eq!(arena.array(1), &[1, 2, 3]);
+    }
+
+    #[test]
+    fn test_unknown_algorithm() {
+        let mut arena = StepperArena::new();
+        assert_eq!(arena.push("heap", &[1, 2]), None);
+        assert!(arena.is_empty());
+    }
+
+    #[test]
+    fn test_summary_is_per_cell() {
+        let mut arena = StepperArena::new();
+        arena.push("bubble", &[1, 2]).unwrap();
+        arena.push("bubble", &[10, 20]).unwrap();
+
+        assert_eq!(arena.summary(1, 1), summary::summarize(&[10, 20], 1));
+    }
+}
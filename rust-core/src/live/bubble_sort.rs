@@ -1,8 +1,11 @@
 //! Bubble Sort stepper for V2 (Live) engine.
 
+use serde::{Deserialize, Serialize};
+use crate::prelude::*;
 use crate::events::SortEvent;
 use super::Stepper;
 
+#[derive(Serialize, Deserialize)]
 pub struct BubbleSortStepper {
     i: usize,        // outer loop index
     j: usize,        // inner loop index
@@ -0,0 +1,152 @@
+//! Comb Sort stepper for V2 (Live) engine.
+//!
+//! Emits the same events as the pregen comb sort, GapChanges included, and
+//! reports the gap of the pass in progress through `current_gap`.
+
+use serde::{Deserialize, Serialize};
+use crate::prelude::*;
+use crate::events::SortEvent;
+use crate::pregen::comb_sort::next_gap;
+use super::Stepper;
+
+#[derive(Serialize, Deserialize)]
+pub struct CombSortStepper {
+    n: usize,
+    gap: usize,      // gap of the current pass, 0 before the first
+    i: usize,        // left index of the next comparison
+    in_pass: bool,
+    sorted: bool,    // a gap-1 pass with no swaps so far
+    done: bool,
+}
+
+impl CombSortStepper {
+    pub fn new(len: usize) -> Self {
+        Self {
+            n: len,
+            gap: 0,
+            i: 0,
+            in_pass: false,
+            sorted: false,
+            done: len <= 1,
+        }
+    }
+}
+
+impl Stepper for CombSortStepper {
+    fn step(&mut self, arr: &mut [i32], limit: usize) -> Vec<SortEvent> {
+        let mut events = Vec::with_capacity(limit);
+
+        while events.len() < limit {
+            if self.done {
+                if events.is_empty() || !matches!(events.last(), Some(SortEvent::Done)) {
+                    events.push(SortEvent::Done);
+                }
+                break;
+            }
+
+            if !self.in_pass {
+                // Start the next pass, shrinking the gap
+                let prev = self.gap;
+                self.gap = next_gap(prev, self.n);
+                self.sorted = self.gap == 1;
+                self.i = 0;
+                self.in_pass = true;
+                if self.gap != prev {
+                    events.push(SortEvent::GapChange { gap: self.gap, prev });
+                }
+                continue;
+            }
+
+            if self.i >= self.n - self.gap {
+                self.in_pass = false;
+                if self.sorted {
+                    // Done goes out with the last GapChange, so a stepper
+                    // that reports done has emitted it
+                    events.push(SortEvent::GapChange { gap: 0, prev: self.gap });
+                    events.push(SortEvent::Done);
+                    self.gap = 0;
+                    self.done = true;
+                    break;
+                }
+                continue;
+            }
+
+            let (i, j) = (self.i, self.i + self.gap);
+            events.push(SortEvent::Compare { i, j });
+
+            if arr[i] > arr[j] {
+                if events.len() < limit {
+                    events.push(SortEvent::Swap { i, j });
+                    arr.swap(i, j);
+                    self.sorted = false;
+                } else {
+                    // Out of room for the swap: redo the comparison next step
+                    events.pop();
+                    break;
+                }
+            }
+
+            self.i += 1;
+        }
+
+        events
+    }
+
+    fn is_done(&self) -> bool {
+        self.done
+    }
+
+    fn current_gap(&self) -> Option<usize> {
+        (self.gap > 0).then_some(self.gap)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pregen::{pregen_sort, Algorithm};
+
+    fn run(input: &[i32], limit: usize) -> (Vec<i32>, Vec<SortEvent>) {
+        let mut arr = input.to_vec();
+        let mut stepper = CombSortStepper::new(arr.len());
+        let mut events = Vec::new();
+        while !stepper.is_done() {
+            events.extend(stepper.step(&mut arr, limit));
+        }
+        (arr, events)
+    }
+
+    #[test]
+    fn test_comb_stepper_matches_pregen() {
+        let input = vec![9, 3, 7, 1, 8, 2, 6, 4, 5, 0, 11, 10];
+        let expected = pregen_sort(Algorithm::Comb, &mut input.clone());
+        for limit in [2, 3, 100] {
+            let (arr, events) = run(&input, limit);
+            assert_eq!(arr, vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11]);
+            assert_eq!(events, expected, "limit {}", limit);
+        }
+    }
+
+    #[test]
+    fn test_comb_stepper_reports_gap() {
+        let mut arr: Vec<i32> = (0..20).rev().collect();
+        let mut stepper = CombSortStepper::new(arr.len());
+        assert_eq!(stepper.current_gap(), None);
+
+        let events = stepper.step(&mut arr, 2);
+        assert_eq!(events[0], SortEvent::GapChange { gap: 15, prev: 0 });
+        assert_eq!(stepper.current_gap(), Some(15));
+
+        while !stepper.is_done() {
+            stepper.step(&mut arr, 100);
+        }
+        assert_eq!(stepper.current_gap(), None);
+    }
+
+    #[test]
+    fn test_comb_stepper_trivial() {
+        let mut stepper = CombSortStepper::new(1);
+        assert!(stepper.is_done());
+        assert_eq!(stepper.step(&mut [4], 10), vec![SortEvent::Done]);
+    }
+}
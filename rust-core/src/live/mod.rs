@@ -3,14 +3,31 @@
 //! State machine implementations that execute incrementally,
 //! suitable for large arrays where pregeneration would use too much memory.
 
+pub mod arena;
 pub mod bubble_sort;
+pub mod comb_sort;
 pub mod quicksort_ll;
+pub mod tuner;
 
+#[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::prelude::*;
 use crate::events::SortEvent;
+#[cfg(feature = "wasm")]
+use crate::packed::{self, Ring};
+#[cfg(feature = "wasm")]
+use crate::profile;
+use crate::summary::{self, BucketSummary};
+use crate::sweep::VerificationSweep;
+#[cfg(feature = "wasm")]
+use crate::throttle;
 
+pub use arena::StepperArena;
 pub use bubble_sort::BubbleSortStepper;
+pub use comb_sort::CombSortStepper;
 pub use quicksort_ll::QuickSortLLStepper;
+pub use tuner::BatchTuner;
 
 /// Trait for live stepping sorting algorithms.
 pub trait Stepper {
@@ -19,21 +36,156 @@ pub trait Stepper {
 
     /// Check if sort is complete.
     fn is_done(&self) -> bool;
+
+    /// Gap of the pass in progress, for gap-based sorts.
+    fn current_gap(&self) -> Option<usize> {
+        None
+    }
 }
 
+/// Names of the available live algorithms.
+pub const LIVE_ALGORITHMS: [&str; 3] = ["bubble", "comb", "quicksort_ll"];
+
 /// Internal enum to hold concrete stepper types.
+#[derive(Serialize, Deserialize)]
 enum StepperKind {
     Bubble(BubbleSortStepper),
+    Comb(CombSortStepper),
     QuickSortLL(QuickSortLLStepper),
 }
 
+impl StepperKind {
+    /// Stepper for an array of length `len`; None if the algorithm is unknown.
+    fn new(algorithm: &str, len: usize) -> Option<StepperKind> {
+        match algorithm.to_lowercase().as_str() {
+            "bubble" | "bubblesort" | "bubble_sort" => {
+                Some(StepperKind::Bubble(BubbleSortStepper::new(len)))
+            }
+            "comb" | "combsort" | "comb_sort" => {
+                Some(StepperKind::Comb(CombSortStepper::new(len)))
+            }
+            "quicksort_ll" | "quicksortll" | "quick_sort_ll" => {
+                Some(StepperKind::QuickSortLL(QuickSortLLStepper::new(len)))
+            }
+            _ => None,
+        }
+    }
+
+    fn step(&mut self, arr: &mut [i32], limit: usize) -> Vec<SortEvent> {
+        match self {
+            StepperKind::Bubble(s) => s.step(arr, limit),
+            StepperKind::Comb(s) => s.step(arr, limit),
+            StepperKind::QuickSortLL(s) => s.step(arr, limit),
+        }
+    }
+
+    fn is_done(&self) -> bool {
+        match self {
+            StepperKind::Bubble(s) => s.is_done(),
+            StepperKind::Comb(s) => s.is_done(),
+            StepperKind::QuickSortLL(s) => s.is_done(),
+        }
+    }
+
+    fn current_gap(&self) -> Option<usize> {
+        match self {
+            StepperKind::Bubble(s) => s.current_gap(),
+            StepperKind::Comb(s) => s.current_gap(),
+            StepperKind::QuickSortLL(s) => s.current_gap(),
+        }
+    }
+}
+
+/// A live sort that owns its array: a stepper plus the data it sorts.
+/// Serializes with its full state, so a sort can be saved and resumed.
+#[derive(Serialize, Deserialize)]
+pub struct LiveSort {
+    inner: StepperKind,
+    arr: Vec<i32>,
+    /// Set by `finish_with_sweep`; runs after the stepper, in place of its Done.
+    sweep: Option<VerificationSweep>,
+}
+
+impl LiveSort {
+    /// Create a live sort for the given algorithm and array.
+    /// Returns None if the algorithm is unknown.
+    pub fn new(algorithm: &str, arr: Vec<i32>) -> Option<LiveSort> {
+        let inner = StepperKind::new(algorithm, arr.len())?;
+
+        Some(LiveSort {
+            inner,
+            arr,
+            sweep: None,
+        })
+    }
+
+    /// End the sort with a verification sweep: once the stepper finishes,
+    /// further steps emit Compare/MarkSorted events across the array, then
+    /// Done. Call it before the stepper finishes, or its Done will already
+    /// have been emitted. Has no effect if the sweep has already started.
+    pub fn finish_with_sweep(&mut self) {
+        if self.sweep.is_none() {
+            self.sweep = Some(VerificationSweep::new(self.arr.len()));
+        }
+    }
+
+    /// Execute up to `limit` steps, return events generated.
+    pub fn step(&mut self, limit: usize) -> Vec<SortEvent> {
+        let stepper_done = self.stepper_done();
+        let mut events = self.inner.step(&mut self.arr, limit);
+
+        if let Some(sweep) = &mut self.sweep {
+            // The stepper's Done moves to the end of the sweep, which starts
+            // with the next call. A finished stepper only repeats its Done
+            if matches!(events.last(), Some(SortEvent::Done)) {
+                events.pop();
+            }
+            if stepper_done {
+                sweep.step(&self.arr, limit, &mut events);
+            }
+        }
+
+        events
+    }
+
+    /// Check if sort is complete, including the sweep if one was requested.
+    pub fn is_done(&self) -> bool {
+        self.stepper_done() && self.sweep.as_ref().is_none_or(VerificationSweep::is_done)
+    }
+
+    fn stepper_done(&self) -> bool {
+        self.inner.is_done()
+    }
+
+    /// Gap of the pass in progress for gap-based algorithms (comb), None
+    /// for others and before the first or after the last pass.
+    pub fn current_gap(&self) -> Option<usize> {
+        self.inner.current_gap()
+    }
+
+    /// Current array state.
+    pub fn array(&self) -> &[i32] {
+        &self.arr
+    }
+
+    /// Current array state reduced to `buckets` min/max/mean summaries.
+    pub fn summary(&self, buckets: usize) -> Vec<BucketSummary> {
+        summary::summarize(&self.arr, buckets)
+    }
+}
+
 /// Wasm-exposed live stepper wrapper.
+#[cfg(feature = "wasm")]
 #[wasm_bindgen]
 pub struct LiveStepper {
-    inner: StepperKind,
-    arr: Vec<i32>,
+    inner: LiveSort,
+    /// Buffer registered with `set_ring_buffer`, with its write position.
+    ring: Option<(js_sys::Int32Array, Ring)>,
+    /// Batch sizes for `step_auto`, once `enable_auto_batch` is called.
+    tuner: Option<BatchTuner>,
 }
 
+#[cfg(feature = "wasm")]
 #[wasm_bindgen]
 impl LiveStepper {
     /// Create a new live stepper for the given algorithm and array.
@@ -42,48 +194,210 @@ impl LiveStepper {
         let arr: Vec<i32> = serde_wasm_bindgen::from_value(array)
             .map_err(|e| JsValue::from_str(&e.to_string()))?;
 
-        let inner = match algorithm.to_lowercase().as_str() {
-            "bubble" | "bubblesort" | "bubble_sort" => {
-                StepperKind::Bubble(BubbleSortStepper::new(arr.len()))
-            }
-            "quicksort_ll" | "quicksortll" | "quick_sort_ll" => {
-                StepperKind::QuickSortLL(QuickSortLLStepper::new(arr.len()))
-            }
-            _ => return Err(JsValue::from_str(&format!("Unknown live algorithm: {}", algorithm))),
-        };
+        let inner = LiveSort::new(algorithm, arr)
+            .ok_or_else(|| JsValue::from_str(&format!("Unknown live algorithm: {}", algorithm)))?;
 
-        Ok(LiveStepper { inner, arr })
+        Ok(LiveStepper { inner, ring: None, tuner: None })
     }
 
     /// Execute up to `limit` steps, return events generated.
     pub fn step(&mut self, limit: usize) -> Result<JsValue, JsValue> {
-        let events = match &mut self.inner {
-            StepperKind::Bubble(s) => s.step(&mut self.arr, limit),
-            StepperKind::QuickSortLL(s) => s.step(&mut self.arr, limit),
+        let events = self.inner.step(limit);
+
+        serde_wasm_bindgen::to_value(&events)
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Turn on `step_auto`, sizing each batch to take about `budget_ms`
+    /// (see `tuner::BatchTuner`). Calling it again changes the budget and
+    /// keeps the measurements.
+    pub fn enable_auto_batch(&mut self, budget_ms: f64) -> Result<(), JsValue> {
+        match &mut self.tuner {
+            Some(tuner) => tuner.set_budget_ms(budget_ms),
+            None => BatchTuner::new(budget_ms).map(|tuner| self.tuner = Some(tuner)),
+        }
+        .map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Step a batch of the tuned size and return its events. The time to
+    /// step and convert the events is measured and tunes the next batch.
+    pub fn step_auto(&mut self) -> Result<JsValue, JsValue> {
+        let Some(tuner) = &mut self.tuner else {
+            return Err(JsValue::from_str("Auto batching is not enabled"));
+        };
+
+        let steps = tuner.batch();
+        let begin = profile::now_millis();
+        let events = self.inner.step(steps);
+        let result = serde_wasm_bindgen::to_value(&events)
+            .map_err(|e| JsValue::from_str(&e.to_string()))?;
+        tuner.record(steps, events.len(), profile::now_millis() - begin);
+
+        Ok(result)
+    }
+
+    /// Batch size of the next `step_auto`, if auto batching is enabled.
+    pub fn auto_batch_size(&self) -> Option<usize> {
+        self.tuner.as_ref().map(BatchTuner::batch)
+    }
+
+    /// Smoothed events per millisecond measured by `step_auto`.
+    pub fn events_per_ms(&self) -> Option<f64> {
+        self.tuner.as_ref().and_then(BatchTuner::events_per_ms)
+    }
+
+    /// Register an Int32Array for `step_into_ring` to write packed events
+    /// into (see the `packed` module), reusing it as a ring. Replaces any
+    /// previously registered buffer and starts writing at its beginning.
+    pub fn set_ring_buffer(&mut self, buffer: js_sys::Int32Array) -> Result<(), JsValue> {
+        let ring = Ring::new(buffer.length() as usize).map_err(|e| JsValue::from_str(&e))?;
+        self.ring = Some((buffer, ring));
+        Ok(())
+    }
+
+    /// Record slot where the next `step_into_ring` batch starts.
+    pub fn ring_head(&self) -> Option<usize> {
+        self.ring.as_ref().map(|(_, ring)| ring.head())
+    }
+
+    /// Execute up to `limit` steps and write the events into the ring
+    /// buffer instead of returning a new array. Returns how many events were
+    /// written, starting at the previous `ring_head()` and wrapping around.
+    /// A step emits at most `2 * limit + 1` events, which must fit the ring.
+    pub fn step_into_ring(&mut self, limit: usize) -> Result<usize, JsValue> {
+        let Some((buffer, ring)) = &mut self.ring else {
+            return Err(JsValue::from_str("No ring buffer registered"));
         };
+        if 2 * limit + 1 > ring.capacity() {
+            return Err(JsValue::from_str(&format!(
+                "A step of {} may not fit in a ring of {} events",
+                limit,
+                ring.capacity()
+            )));
+        }
+
+        let events = self.inner.step(limit);
+        let words = packed::pack_events(&events).map_err(|e| JsValue::from_str(&e))?;
+        let [first, second] = ring.place(events.len()).map_err(|e| JsValue::from_str(&e))?;
+        let split = first.len();
+        buffer.subarray(first.start as u32, first.end as u32).copy_from(&words[..split]);
+        buffer.subarray(second.start as u32, second.end as u32).copy_from(&words[split..]);
+        Ok(events.len())
+    }
+
+    /// Execute up to `limit` steps and return the events cut down to at
+    /// most `budget` for one frame (see `throttle::throttle_frame`).
+    pub fn step_throttled(&mut self, limit: usize, budget: usize) -> Result<JsValue, JsValue> {
+        let events = throttle::throttle_frame(&self.inner.step(limit), budget);
 
         serde_wasm_bindgen::to_value(&events)
             .map_err(|e| JsValue::from_str(&e.to_string()))
     }
 
+    /// Resume a sort from a continuation returned by `pregen_sort_capped`.
+    pub fn from_continuation(continuation: JsValue) -> Result<LiveStepper, JsValue> {
+        let inner: LiveSort = serde_wasm_bindgen::from_value(continuation)
+            .map_err(|e| JsValue::from_str(&e.to_string()))?;
+
+        Ok(LiveStepper { inner, ring: None, tuner: None })
+    }
+
+    /// End the sort with a verification sweep before Done.
+    pub fn finish_with_sweep(&mut self) {
+        self.inner.finish_with_sweep();
+    }
+
     /// Check if sort is complete.
     pub fn is_done(&self) -> bool {
-        match &self.inner {
-            StepperKind::Bubble(s) => s.is_done(),
-            StepperKind::QuickSortLL(s) => s.is_done(),
-        }
+        self.inner.is_done()
+    }
+
+    /// Gap of the pass in progress for gap-based algorithms, to label the
+    /// active gap with. Undefined otherwise.
+    pub fn current_gap(&self) -> Option<usize> {
+        self.inner.current_gap()
     }
 
     /// Get current array state.
     pub fn get_array(&self) -> Result<JsValue, JsValue> {
-        serde_wasm_bindgen::to_value(&self.arr)
+        serde_wasm_bindgen::to_value(self.inner.array())
             .map_err(|e| JsValue::from_str(&e.to_string()))
     }
+
+    /// Summarize the current array in `buckets` screen columns, without
+    /// copying the whole array to JS. Returns a Float64Array of
+    /// `[min, max, mean]` per bucket.
+    pub fn get_summary(&self, buckets: usize) -> js_sys::Float64Array {
+        let summary = self.inner.summary(buckets);
+        js_sys::Float64Array::from(summary::flatten_summary(&summary).as_slice())
+    }
 }
 
 /// Get list of available live algorithms.
+#[cfg(feature = "wasm")]
 #[wasm_bindgen]
 pub fn get_live_algorithms() -> JsValue {
-    let algorithms = vec!["bubble", "quicksort_ll"];
-    serde_wasm_bindgen::to_value(&algorithms).unwrap()
+    serde_wasm_bindgen::to_value(&LIVE_ALGORITHMS).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_live_sort_all_algorithms() {
+        for name in LIVE_ALGORITHMS {
+            let mut live = LiveSort::new(name, vec![5, 3, 8, 1, 9, 2]).unwrap();
+            while !live.is_done() {
+                live.step(4);
+            }
+            assert_eq!(live.array(), &[1, 2, 3, 5, 8, 9], "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_live_sort_unknown_algorithm() {
+        assert!(LiveSort::new("bogus", vec![1, 2]).is_none());
+    }
+
+    #[test]
+    fn test_live_sort_finish_with_sweep() {
+        for name in LIVE_ALGORITHMS {
+            let input = vec![5, 3, 8, 1, 9, 2];
+            let mut live = LiveSort::new(name, input.clone()).unwrap();
+            live.finish_with_sweep();
+
+            let mut events = Vec::new();
+            while !live.is_done() {
+                events.extend(live.step(4));
+            }
+
+            let marked = events.iter().filter(|e| matches!(e, SortEvent::MarkSorted { .. })).count();
+            assert_eq!(marked, input.len(), "{}", name);
+            assert_eq!(events.iter().filter(|e| **e == SortEvent::Done).count(), 1);
+            assert_eq!(events.last(), Some(&SortEvent::Done));
+        }
+    }
+
+    #[test]
+    fn test_live_sort_current_gap() {
+        let mut comb = LiveSort::new("comb", (0..40).rev().collect()).unwrap();
+        let mut bubble = LiveSort::new("bubble", vec![2, 1]).unwrap();
+        assert_eq!(comb.current_gap(), None);
+
+        let events = comb.step(1);
+        assert_eq!(events, vec![SortEvent::GapChange { gap: 30, prev: 0 }]);
+        assert_eq!(comb.current_gap(), Some(30));
+        bubble.step(1);
+        assert_eq!(bubble.current_gap(), None);
+    }
+
+    #[test]
+    fn test_live_sort_emits_done_for_trivial_input() {
+        for name in LIVE_ALGORITHMS {
+            let mut live = LiveSort::new(name, vec![7]).unwrap();
+            assert!(live.is_done());
+            assert_eq!(live.step(4), vec![SortEvent::Done], "{}", name);
+        }
+    }
 }
@@ -2,10 +2,13 @@
 //!
 //! Uses explicit stack instead of recursion for state machine approach.
 
+use serde::{Deserialize, Serialize};
+use crate::prelude::*;
 use crate::events::SortEvent;
 use super::Stepper;
 
 /// State of an in-progress partition operation.
+#[derive(Serialize, Deserialize)]
 struct PartitionState {
     lo: usize,
     hi: usize,
@@ -15,6 +18,7 @@ struct PartitionState {
     entered: bool, // whether we've emitted EnterRange
 }
 
+#[derive(Serialize, Deserialize)]
 pub struct QuickSortLLStepper {
     stack: Vec<(usize, usize)>,        // pending (lo, hi) ranges
     current: Option<PartitionState>,   // active partition
@@ -36,87 +40,123 @@ impl QuickSortLLStepper {
         stepper
     }
 
-    fn start_partition(&mut self, lo: usize, hi: usize, arr: &[i32]) {
-        self.current = Some(PartitionState {
+    fn start_partition(lo: usize, hi: usize, arr: &[i32]) -> PartitionState {
+        PartitionState {
             lo,
             hi,
             i: lo,
             j: lo,
             pivot: arr[hi],
             entered: false,
-        });
+        }
     }
 }
 
+/// Run `j_end - j` scan steps of a Lomuto partition with pivot at `hi`,
+/// returning the new partition pointer.
+///
+/// This is the hot loop at 1M+ elements: it works on locals and a single
+/// reservation for the whole run instead of going through the stepper
+/// state on every step.
+#[inline]
+fn scan(
+    arr: &mut [i32],
+    events: &mut Vec<SortEvent>,
+    mut i: usize,
+    j: usize,
+    j_end: usize,
+    hi: usize,
+    pivot: i32,
+) -> usize {
+    events.reserve(2 * (j_end - j));
+    let window = &mut arr[..=hi];
+
+    for j in j..j_end {
+        events.push(SortEvent::Compare { i: j, j: hi });
+        if window[j] <= pivot {
+            if i != j {
+                events.push(SortEvent::Swap { i, j });
+                window.swap(i, j);
+            }
+            i += 1;
+        }
+    }
+
+    i
+}
+
 impl Stepper for QuickSortLLStepper {
     fn step(&mut self, arr: &mut [i32], limit: usize) -> Vec<SortEvent> {
-        let mut events = Vec::with_capacity(limit);
+        // A step emits at most two events (Compare + Swap, or Swap + ExitRange)
+        let mut events = Vec::with_capacity(2 * limit + 1);
+        let mut budget = limit;
 
-        for _ in 0..limit {
+        // Keep the active partition in a local for the whole batch and
+        // write it back once at the end
+        let mut current = self.current.take();
+
+        while budget > 0 {
             if self.done {
-                if events.is_empty() || !matches!(events.last(), Some(SortEvent::Done)) {
+                if !matches!(events.last(), Some(SortEvent::Done)) {
                     events.push(SortEvent::Done);
                 }
                 break;
             }
 
             // If no active partition, start one from stack
-            if self.current.is_none() {
-                if let Some((lo, hi)) = self.stack.pop() {
-                    self.start_partition(lo, hi, arr);
-                } else {
-                    self.done = true;
-                    events.push(SortEvent::Done);
-                    break;
-                }
-            }
-
-            let state = self.current.as_mut().unwrap();
+            let state = match &mut current {
+                Some(state) => state,
+                None => match self.stack.pop() {
+                    Some((lo, hi)) => current.insert(Self::start_partition(lo, hi, arr)),
+                    None => {
+                        self.done = true;
+                        events.push(SortEvent::Done);
+                        break;
+                    }
+                },
+            };
 
             // Emit EnterRange on first step of partition
             if !state.entered {
                 events.push(SortEvent::EnterRange { lo: state.lo, hi: state.hi });
                 state.entered = true;
+                budget -= 1;
                 continue;
             }
 
-            // Partitioning: scan with j
-            if state.j < state.hi {
-                events.push(SortEvent::Compare { i: state.j, j: state.hi });
-
-                if arr[state.j] <= state.pivot {
-                    if state.i != state.j {
-                        events.push(SortEvent::Swap { i: state.i, j: state.j });
-                        arr.swap(state.i, state.j);
-                    }
-                    state.i += 1;
-                }
-                state.j += 1;
-            } else {
-                // Partition complete - place pivot
-                if state.i != state.hi {
-                    events.push(SortEvent::Swap { i: state.i, j: state.hi });
-                    arr.swap(state.i, state.hi);
-                }
-
-                let pivot_idx = state.i;
-                let lo = state.lo;
-                let hi = state.hi;
+            // Partitioning: scan with j, as many steps as the budget allows
+            let steps = budget.min(state.hi - state.j);
+            if steps > 0 {
+                let j_end = state.j + steps;
+                state.i = scan(arr, &mut events, state.i, state.j, j_end, state.hi, state.pivot);
+                state.j = j_end;
+                budget -= steps;
+                continue;
+            }
 
-                events.push(SortEvent::ExitRange { lo, hi });
+            // Partition complete - place pivot
+            let (lo, hi, pivot_idx) = (state.lo, state.hi, state.i);
+            if pivot_idx != hi {
+                events.push(SortEvent::Swap { i: pivot_idx, j: hi });
+                arr.swap(pivot_idx, hi);
+            }
 
-                // Push sub-ranges to stack (right first so left is processed first)
-                if pivot_idx + 1 < hi {
-                    self.stack.push((pivot_idx + 1, hi));
-                }
-                if pivot_idx > lo {
-                    self.stack.push((lo, pivot_idx - 1));
-                }
+            events.push(SortEvent::ExitRange { lo, hi });
+            budget -= 1;
 
-                self.current = None;
+            // Push sub-ranges to stack (right first so left is processed first).
+            // Single elements are skipped, as in the pregen version
+            if pivot_idx + 1 < hi {
+                self.stack.push((pivot_idx + 1, hi));
             }
+            if pivot_idx > lo + 1 {
+                self.stack.push((lo, pivot_idx - 1));
+            }
+
+            current = None;
         }
 
+        self.current = current;
         events
     }
 
@@ -179,4 +219,45 @@ mod tests {
 
         assert_eq!(arr, vec![1, 1, 2, 3, 3, 4, 5, 5, 5, 6, 9]);
     }
+
+    #[test]
+    fn test_quicksort_stepper_events_replay() {
+        // Pseudo-random input, so the scan sees both outcomes
+        let mut arr: Vec<i32> = (0..500).map(|x| (x * 7919) % 503).collect();
+        let mut replay = arr.clone();
+        let mut stepper = QuickSortLLStepper::new(arr.len());
+
+        while !stepper.is_done() {
+            for event in stepper.step(&mut arr, 37) {
+                match event {
+                    SortEvent::Swap { i, j } => replay.swap(i, j),
+                    SortEvent::Overwrite { idx, new_val, .. } => replay[idx] = new_val,
+                    _ => {}
+                }
+            }
+            assert_eq!(replay, arr);
+        }
+
+        assert!(arr.windows(2).all(|w| w[0] <= w[1]));
+    }
+
+    #[test]
+    fn test_quicksort_stepper_batch_size_independent() {
+        let input: Vec<i32> = (0..200).map(|x| (x * 31) % 97).collect();
+
+        let run = |limit: usize| {
+            let mut arr = input.clone();
+            let mut stepper = QuickSortLLStepper::new(arr.len());
+            let mut events = vec![];
+            while !stepper.is_done() {
+                events.extend(stepper.step(&mut arr, limit));
+            }
+            events
+        };
+
+        let one = run(1);
+        assert_eq!(one, run(7));
+        assert_eq!(one, run(10_000));
+        assert!(!one.iter().any(|e| matches!(e, SortEvent::Swap { i, j } if i == j)));
+    }
 }
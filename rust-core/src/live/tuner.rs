@@ -0,0 +1,195 @@
+//! Batch size auto-tuning for live playback.
+//!
+//! How many steps fit in a frame varies by orders of magnitude between
+//! algorithms and array sizes, so a fixed batch either stutters or wastes
+//! frames. A `BatchTuner` is told how long each batch took and picks the
+//! next one to fill a time budget per frame: it keeps a moving average of
+//! the step rate, shrinks at once when a batch runs over, and at most
+//! doubles per batch so one fast measurement can't cause a long stall.
+//!
+//! The tuner doesn't read a clock itself; callers time their `step` calls
+//! (`LiveStepper` does when auto batching is on).
+
+use crate::prelude::*;
+
+/// Weight of the newest measurement in the moving averages.
+const SMOOTHING: f64 = 0.3;
+
+/// Picks batch sizes that keep each batch within a time budget.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchTuner {
+    budget_ms: f64,
+    min_batch: usize,
+    max_batch: usize,
+    batch: usize,
+    /// Moving averages, None until the first timed batch.
+    steps_per_ms: Option<f64>,
+    events_per_ms: Option<f64>,
+}
+
+impl BatchTuner {
+    /// Batch size before the first measurement.
+    pub const INITIAL_BATCH: usize = 64;
+    /// Default upper bound on a batch.
+    pub const MAX_BATCH: usize = 1 << 20;
+
+    /// A tuner aiming for `budget_ms` per batch, e.g. a quarter of a 60fps
+    /// frame. Fails unless the budget is positive and finite.
+    pub fn new(budget_ms: f64) -> Result<Self, String> {
+        Self::with_limits(budget_ms, 1, Self::MAX_BATCH)
+    }
+
+    /// A tuner whose batches stay within min_batch..=max_batch.
+    pub fn with_limits(budget_ms: f64, min_batch: usize, max_batch: usize) -> Result<Self, String> {
+        if !(budget_ms.is_finite() && budget_ms > 0.0) {
+            return Err(format!("Frame budget must be positive, got {}", budget_ms));
+        }
+        if min_batch == 0 || min_batch > max_batch {
+            return Err(format!("Invalid batch limits {}..={}", min_batch, max_batch));
+        }
+
+        Ok(Self {
+            budget_ms,
+            min_batch,
+            max_batch,
+            batch: Self::INITIAL_BATCH.clamp(min_batch, max_batch),
+            steps_per_ms: None,
+            events_per_ms: None,
+        })
+    }
+
+    /// Batch size to use for the next step.
+    pub fn batch(&self) -> usize {
+        self.batch
+    }
+
+    pub fn budget_ms(&self) -> f64 {
+        self.budget_ms
+    }
+
+    /// Change the budget, e.g. when the display's refresh rate changes.
+    /// Measurements are kept.
+    pub fn set_budget_ms(&mut self, budget_ms: f64) -> Result<(), String> {
+        if !(budget_ms.is_finite() && budget_ms > 0.0) {
+            return Err(format!("Frame budget must be positive, got {}", budget_ms));
+        }
+        self.budget_ms = budget_ms;
+        Ok(())
+    }
+
+    /// Smoothed steps per millisecond, once a batch has been timed.
+    pub fn steps_per_ms(&self) -> Option<f64> {
+        self.steps_per_ms
+    }
+
+    /// Smoothed events per millisecond, once a batch has been timed.
+    pub fn events_per_ms(&self) -> Option<f64> {
+        self.events_per_ms
+    }
+
+    /// Record that a batch of `steps` produced `events` in `millis`, and
+    /// return the next batch size. Batches too quick for the clock to
+    /// measure double the batch without updating the averages.
+    pub fn record(&mut self, steps: usize, events: usize, millis: f64) -> usize {
+        if steps == 0 || millis.is_nan() {
+            return self.batch;
+        }
+        if millis <= 0.0 {
+            self.batch = (self.batch * 2).clamp(self.min_batch, self.max_batch);
+            return self.batch;
+        }
+
+        let smooth = |average: Option<f64>, sample: f64| match average {
+            Some(average) => average + SMOOTHING * (sample - average),
+            None => sample,
+        };
+        let steps_per_ms = smooth(self.steps_per_ms, steps as f64 / millis);
+        self.steps_per_ms = Some(steps_per_ms);
+        self.events_per_ms = Some(smooth(self.events_per_ms, events as f64 / millis));
+
+        let mut target = (steps_per_ms * self.budget_ms) as usize;
+        if millis > self.budget_ms {
+            // Over budget: scale this batch down directly, whatever the average says
+            target = target.min((steps as f64 * self.budget_ms / millis) as usize);
+        }
+        self.batch = target.min(self.batch * 2).clamp(self.min_batch, self.max_batch);
+        self.batch
+    }
+
+    /// Forget the measurements and start over from the initial batch.
+    pub fn reset(&mut self) {
+        self.batch = Self::INITIAL_BATCH.clamp(self.min_batch, self.max_batch);
+        self.steps_per_ms = None;
+        self.events_per_ms = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Feed the tuner batches from a stepper running at `rate` steps per ms.
+    fn run(tuner: &mut BatchTuner, rate: f64, batches: usize) {
+        for _ in 0..batches {
+            let steps = tuner.batch();
+            tuner.record(steps, steps * 2, steps as f64 / rate);
+        }
+    }
+
+    #[test]
+    fn test_converges_to_budget() {
+        let mut tuner = BatchTuner::new(4.0).unwrap();
+        run(&mut tuner, 1000.0, 20);
+
+        assert_eq!(tuner.batch(), 4000);
+        assert_eq!(tuner.steps_per_ms(), Some(1000.0));
+        assert_eq!(tuner.events_per_ms(), Some(2000.0));
+    }
+
+    #[test]
+    fn test_grows_at_most_double() {
+        let mut tuner = BatchTuner::new(4.0).unwrap();
+        assert_eq!(tuner.record(64, 64, 0.01), 128);
+        assert_eq!(tuner.record(128, 128, 0.01), 256);
+    }
+
+    #[test]
+    fn test_shrinks_at_once_when_over_budget() {
+        let mut tuner = BatchTuner::new(4.0).unwrap();
+        run(&mut tuner, 1000.0, 20);
+
+        // The stepper slows down 10x: the next batch fits the budget again
+        let next = tuner.record(4000, 4000, 40.0);
+        assert_eq!(next, 400);
+    }
+
+    #[test]
+    fn test_unmeasurable_batches_double() {
+        let mut tuner = BatchTuner::new(4.0).unwrap();
+        assert_eq!(tuner.record(64, 100, 0.0), 128);
+        assert_eq!(tuner.steps_per_ms(), None);
+        assert_eq!(tuner.record(0, 0, 5.0), 128);
+    }
+
+    #[test]
+    fn test_limits() {
+        let mut tuner = BatchTuner::with_limits(4.0, 10, 100).unwrap();
+        run(&mut tuner, 1000.0, 10);
+        assert_eq!(tuner.batch(), 100);
+
+        run(&mut tuner, 0.001, 10);
+        assert_eq!(tuner.batch(), 10);
+
+        tuner.reset();
+        assert_eq!((tuner.batch(), tuner.steps_per_ms()), (64, None));
+    }
+
+    #[test]
+    fn test_rejects_bad_settings() {
+        assert!(BatchTuner::new(0.0).is_err());
+        assert!(BatchTuner::new(f64::NAN).is_err());
+        assert!(BatchTuner::with_limits(4.0, 0, 10).is_err());
+        assert!(BatchTuner::with_limits(4.0, 20, 10).is_err());
+        assert!(BatchTuner::new(4.0).unwrap().set_budget_ms(-1.0).is_err());
+    }
+}
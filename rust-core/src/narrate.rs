@@ -0,0 +1,333 @@
+//! Plain-language narration of trace segments.
+//!
+//! A teaching UI wants a line of text per step ("compare a[3]=7 with pivot
+//! a[9]=4; 7 > 4 so leave it"). The values come from replaying the trace,
+//! and what a Compare means depends on the algorithm: Lomuto quicksort
+//! compares against the pivot at the end of its range, insertion sort
+//! against a key it holds outside the array, selection sort against its
+//! current minimum. `narrate` knows those readings, so the frontend only
+//! displays the text.
+
+use core::ops::Range;
+
+use crate::prelude::*;
+use crate::events::SortEvent;
+use crate::pregen::{Algorithm, Category};
+
+/// How an algorithm's Compare events read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Style {
+    /// Two elements, swapped if a Swap of the pair follows.
+    Plain,
+    /// Neighbours of an exchange sort: swapped or left alone.
+    Exchange,
+    /// Lomuto partition: j is the pivot at the end of the current range.
+    Pivot,
+    /// Insertion: the key being inserted is held outside the array, j is
+    /// its hole.
+    HeldKey,
+    /// Selection: i is the current minimum.
+    Minimum,
+}
+
+impl Style {
+    fn of(algorithm: Algorithm) -> Style {
+        match algorithm {
+            Algorithm::QuickSortLL => Style::Pivot,
+            Algorithm::Insertion => Style::HeldKey,
+            Algorithm::Selection => Style::Minimum,
+            Algorithm::QuickSortLR | Algorithm::QuickSortStable => Style::Plain,
+            _ if algorithm.category() == Category::Exchange => Style::Exchange,
+            _ => Style::Plain,
+        }
+    }
+}
+
+/// Replay state while narrating.
+struct Narrator<'a> {
+    style: Style,
+    array: Vec<i32>,
+    ranges: Vec<(usize, usize)>,
+    /// Insertion sort's key, from the first Compare of a pass until it is
+    /// written back.
+    key: Option<i32>,
+    events: &'a [SortEvent],
+}
+
+/// Describe `events[range]`, one line per event, for a trace of
+/// `algorithm` on `input`. Events before the range are replayed silently.
+pub fn narrate(
+    algorithm: Algorithm,
+    input: &[i32],
+    events: &[SortEvent],
+    range: Range<usize>,
+) -> Result<Vec<String>, String> {
+    if range.start > range.end || range.end > events.len() {
+        return Err(format!(
+            "Range {}..{} out of bounds for {} events",
+            range.start,
+            range.end,
+            events.len()
+        ));
+    }
+    if let Some(n) = events[..range.end].iter().position(|e| !e.in_bounds(input.len())) {
+        return Err(format!("Event {} is out of bounds for an array of {}", n, input.len()));
+    }
+
+    let mut narrator = Narrator {
+        style: Style::of(algorithm),
+        array: input.to_vec(),
+        ranges: Vec::new(),
+        key: None,
+        events,
+    };
+
+    let mut lines = Vec::with_capacity(range.len());
+    for n in 0..range.end {
+        let line = narrator.describe(n);
+        if n >= range.start {
+            lines.push(line);
+        }
+        narrator.advance(n);
+    }
+    Ok(lines)
+}
+
+impl Narrator<'_> {
+    fn describe(&self, n: usize) -> String {
+        let a = &self.array;
+        match &self.events[n] {
+            SortEvent::Compare { i, j } => self.describe_compare(n, *i, *j),
+            SortEvent::Swap { i, j } => {
+                format!("swap a[{}]={} and a[{}]={}", i, a[*i], j, a[*j])
+            }
+            SortEvent::Overwrite { idx, new_val, .. } => self.describe_overwrite(*idx, *new_val),
+            SortEvent::EnterRange { lo, hi } if self.style == Style::Pivot => {
+                format!("partition a[{}..={}] around pivot a[{}]={}", lo, hi, hi, a[*hi])
+            }
+            SortEvent::EnterRange { lo, hi } => format!("work on a[{}..={}]", lo, hi),
+            SortEvent::ExitRange { lo, hi } => format!("done with a[{}..={}]", lo, hi),
+            SortEvent::Phase { name } => format!("phase: {}", name),
+            SortEvent::Found { idx } => format!("found a[{}]={}", idx, a[*idx]),
+            SortEvent::MarkSorted { idx } => {
+                format!("a[{}]={} is in its final place", idx, a[*idx])
+            }
+            SortEvent::Highlight(h) => format!("{}: {}", h.tag, list_indices(&h.indices)),
+            SortEvent::BlockMove(m) => format!(
+                "move a[{}..{}] to start at a[{}]",
+                m.from,
+                m.from + m.len,
+                m.to
+            ),
+            SortEvent::Rotate(r) => format!("rotate a[{}..={}] left by {}", r.lo, r.hi, r.by),
+            SortEvent::SetCursor(c) => match c.index {
+                Some(index) => format!("{} moves to a[{}]={}", c.id, index, a[index]),
+                None => format!("{} is cleared", c.id),
+            },
+            SortEvent::EqualRange { lo, hi } => {
+                format!("a[{}..={}] all equal {}", lo, hi, a[*lo])
+            }
+            SortEvent::Snapshot(_) => "snapshot of the array".to_string(),
+            SortEvent::Flip { k } => format!("flip the first {} elements", k + 1),
+            SortEvent::GapChange { gap, .. } => format!("gap is now {}", gap),
+            SortEvent::Done => "sorted".to_string(),
+        }
+    }
+
+    fn describe_compare(&self, n: usize, i: usize, j: usize) -> String {
+        let a = &self.array;
+        let next = self.events.get(n + 1);
+        let swaps_pair = matches!(
+            next,
+            Some(SortEvent::Swap { i: x, j: y }) if (*x, *y) == (i, j) || (*x, *y) == (j, i)
+        );
+
+        match self.style {
+            Style::Pivot if self.ranges.last().is_some_and(|&(_, hi)| hi == j) => {
+                let (x, p) = (a[i], a[j]);
+                let subject = format!("compare a[{}]={} with pivot a[{}]={}", i, x, j, p);
+                if x <= p {
+                    format!("{}; {} <= {} so it joins the low side", subject, x, p)
+                } else {
+                    format!("{}; {} > {} so leave it", subject, x, p)
+                }
+            }
+            Style::HeldKey => {
+                let (x, key) = (a[i], self.key.unwrap_or(a[j]));
+                let subject = format!("compare a[{}]={} with key {}", i, x, key);
+                if x > key {
+                    format!("{}; {} > {} so shift {} right", subject, x, key, x)
+                } else {
+                    format!("{}; {} <= {} so the key goes at a[{}]", subject, x, key, j)
+                }
+            }
+            Style::Minimum => {
+                let (min, x) = (a[i], a[j]);
+                let subject = format!("compare a[{}]={} with the minimum a[{}]={}", j, x, i, min);
+                if x < min {
+                    format!("{}; {} < {} so it is the new minimum", subject, x, min)
+                } else {
+                    format!("{}; {} >= {} so keep the minimum", subject, x, min)
+                }
+            }
+            _ => {
+                let (x, y) = (a[i], a[j]);
+                let relation = match x.cmp(&y) {
+                    core::cmp::Ordering::Less => "<",
+                    core::cmp::Ordering::Equal => "=",
+                    core::cmp::Ordering::Greater => ">",
+                };
+                let outcome = if swaps_pair {
+                    " so swap them"
+                } else if self.style == Style::Exchange {
+                    " so leave them"
+                } else {
+                    ""
+                };
+                format!(
+                    "compare a[{}]={} with a[{}]={}; {} {} {}{}",
+                    i, x, j, y, x, relation, y, outcome
+                )
+            }
+        }
+    }
+
+    fn describe_overwrite(&self, idx: usize, value: i32) -> String {
+        if self.style == Style::HeldKey {
+            if self.key == Some(value) && (idx == 0 || self.array[idx - 1] != value) {
+                return format!("insert key {} at a[{}]", value, idx);
+            }
+            if idx > 0 && self.array[idx - 1] == value {
+                return format!("shift {} right into a[{}]", value, idx);
+            }
+        }
+        format!("write {} into a[{}] (was {})", value, idx, self.array[idx])
+    }
+
+    fn advance(&mut self, n: usize) {
+        let event = &self.events[n];
+        match event {
+            SortEvent::Compare { i, j } if self.style == Style::HeldKey => {
+                let key = *self.key.get_or_insert(self.array[*j]);
+                // A smaller neighbour ends the pass; the key stays for its write
+                if self.array[*i] <= key && !matches!(
+                    self.events.get(n + 1),
+                    Some(SortEvent::Overwrite { new_val, .. }) if *new_val == key
+                ) {
+                    self.key = None;
+                }
+            }
+            SortEvent::Overwrite { idx, new_val, .. }
+                if self.style == Style::HeldKey
+                    && self.key == Some(*new_val)
+                    && (*idx == 0 || self.array[idx - 1] != *new_val) =>
+            {
+                self.key = None;
+            }
+            SortEvent::EnterRange { lo, hi } => self.ranges.push((*lo, *hi)),
+            SortEvent::ExitRange { .. } => {
+                self.ranges.pop();
+            }
+            _ => {}
+        }
+        event.apply(&mut self.array);
+    }
+}
+
+/// "a[1], a[4]" for a Highlight.
+fn list_indices(indices: &[usize]) -> String {
+    let parts: Vec<String> = indices.iter().map(|i| format!("a[{}]", i)).collect();
+    parts.join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pregen::pregen_sort;
+
+    fn narrate_all(algorithm: Algorithm, input: &[i32]) -> Vec<String> {
+        let events = pregen_sort(algorithm, &mut input.to_vec());
+        narrate(algorithm, input, &events, 0..events.len()).unwrap()
+    }
+
+    #[test]
+    fn test_pivot_narration() {
+        let lines = narrate_all(Algorithm::QuickSortLL, &[7, 2, 4]);
+
+        assert_eq!(lines[0], "partition a[0..=2] around pivot a[2]=4");
+        assert_eq!(lines[1], "compare a[0]=7 with pivot a[2]=4; 7 > 4 so leave it");
+        assert_eq!(lines[2], "compare a[1]=2 with pivot a[2]=4; 2 <= 4 so it joins the low side");
+        assert_eq!(lines[3], "swap a[0]=7 and a[1]=2");
+        assert_eq!(lines.last().unwrap(), "sorted");
+    }
+
+    #[test]
+    fn test_insertion_tracks_key() {
+        let lines = narrate_all(Algorithm::Insertion, &[5, 6, 3]);
+
+        assert_eq!(
+            lines,
+            vec![
+                "compare a[0]=5 with key 6; 5 <= 6 so the key goes at a[1]",
+                "compare a[1]=6 with key 3; 6 > 3 so shift 6 right",
+                "shift 6 right into a[2]",
+                "compare a[0]=5 with key 3; 5 > 3 so shift 5 right",
+                "shift 5 right into a[1]",
+                "insert key 3 at a[0]",
+                "sorted",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_exchange_and_minimum() {
+        let bubble = narrate_all(Algorithm::Bubble, &[2, 1]);
+        assert_eq!(bubble[0], "compare a[0]=2 with a[1]=1; 2 > 1 so swap them");
+        let sorted = narrate_all(Algorithm::Bubble, &[1, 2]);
+        assert_eq!(sorted[0], "compare a[0]=1 with a[1]=2; 1 < 2 so leave them");
+
+        let selection = narrate_all(Algorithm::Selection, &[3, 1]);
+        assert_eq!(
+            selection.iter().find(|l| l.starts_with("compare")).unwrap(),
+            "compare a[1]=1 with the minimum a[0]=3; 1 < 3 so it is the new minimum"
+        );
+    }
+
+    #[test]
+    fn test_segment_replays_prefix() {
+        let input = [4, 3, 2, 1];
+        let events = pregen_sort(Algorithm::Bubble, &mut input.to_vec());
+        let all = narrate(Algorithm::Bubble, &input, &events, 0..events.len()).unwrap();
+
+        let segment = narrate(Algorithm::Bubble, &input, &events, 3..6).unwrap();
+        assert_eq!(segment, all[3..6]);
+        assert!(narrate(Algorithm::Bubble, &input, &events, 2..2).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_plain_merge_narration() {
+        let lines = narrate_all(Algorithm::MergeSort, &[2, 1]);
+
+        assert!(lines.iter().any(|l| l == "compare a[0]=2 with a[1]=1; 2 > 1"));
+        assert!(lines.iter().any(|l| l.starts_with("write 1 into a[0]")));
+    }
+
+    #[test]
+    fn test_rejects_bad_input() {
+        let events = vec![SortEvent::Compare { i: 0, j: 5 }, SortEvent::Done];
+
+        assert!(narrate(Algorithm::Bubble, &[1, 2], &events, 0..2).is_err());
+        assert!(narrate(Algorithm::Bubble, &[1, 2], &events, 1..3).is_err());
+        assert!(narrate(Algorithm::Bubble, &[1, 2], &events, 1..2).is_err());
+    }
+
+    #[test]
+    fn test_every_algorithm_narrates() {
+        let input = [5, 1, 4, 1, 5, 9, 2, 6];
+        for &algorithm in Algorithm::all() {
+            let events = pregen_sort(algorithm, &mut input.to_vec());
+            let lines = narrate(algorithm, &input, &events, 0..events.len()).unwrap();
+            assert_eq!(lines.len(), events.len(), "{}", algorithm.as_str());
+        }
+    }
+}
@@ -0,0 +1,521 @@
+//! Comparator network generation.
+//!
+//! A sorting network is a fixed sequence of comparators, independent of the
+//! data, so it can be generated for a given n without running a sort.
+//! `generate` returns the network as data for frontends to draw circuit
+//! diagrams: every comparator with its two wires and the round it runs in.
+//!
+//! Rounds are assigned as early as possible: a comparator runs one round
+//! after the latest earlier comparator sharing one of its wires. This keeps
+//! the order on every wire, so the layered network sorts exactly like the
+//! sequence it came from, and comparators in a round touch disjoint wires.
+//!
+//! `check_zero_one` tests any comparator list, e.g. a user's own network,
+//! against the zero-one principle: a network sorts every input iff it sorts
+//! every input of 0s and 1s. `run_network` runs such a list on an actual
+//! array and returns the trace, one Phase event per round.
+//! `sort_in_rounds` does the same for the network sorts of the pregen list,
+//! so their traces can be animated a whole round at a time.
+
+use serde::Serialize;
+use crate::engine::{PREGEN_EVENT_BUDGET, PREGEN_MAX_LEN};
+use crate::events::SortEvent;
+use crate::pregen::{odd_even_merge_sort, pairwise_sort, Algorithm};
+
+/// Networks that can be generated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NetworkKind {
+    Bitonic,
+    OddEvenMerge,
+    Pairwise,
+    Insertion,
+}
+
+impl NetworkKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NetworkKind::Bitonic => "bitonic",
+            NetworkKind::OddEvenMerge => "odd_even_merge",
+            NetworkKind::Pairwise => "pairwise",
+            NetworkKind::Insertion => "insertion",
+        }
+    }
+
+    pub fn all() -> &'static [NetworkKind] {
+        &[
+            NetworkKind::Bitonic,
+            NetworkKind::OddEvenMerge,
+            NetworkKind::Pairwise,
+            NetworkKind::Insertion,
+        ]
+    }
+
+    /// Parse network name from string.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<NetworkKind> {
+        match s.to_lowercase().as_str() {
+            "bitonic" => Some(NetworkKind::Bitonic),
+            "odd_even_merge" | "oddevenmerge" | "batcher" => Some(NetworkKind::OddEvenMerge),
+            "pairwise" => Some(NetworkKind::Pairwise),
+            "insertion" => Some(NetworkKind::Insertion),
+            _ => None,
+        }
+    }
+
+    /// The network a pregen algorithm runs, if it is a sorting network.
+    pub fn for_algorithm(algorithm: Algorithm) -> Option<NetworkKind> {
+        match algorithm {
+            Algorithm::Bitonic => Some(NetworkKind::Bitonic),
+            Algorithm::OddEvenMerge => Some(NetworkKind::OddEvenMerge),
+            Algorithm::Pairwise => Some(NetworkKind::Pairwise),
+            _ => None,
+        }
+    }
+}
+
+/// One comparator: after it runs, wire `i` holds the minimum and wire `j`
+/// the maximum. `i` is usually the lower wire, but descending stages of
+/// bitonic networks have `i > j`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct NetworkComparator {
+    pub i: usize,
+    pub j: usize,
+    pub round: usize,
+}
+
+/// A comparator network on `n` wires, ordered by round.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Network {
+    pub n: usize,
+    pub comparators: Vec<NetworkComparator>,
+}
+
+impl Network {
+    /// Number of rounds.
+    pub fn depth(&self) -> usize {
+        self.comparators.last().map_or(0, |c| c.round + 1)
+    }
+
+    /// Number of comparators.
+    pub fn size(&self) -> usize {
+        self.comparators.len()
+    }
+}
+
+/// Generate the network of the given kind for n wires. Fails above
+/// `PREGEN_MAX_LEN` wires, since the networks are meant to be drawn.
+pub fn generate(kind: NetworkKind, n: usize) -> Result<Network, String> {
+    if n > PREGEN_MAX_LEN {
+        return Err(format!("{} wires exceeds the network limit of {}", n, PREGEN_MAX_LEN));
+    }
+
+    let pairs = match kind {
+        NetworkKind::Bitonic => {
+            let mut pairs = Vec::new();
+            bitonic_sort(0, n, true, &mut pairs);
+            pairs
+        }
+        NetworkKind::OddEvenMerge => odd_even_merge_sort::rounds(n).concat(),
+        NetworkKind::Pairwise => pairwise_sort::rounds(n).concat(),
+        NetworkKind::Insertion => {
+            (1..n).flat_map(|i| (0..i).rev().map(|j| (j, j + 1))).collect()
+        }
+    };
+
+    Ok(Network { n, comparators: layer(n, &pairs) })
+}
+
+/// Assign each comparator the earliest round after every earlier comparator
+/// on its wires, and order the result by round.
+pub fn layer(n: usize, pairs: &[(usize, usize)]) -> Vec<NetworkComparator> {
+    let mut next_round = vec![0; n];
+    let mut comparators: Vec<NetworkComparator> = pairs
+        .iter()
+        .map(|&(i, j)| {
+            let round = next_round[i].max(next_round[j]);
+            next_round[i] = round + 1;
+            next_round[j] = round + 1;
+            NetworkComparator { i, j, round }
+        })
+        .collect();
+
+    // Stable, so comparators keep their sequence order within a round
+    comparators.sort_by_key(|c| c.round);
+    comparators
+}
+
+/// Largest network `check_zero_one` accepts; it runs all 2^n inputs.
+pub const ZERO_ONE_MAX_WIRES: usize = 24;
+
+/// Result of a zero-one check.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ZeroOneCheck {
+    pub sorts: bool,
+    /// First 0/1 input the network leaves unsorted, if any.
+    pub counterexample: Option<Counterexample>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Counterexample {
+    pub input: Vec<i32>,
+    pub output: Vec<i32>,
+}
+
+/// Check whether the comparators, each leaving the minimum on its first
+/// wire, sort every 0/1 input on n wires. Fails for invalid comparators and
+/// above `ZERO_ONE_MAX_WIRES` wires.
+pub fn check_zero_one(n: usize, comparators: &[(usize, usize)]) -> Result<ZeroOneCheck, String> {
+    if n > ZERO_ONE_MAX_WIRES {
+        return Err(format!(
+            "{} wires exceeds the zero-one check limit of {}",
+            n, ZERO_ONE_MAX_WIRES
+        ));
+    }
+    check_comparators(n, comparators)?;
+
+    // Bit-parallel: each wire holds one bit of 64 inputs at a time. Input x
+    // has bit k of x on wire k, so the low 6 wires follow fixed patterns
+    const LOW_WIRES: [u64; 6] = [
+        0xAAAA_AAAA_AAAA_AAAA,
+        0xCCCC_CCCC_CCCC_CCCC,
+        0xF0F0_F0F0_F0F0_F0F0,
+        0xFF00_FF00_FF00_FF00,
+        0xFFFF_0000_FFFF_0000,
+        0xFFFF_FFFF_0000_0000,
+    ];
+    let inputs = 1u64 << n;
+    let lanes = if n < 6 { (1u64 << inputs) - 1 } else { u64::MAX };
+    let mut wires = vec![0u64; n];
+
+    let mut base = 0;
+    while base < inputs {
+        for (k, wire) in wires.iter_mut().enumerate() {
+            *wire = if k < 6 {
+                LOW_WIRES[k]
+            } else if (base >> k) & 1 == 1 {
+                u64::MAX
+            } else {
+                0
+            };
+        }
+        for &(i, j) in comparators {
+            let (a, b) = (wires[i], wires[j]);
+            wires[i] = a & b;
+            wires[j] = a | b;
+        }
+
+        // A lane is unsorted where a 1 comes right before a 0
+        let unsorted = wires.windows(2).fold(0, |acc, w| acc | (w[0] & !w[1])) & lanes;
+        if unsorted != 0 {
+            let x = base + unsorted.trailing_zeros() as u64;
+            let input: Vec<i32> = (0..n).map(|k| ((x >> k) & 1) as i32).collect();
+            let mut output = input.clone();
+            for &(i, j) in comparators {
+                if output[i] > output[j] {
+                    output.swap(i, j);
+                }
+            }
+            let counterexample = Counterexample { input, output };
+            return Ok(ZeroOneCheck { sorts: false, counterexample: Some(counterexample) });
+        }
+        base += 64;
+    }
+
+    Ok(ZeroOneCheck { sorts: true, counterexample: None })
+}
+
+/// Trace of running a comparator network on an array.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct NetworkRun {
+    pub events: Vec<SortEvent>,
+    /// Number of rounds, each announced by a `Phase { name: "round <r>" }`.
+    pub depth: usize,
+    /// Whether the network left the array sorted.
+    pub sorted: bool,
+}
+
+/// Run comparators, each leaving the minimum on its first index, on array.
+///
+/// The comparators are grouped into rounds like `generate` does and run
+/// round by round, which sorts exactly like running them in the given
+/// order. Every round starts with a Phase event, followed by a Compare and
+/// possibly a Swap per comparator; the trace ends with Done.
+pub fn run_network(
+    array: &mut [i32],
+    comparators: &[(usize, usize)],
+) -> Result<NetworkRun, String> {
+    let n = array.len();
+    if n > PREGEN_MAX_LEN {
+        return Err(format!("{} elements exceeds the network limit of {}", n, PREGEN_MAX_LEN));
+    }
+    if comparators.len() > PREGEN_EVENT_BUDGET / 2 {
+        return Err(format!(
+            "{} comparators exceeds the limit of {}",
+            comparators.len(),
+            PREGEN_EVENT_BUDGET / 2
+        ));
+    }
+    check_comparators(n, comparators)?;
+
+    let layered = layer(n, comparators);
+    let mut events = Vec::with_capacity(2 * layered.len() + 1);
+    for (k, c) in layered.iter().enumerate() {
+        if k == 0 || layered[k - 1].round != c.round {
+            events.push(SortEvent::Phase { name: format!("round {}", c.round) });
+        }
+        events.push(SortEvent::Compare { i: c.i, j: c.j });
+        if array[c.i] > array[c.j] {
+            events.push(SortEvent::Swap { i: c.i, j: c.j });
+            array.swap(c.i, c.j);
+        }
+    }
+    events.push(SortEvent::Done);
+
+    let depth = layered.last().map_or(0, |c| c.round + 1);
+    let sorted = array.windows(2).all(|w| w[0] <= w[1]);
+    Ok(NetworkRun { events, depth, sorted })
+}
+
+/// Sort array with a network algorithm, grouping its comparators into
+/// rounds. The trace has the same comparators as `pregen_sort`, reordered
+/// round by round, with a Phase event starting each round.
+pub fn sort_in_rounds(algorithm: Algorithm, array: &mut [i32]) -> Result<NetworkRun, String> {
+    let kind = NetworkKind::for_algorithm(algorithm)
+        .ok_or_else(|| format!("{} is not a sorting network", algorithm.as_str()))?;
+    let network = generate(kind, array.len())?;
+    let pairs: Vec<_> = network.comparators.iter().map(|c| (c.i, c.j)).collect();
+    run_network(array, &pairs)
+}
+
+/// Every comparator must join two different wires below n.
+fn check_comparators(n: usize, comparators: &[(usize, usize)]) -> Result<(), String> {
+    match comparators.iter().find(|&&(i, j)| i >= n || j >= n || i == j) {
+        Some(&(i, j)) => Err(format!("Invalid comparator ({}, {}) for {} wires", i, j, n)),
+        None => Ok(()),
+    }
+}
+
+/// Comparators of the arbitrary-length bitonic sort on wires lo..lo + len,
+/// in the order `pregen::bitonic_sort` runs them.
+fn bitonic_sort(lo: usize, len: usize, ascending: bool, pairs: &mut Vec<(usize, usize)>) {
+    if len <= 1 {
+        return;
+    }
+
+    let half = len / 2;
+    bitonic_sort(lo, half, !ascending, pairs);
+    bitonic_sort(lo + half, len - half, ascending, pairs);
+    bitonic_merge(lo, len, ascending, pairs);
+}
+
+fn bitonic_merge(lo: usize, len: usize, ascending: bool, pairs: &mut Vec<(usize, usize)>) {
+    if len <= 1 {
+        return;
+    }
+
+    // Largest power of 2 strictly below len
+    let mut m = 1;
+    while m * 2 < len {
+        m *= 2;
+    }
+
+    for i in lo..(lo + len - m) {
+        pairs.push(if ascending { (i, i + m) } else { (i + m, i) });
+    }
+
+    bitonic_merge(lo, m, ascending, pairs);
+    bitonic_merge(lo + m, len - m, ascending, pairs);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pregen::pregen_sort;
+
+    fn run(network: &Network, array: &mut [i32]) {
+        for c in &network.comparators {
+            if array[c.i] > array[c.j] {
+                array.swap(c.i, c.j);
+            }
+        }
+    }
+
+    #[test]
+    fn test_networks_sort_all_zero_one_inputs() {
+        for &kind in NetworkKind::all() {
+            for n in 0..=10usize {
+                let network = generate(kind, n).unwrap();
+                for bits in 0u32..(1 << n) {
+                    let mut array: Vec<i32> = (0..n).map(|k| ((bits >> k) & 1) as i32).collect();
+                    run(&network, &mut array);
+                    assert!(array.windows(2).all(|w| w[0] <= w[1]), "{:?}, n = {}", kind, n);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_rounds_touch_disjoint_wires() {
+        for &kind in NetworkKind::all() {
+            let network = generate(kind, 37).unwrap();
+            for round in 0..network.depth() {
+                let mut wires: Vec<usize> = network
+                    .comparators
+                    .iter()
+                    .filter(|c| c.round == round)
+                    .flat_map(|c| [c.i, c.j])
+                    .collect();
+                assert!(!wires.is_empty());
+                let len = wires.len();
+                wires.sort();
+                wires.dedup();
+                assert_eq!(wires.len(), len, "{:?}, round {}", kind, round);
+            }
+        }
+    }
+
+    #[test]
+    fn test_network_matches_pregen_comparisons() {
+        let pairs = |algorithm, n: i32| {
+            pregen_sort(algorithm, &mut (0..n).rev().collect::<Vec<_>>())
+                .into_iter()
+                .filter_map(|e| match e {
+                    SortEvent::Compare { i, j } => Some((i.min(j), i.max(j))),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+        let kinds = [
+            (NetworkKind::Bitonic, Algorithm::Bitonic),
+            (NetworkKind::OddEvenMerge, Algorithm::OddEvenMerge),
+            (NetworkKind::Pairwise, Algorithm::Pairwise),
+        ];
+        for (kind, algorithm) in kinds {
+            let network = generate(kind, 23).unwrap();
+            let mut generated: Vec<_> =
+                network.comparators.iter().map(|c| (c.i.min(c.j), c.i.max(c.j))).collect();
+            let mut expected = pairs(algorithm, 23);
+            generated.sort();
+            expected.sort();
+            assert_eq!(generated, expected, "{:?}", kind);
+        }
+    }
+
+    #[test]
+    fn test_insertion_network_depth() {
+        let network = generate(NetworkKind::Insertion, 8).unwrap();
+        assert_eq!(network.size(), 28);
+        assert_eq!(network.depth(), 2 * 8 - 3);
+    }
+
+    #[test]
+    fn test_small_networks() {
+        assert!(generate(NetworkKind::Bitonic, 1).unwrap().comparators.is_empty());
+        let network = generate(NetworkKind::OddEvenMerge, 2).unwrap();
+        assert_eq!(network.comparators, vec![NetworkComparator { i: 0, j: 1, round: 0 }]);
+        assert_eq!(network.depth(), 1);
+        assert!(generate(NetworkKind::Pairwise, PREGEN_MAX_LEN + 1).is_err());
+    }
+
+    #[test]
+    fn test_generated_networks_pass_zero_one_check() {
+        for &kind in NetworkKind::all() {
+            for n in [0, 1, 5, 6, 7, 13] {
+                let network = generate(kind, n).unwrap();
+                let pairs: Vec<_> = network.comparators.iter().map(|c| (c.i, c.j)).collect();
+                let check = check_zero_one(n, &pairs).unwrap();
+                assert!(check.sorts, "{:?}, n = {}", kind, n);
+                assert_eq!(check.counterexample, None);
+            }
+        }
+    }
+
+    #[test]
+    fn test_zero_one_counterexample() {
+        // Missing the final (0, 1) comparator of the 3-wire network
+        let check = check_zero_one(3, &[(0, 1), (1, 2)]).unwrap();
+        assert!(!check.sorts);
+        let counterexample = check.counterexample.unwrap();
+        assert_eq!(counterexample.input, vec![1, 1, 0]);
+        assert_eq!(counterexample.output, vec![1, 0, 1]);
+    }
+
+    #[test]
+    fn test_zero_one_check_rejects_bad_input() {
+        assert!(check_zero_one(3, &[(0, 3)]).is_err());
+        assert!(check_zero_one(3, &[(1, 1)]).is_err());
+        assert!(check_zero_one(ZERO_ONE_MAX_WIRES + 1, &[]).is_err());
+        assert!(!check_zero_one(20, &[(0, 1)]).unwrap().sorts);
+    }
+
+    #[test]
+    fn test_run_network_trace() {
+        let mut array = vec![3, 1, 2];
+        let run = run_network(&mut array, &[(0, 1), (1, 2), (0, 1)]).unwrap();
+
+        assert_eq!(array, vec![1, 2, 3]);
+        assert!(run.sorted);
+        assert_eq!(run.depth, 3);
+        assert_eq!(
+            run.events,
+            vec![
+                SortEvent::Phase { name: "round 0".to_string() },
+                SortEvent::Compare { i: 0, j: 1 },
+                SortEvent::Swap { i: 0, j: 1 },
+                SortEvent::Phase { name: "round 1".to_string() },
+                SortEvent::Compare { i: 1, j: 2 },
+                SortEvent::Swap { i: 1, j: 2 },
+                SortEvent::Phase { name: "round 2".to_string() },
+                SortEvent::Compare { i: 0, j: 1 },
+                SortEvent::Done,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_run_network_reports_unsorted() {
+        let mut array = vec![2, 1, 0, 3];
+        // Reversed comparator puts the minimum on the higher index
+        let run = run_network(&mut array, &[(0, 2), (1, 0)]).unwrap();
+        assert_eq!(run.depth, 2);
+        assert_eq!(array, vec![1, 0, 2, 3]);
+        assert!(!run.sorted);
+
+        assert!(run_network(&mut [1, 2], &[(0, 2)]).is_err());
+    }
+
+    #[test]
+    fn test_sort_in_rounds() {
+        let input: Vec<i32> = (0..29).map(|x| (x * 37 + 11) % 23).collect();
+        for &algorithm in Algorithm::all() {
+            let Some(kind) = NetworkKind::for_algorithm(algorithm) else {
+                assert!(sort_in_rounds(algorithm, &mut input.clone()).is_err());
+                continue;
+            };
+            let mut array = input.clone();
+            let run = sort_in_rounds(algorithm, &mut array).unwrap();
+
+            let mut expected = input.clone();
+            let events = pregen_sort(algorithm, &mut expected);
+            assert_eq!(array, expected, "{:?}", algorithm);
+            assert!(run.sorted);
+            assert_eq!(run.depth, generate(kind, input.len()).unwrap().depth());
+
+            let compares = |events: &[SortEvent]| {
+                events.iter().filter(|e| matches!(e, SortEvent::Compare { .. })).count()
+            };
+            assert_eq!(compares(&run.events), compares(&events), "{:?}", algorithm);
+            let phases = run.events.iter().filter(|e| matches!(e, SortEvent::Phase { .. }));
+            assert_eq!(phases.count(), run.depth);
+        }
+    }
+
+    #[test]
+    fn test_network_kind_from_str() {
+        for &kind in NetworkKind::all() {
+            assert_eq!(NetworkKind::from_str(kind.as_str()), Some(kind));
+        }
+        assert_eq!(NetworkKind::from_str("Batcher"), Some(NetworkKind::OddEvenMerge));
+        assert_eq!(NetworkKind::from_str("bogus"), None);
+    }
+}
@@ -0,0 +1,300 @@
+//! Normalized display heights.
+//!
+//! Drawing bars needs every value scaled into 0..1, and scanning a
+//! million-element array for its range every frame is wasted work. A
+//! `Normalizer` scans the input once and then maps any value, including
+//! ones an Overwrite brings in later, to a height in O(1) (linear) or
+//! O(log n) (rank).
+//!
+//! Linear scale matches the frontend's bar formula: `(v - min) / (max -
+//! min)`, and 0 when all values are equal. Rank scale spaces the distinct
+//! values evenly, which keeps skewed or huge ranges readable. Floats are
+//! ordered as in `float`, NaNs last: a NaN is drawn full height, and the
+//! infinities sit at the ends without stretching the linear range.
+
+use core::cmp::Ordering;
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+use crate::prelude::*;
+use crate::float::{compare_f64, NanPolicy};
+
+/// How values map to heights.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Scale {
+    /// Proportional to the value within the input's range.
+    #[default]
+    Linear,
+    /// Proportional to the value's rank among the distinct input values.
+    Rank,
+}
+
+impl Scale {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Scale::Linear => "linear",
+            Scale::Rank => "rank",
+        }
+    }
+
+    /// Parse scale name from string.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Scale> {
+        match s.to_lowercase().as_str() {
+            "linear" => Some(Scale::Linear),
+            "rank" | "ranks" => Some(Scale::Rank),
+            _ => None,
+        }
+    }
+}
+
+/// Element types a Normalizer can scale.
+pub trait DisplayValue: Copy {
+    /// The value as a float, for linear scaling.
+    fn to_f64(self) -> f64;
+    /// Total order used for ranks.
+    fn order(&self, other: &Self) -> Ordering;
+}
+
+impl DisplayValue for i32 {
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+
+    fn order(&self, other: &Self) -> Ordering {
+        self.cmp(other)
+    }
+}
+
+impl DisplayValue for i64 {
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+
+    fn order(&self, other: &Self) -> Ordering {
+        self.cmp(other)
+    }
+}
+
+impl DisplayValue for f64 {
+    fn to_f64(self) -> f64 {
+        self
+    }
+
+    fn order(&self, other: &Self) -> Ordering {
+        compare_f64(*self, *other, NanPolicy::Last)
+    }
+}
+
+/// Dense rank of each value: equal values share a rank, and ranks run
+/// from 0 to the number of distinct values minus one.
+pub fn ranks<T: DisplayValue>(values: &[T]) -> Vec<u32> {
+    let distinct = distinct_sorted(values);
+    values.iter().map(|v| rank_in(&distinct, v) as u32).collect()
+}
+
+fn distinct_sorted<T: DisplayValue>(values: &[T]) -> Vec<T> {
+    let mut sorted = values.to_vec();
+    sorted.sort_unstable_by(T::order);
+    sorted.dedup_by(|a, b| a.order(b) == Ordering::Equal);
+    sorted
+}
+
+/// Number of distinct values below `value`.
+fn rank_in<T: DisplayValue>(distinct: &[T], value: &T) -> usize {
+    distinct.partition_point(|v| v.order(value) == Ordering::Less)
+}
+
+/// Maps values to display heights in 0..1, scanned from an input once.
+#[derive(Debug, Clone)]
+pub struct Normalizer<T> {
+    scale: Scale,
+    /// Finite range of the input; (0, 0) when it has no finite values.
+    min: f64,
+    max: f64,
+    /// Distinct input values in order, for Rank scale only.
+    distinct: Vec<T>,
+}
+
+impl<T: DisplayValue> Normalizer<T> {
+    pub fn new(values: &[T], scale: Scale) -> Self {
+        let (min, max) = values
+            .iter()
+            .map(|v| v.to_f64())
+            .filter(|v| v.is_finite())
+            .fold(None, |range: Option<(f64, f64)>, v| match range {
+                Some((min, max)) => Some((min.min(v), max.max(v))),
+                None => Some((v, v)),
+            })
+            .unwrap_or((0.0, 0.0));
+
+        let distinct = match scale {
+            Scale::Rank => distinct_sorted(values),
+            Scale::Linear => Vec::new(),
+        };
+
+        Self { scale, min, max, distinct }
+    }
+
+    pub fn scale(&self) -> Scale {
+        self.scale
+    }
+
+    /// Smallest finite input value.
+    pub fn min(&self) -> f64 {
+        self.min
+    }
+
+    /// Largest finite input value.
+    pub fn max(&self) -> f64 {
+        self.max
+    }
+
+    /// Height of a value. Values outside the input's range clamp to 0 or 1;
+    /// under Rank scale they take the rank they would have had.
+    pub fn height(&self, value: T) -> f64 {
+        match self.scale {
+            Scale::Linear => {
+                let v = value.to_f64();
+                if v.is_nan() || v == f64::INFINITY {
+                    return 1.0;
+                }
+                let range = self.max - self.min;
+                if range == 0.0 {
+                    return 0.0;
+                }
+                ((v - self.min) / range).clamp(0.0, 1.0)
+            }
+            Scale::Rank => {
+                let top = self.distinct.len().saturating_sub(1);
+                if top == 0 {
+                    return 0.0;
+                }
+                (rank_in(&self.distinct, &value).min(top) as f64) / top as f64
+            }
+        }
+    }
+
+    /// Heights of many values, as f32 for a Float32Array.
+    pub fn heights(&self, values: &[T]) -> Vec<f32> {
+        values.iter().map(|&v| self.height(v) as f32).collect()
+    }
+}
+
+/// Wasm-exposed normalizer over JS numbers.
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_name = ValueNormalizer)]
+pub struct WasmNormalizer {
+    inner: Normalizer<f64>,
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_class = ValueNormalizer)]
+impl WasmNormalizer {
+    /// Scan array once; scale is "linear" (default) or "rank".
+    #[wasm_bindgen(constructor)]
+    pub fn new(array: &[f64], scale: Option<String>) -> Result<WasmNormalizer, JsValue> {
+        let scale = match scale.as_deref() {
+            Some(name) => Scale::from_str(name)
+                .ok_or_else(|| JsValue::from_str(&format!("Unknown scale: {}", name)))?,
+            None => Scale::default(),
+        };
+        Ok(WasmNormalizer { inner: Normalizer::new(array, scale) })
+    }
+
+    pub fn min(&self) -> f64 {
+        self.inner.min()
+    }
+
+    pub fn max(&self) -> f64 {
+        self.inner.max()
+    }
+
+    /// Height of one value, e.g. the new value of an Overwrite.
+    pub fn height(&self, value: f64) -> f64 {
+        self.inner.height(value)
+    }
+
+    /// Heights of every value, as a Float32Array.
+    pub fn heights(&self, array: &[f64]) -> js_sys::Float32Array {
+        js_sys::Float32Array::from(self.inner.heights(array).as_slice())
+    }
+}
+
+/// Dense ranks of JS numbers, as a Uint32Array.
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn value_ranks(array: &[f64]) -> js_sys::Uint32Array {
+    js_sys::Uint32Array::from(ranks(array).as_slice())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_linear_heights() {
+        let values = [-10, 0, 30];
+        let normalizer = Normalizer::new(&values, Scale::Linear);
+
+        assert_eq!(normalizer.heights(&values), vec![0.0, 0.25, 1.0]);
+        assert_eq!((normalizer.min(), normalizer.max()), (-10.0, 30.0));
+        // Values brought in later clamp to the input's range
+        assert_eq!(normalizer.height(50), 1.0);
+        assert_eq!(normalizer.height(-20), 0.0);
+    }
+
+    #[test]
+    fn test_constant_and_empty() {
+        assert_eq!(Normalizer::new(&[7, 7], Scale::Linear).heights(&[7, 7]), vec![0.0, 0.0]);
+        assert_eq!(Normalizer::new(&[7, 7], Scale::Rank).heights(&[7, 7]), vec![0.0, 0.0]);
+
+        let empty: [i32; 0] = [];
+        assert!(Normalizer::new(&empty, Scale::Rank).heights(&empty).is_empty());
+    }
+
+    #[test]
+    fn test_rank_heights_ignore_gaps() {
+        let values: [i64; 4] = [i64::MIN, 5, i64::MAX, 5];
+        let normalizer = Normalizer::new(&values, Scale::Rank);
+
+        assert_eq!(normalizer.heights(&values), vec![0.0, 0.5, 1.0, 0.5]);
+        assert_eq!(normalizer.height(6), 1.0);
+    }
+
+    #[test]
+    fn test_huge_i64_range() {
+        let values: [i64; 3] = [i64::MIN, 0, i64::MAX];
+        let heights = Normalizer::new(&values, Scale::Linear).heights(&values);
+
+        assert_eq!(heights[0], 0.0);
+        assert!((heights[1] - 0.5).abs() < 1e-6);
+        assert_eq!(heights[2], 1.0);
+    }
+
+    #[test]
+    fn test_float_specials() {
+        let values = [f64::NEG_INFINITY, -1.0, 1.0, f64::INFINITY, f64::NAN];
+        let linear = Normalizer::new(&values, Scale::Linear);
+
+        assert_eq!((linear.min(), linear.max()), (-1.0, 1.0));
+        assert_eq!(linear.heights(&values), vec![0.0, 0.0, 1.0, 1.0, 1.0]);
+
+        let rank = Normalizer::new(&values, Scale::Rank);
+        assert_eq!(rank.heights(&values), vec![0.0, 0.25, 0.5, 0.75, 1.0]);
+    }
+
+    #[test]
+    fn test_dense_ranks() {
+        assert_eq!(ranks(&[30, 10, 30, -5]), vec![2, 1, 2, 0]);
+        assert_eq!(ranks(&[0.0, -0.0, f64::NAN, f64::NAN]), vec![1, 0, 2, 2]);
+    }
+
+    #[test]
+    fn test_scale_names() {
+        for scale in [Scale::Linear, Scale::Rank] {
+            assert_eq!(Scale::from_str(scale.as_str()), Some(scale));
+        }
+        assert_eq!(Scale::from_str("log"), None);
+    }
+}
@@ -0,0 +1,295 @@
+//! Fixed-width event records for typed arrays.
+//!
+//! Every event with only numeric fields packs into `RECORD_WORDS` i32
+//! words: a type code followed by up to three fields, in declaration order,
+//! unused words 0. Records can be written straight into an Int32Array that
+//! JavaScript allocated once, instead of building a fresh array of event
+//! objects per batch. Phase, Highlight, SetCursor and Snapshot carry strings
+//! or lists and have no packed form.
+//!
+//! | code | event      | fields                |
+//! |------|------------|-----------------------|
+//! | 0    | Swap       | i, j                  |
+//! | 1    | Overwrite  | idx, old_val, new_val |
+//! | 2    | Compare    | i, j                  |
+//! | 3    | EnterRange | lo, hi                |
+//! | 4    | ExitRange  | lo, hi                |
+//! | 6    | Found      | idx                   |
+//! | 7    | MarkSorted | idx                   |
+//! | 9    | BlockMove  | from, to, len         |
+//! | 10   | Rotate     | lo, hi, by            |
+//! | 12   | EqualRange | lo, hi                |
+//! | 14   | Flip       | k                     |
+//! | 15   | Done       |                       |
+//! | 16   | GapChange  | gap, prev             |
+//!
+//! Codes follow `EventCounts::by_type`, so 5 (Phase), 8 (Highlight), 11
+//! (SetCursor) and 13 (Snapshot) are unused. GapChange came after Done and
+//! takes the next free code, so existing records keep their meaning.
+//!
+//! `Ring` tracks where records go in a caller-owned buffer that is reused
+//! as a ring, for pushing batches from a live stepper.
+
+use core::ops::Range;
+
+use crate::prelude::*;
+use crate::events::{EventSink, SortEvent};
+
+/// Words per packed event.
+pub const RECORD_WORDS: usize = 4;
+
+/// Pack an event, or None for Phase, Highlight, SetCursor and Snapshot.
+pub fn pack(event: &SortEvent) -> Option<[i32; RECORD_WORDS]> {
+    let w = |v: usize| v as i32;
+    Some(match event {
+        SortEvent::Swap { i, j } => [0, w(*i), w(*j), 0],
+        SortEvent::Overwrite { idx, old_val, new_val } => [1, w(*idx), *old_val, *new_val],
+        SortEvent::Compare { i, j } => [2, w(*i), w(*j), 0],
+        SortEvent::EnterRange { lo, hi } => [3, w(*lo), w(*hi), 0],
+        SortEvent::ExitRange { lo, hi } => [4, w(*lo), w(*hi), 0],
+        SortEvent::Phase { .. } => return None,
+        SortEvent::Found { idx } => [6, w(*idx), 0, 0],
+        SortEvent::MarkSorted { idx } => [7, w(*idx), 0, 0],
+        SortEvent::Highlight(_) => return None,
+        SortEvent::BlockMove(m) => [9, w(m.from), w(m.to), w(m.len)],
+        SortEvent::Rotate(r) => [10, w(r.lo), w(r.hi), w(r.by)],
+        SortEvent::SetCursor(_) => return None,
+        SortEvent::EqualRange { lo, hi } => [12, w(*lo), w(*hi), 0],
+        SortEvent::Snapshot(_) => return None,
+        SortEvent::Flip { k } => [14, w(*k), 0, 0],
+        SortEvent::GapChange { gap, prev } => [16, w(*gap), w(*prev), 0],
+        SortEvent::Done => [15, 0, 0, 0],
+    })
+}
+
+/// Unpack a record, or None for an unknown code.
+pub fn unpack(record: [i32; RECORD_WORDS]) -> Option<SortEvent> {
+    let [code, a, b, c] = record;
+    let u = |v: i32| v as usize;
+    Some(match code {
+        0 => SortEvent::Swap { i: u(a), j: u(b) },
+        1 => SortEvent::Overwrite { idx: u(a), old_val: b, new_val: c },
+        2 => SortEvent::Compare { i: u(a), j: u(b) },
+        3 => SortEvent::EnterRange { lo: u(a), hi: u(b) },
+        4 => SortEvent::ExitRange { lo: u(a), hi: u(b) },
+        6 => SortEvent::Found { idx: u(a) },
+        7 => SortEvent::MarkSorted { idx: u(a) },
+        9 => SortEvent::block_move(u(a), u(b), u(c)),
+        10 => SortEvent::rotate(u(a), u(b), u(c)),
+        12 => SortEvent::EqualRange { lo: u(a), hi: u(b) },
+        14 => SortEvent::Flip { k: u(a) },
+        15 => SortEvent::Done,
+        16 => SortEvent::GapChange { gap: u(a), prev: u(b) },
+        _ => return None,
+    })
+}
+
+/// Pack events back to back, failing on the first one without a packed
+/// form.
+pub fn pack_events(events: &[SortEvent]) -> Result<Vec<i32>, String> {
+    let mut words = Vec::with_capacity(events.len() * RECORD_WORDS);
+    for event in events {
+        let record = pack(event).ok_or_else(|| format!("{:?} has no packed form", event))?;
+        words.extend_from_slice(&record);
+    }
+    Ok(words)
+}
+
+/// Append the records of the events that have a packed form to `words`,
+/// skipping the rest. Returns how many records were appended.
+pub fn pack_events_skipping(events: &[SortEvent], words: &mut Vec<i32>) -> usize {
+    let before = words.len();
+    for record in events.iter().filter_map(pack) {
+        words.extend_from_slice(&record);
+    }
+    (words.len() - before) / RECORD_WORDS
+}
+
+/// An `EventSink` that packs events as they are emitted, appending their
+/// records to a words buffer and counting the events without a packed
+/// form. A run packed this way never holds its events as a vector.
+pub struct PackedSink<'a> {
+    words: &'a mut Vec<i32>,
+    start: usize,
+    skipped: usize,
+}
+
+impl<'a> PackedSink<'a> {
+    pub fn new(words: &'a mut Vec<i32>) -> Self {
+        let start = words.len();
+        Self { words, start, skipped: 0 }
+    }
+
+    /// Records appended so far.
+    pub fn records(&self) -> usize {
+        (self.words.len() - self.start) / RECORD_WORDS
+    }
+
+    /// Events skipped so far for having no packed form.
+    pub fn skipped(&self) -> usize {
+        self.skipped
+    }
+}
+
+impl EventSink for PackedSink<'_> {
+    fn push(&mut self, event: SortEvent) {
+        match pack(&event) {
+            Some(record) => self.words.extend_from_slice(&record),
+            None => self.skipped += 1,
+        }
+    }
+}
+
+/// Write position in a ring buffer of packed records.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ring {
+    /// Capacity in records.
+    capacity: usize,
+    /// Record slot of the next write.
+    head: usize,
+}
+
+impl Ring {
+    /// Ring over a buffer of `words` i32s; a trailing partial record is
+    /// unused. Fails if the buffer can't hold a single record.
+    pub fn new(words: usize) -> Result<Ring, String> {
+        let capacity = words / RECORD_WORDS;
+        if capacity == 0 {
+            return Err(format!("A ring needs at least {} words", RECORD_WORDS));
+        }
+        Ok(Ring { capacity, head: 0 })
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Record slot the next write starts at.
+    pub fn head(&self) -> usize {
+        self.head
+    }
+
+    /// Reserve space for `records` records and advance the head. Returns
+    /// the word ranges to fill, in order; the second is empty unless the
+    /// write wraps around. Fails if the records don't fit in the ring.
+    pub fn place(&mut self, records: usize) -> Result<[Range<usize>; 2], String> {
+        if records > self.capacity {
+            return Err(format!("{} events don't fit in a ring of {}", records, self.capacity));
+        }
+
+        let first = records.min(self.capacity - self.head);
+        let ranges = [
+            self.head * RECORD_WORDS..(self.head + first) * RECORD_WORDS,
+            0..(records - first) * RECORD_WORDS,
+        ];
+        self.head = (self.head + records) % self.capacity;
+        Ok(ranges)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::live::{LiveSort, LIVE_ALGORITHMS};
+    use crate::patterns::{self, Pattern};
+
+    #[test]
+    fn test_packed_sink_matches_pack_events_skipping() {
+        let mut input: Vec<i32> = (0..40).map(|x| (x * 17) % 23).collect();
+        let mut array = input.clone();
+        let events = crate::pregen::pregen_sort(crate::pregen::Algorithm::Timsort, &mut array);
+        let mut expected = Vec::new();
+        let records = pack_events_skipping(&events, &mut expected);
+
+        let mut words = vec![-1];
+        let mut sink = PackedSink::new(&mut words);
+        crate::pregen::pregen_sort_into(crate::pregen::Algorithm::Timsort, &mut input, &mut sink);
+        assert_eq!((sink.records(), sink.skipped()), (records, events.len() - records));
+        assert!(sink.skipped() > 0);
+        assert_eq!(words[0], -1);
+        assert_eq!(&words[1..], expected.as_slice());
+        assert_eq!(input, array);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let events = vec![
+            SortEvent::Swap { i: 1, j: 2 },
+            SortEvent::Overwrite { idx: 3, old_val: -7, new_val: 9 },
+            SortEvent::Compare { i: 0, j: 5 },
+            SortEvent::EnterRange { lo: 0, hi: 9 },
+            SortEvent::ExitRange { lo: 0, hi: 9 },
+            SortEvent::Found { idx: 4 },
+            SortEvent::MarkSorted { idx: 6 },
+            SortEvent::block_move(7, 2, 3),
+            SortEvent::rotate(0, 9, 4),
+            SortEvent::EqualRange { lo: 2, hi: 5 },
+            SortEvent::Flip { k: 3 },
+            SortEvent::GapChange { gap: 4, prev: 0 },
+            SortEvent::Done,
+        ];
+        for event in events {
+            assert_eq!(unpack(pack(&event).unwrap()), Some(event));
+        }
+    }
+
+    #[test]
+    fn test_unpackable_events() {
+        assert_eq!(pack(&SortEvent::Phase { name: "merge".to_string() }), None);
+        assert_eq!(pack(&SortEvent::highlight(vec![1], "min")), None);
+        assert_eq!(pack(&SortEvent::set_cursor("min", Some(1), None)), None);
+        assert_eq!(unpack([5, 0, 0, 0]), None);
+        assert_eq!(unpack([11, 0, 0, 0]), None);
+        assert_eq!(pack(&SortEvent::snapshot(&[1, 2])), None);
+        assert!(pack_events(&[SortEvent::Done, SortEvent::highlight(vec![], "x")]).is_err());
+    }
+
+    #[test]
+    fn test_pack_events_skipping() {
+        let events = vec![
+            SortEvent::Phase { name: "merge".to_string() },
+            SortEvent::Swap { i: 1, j: 2 },
+            SortEvent::highlight(vec![1], "min"),
+            SortEvent::Done,
+        ];
+        let mut words = vec![7];
+
+        assert_eq!(pack_events_skipping(&events, &mut words), 2);
+        assert_eq!(words, vec![7, 0, 1, 2, 0, 15, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_ring_wraps() {
+        let mut ring = Ring::new(4 * RECORD_WORDS + 1).unwrap();
+        assert_eq!(ring.capacity(), 4);
+
+        assert_eq!(ring.place(3).unwrap(), [0..12, 0..0]);
+        assert_eq!(ring.head(), 3);
+        assert_eq!(ring.place(2).unwrap(), [12..16, 0..4]);
+        assert_eq!(ring.head(), 1);
+        assert_eq!(ring.place(3).unwrap(), [4..16, 0..0]);
+        assert_eq!(ring.head(), 0);
+    }
+
+    #[test]
+    fn test_ring_rejects_oversized_batches() {
+        assert!(Ring::new(RECORD_WORDS - 1).is_err());
+        let mut ring = Ring::new(2 * RECORD_WORDS).unwrap();
+        assert!(ring.place(3).is_err());
+        assert_eq!(ring.head(), 0);
+    }
+
+    #[test]
+    fn test_live_events_pack() {
+        let input = patterns::generate(Pattern::Uniform, 50, 2);
+        for name in LIVE_ALGORITHMS {
+            let mut live = LiveSort::new(name, input.clone()).unwrap();
+            live.finish_with_sweep();
+            while !live.is_done() {
+                let events = live.step(100);
+                let words = pack_events(&events).unwrap();
+                assert_eq!(words.len(), events.len() * RECORD_WORDS);
+            }
+        }
+    }
+}
@@ -0,0 +1,132 @@
+//! Consistency checks between the pregen and live engines.
+//!
+//! Algorithms implemented in both engines should sort identically. The
+//! check runs both on the same input and compares the final arrays and the
+//! number of events of each type, reporting every difference.
+
+use serde::Serialize;
+use crate::events::EventCounts;
+use crate::live::LiveSort;
+use crate::pregen::{pregen_sort, Algorithm};
+
+/// Steps per live batch. Live steppers are batch-size independent, so the
+/// value only affects speed.
+const LIVE_BATCH: usize = 1024;
+
+/// One difference between the two engines.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind")]
+pub enum Mismatch {
+    /// The engines left the array in different states.
+    FinalArray { pregen: Vec<i32>, live: Vec<i32> },
+    /// The engines emitted a different number of events of one type.
+    EventCount {
+        event: &'static str,
+        pregen: usize,
+        live: usize,
+    },
+}
+
+/// Result of `check_engine_parity`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ParityReport {
+    pub algorithm: &'static str,
+    pub pregen_counts: EventCounts,
+    pub live_counts: EventCounts,
+    pub mismatches: Vec<Mismatch>,
+}
+
+impl ParityReport {
+    pub fn is_consistent(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+/// Run algorithm in both engines on copies of array and compare the results.
+/// Fails if the algorithm doesn't exist in both engines.
+pub fn check_engine_parity(algorithm: &str, array: &[i32]) -> Result<ParityReport, String> {
+    let pregen_algorithm = Algorithm::from_str(algorithm)
+        .ok_or_else(|| format!("Unknown algorithm: {}", algorithm))?;
+    let mut live = LiveSort::new(algorithm, array.to_vec())
+        .ok_or_else(|| format!("No live implementation of {}", algorithm))?;
+
+    let mut pregen_array = array.to_vec();
+    let pregen_events = pregen_sort(pregen_algorithm, &mut pregen_array);
+    let pregen_counts = EventCounts::from_events(&pregen_events);
+
+    // Step at least once: a stepper that starts out done (n <= 1) still
+    // emits its Done on the first step
+    let mut live_counts = EventCounts::default();
+    loop {
+        for event in live.step(LIVE_BATCH) {
+            live_counts.add(&event);
+        }
+        if live.is_done() {
+            break;
+        }
+    }
+
+    let mut mismatches = Vec::new();
+    if pregen_array != live.array() {
+        mismatches.push(Mismatch::FinalArray {
+            pregen: pregen_array,
+            live: live.array().to_vec(),
+        });
+    }
+
+    let counts = pregen_counts.by_type().into_iter().zip(live_counts.by_type());
+    for ((event, pregen), (_, live)) in counts {
+        if pregen != live {
+            mismatches.push(Mismatch::EventCount { event, pregen, live });
+        }
+    }
+
+    Ok(ParityReport {
+        algorithm: pregen_algorithm.as_str(),
+        pregen_counts,
+        live_counts,
+        mismatches,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::live::LIVE_ALGORITHMS;
+    use crate::patterns::{self, Pattern};
+
+    #[test]
+    fn test_live_algorithms_match_pregen() {
+        for name in LIVE_ALGORITHMS {
+            for &pattern in Pattern::all() {
+                let input = patterns::generate(pattern, 200, 3);
+                let report = check_engine_parity(name, &input).unwrap();
+                assert!(report.is_consistent(), "{} on {:?}: {:?}", name, pattern, report.mismatches);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parity_counts_events() {
+        let report = check_engine_parity("bubble", &[3, 1, 2]).unwrap();
+
+        assert_eq!(report.algorithm, "bubble");
+        assert_eq!(report.pregen_counts, report.live_counts);
+        assert_eq!(report.pregen_counts.swaps, 2);
+        assert_eq!(report.pregen_counts.done, 1);
+    }
+
+    #[test]
+    fn test_parity_requires_both_engines() {
+        assert!(check_engine_parity("heap", &[2, 1]).unwrap_err().contains("live"));
+        assert!(check_engine_parity("bogus", &[2, 1]).unwrap_err().contains("Unknown"));
+    }
+
+    #[test]
+    fn test_parity_empty_input() {
+        for name in LIVE_ALGORITHMS {
+            let report = check_engine_parity(name, &[]).unwrap();
+            assert!(report.is_consistent(), "{} {:?}", name, report.mismatches);
+        }
+    }
+}
@@ -0,0 +1,195 @@
+//! Seeded input generation for offline traces and benchmarks.
+//!
+//! The web UI draws its arrays from `Math.random`; native tools need the
+//! same kinds of input, reproducible from a seed. The same (pattern, n,
+//! seed) always produces the same array.
+
+use crate::prelude::*;
+
+/// Shape of a generated input array.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pattern {
+    /// Values drawn independently from 1..=100, duplicates likely.
+    Random,
+    /// A shuffled permutation of 1..=n (the UI's "uniform" distribution).
+    Uniform,
+    Sorted,
+    Reversed,
+    /// Sorted, with about 5% of the elements swapped with a neighbour.
+    NearlySorted,
+    /// Only a handful of distinct values, shuffled.
+    FewUnique,
+}
+
+impl Pattern {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Pattern::Random => "random",
+            Pattern::Uniform => "uniform",
+            Pattern::Sorted => "sorted",
+            Pattern::Reversed => "reversed",
+            Pattern::NearlySorted => "nearly_sorted",
+            Pattern::FewUnique => "few_unique",
+        }
+    }
+
+    pub fn all() -> &'static [Pattern] {
+        const PATTERNS: [Pattern; 6] = [
+            Pattern::Random,
+            Pattern::Uniform,
+            Pattern::Sorted,
+            Pattern::Reversed,
+            Pattern::NearlySorted,
+            Pattern::FewUnique,
+        ];
+        &PATTERNS
+    }
+
+    /// Parse pattern name from string.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Pattern> {
+        match s.to_lowercase().as_str() {
+            "random" => Some(Pattern::Random),
+            "uniform" | "shuffled" | "permutation" => Some(Pattern::Uniform),
+            "sorted" | "ascending" => Some(Pattern::Sorted),
+            "reversed" | "reverse" | "descending" => Some(Pattern::Reversed),
+            "nearly_sorted" | "nearlysorted" | "almost_sorted" => Some(Pattern::NearlySorted),
+            "few_unique" | "fewunique" => Some(Pattern::FewUnique),
+            _ => None,
+        }
+    }
+}
+
+/// Value range of the Random pattern, matching the web UI.
+pub const RANDOM_VALUE_MIN: i32 = 1;
+pub const RANDOM_VALUE_MAX: i32 = 100;
+
+/// Number of distinct values in the FewUnique pattern.
+const FEW_UNIQUE_VALUES: u64 = 5;
+
+/// Generate an array of n elements with the given pattern and seed.
+pub fn generate(pattern: Pattern, n: usize, seed: u64) -> Vec<i32> {
+    let mut rng = Rng::new(seed);
+    let ascending = || (1..=n as i32).collect::<Vec<i32>>();
+
+    match pattern {
+        Pattern::Random => {
+            let range = (RANDOM_VALUE_MAX - RANDOM_VALUE_MIN + 1) as u64;
+            (0..n).map(|_| RANDOM_VALUE_MIN + rng.below(range) as i32).collect()
+        }
+        Pattern::Uniform => {
+            let mut array = ascending();
+            rng.shuffle(&mut array);
+            array
+        }
+        Pattern::Sorted => ascending(),
+        Pattern::Reversed => (1..=n as i32).rev().collect(),
+        Pattern::NearlySorted => {
+            let mut array = ascending();
+            if n > 1 {
+                for _ in 0..n.div_ceil(20) {
+                    let i = rng.below(n as u64 - 1) as usize;
+                    array.swap(i, i + 1);
+                }
+            }
+            array
+        }
+        Pattern::FewUnique => {
+            let step = (n as u64 / FEW_UNIQUE_VALUES).max(1) as i32;
+            (0..n).map(|_| (rng.below(FEW_UNIQUE_VALUES) as i32 + 1) * step).collect()
+        }
+    }
+}
+
+/// Small xorshift64* generator; not for anything but test inputs.
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        // Mix the seed so that small seeds (0, 1, 2, ...) diverge quickly;
+        // xorshift also needs a non-zero state
+        Rng(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// Uniform value in [0, bound).
+    fn below(&mut self, bound: u64) -> u64 {
+        self.next() % bound
+    }
+
+    /// Fisher-Yates shuffle.
+    fn shuffle(&mut self, array: &mut [i32]) {
+        for i in (1..array.len()).rev() {
+            let j = self.below(i as u64 + 1) as usize;
+            array.swap(i, j);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_lengths() {
+        for &pattern in Pattern::all() {
+            for n in [0, 1, 2, 17, 100] {
+                assert_eq!(generate(pattern, n, 7).len(), n, "{:?}", pattern);
+            }
+        }
+    }
+
+    #[test]
+    fn test_generate_deterministic() {
+        for &pattern in Pattern::all() {
+            assert_eq!(generate(pattern, 50, 42), generate(pattern, 50, 42));
+        }
+        assert_ne!(generate(Pattern::Uniform, 50, 1), generate(Pattern::Uniform, 50, 2));
+    }
+
+    #[test]
+    fn test_uniform_is_permutation() {
+        let mut array = generate(Pattern::Uniform, 64, 3);
+        array.sort();
+        assert_eq!(array, (1..=64).collect::<Vec<i32>>());
+    }
+
+    #[test]
+    fn test_random_value_range() {
+        let array = generate(Pattern::Random, 1000, 9);
+        assert!(array.iter().all(|v| (RANDOM_VALUE_MIN..=RANDOM_VALUE_MAX).contains(v)));
+    }
+
+    #[test]
+    fn test_ordered_patterns() {
+        assert_eq!(generate(Pattern::Sorted, 4, 0), vec![1, 2, 3, 4]);
+        assert_eq!(generate(Pattern::Reversed, 4, 0), vec![4, 3, 2, 1]);
+
+        let nearly = generate(Pattern::NearlySorted, 100, 5);
+        let out_of_place = nearly.iter().enumerate().filter(|(i, &v)| v != *i as i32 + 1).count();
+        assert!(out_of_place > 0 && out_of_place <= 10);
+    }
+
+    #[test]
+    fn test_few_unique_values() {
+        let mut array = generate(Pattern::FewUnique, 200, 11);
+        array.sort();
+        array.dedup();
+        assert!(array.len() <= FEW_UNIQUE_VALUES as usize);
+    }
+
+    #[test]
+    fn test_from_str() {
+        for &pattern in Pattern::all() {
+            assert_eq!(Pattern::from_str(pattern.as_str()), Some(pattern));
+        }
+        assert_eq!(Pattern::from_str("descending"), Some(Pattern::Reversed));
+        assert_eq!(Pattern::from_str("bogus"), None);
+    }
+}
@@ -0,0 +1,138 @@
+//! Rearranging an array by a given permutation.
+//!
+//! A permutation `perm` says where each slot's element comes from: after
+//! applying it, `array[i]` holds what was at `perm[i]`. It splits into
+//! disjoint cycles, and a cycle of length k takes k - 1 swaps, so the
+//! whole permutation takes n minus the number of cycles: the fewest swaps
+//! that can do it. Each cycle with more than one element is highlighted
+//! with the "cycle" tag before its swaps.
+
+use crate::prelude::*;
+use crate::events::SortEvent;
+
+/// Fail unless perm is a permutation of 0..n.
+pub fn check_permutation(perm: &[usize], n: usize) -> Result<(), String> {
+    if perm.len() != n {
+        return Err(format!("Permutation has {} entries for {} elements", perm.len(), n));
+    }
+    let mut seen = vec![false; n];
+    for &p in perm {
+        if p >= n || seen[p] {
+            return Err(format!("{} is out of range or repeated", p));
+        }
+        seen[p] = true;
+    }
+    Ok(())
+}
+
+/// The cycles of a valid permutation, each starting at its smallest index,
+/// in order of that index. Fixed points are cycles of length one.
+pub fn cycles(perm: &[usize]) -> Vec<Vec<usize>> {
+    let mut visited = vec![false; perm.len()];
+    let mut cycles = Vec::new();
+    for start in 0..perm.len() {
+        if visited[start] {
+            continue;
+        }
+        let mut cycle = Vec::new();
+        let mut j = start;
+        while !visited[j] {
+            visited[j] = true;
+            cycle.push(j);
+            j = perm[j];
+        }
+        cycles.push(cycle);
+    }
+    cycles
+}
+
+/// Apply a valid permutation with one swap per element of each cycle but
+/// the last, appending the events.
+pub fn permute_into(array: &mut [i32], perm: &[usize], events: &mut Vec<SortEvent>) {
+    for cycle in cycles(perm) {
+        if cycle.len() < 2 {
+            continue;
+        }
+        events.push(SortEvent::highlight(cycle.clone(), "cycle"));
+        // Slot j takes its element from perm[j], which then holds the
+        // cycle's first element until the last slot, which wants exactly it
+        for &j in &cycle[..cycle.len() - 1] {
+            events.push(SortEvent::Swap { i: j, j: perm[j] });
+            array.swap(j, perm[j]);
+        }
+    }
+}
+
+/// Rearrange array so that `array[i]` becomes the old `array[perm[i]]`.
+/// Fails if perm is not a permutation of the array's indices.
+pub fn pregen_permute(array: &mut [i32], perm: &[usize]) -> Result<Vec<SortEvent>, String> {
+    check_permutation(perm, array.len())?;
+    let mut events = Vec::new();
+    permute_into(array, perm, &mut events);
+    events.push(SortEvent::Done);
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patterns::{self, Pattern};
+
+    fn swaps(events: &[SortEvent]) -> usize {
+        events.iter().filter(|e| matches!(e, SortEvent::Swap { .. })).count()
+    }
+
+    #[test]
+    fn test_gathers_by_permutation() {
+        let mut array = vec![10, 20, 30, 40, 50];
+        let perm = [3, 0, 4, 1, 2];
+        let events = pregen_permute(&mut array, &perm).unwrap();
+
+        assert_eq!(array, vec![40, 10, 50, 20, 30]);
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_swap_count_is_minimal() {
+        // Cycles (0 1 2), (3 4) and the fixed point 5
+        let perm = [1, 2, 0, 4, 3, 5];
+        assert_eq!(cycles(&perm), vec![vec![0, 1, 2], vec![3, 4], vec![5]]);
+
+        let mut array: Vec<i32> = (0..6).collect();
+        let events = pregen_permute(&mut array, &perm).unwrap();
+        assert_eq!(swaps(&events), 6 - 3);
+        assert_eq!(array, vec![1, 2, 0, 4, 3, 5]);
+    }
+
+    #[test]
+    fn test_identity_is_free() {
+        let mut array = vec![3, 1, 2];
+        let events = pregen_permute(&mut array, &[0, 1, 2]).unwrap();
+        assert_eq!(events, vec![SortEvent::Done]);
+        assert_eq!(array, vec![3, 1, 2]);
+    }
+
+    #[test]
+    fn test_argsort_permutation_sorts() {
+        let input = patterns::generate(Pattern::Random, 200, 6);
+        let mut perm: Vec<usize> = (0..input.len()).collect();
+        perm.sort_by_key(|&i| input[i]);
+
+        let mut array = input.clone();
+        let events = pregen_permute(&mut array, &perm).unwrap();
+        assert!(array.windows(2).all(|w| w[0] <= w[1]));
+
+        let mut replayed = input.clone();
+        for event in &events {
+            event.apply(&mut replayed);
+        }
+        assert_eq!(replayed, array);
+    }
+
+    #[test]
+    fn test_rejects_invalid_permutations() {
+        assert!(pregen_permute(&mut [1, 2, 3], &[0, 1]).is_err());
+        assert!(pregen_permute(&mut [1, 2, 3], &[0, 1, 3]).is_err());
+        assert!(pregen_permute(&mut [1, 2, 3], &[0, 1, 1]).is_err());
+    }
+}
@@ -0,0 +1,113 @@
+//! Opt-in sortedness check before a sort.
+//!
+//! Most algorithms do their full work on sorted input. With a precheck the
+//! trace starts with a left-to-right scan of neighbouring pairs; if it
+//! finds no inversion the trace ends right there, marked by an
+//! "already_sorted" phase, and the sort is skipped. Otherwise the sort runs
+//! as usual after the scan. This shows what an adaptive check costs and
+//! buys, and saves full traces of sorted inputs.
+
+use serde::Serialize;
+use crate::events::SortEvent;
+use crate::pregen::{pregen_sort_into, Algorithm};
+
+/// Phase starting the scan.
+pub const PRECHECK_PHASE: &str = "precheck";
+
+/// Phase ending the trace of an input the scan found sorted.
+pub const ALREADY_SORTED_PHASE: &str = "already_sorted";
+
+/// How a prechecked run ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    /// The scan found the input sorted; the sort was skipped.
+    AlreadySorted,
+    /// The scan found an inversion and the sort ran.
+    Sorted,
+}
+
+/// Scan array for an inversion, appending a Phase and a Compare per pair
+/// up to the first inversion. Returns true if there is none.
+pub fn scan(array: &[i32], events: &mut Vec<SortEvent>) -> bool {
+    events.push(SortEvent::Phase { name: PRECHECK_PHASE.to_string() });
+    for i in 1..array.len() {
+        events.push(SortEvent::Compare { i: i - 1, j: i });
+        if array[i - 1] > array[i] {
+            return false;
+        }
+    }
+    true
+}
+
+/// Run a pregeneration sort after a sortedness scan, skipping the sort if
+/// the input is already sorted.
+pub fn pregen_sort_prechecked(
+    algorithm: Algorithm,
+    array: &mut [i32],
+) -> (Vec<SortEvent>, Outcome) {
+    let mut events = Vec::new();
+    if scan(array, &mut events) {
+        events.push(SortEvent::Phase { name: ALREADY_SORTED_PHASE.to_string() });
+        events.push(SortEvent::Done);
+        return (events, Outcome::AlreadySorted);
+    }
+
+    pregen_sort_into(algorithm, array, &mut events);
+    (events, Outcome::Sorted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pregen::pregen_sort;
+
+    #[test]
+    fn test_sorted_input_ends_early() {
+        let mut array = vec![1, 2, 2, 5];
+        let (events, outcome) = pregen_sort_prechecked(Algorithm::Bubble, &mut array);
+
+        assert_eq!(outcome, Outcome::AlreadySorted);
+        assert_eq!(
+            events,
+            vec![
+                SortEvent::Phase { name: "precheck".to_string() },
+                SortEvent::Compare { i: 0, j: 1 },
+                SortEvent::Compare { i: 1, j: 2 },
+                SortEvent::Compare { i: 2, j: 3 },
+                SortEvent::Phase { name: "already_sorted".to_string() },
+                SortEvent::Done,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unsorted_input_runs_the_sort() {
+        let input = vec![1, 3, 2, 4];
+        let mut array = input.clone();
+        let (events, outcome) = pregen_sort_prechecked(Algorithm::HeapSort, &mut array);
+
+        assert_eq!(outcome, Outcome::Sorted);
+        assert_eq!(array, vec![1, 2, 3, 4]);
+        // Scan up to the inversion at (1, 2), then the plain trace
+        let plain = pregen_sort(Algorithm::HeapSort, &mut input.clone());
+        assert_eq!(events[3..], plain[..]);
+    }
+
+    #[test]
+    fn test_trivial_inputs_are_sorted() {
+        for mut input in [vec![], vec![7]] {
+            let (events, outcome) = pregen_sort_prechecked(Algorithm::MergeSort, &mut input);
+            assert_eq!(outcome, Outcome::AlreadySorted);
+            assert_eq!(events.len(), 3);
+        }
+    }
+
+    #[test]
+    fn test_precheck_saves_work_on_sorted_input() {
+        let mut array: Vec<i32> = (0..200).collect();
+        let (events, _) = pregen_sort_prechecked(Algorithm::Selection, &mut array);
+        let plain = pregen_sort(Algorithm::Selection, &mut (0..200).collect::<Vec<_>>());
+        assert!(events.len() < plain.len() / 10);
+    }
+}
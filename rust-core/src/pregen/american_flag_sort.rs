@@ -0,0 +1,192 @@
+//! American Flag Sort implementation for V1 (Pregeneration) engine.
+//!
+//! In-place MSD radix sort. Each pass counts digit occurrences to find bucket
+//! boundaries, then permutes elements into their buckets by following swap
+//! cycles instead of copying through a temporary array. Buckets are then
+//! sorted recursively on the next digit.
+//!
+//! Keys are taken relative to the minimum value, so negative numbers work.
+
+use crate::events::{EventSink, SortEvent};
+use super::{estimate, AlgorithmInfo, PregenSort};
+
+pub struct AmericanFlagSort;
+
+const RADIX: u64 = 10;
+
+impl PregenSort for AmericanFlagSort {
+    const INFO: AlgorithmInfo = AlgorithmInfo {
+        display_name: "American Flag Sort",
+        best: "O(n·k)",
+        average: "O(n·k)",
+        worst: "O(n·k)",
+        space: "O(k·b)",
+        stable: false,
+        in_place: true,
+        adaptive: false,
+        description: "In-place MSD radix sort that permutes elements into buckets by swap cycles.",
+    };
+
+    fn estimated_events(n: usize) -> usize {
+        estimate::linear(n, 3.5)
+    }
+
+    fn sort_into(array: &mut [i32], events: &mut impl EventSink) {
+        let n = array.len();
+
+        if n <= 1 {
+            events.push(SortEvent::Done);
+            return;
+        }
+
+        let min_val = *array.iter().min().unwrap();
+        let max_key = key(*array.iter().max().unwrap(), min_val);
+
+        // Highest digit position needed for the largest key
+        let mut max_exp = 1;
+        while max_key / max_exp >= RADIX {
+            max_exp *= RADIX;
+        }
+
+        flag_sort(array, 0, n, max_exp, min_val, events);
+
+        events.push(SortEvent::Done);
+    }
+}
+
+/// Non-negative key of a value relative to the array minimum.
+fn key(value: i32, min_val: i32) -> u64 {
+    (value as i64 - min_val as i64) as u64
+}
+
+fn digit(value: i32, min_val: i32, exp: u64) -> usize {
+    ((key(value, min_val) / exp) % RADIX) as usize
+}
+
+/// Sort array[lo..hi] by the digit at position exp, then recurse.
+fn flag_sort(
+    array: &mut [i32],
+    lo: usize,
+    hi: usize,
+    exp: u64,
+    min_val: i32,
+    events: &mut impl EventSink,
+) {
+    if hi <= lo + 1 {
+        return;
+    }
+
+    events.push(SortEvent::EnterRange { lo, hi: hi - 1 });
+
+    // Count occurrences of each digit
+    let mut count = [0usize; RADIX as usize];
+    for i in lo..hi {
+        count[digit(array[i], min_val, exp)] += 1;
+    }
+
+    // Bucket boundaries: bucket d occupies [start[d], start[d + 1])
+    let mut start = [lo; RADIX as usize + 1];
+    for d in 0..RADIX as usize {
+        start[d + 1] = start[d] + count[d];
+    }
+
+    // Permute in place: next[d] is the first unplaced slot of bucket d
+    let mut next = start;
+    for d in 0..RADIX as usize {
+        while next[d] < start[d + 1] {
+            let target = digit(array[next[d]], min_val, exp);
+            if target == d {
+                next[d] += 1;
+            } else {
+                events.push(SortEvent::Swap { i: next[d], j: next[target] });
+                array.swap(next[d], next[target]);
+                next[target] += 1;
+            }
+        }
+    }
+
+    events.push(SortEvent::ExitRange { lo, hi: hi - 1 });
+
+    // Recursively sort each bucket on the next digit
+    if exp >= RADIX {
+        for d in 0..RADIX as usize {
+            flag_sort(array, start[d], start[d + 1], exp / RADIX, min_val, events);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_american_flag_sort_basic() {
+        let mut array = vec![170, 45, 75, 90, 802, 24, 2, 66];
+        let events = AmericanFlagSort::sort(&mut array);
+
+        assert_eq!(array, vec![2, 24, 45, 66, 75, 90, 170, 802]);
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_american_flag_sort_already_sorted() {
+        let mut array = vec![1, 2, 3, 4, 5];
+        let events = AmericanFlagSort::sort(&mut array);
+
+        assert_eq!(array, vec![1, 2, 3, 4, 5]);
+        let swap_count = events.iter().filter(|e| matches!(e, SortEvent::Swap { .. })).count();
+        assert_eq!(swap_count, 0);
+    }
+
+    #[test]
+    fn test_american_flag_sort_reverse() {
+        let mut array: Vec<i32> = (0..120).rev().collect();
+        AmericanFlagSort::sort(&mut array);
+
+        let expected: Vec<i32> = (0..120).collect();
+        assert_eq!(array, expected);
+    }
+
+    #[test]
+    fn test_american_flag_sort_negative() {
+        let mut array = vec![-5, 13, 0, -120, 7, 7, -1];
+        AmericanFlagSort::sort(&mut array);
+
+        assert_eq!(array, vec![-120, -5, -1, 0, 7, 7, 13]);
+    }
+
+    #[test]
+    fn test_american_flag_sort_extremes() {
+        let mut array = vec![i32::MAX, i32::MIN, 0, -1, 1];
+        AmericanFlagSort::sort(&mut array);
+
+        assert_eq!(array, vec![i32::MIN, -1, 0, 1, i32::MAX]);
+    }
+
+    #[test]
+    fn test_american_flag_sort_empty() {
+        let mut array: Vec<i32> = vec![];
+        let events = AmericanFlagSort::sort(&mut array);
+
+        assert!(array.is_empty());
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_american_flag_sort_single() {
+        let mut array = vec![42];
+        let events = AmericanFlagSort::sort(&mut array);
+
+        assert_eq!(array, vec![42]);
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_american_flag_sort_only_swaps() {
+        let mut array = vec![321, 123, 213, 312, 132, 231];
+        let events = AmericanFlagSort::sort(&mut array);
+
+        assert_eq!(array, vec![123, 132, 213, 231, 312, 321]);
+        assert!(!events.iter().any(|e| matches!(e, SortEvent::Overwrite { .. })));
+    }
+}
@@ -3,26 +3,42 @@
 //! Uses binary search to find the insertion position, reducing comparisons
 //! from O(n) to O(log n) per element, though shifts remain O(n).
 
-use crate::events::SortEvent;
-use super::PregenSort;
+use crate::events::{EventSink, SortEvent};
+use super::{estimate, AlgorithmInfo, PregenSort};
 
 pub struct BinaryInsertionSort;
 
 impl PregenSort for BinaryInsertionSort {
-    fn sort(array: &mut [i32]) -> Vec<SortEvent> {
-        let mut events = Vec::new();
+    const INFO: AlgorithmInfo = AlgorithmInfo {
+        display_name: "Binary Insertion Sort",
+        best: "O(n log n)",
+        average: "O(n²)",
+        worst: "O(n²)",
+        space: "O(1)",
+        stable: true,
+        in_place: true,
+        adaptive: true,
+        description:
+            "Insertion sort that binary-searches the insertion point; shifts stay quadratic.",
+    };
+
+    fn estimated_events(n: usize) -> usize {
+        estimate::quadratic(n, 0.25) + estimate::n_log_n(n, 1.0)
+    }
+
+    fn sort_into(array: &mut [i32], events: &mut impl EventSink) {
         let n = array.len();
 
         if n <= 1 {
             events.push(SortEvent::Done);
-            return events;
+            return;
         }
 
         for i in 1..n {
             let value = array[i];
 
             // Binary search for insertion position in sorted portion [0, i)
-            let insert_pos = binary_search_insert_pos(array, i, value, &mut events);
+            let insert_pos = binary_search_insert_pos(array, i, value, events);
 
             // Shift elements right to make room (via overwrites)
             for j in (insert_pos..i).rev() {
@@ -46,7 +62,6 @@ impl PregenSort for BinaryInsertionSort {
         }
 
         events.push(SortEvent::Done);
-        events
     }
 }
 
@@ -56,7 +71,7 @@ fn binary_search_insert_pos(
     array: &[i32],
     right: usize,
     value: i32,
-    events: &mut Vec<SortEvent>,
+    events: &mut impl EventSink,
 ) -> usize {
     let mut lo = 0;
     let mut hi = right;
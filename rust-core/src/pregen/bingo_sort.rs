@@ -0,0 +1,186 @@
+//! Bingo Sort implementation for V1 (Pregeneration) engine.
+//!
+//! Selection sort variant for inputs with many duplicates. Each pass takes
+//! the current minimum (the "bingo" value), moves every element equal to it
+//! to the front of the unsorted region, and finds the next minimum on the
+//! way. The number of passes equals the number of distinct values. Each
+//! pass's block of copies is marked with an EqualRange event.
+
+use crate::events::{EventSink, SortEvent};
+use super::{estimate, AlgorithmInfo, PregenSort};
+
+pub struct BingoSort;
+
+impl PregenSort for BingoSort {
+    const INFO: AlgorithmInfo = AlgorithmInfo {
+        display_name: "Bingo Sort",
+        best: "O(n)",
+        average: "O(n·m)",
+        worst: "O(n²)",
+        space: "O(1)",
+        stable: false,
+        in_place: true,
+        adaptive: false,
+        description:
+            "Selection sort that moves all copies of the minimum per pass; m is the number of distinct values.",
+    };
+
+    fn estimated_events(n: usize) -> usize {
+        estimate::quadratic(n, 1.0)
+    }
+
+    fn sort_into(array: &mut [i32], events: &mut impl EventSink) {
+        let n = array.len();
+
+        if n <= 1 {
+            events.push(SortEvent::Done);
+            return;
+        }
+
+        // Find the first bingo value (overall minimum)
+        let mut bingo_idx = 0;
+        for i in 1..n {
+            events.push(SortEvent::Compare { i: bingo_idx, j: i });
+            if array[i] < array[bingo_idx] {
+                bingo_idx = i;
+            }
+        }
+
+        let mut next = 0;
+        loop {
+            // Place the known bingo element; it anchors this pass's comparisons
+            if bingo_idx != next {
+                events.push(SortEvent::Swap { i: next, j: bingo_idx });
+                array.swap(next, bingo_idx);
+            }
+            let bingo_pos = next;
+            next += 1;
+
+            let mut next_min: Option<usize> = None;
+            let scan_start = next;
+            for i in scan_start..n {
+                events.push(SortEvent::Compare { i, j: bingo_pos });
+                if array[i] == array[bingo_pos] {
+                    // Another bingo: move it next to the others
+                    if i != next {
+                        events.push(SortEvent::Swap { i: next, j: i });
+                        array.swap(next, i);
+                        if next_min == Some(next) {
+                            next_min = Some(i);
+                        }
+                    }
+                    next += 1;
+                    continue;
+                }
+
+                // Track the minimum of the remaining values for the next pass
+                match next_min {
+                    None => next_min = Some(i),
+                    Some(m) => {
+                        events.push(SortEvent::Compare { i: m, j: i });
+                        if array[i] < array[m] {
+                            next_min = Some(i);
+                        }
+                    }
+                }
+            }
+
+            if next - bingo_pos > 1 {
+                events.push(SortEvent::EqualRange { lo: bingo_pos, hi: next - 1 });
+            }
+
+            match next_min {
+                Some(m) => bingo_idx = m,
+                None => break,
+            }
+        }
+
+        events.push(SortEvent::Done);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bingo_sort_basic() {
+        let mut array = vec![5, 3, 8, 4, 2];
+        let events = BingoSort::sort(&mut array);
+
+        assert_eq!(array, vec![2, 3, 4, 5, 8]);
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_bingo_sort_already_sorted() {
+        let mut array = vec![1, 2, 3, 4, 5];
+        let events = BingoSort::sort(&mut array);
+
+        assert_eq!(array, vec![1, 2, 3, 4, 5]);
+        assert!(!events.iter().any(|e| e.is_mutation()));
+    }
+
+    #[test]
+    fn test_bingo_sort_reverse() {
+        let mut array: Vec<i32> = (0..30).rev().collect();
+        BingoSort::sort(&mut array);
+
+        let expected: Vec<i32> = (0..30).collect();
+        assert_eq!(array, expected);
+    }
+
+    #[test]
+    fn test_bingo_sort_few_unique() {
+        let mut array = vec![2, 0, 1, 2, 0, 1, 1, 2, 0, 2];
+        let events = BingoSort::sort(&mut array);
+
+        assert_eq!(array, vec![0, 0, 0, 1, 1, 1, 2, 2, 2, 2]);
+        // One scan to find the minimum plus one pass per distinct value
+        let compare_count = events.iter().filter(|e| matches!(e, SortEvent::Compare { .. })).count();
+        assert!(compare_count < 2 * 3 * array.len());
+    }
+
+    #[test]
+    fn test_bingo_sort_marks_equal_blocks() {
+        let input = vec![2, 0, 1, 2, 0, 1, 1, 2, 0, 2];
+        let events = BingoSort::sort(&mut input.clone());
+
+        let mut replayed = input.clone();
+        let mut ranges = 0;
+        for event in &events {
+            event.apply(&mut replayed);
+            if let SortEvent::EqualRange { lo, hi } = *event {
+                assert!(replayed[lo..=hi].iter().all(|&v| v == replayed[lo]), "{}..={}", lo, hi);
+                ranges += 1;
+            }
+        }
+        assert!(ranges > 0);
+    }
+
+    #[test]
+    fn test_bingo_sort_empty() {
+        let mut array: Vec<i32> = vec![];
+        let events = BingoSort::sort(&mut array);
+
+        assert!(array.is_empty());
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_bingo_sort_single() {
+        let mut array = vec![42];
+        let events = BingoSort::sort(&mut array);
+
+        assert_eq!(array, vec![42]);
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_bingo_sort_duplicates() {
+        let mut array = vec![3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5];
+        BingoSort::sort(&mut array);
+
+        assert_eq!(array, vec![1, 1, 2, 3, 3, 4, 5, 5, 5, 6, 9]);
+    }
+}
@@ -1,87 +1,105 @@
 //! Bitonic Sort implementation for V1 (Pregeneration) engine.
 //!
 //! A parallel sorting algorithm based on bitonic sequences.
-//! Note: Classic bitonic sort requires array length to be a power of 2.
-//! This implementation pads arrays internally to handle arbitrary sizes.
+//! Classic bitonic sort requires the length to be a power of 2. This uses
+//! the arbitrary-length variant instead: halves are sorted in opposite
+//! directions, and each merge compares element i with i + m, where m is the
+//! largest power of 2 below the merge length. The comparator sequence
+//! touches only real positions, so no padding is needed and every event
+//! is exactly what the network does.
 
-use crate::events::SortEvent;
-use super::PregenSort;
+use crate::events::{EventSink, SortEvent};
+use super::{estimate, AlgorithmInfo, PregenSort};
 
 pub struct BitonicSort;
 
 impl PregenSort for BitonicSort {
-    fn sort(array: &mut [i32]) -> Vec<SortEvent> {
-        let mut events = Vec::new();
+    const INFO: AlgorithmInfo = AlgorithmInfo {
+        display_name: "Bitonic Sort",
+        best: "O(n log² n)",
+        average: "O(n log² n)",
+        worst: "O(n log² n)",
+        space: "O(1)",
+        stable: false,
+        in_place: true,
+        adaptive: false,
+        description: "Sorting network that merges bitonic sequences; any length.",
+    };
+
+    fn estimated_events(n: usize) -> usize {
+        estimate::n_log2_n(n, 0.42)
+    }
+
+    fn sort_into(array: &mut [i32], events: &mut impl EventSink) {
         let n = array.len();
 
         if n <= 1 {
             events.push(SortEvent::Done);
-            return events;
-        }
-
-        // Bitonic sort requires power-of-2 length
-        // Pad array to next power of 2 with i32::MAX as sentinel
-        let padded_len = n.next_power_of_two();
-        let mut padded: Vec<i32> = array.to_vec();
-        padded.resize(padded_len, i32::MAX);
-
-        // Track what the frontend sees (only events within bounds)
-        let mut frontend_view = array.to_vec();
-
-        // Iterative bitonic sort
-        let mut k = 2;
-        while k <= padded_len {
-            let mut j = k / 2;
-            while j > 0 {
-                for i in 0..padded_len {
-                    let l = i ^ j;
-                    if l > i {
-                        let ascending = (i & k) == 0;
-                        let should_swap = if ascending {
-                            padded[i] > padded[l]
-                        } else {
-                            padded[i] < padded[l]
-                        };
-
-                        // Only emit events for indices within the original array
-                        if i < n && l < n {
-                            events.push(SortEvent::Compare { i, j: l });
-                            if should_swap {
-                                events.push(SortEvent::Swap { i, j: l });
-                                frontend_view.swap(i, l);
-                            }
-                        }
-
-                        if should_swap {
-                            padded.swap(i, l);
-                        }
-                    }
-                }
-                j /= 2;
-            }
-            k *= 2;
+            return;
         }
 
-        // Copy back (only the original n elements)
-        array.copy_from_slice(&padded[..n]);
-
-        // Emit correction Overwrite events for any positions that diverged
-        // due to swaps with padding area
-        for i in 0..n {
-            if frontend_view[i] != array[i] {
-                events.push(SortEvent::Overwrite {
-                    idx: i,
-                    old_val: frontend_view[i],
-                    new_val: array[i],
-                });
-            }
-        }
+        bitonic_sort(array, 0, n, true, events);
 
         events.push(SortEvent::Done);
-        events
     }
 }
 
+/// Sort array[lo..lo + len] in the given direction.
+fn bitonic_sort(
+    array: &mut [i32],
+    lo: usize,
+    len: usize,
+    ascending: bool,
+    events: &mut impl EventSink,
+) {
+    if len <= 1 {
+        return;
+    }
+
+    // Sort halves in opposite directions to form a bitonic sequence
+    let half = len / 2;
+    bitonic_sort(array, lo, half, !ascending, events);
+    bitonic_sort(array, lo + half, len - half, ascending, events);
+    bitonic_merge(array, lo, len, ascending, events);
+}
+
+/// Merge the bitonic sequence array[lo..lo + len] in the given direction.
+fn bitonic_merge(
+    array: &mut [i32],
+    lo: usize,
+    len: usize,
+    ascending: bool,
+    events: &mut impl EventSink,
+) {
+    if len <= 1 {
+        return;
+    }
+
+    // Largest power of 2 strictly below len
+    let mut m = 1;
+    while m * 2 < len {
+        m *= 2;
+    }
+
+    for i in lo..(lo + len - m) {
+        let j = i + m;
+        events.push(SortEvent::Compare { i, j });
+
+        let should_swap = if ascending {
+            array[i] > array[j]
+        } else {
+            array[i] < array[j]
+        };
+        if should_swap {
+            events.push(SortEvent::Swap { i, j });
+            array.swap(i, j);
+        }
+    }
+
+    bitonic_merge(array, lo, m, ascending, events);
+    bitonic_merge(array, lo + m, len - m, ascending, events);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -166,4 +184,32 @@ mod tests {
         assert_eq!(array, vec![1, 2]);
         assert!(matches!(events.last(), Some(SortEvent::Done)));
     }
+
+    #[test]
+    fn test_bitonic_sort_no_overwrites_for_any_length() {
+        for n in 2..40 {
+            let mut array: Vec<i32> = (0..n).map(|x| (x * 37 + 11) % 23).collect();
+            let events = BitonicSort::sort(&mut array);
+
+            assert!(array.windows(2).all(|w| w[0] <= w[1]), "n = {}", n);
+            assert!(!events.iter().any(|e| matches!(e, SortEvent::Overwrite { .. })));
+            for event in &events {
+                if let SortEvent::Compare { i, j } = event {
+                    assert!(*i < n as usize && *j < n as usize);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_bitonic_sort_zero_one_principle() {
+        // A comparator network sorts everything iff it sorts all 0/1 inputs
+        for n in [3usize, 5, 6, 7, 9, 10, 11] {
+            for bits in 0u32..(1 << n) {
+                let mut array: Vec<i32> = (0..n).map(|k| ((bits >> k) & 1) as i32).collect();
+                BitonicSort::sort(&mut array);
+                assert!(array.windows(2).all(|w| w[0] <= w[1]), "n = {}, bits = {:b}", n, bits);
+            }
+        }
+    }
 }
@@ -1,18 +1,34 @@
 //! Bubble Sort implementation for V1 (Pregeneration) engine.
 
-use crate::events::SortEvent;
-use super::PregenSort;
+use crate::events::{EventSink, SortEvent};
+use super::{estimate, AlgorithmInfo, PregenSort};
 
 pub struct BubbleSort;
 
 impl PregenSort for BubbleSort {
-    fn sort(array: &mut [i32]) -> Vec<SortEvent> {
-        let mut events = Vec::new();
+    const INFO: AlgorithmInfo = AlgorithmInfo {
+        display_name: "Bubble Sort",
+        best: "O(n)",
+        average: "O(n²)",
+        worst: "O(n²)",
+        space: "O(1)",
+        stable: true,
+        in_place: true,
+        adaptive: true,
+        description:
+            "Repeatedly swaps adjacent out-of-order pairs; stops after a pass without swaps.",
+    };
+
+    fn estimated_events(n: usize) -> usize {
+        estimate::quadratic(n, 0.75)
+    }
+
+    fn sort_into(array: &mut [i32], events: &mut impl EventSink) {
         let n = array.len();
 
         if n <= 1 {
             events.push(SortEvent::Done);
-            return events;
+            return;
         }
 
         for i in 0..n {
@@ -37,7 +53,6 @@ impl PregenSort for BubbleSort {
         }
 
         events.push(SortEvent::Done);
-        events
     }
 }
 
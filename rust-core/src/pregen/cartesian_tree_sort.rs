@@ -0,0 +1,206 @@
+//! Cartesian Tree Sort implementation for V1 (Pregeneration) engine.
+//!
+//! Levcopoulos–Petersson adaptive sort:
+//! 1. Build a min-Cartesian tree of the input with a single stack pass.
+//! 2. Push the root into a priority queue; repeatedly pop the minimum and
+//!    push its tree children.
+//!
+//! The queue only ever holds the "frontier" of the tree, which stays small
+//! when the input is nearly sorted, so the work shrinks with presortedness.
+//! Both phases work on positions of the untouched input, so every Compare
+//! event is meaningful; the sorted order is written back at the end.
+
+use crate::prelude::*;
+use crate::events::{EventSink, SortEvent};
+use super::{estimate, AlgorithmInfo, PregenSort};
+
+pub struct CartesianTreeSort;
+
+impl PregenSort for CartesianTreeSort {
+    const INFO: AlgorithmInfo = AlgorithmInfo {
+        display_name: "Cartesian Tree Sort",
+        best: "O(n)",
+        average: "O(n log n)",
+        worst: "O(n log n)",
+        space: "O(n)",
+        stable: false,
+        in_place: false,
+        adaptive: true,
+        description:
+            "Builds a Cartesian tree and pops its nodes in order through a priority queue.",
+    };
+
+    fn estimated_events(n: usize) -> usize {
+        estimate::n_log_n(n, 1.75)
+    }
+
+    fn sort_into(array: &mut [i32], events: &mut impl EventSink) {
+        let n = array.len();
+
+        if n <= 1 {
+            events.push(SortEvent::Done);
+            return;
+        }
+
+        // Phase 1: build the Cartesian tree with a stack of the rightmost path
+        let mut left: Vec<Option<usize>> = vec![None; n];
+        let mut right: Vec<Option<usize>> = vec![None; n];
+        let mut stack: Vec<usize> = Vec::new();
+
+        for i in 0..n {
+            let mut last = None;
+            while let Some(&top) = stack.last() {
+                events.push(SortEvent::Compare { i: top, j: i });
+                if array[top] > array[i] {
+                    last = stack.pop();
+                } else {
+                    break;
+                }
+            }
+            left[i] = last;
+            if let Some(&top) = stack.last() {
+                right[top] = Some(i);
+            }
+            stack.push(i);
+        }
+        let root = stack[0];
+
+        // Phase 2: pop minima from a priority queue seeded with the root
+        let mut heap = vec![root];
+        let mut order = Vec::with_capacity(n);
+
+        while !heap.is_empty() {
+            let last = heap.len() - 1;
+            heap.swap(0, last);
+            let node = heap.pop().unwrap();
+            sift_down(array, &mut heap, 0, events);
+            order.push(node);
+
+            for child in [left[node], right[node]].into_iter().flatten() {
+                heap.push(child);
+                let last = heap.len() - 1;
+                sift_up(array, &mut heap, last, events);
+            }
+        }
+
+        // Write the sorted order back into the array
+        let sorted: Vec<i32> = order.iter().map(|&i| array[i]).collect();
+        for (idx, value) in sorted.into_iter().enumerate() {
+            if array[idx] != value {
+                events.push(SortEvent::Overwrite {
+                    idx,
+                    old_val: array[idx],
+                    new_val: value,
+                });
+                array[idx] = value;
+            }
+        }
+
+        events.push(SortEvent::Done);
+    }
+}
+
+/// Heap ordering on positions: smaller value first, ties by position (stable).
+fn less(array: &[i32], a: usize, b: usize, events: &mut impl EventSink) -> bool {
+    events.push(SortEvent::Compare { i: a, j: b });
+    (array[a], a) < (array[b], b)
+}
+
+fn sift_up(array: &[i32], heap: &mut [usize], mut pos: usize, events: &mut impl EventSink) {
+    while pos > 0 {
+        let parent = (pos - 1) / 2;
+        if less(array, heap[pos], heap[parent], events) {
+            heap.swap(pos, parent);
+            pos = parent;
+        } else {
+            break;
+        }
+    }
+}
+
+fn sift_down(array: &[i32], heap: &mut [usize], mut pos: usize, events: &mut impl EventSink) {
+    loop {
+        let left = 2 * pos + 1;
+        let right = 2 * pos + 2;
+        let mut smallest = pos;
+
+        if left < heap.len() && less(array, heap[left], heap[smallest], events) {
+            smallest = left;
+        }
+        if right < heap.len() && less(array, heap[right], heap[smallest], events) {
+            smallest = right;
+        }
+        if smallest == pos {
+            break;
+        }
+        heap.swap(pos, smallest);
+        pos = smallest;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compare_count(events: &[SortEvent]) -> usize {
+        events.iter().filter(|e| matches!(e, SortEvent::Compare { .. })).count()
+    }
+
+    #[test]
+    fn test_cartesian_tree_sort_basic() {
+        let mut array = vec![5, 3, 8, 4, 2];
+        let events = CartesianTreeSort::sort(&mut array);
+
+        assert_eq!(array, vec![2, 3, 4, 5, 8]);
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_cartesian_tree_sort_reverse() {
+        let mut array: Vec<i32> = (0..100).rev().collect();
+        CartesianTreeSort::sort(&mut array);
+
+        let expected: Vec<i32> = (0..100).collect();
+        assert_eq!(array, expected);
+    }
+
+    #[test]
+    fn test_cartesian_tree_sort_duplicates() {
+        let mut array = vec![3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5];
+        CartesianTreeSort::sort(&mut array);
+
+        assert_eq!(array, vec![1, 1, 2, 3, 3, 4, 5, 5, 5, 6, 9]);
+    }
+
+    #[test]
+    fn test_cartesian_tree_sort_empty() {
+        let mut array: Vec<i32> = vec![];
+        let events = CartesianTreeSort::sort(&mut array);
+
+        assert!(array.is_empty());
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_cartesian_tree_sort_single() {
+        let mut array = vec![42];
+        let events = CartesianTreeSort::sort(&mut array);
+
+        assert_eq!(array, vec![42]);
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_cartesian_tree_sort_adaptive() {
+        let mut sorted: Vec<i32> = (0..256).collect();
+        let sorted_events = CartesianTreeSort::sort(&mut sorted);
+
+        let mut shuffled: Vec<i32> = (0..256).map(|x| (x * 97) % 256).collect();
+        let shuffled_events = CartesianTreeSort::sort(&mut shuffled);
+
+        // Sorted input: linear work, no writes
+        assert!(compare_count(&sorted_events) < 2 * 256);
+        assert!(!sorted_events.iter().any(|e| e.is_mutation()));
+        assert!(compare_count(&shuffled_events) > 2 * compare_count(&sorted_events));
+    }
+}
@@ -0,0 +1,153 @@
+//! Circle Sort implementation for V1 (Pregeneration) engine.
+//!
+//! Compares mirrored pairs from the two ends of a range moving inward
+//! ("circles"), swapping out-of-order pairs, then recurses on both halves.
+//! Whole passes are repeated until one completes without any swap.
+//! Emits EnterRange/ExitRange events for every circle.
+
+use crate::events::{EventSink, SortEvent};
+use super::{estimate, AlgorithmInfo, PregenSort};
+
+pub struct CircleSort;
+
+impl PregenSort for CircleSort {
+    const INFO: AlgorithmInfo = AlgorithmInfo {
+        display_name: "Circle Sort",
+        best: "O(n log n)",
+        average: "O(n log² n)",
+        worst: "O(n log² n)",
+        space: "O(log n)",
+        stable: false,
+        in_place: true,
+        adaptive: true,
+        description:
+            "Swaps mirrored pairs inward across each range, recursing on halves until a pass makes no swap.",
+    };
+
+    fn estimated_events(n: usize) -> usize {
+        estimate::n_log2_n(n, 0.75)
+    }
+
+    fn sort_into(array: &mut [i32], events: &mut impl EventSink) {
+        let n = array.len();
+
+        if n > 1 {
+            while circle(array, 0, n - 1, events) {}
+        }
+
+        events.push(SortEvent::Done);
+    }
+}
+
+/// Run one circle over [lo, hi] and recurse on its halves.
+/// Returns true if any swap happened.
+fn circle(array: &mut [i32], lo: usize, hi: usize, events: &mut impl EventSink) -> bool {
+    if lo >= hi {
+        return false;
+    }
+
+    events.push(SortEvent::EnterRange { lo, hi });
+
+    let mut swapped = false;
+    let mut i = lo;
+    let mut j = hi;
+
+    while i < j {
+        events.push(SortEvent::Compare { i, j });
+        if array[i] > array[j] {
+            events.push(SortEvent::Swap { i, j });
+            array.swap(i, j);
+            swapped = true;
+        }
+        i += 1;
+        j -= 1;
+    }
+
+    // Odd-length range: the middle element is compared with its right neighbour
+    if i == j {
+        events.push(SortEvent::Compare { i, j: i + 1 });
+        if array[i] > array[i + 1] {
+            events.push(SortEvent::Swap { i, j: i + 1 });
+            array.swap(i, i + 1);
+            swapped = true;
+        }
+    }
+
+    events.push(SortEvent::ExitRange { lo, hi });
+
+    let mid = lo + (hi - lo) / 2;
+    let left_swapped = circle(array, lo, mid, events);
+    let right_swapped = circle(array, mid + 1, hi, events);
+
+    swapped || left_swapped || right_swapped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_circle_sort_basic() {
+        let mut array = vec![5, 3, 8, 4, 2];
+        let events = CircleSort::sort(&mut array);
+
+        assert_eq!(array, vec![2, 3, 4, 5, 8]);
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_circle_sort_already_sorted() {
+        let mut array = vec![1, 2, 3, 4, 5];
+        let events = CircleSort::sort(&mut array);
+
+        assert_eq!(array, vec![1, 2, 3, 4, 5]);
+        assert!(!events.iter().any(|e| e.is_mutation()));
+    }
+
+    #[test]
+    fn test_circle_sort_reverse() {
+        let mut array: Vec<i32> = (0..41).rev().collect();
+        CircleSort::sort(&mut array);
+
+        let expected: Vec<i32> = (0..41).collect();
+        assert_eq!(array, expected);
+    }
+
+    #[test]
+    fn test_circle_sort_empty() {
+        let mut array: Vec<i32> = vec![];
+        let events = CircleSort::sort(&mut array);
+
+        assert!(array.is_empty());
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_circle_sort_single() {
+        let mut array = vec![42];
+        let events = CircleSort::sort(&mut array);
+
+        assert_eq!(array, vec![42]);
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_circle_sort_duplicates() {
+        let mut array = vec![3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5];
+        CircleSort::sort(&mut array);
+
+        assert_eq!(array, vec![1, 1, 2, 3, 3, 4, 5, 5, 5, 6, 9]);
+    }
+
+    #[test]
+    fn test_circle_sort_emits_range_events() {
+        let mut array = vec![6, 2, 7, 1, 5, 3];
+        let events = CircleSort::sort(&mut array);
+
+        let enter_count = events.iter().filter(|e| matches!(e, SortEvent::EnterRange { .. })).count();
+        let exit_count = events.iter().filter(|e| matches!(e, SortEvent::ExitRange { .. })).count();
+
+        assert!(enter_count > 0);
+        assert_eq!(enter_count, exit_count);
+    }
+}
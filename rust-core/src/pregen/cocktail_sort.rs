@@ -2,29 +2,49 @@
 //!
 //! Cocktail sort is a variation of bubble sort that sorts in both directions
 //! on each pass through the list. This can be more efficient than bubble sort
-//! for certain inputs (e.g., "turtles" - small values at the end).
+//! for certain inputs (e.g., "turtles" - small values at the end). The
+//! shrinking bounds of the unsorted part are the "start" and "end" cursors.
 
-use crate::events::SortEvent;
-use super::PregenSort;
+use crate::events::{Cursor, EventSink, SortEvent};
+use super::{estimate, AlgorithmInfo, PregenSort};
 
 pub struct CocktailSort;
 
 impl PregenSort for CocktailSort {
-    fn sort(array: &mut [i32]) -> Vec<SortEvent> {
-        let mut events = Vec::new();
+    const INFO: AlgorithmInfo = AlgorithmInfo {
+        display_name: "Cocktail Sort",
+        best: "O(n)",
+        average: "O(n²)",
+        worst: "O(n²)",
+        space: "O(1)",
+        stable: true,
+        in_place: true,
+        adaptive: true,
+        description: "Bubble sort that alternates direction on every pass.",
+    };
+
+    fn estimated_events(n: usize) -> usize {
+        estimate::quadratic(n, 0.63)
+    }
+
+    fn sort_into(array: &mut [i32], events: &mut impl EventSink) {
         let n = array.len();
 
         if n <= 1 {
             events.push(SortEvent::Done);
-            return events;
+            return;
         }
 
         let mut start = 0;
         let mut end = n - 1;
         let mut swapped = true;
+        let mut start_cursor = Cursor::new("start");
+        let mut end_cursor = Cursor::new("end");
 
         while swapped {
             swapped = false;
+            start_cursor.set(Some(start), events);
+            end_cursor.set(Some(end), events);
 
             // Forward pass (left to right)
             for i in start..end {
@@ -43,6 +63,7 @@ impl PregenSort for CocktailSort {
 
             // Reduce end because the last element is now in place
             end -= 1;
+            end_cursor.set(Some(end), events);
             swapped = false;
 
             // Backward pass (right to left)
@@ -60,8 +81,9 @@ impl PregenSort for CocktailSort {
             start += 1;
         }
 
+        start_cursor.set(None, events);
+        end_cursor.set(None, events);
         events.push(SortEvent::Done);
-        events
     }
 }
 
@@ -122,4 +144,22 @@ mod tests {
 
         assert_eq!(array, vec![1, 2, 3, 4, 5]);
     }
+
+    #[test]
+    fn test_cocktail_sort_bounds_cursors_shrink() {
+        let mut array = vec![5, 4, 3, 2, 1];
+        let events = CocktailSort::sort(&mut array);
+
+        let bounds = |id: &str| -> Vec<Option<usize>> {
+            events
+                .iter()
+                .filter_map(|e| match e {
+                    SortEvent::SetCursor(c) if c.id == id => Some(c.index),
+                    _ => None,
+                })
+                .collect()
+        };
+        assert_eq!(bounds("start"), vec![Some(0), Some(1), Some(2), None]);
+        assert_eq!(bounds("end"), vec![Some(4), Some(3), Some(2), None]);
+    }
 }
@@ -2,10 +2,11 @@
 //!
 //! Improves on bubble sort by using a gap sequence that shrinks by a factor
 //! of ~1.3 (the "shrink factor"). Eliminates "turtles" (small values near
-//! the end) more efficiently than bubble sort.
+//! the end) more efficiently than bubble sort. A GapChange event marks each
+//! pass that shrinks the gap, and a last one back to 0 ends the sort.
 
-use crate::events::SortEvent;
-use super::PregenSort;
+use crate::events::{EventSink, SortEvent};
+use super::{estimate, AlgorithmInfo, PregenSort};
 
 pub struct CombSort;
 
@@ -13,25 +14,44 @@ pub struct CombSort;
 const SHRINK_FACTOR: f64 = 1.3;
 
 impl PregenSort for CombSort {
-    fn sort(array: &mut [i32]) -> Vec<SortEvent> {
-        let mut events = Vec::new();
+    const INFO: AlgorithmInfo = AlgorithmInfo {
+        display_name: "Comb Sort",
+        best: "O(n log n)",
+        average: "O(n²/2^p)",
+        worst: "O(n²)",
+        space: "O(1)",
+        stable: false,
+        in_place: true,
+        adaptive: false,
+        description:
+            "Bubble sort over a gap that shrinks by about 1.3 each pass; p is the number of passes.",
+    };
+
+    fn estimated_events(n: usize) -> usize {
+        estimate::n_log_n(n, 2.8)
+    }
+
+    fn sort_into(array: &mut [i32], events: &mut impl EventSink) {
         let n = array.len();
 
         if n <= 1 {
             events.push(SortEvent::Done);
-            return events;
+            return;
         }
 
-        let mut gap = n;
+        let mut gap = 0;
         let mut sorted = false;
 
         while !sorted {
             // Shrink the gap
-            gap = ((gap as f64) / SHRINK_FACTOR).floor() as usize;
-            if gap <= 1 {
-                gap = 1;
+            let prev = gap;
+            gap = next_gap(gap, n);
+            if gap == 1 {
                 sorted = true; // Will become false if any swap happens
             }
+            if gap != prev {
+                events.push(SortEvent::GapChange { gap, prev });
+            }
 
             // Compare elements with current gap
             for i in 0..n - gap {
@@ -46,11 +66,18 @@ impl PregenSort for CombSort {
             }
         }
 
+        events.push(SortEvent::GapChange { gap: 0, prev: gap });
         events.push(SortEvent::Done);
-        events
     }
 }
 
+/// Gap of the pass after one with `gap`, for an array of n > 1 elements;
+/// gap 0 is before the first pass. Never below 1.
+pub(crate) fn next_gap(gap: usize, n: usize) -> usize {
+    let from = if gap == 0 { n } else { gap };
+    (((from as f64) / SHRINK_FACTOR) as usize).max(1)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -116,4 +143,20 @@ mod tests {
 
         assert_eq!(array, vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
     }
+
+    #[test]
+    fn test_comb_sort_announces_shrinking_gaps() {
+        let mut array: Vec<i32> = (0..20).rev().collect();
+        let events = CombSort::sort(&mut array);
+
+        let gaps: Vec<usize> = events
+            .iter()
+            .filter_map(|e| match *e {
+                SortEvent::GapChange { gap, .. } => Some(gap),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(gaps, vec![15, 11, 8, 6, 4, 3, 2, 1, 0]);
+        assert_eq!(events[events.len() - 2], SortEvent::GapChange { gap: 0, prev: 1 });
+    }
 }
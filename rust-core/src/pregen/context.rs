@@ -0,0 +1,168 @@
+//! Reusable buffers for repeated pregeneration runs.
+//!
+//! Every `pregen_sort` call builds a fresh event vector, which for large
+//! quadratic sorts reaches hundreds of MB. Allocating and freeing that on
+//! every run fragments the wasm heap, which never returns memory to the
+//! host. A `PregenContext` keeps its event buffer, working array and the
+//! algorithms' aux arrays (`Scratch`) between runs and only grows them;
+//! `shrink` releases them when the caller is done.
+//!
+//! Only the algorithms with O(n) aux arrays take theirs from `Scratch`:
+//! the merge sorts, timsort, and radix LSD/MSD, flash and sample sort.
+//! Small per-call buffers elsewhere are still allocated per run.
+
+use crate::prelude::*;
+use crate::events::SortEvent;
+use super::{estimate, estimated_events, pregen_sort_into_with, Algorithm};
+
+#[derive(Default)]
+pub struct PregenContext {
+    events: Vec<SortEvent>,
+    array: Vec<i32>,
+    scratch: Scratch,
+}
+
+/// Aux arrays an algorithm works in besides the array it sorts; see
+/// `PregenSort::sort_into_with`. Contents don't carry over between uses.
+#[derive(Default)]
+pub struct Scratch {
+    pub(crate) values: Vec<i32>,
+    pub(crate) indices: Vec<usize>,
+}
+
+impl Scratch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Bytes currently held by the buffers.
+    pub fn retained_bytes(&self) -> usize {
+        self.values.capacity() * core::mem::size_of::<i32>()
+            + self.indices.capacity() * core::mem::size_of::<usize>()
+    }
+}
+
+/// `buffer` cleared and refilled with `len` default values.
+#[cfg(feature = "distribution")]
+pub(crate) fn zeroed<T: Copy + Default>(buffer: &mut Vec<T>, len: usize) -> &mut [T] {
+    buffer.clear();
+    buffer.resize(len, T::default());
+    buffer
+}
+
+/// `buffer` cleared and refilled with a copy of `values`.
+pub(crate) fn copied<'a>(buffer: &'a mut Vec<i32>, values: &[i32]) -> &'a mut [i32] {
+    buffer.clear();
+    buffer.extend_from_slice(values);
+    buffer
+}
+
+impl PregenContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sort a copy of input, reusing the context's buffers.
+    /// Results stay valid until the next run; see `events` and `array`.
+    pub fn run(&mut self, algorithm: Algorithm, input: &[i32]) -> &[SortEvent] {
+        self.events.clear();
+        self.array.clear();
+        self.array.extend_from_slice(input);
+        self.events.reserve(estimate::reserve_limit(estimated_events(algorithm, input.len())));
+
+        pregen_sort_into_with(algorithm, &mut self.array, &mut self.events, &mut self.scratch);
+        &self.events
+    }
+
+    /// Events of the last run.
+    pub fn events(&self) -> &[SortEvent] {
+        &self.events
+    }
+
+    /// Sorted array of the last run.
+    pub fn array(&self) -> &[i32] {
+        &self.array
+    }
+
+    /// Bytes currently held by the retained buffers.
+    pub fn retained_bytes(&self) -> usize {
+        self.events.capacity() * core::mem::size_of::<SortEvent>()
+            + self.array.capacity() * core::mem::size_of::<i32>()
+            + self.scratch.retained_bytes()
+    }
+
+    /// Release all retained memory. The context stays usable.
+    pub fn shrink(&mut self) {
+        self.events = Vec::new();
+        self.array = Vec::new();
+        self.scratch = Scratch::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pregen::pregen_sort;
+
+    #[test]
+    fn test_context_matches_pregen_sort() {
+        let mut context = PregenContext::new();
+        let input = vec![5, 3, 8, 4, 2, 7, 1];
+
+        for &algorithm in Algorithm::all() {
+            let mut expected_array = input.clone();
+            let expected = pregen_sort(algorithm, &mut expected_array);
+
+            assert_eq!(context.run(algorithm, &input), expected.as_slice(), "{:?}", algorithm);
+            assert_eq!(context.array(), expected_array.as_slice());
+        }
+    }
+
+    #[test]
+    fn test_context_reuses_buffers() {
+        let mut context = PregenContext::new();
+        let input: Vec<i32> = (0..64).rev().collect();
+
+        context.run(Algorithm::Bubble, &input);
+        let retained = context.retained_bytes();
+        let events_ptr = context.events().as_ptr();
+
+        // A smaller run fits in the buffers from the first one
+        context.run(Algorithm::Bubble, &input[..32]);
+        assert_eq!(context.retained_bytes(), retained);
+        assert_eq!(context.events().as_ptr(), events_ptr);
+        assert_eq!(context.array().len(), 32);
+    }
+
+    #[test]
+    fn test_context_reuses_scratch() {
+        let mut context = PregenContext::new();
+        let input: Vec<i32> = (0..64).rev().collect();
+
+        context.run(Algorithm::MergeSort, &input);
+        let aux_ptr = context.scratch.values.as_ptr();
+        assert!(context.scratch.values.capacity() >= 64);
+
+        // Other aux-heavy algorithms take the same buffer
+        context.run(Algorithm::Timsort, &input[..48]);
+        context.run(Algorithm::MergeSortBottomUp, &input[..32]);
+        assert_eq!(context.scratch.values.as_ptr(), aux_ptr);
+        assert_eq!(context.array(), (32..64).collect::<Vec<_>>().as_slice());
+    }
+
+    #[test]
+    fn test_context_shrink() {
+        let mut context = PregenContext::new();
+        context.run(Algorithm::MergeSort, &[4, 1, 3, 2]);
+        assert!(context.retained_bytes() > 0);
+
+        context.shrink();
+        assert_eq!(context.retained_bytes(), 0);
+        assert_eq!(context.scratch.retained_bytes(), 0);
+        assert!(context.events().is_empty());
+
+        // Still usable afterwards
+        context.run(Algorithm::MergeSort, &[2, 1]);
+        assert_eq!(context.array(), &[1, 2]);
+    }
+}
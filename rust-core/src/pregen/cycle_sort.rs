@@ -4,19 +4,36 @@
 //! where writes are expensive (e.g., flash memory). Each element is
 //! moved at most once to its final position.
 
-use crate::events::SortEvent;
-use super::PregenSort;
+use crate::events::{EventSink, SortEvent};
+use super::{estimate, AlgorithmInfo, PregenSort};
 
 pub struct CycleSort;
 
 impl PregenSort for CycleSort {
-    fn sort(array: &mut [i32]) -> Vec<SortEvent> {
-        let mut events = Vec::new();
+    const INFO: AlgorithmInfo = AlgorithmInfo {
+        display_name: "Cycle Sort",
+        best: "O(n²)",
+        average: "O(n²)",
+        worst: "O(n²)",
+        space: "O(1)",
+        stable: false,
+        in_place: true,
+        adaptive: false,
+        description:
+            "Rotates each permutation cycle into place, writing every element at most once.",
+    };
+
+    fn estimated_events(n: usize) -> usize {
+        estimate::quadratic(n, 1.5)
+    }
+
+    #[allow(clippy::needless_range_loop)]
+    fn sort_into(array: &mut [i32], events: &mut impl EventSink) {
         let n = array.len();
 
         if n <= 1 {
             events.push(SortEvent::Done);
-            return events;
+            return;
         }
 
         // Traverse array elements and put each to the right place
@@ -52,7 +69,7 @@ impl PregenSort for CycleSort {
                     old_val,
                     new_val: item,
                 });
-                std::mem::swap(&mut item, &mut array[pos]);
+                core::mem::swap(&mut item, &mut array[pos]);
             }
 
             // Rotate rest of the cycle
@@ -80,13 +97,12 @@ impl PregenSort for CycleSort {
                         old_val,
                         new_val: item,
                     });
-                    std::mem::swap(&mut item, &mut array[pos]);
+                    core::mem::swap(&mut item, &mut array[pos]);
                 }
             }
         }
 
         events.push(SortEvent::Done);
-        events
     }
 }
 
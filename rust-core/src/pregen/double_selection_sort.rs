@@ -0,0 +1,147 @@
+//! Double Selection Sort implementation for V1 (Pregeneration) engine.
+//!
+//! Selection sort variant that finds both the minimum and the maximum of the
+//! unsorted middle on every pass, placing them at the left and right ends.
+//! Halves the number of passes and converges symmetrically from both sides.
+
+use crate::events::{EventSink, SortEvent};
+use super::{estimate, AlgorithmInfo, PregenSort};
+
+pub struct DoubleSelectionSort;
+
+impl PregenSort for DoubleSelectionSort {
+    const INFO: AlgorithmInfo = AlgorithmInfo {
+        display_name: "Double Selection Sort",
+        best: "O(n²)",
+        average: "O(n²)",
+        worst: "O(n²)",
+        space: "O(1)",
+        stable: false,
+        in_place: true,
+        adaptive: false,
+        description: "Selects both the minimum and the maximum on each pass, filling both ends.",
+    };
+
+    fn estimated_events(n: usize) -> usize {
+        estimate::quadratic(n, 0.5)
+    }
+
+    fn sort_into(array: &mut [i32], events: &mut impl EventSink) {
+        let n = array.len();
+
+        if n <= 1 {
+            events.push(SortEvent::Done);
+            return;
+        }
+
+        let mut left = 0;
+        let mut right = n - 1;
+
+        while left < right {
+            let mut min_idx = left;
+            let mut max_idx = left;
+
+            // Find minimum and maximum of the unsorted portion
+            for j in (left + 1)..=right {
+                events.push(SortEvent::Compare { i: min_idx, j });
+                if array[j] < array[min_idx] {
+                    min_idx = j;
+                }
+
+                events.push(SortEvent::Compare { i: max_idx, j });
+                if array[j] > array[max_idx] {
+                    max_idx = j;
+                }
+            }
+
+            // Place minimum at the left end
+            if min_idx != left {
+                events.push(SortEvent::Swap { i: left, j: min_idx });
+                array.swap(left, min_idx);
+
+                // The maximum may have just been moved out of `left`
+                if max_idx == left {
+                    max_idx = min_idx;
+                }
+            }
+
+            // Place maximum at the right end
+            if max_idx != right {
+                events.push(SortEvent::Swap { i: right, j: max_idx });
+                array.swap(right, max_idx);
+            }
+
+            left += 1;
+            right -= 1;
+        }
+
+        events.push(SortEvent::Done);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_double_selection_sort_basic() {
+        let mut array = vec![5, 3, 8, 4, 2];
+        let events = DoubleSelectionSort::sort(&mut array);
+
+        assert_eq!(array, vec![2, 3, 4, 5, 8]);
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_double_selection_sort_already_sorted() {
+        let mut array = vec![1, 2, 3, 4, 5];
+        let events = DoubleSelectionSort::sort(&mut array);
+
+        assert_eq!(array, vec![1, 2, 3, 4, 5]);
+        let swap_count = events.iter().filter(|e| matches!(e, SortEvent::Swap { .. })).count();
+        assert_eq!(swap_count, 0);
+    }
+
+    #[test]
+    fn test_double_selection_sort_reverse() {
+        let mut array = vec![5, 4, 3, 2, 1];
+        DoubleSelectionSort::sort(&mut array);
+
+        assert_eq!(array, vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_double_selection_sort_max_at_left() {
+        // Maximum starts where the minimum gets placed
+        let mut array = vec![9, 1, 5, 3, 7];
+        DoubleSelectionSort::sort(&mut array);
+
+        assert_eq!(array, vec![1, 3, 5, 7, 9]);
+    }
+
+    #[test]
+    fn test_double_selection_sort_empty() {
+        let mut array: Vec<i32> = vec![];
+        let events = DoubleSelectionSort::sort(&mut array);
+
+        assert!(array.is_empty());
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_double_selection_sort_single() {
+        let mut array = vec![42];
+        let events = DoubleSelectionSort::sort(&mut array);
+
+        assert_eq!(array, vec![42]);
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_double_selection_sort_duplicates() {
+        let mut array = vec![3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5];
+        DoubleSelectionSort::sort(&mut array);
+
+        assert_eq!(array, vec![1, 1, 2, 3, 3, 4, 5, 5, 5, 6, 9]);
+    }
+}
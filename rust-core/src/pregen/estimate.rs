@@ -0,0 +1,105 @@
+//! Event count estimates for pre-sizing pregen event buffers.
+//!
+//! Each algorithm's `PregenSort::estimated_events` is one of these growth
+//! curves with a constant measured on shuffled permutations. Estimates are
+//! only used as an initial capacity: an underestimate costs a reallocation,
+//! an overestimate costs memory, and neither changes the events.
+
+/// Largest capacity reserved up front, in events (about 400 MB).
+/// Runs that need more grow the buffer as usual.
+pub const MAX_RESERVED_EVENTS: usize = 1 << 24;
+
+/// Clamp an estimate to what is safe to reserve.
+pub fn reserve_limit(events: usize) -> usize {
+    events.min(MAX_RESERVED_EVENTS)
+}
+
+/// c * n
+pub fn linear(n: usize, c: f64) -> usize {
+    scaled(n as f64, c)
+}
+
+/// c * n log2 n
+pub fn n_log_n(n: usize, c: f64) -> usize {
+    scaled(n as f64 * log2(n), c)
+}
+
+/// c * n (log2 n)^2, the size of the classic sorting networks.
+pub fn n_log2_n(n: usize, c: f64) -> usize {
+    let log = log2(n);
+    scaled(n as f64 * log * log, c)
+}
+
+/// c * n^1.5
+pub fn n_sqrt_n(n: usize, c: f64) -> usize {
+    scaled(n as f64 * n.isqrt() as f64, c)
+}
+
+/// c * n^2
+pub fn quadratic(n: usize, c: f64) -> usize {
+    let n = n as f64;
+    scaled(n * n, c)
+}
+
+/// log2 n, interpolated linearly between powers of two. Float log2 needs
+/// std, and estimates don't need better.
+fn log2(n: usize) -> f64 {
+    let n = n.max(2);
+    let k = n.ilog2();
+    k as f64 + (n - (1 << k)) as f64 / (1usize << k) as f64
+}
+
+/// Every run emits at least a Done event.
+fn scaled(size: f64, c: f64) -> usize {
+    (size * c) as usize + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patterns::{self, Pattern};
+    use crate::pregen::{estimated_events, pregen_sort, Algorithm};
+
+    #[test]
+    fn test_growth_curves() {
+        assert_eq!(linear(100, 2.0), 201);
+        assert_eq!(n_log_n(1024, 1.0), 10241);
+        assert_eq!(n_log2_n(1024, 1.0), 102401);
+        assert_eq!(n_sqrt_n(100, 1.0), 1001);
+        assert_eq!(quadratic(100, 0.5), 5001);
+    }
+
+    #[test]
+    fn test_small_inputs() {
+        for n in [0, 1] {
+            assert_eq!(n_log_n(n, 2.0), 2 * n + 1);
+            assert!(quadratic(n, 0.5) >= 1);
+        }
+    }
+
+    #[test]
+    fn test_reserve_limit() {
+        assert_eq!(reserve_limit(10), 10);
+        assert_eq!(reserve_limit(quadratic(1_000_000, 0.75)), MAX_RESERVED_EVENTS);
+    }
+
+    #[test]
+    fn test_estimates_track_actual_counts() {
+        for &algorithm in Algorithm::all() {
+            for n in [256, 1024] {
+                let mut array = patterns::generate(Pattern::Uniform, n, 1);
+                let actual = pregen_sort(algorithm, &mut array).len();
+                let estimated = estimated_events(algorithm, n);
+
+                assert!(
+                    estimated >= actual / 2 && estimated <= actual * 2,
+                    "{:?} n={}: estimated {} actual {}",
+                    algorithm,
+                    n,
+                    estimated,
+                    actual
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,112 @@
+//! Exchange Sort implementation for V1 (Pregeneration) engine.
+//!
+//! Compares the element at position i with every later element j and swaps
+//! whenever they are out of order, so position i holds the minimum of the
+//! remaining elements after each outer pass. Unlike bubble sort, the
+//! compared pairs are not adjacent.
+
+use crate::events::{EventSink, SortEvent};
+use super::{estimate, AlgorithmInfo, PregenSort};
+
+pub struct ExchangeSort;
+
+impl PregenSort for ExchangeSort {
+    const INFO: AlgorithmInfo = AlgorithmInfo {
+        display_name: "Exchange Sort",
+        best: "O(n²)",
+        average: "O(n²)",
+        worst: "O(n²)",
+        space: "O(1)",
+        stable: false,
+        in_place: true,
+        adaptive: false,
+        description: "Compares each position with every later one and swaps when out of order.",
+    };
+
+    fn estimated_events(n: usize) -> usize {
+        estimate::quadratic(n, 0.75)
+    }
+
+    fn sort_into(array: &mut [i32], events: &mut impl EventSink) {
+        let n = array.len();
+
+        if n <= 1 {
+            events.push(SortEvent::Done);
+            return;
+        }
+
+        for i in 0..n - 1 {
+            for j in (i + 1)..n {
+                events.push(SortEvent::Compare { i, j });
+
+                if array[i] > array[j] {
+                    events.push(SortEvent::Swap { i, j });
+                    array.swap(i, j);
+                }
+            }
+        }
+
+        events.push(SortEvent::Done);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exchange_sort_basic() {
+        let mut array = vec![5, 3, 8, 4, 2];
+        let events = ExchangeSort::sort(&mut array);
+
+        assert_eq!(array, vec![2, 3, 4, 5, 8]);
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_exchange_sort_already_sorted() {
+        let mut array = vec![1, 2, 3, 4, 5];
+        let events = ExchangeSort::sort(&mut array);
+
+        assert_eq!(array, vec![1, 2, 3, 4, 5]);
+        let swap_count = events.iter().filter(|e| matches!(e, SortEvent::Swap { .. })).count();
+        assert_eq!(swap_count, 0);
+    }
+
+    #[test]
+    fn test_exchange_sort_reverse() {
+        let mut array = vec![5, 4, 3, 2, 1];
+        ExchangeSort::sort(&mut array);
+
+        assert_eq!(array, vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_exchange_sort_empty() {
+        let mut array: Vec<i32> = vec![];
+        let events = ExchangeSort::sort(&mut array);
+
+        assert!(array.is_empty());
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_exchange_sort_single() {
+        let mut array = vec![42];
+        let events = ExchangeSort::sort(&mut array);
+
+        assert_eq!(array, vec![42]);
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_exchange_sort_compares_all_pairs() {
+        let mut array = vec![3, 1, 2, 5, 4];
+        let events = ExchangeSort::sort(&mut array);
+
+        assert_eq!(array, vec![1, 2, 3, 4, 5]);
+        // Always n(n-1)/2 comparisons, regardless of input order
+        let compare_count = events.iter().filter(|e| matches!(e, SortEvent::Compare { .. })).count();
+        assert_eq!(compare_count, 10);
+    }
+}
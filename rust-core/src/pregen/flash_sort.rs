@@ -0,0 +1,261 @@
+//! Flashsort implementation for V1 (Pregeneration) engine.
+//!
+//! Distribution sort in three phases:
+//! 1. Classification: values are mapped linearly onto m ≈ 0.43n classes
+//!    and class sizes are counted.
+//! 2. Permutation: elements are moved into their class regions by following
+//!    cycles, carrying one element "in hand" (emitted as Overwrite events).
+//! 3. Insertion: a final insertion sort fixes order within each class.
+//!
+//! Runs in close to linear time on uniformly distributed data.
+
+use crate::events::{EventSink, SortEvent};
+use super::context::{zeroed, Scratch};
+use super::{estimate, AlgorithmInfo, PregenSort};
+
+pub struct FlashSort;
+
+impl PregenSort for FlashSort {
+    const INFO: AlgorithmInfo = AlgorithmInfo {
+        display_name: "Flashsort",
+        best: "O(n)",
+        average: "O(n)",
+        worst: "O(n²)",
+        space: "O(m)",
+        stable: false,
+        in_place: true,
+        adaptive: false,
+        description:
+            "Distributes elements into m value classes in place, then insertion-sorts them.",
+    };
+
+    fn estimated_events(n: usize) -> usize {
+        estimate::linear(n, 5.0)
+    }
+
+    fn sort_into(array: &mut [i32], events: &mut impl EventSink) {
+        Self::sort_into_with(array, events, &mut Scratch::new());
+    }
+
+    fn sort_into_with(array: &mut [i32], events: &mut impl EventSink, scratch: &mut Scratch) {
+        let n = array.len();
+
+        if n <= 1 {
+            events.push(SortEvent::Done);
+            return;
+        }
+
+        // Find min and max
+        let mut min_idx = 0;
+        let mut max_idx = 0;
+        for i in 1..n {
+            events.push(SortEvent::Compare { i, j: min_idx });
+            if array[i] < array[min_idx] {
+                min_idx = i;
+            }
+            events.push(SortEvent::Compare { i, j: max_idx });
+            if array[i] > array[max_idx] {
+                max_idx = i;
+            }
+        }
+
+        let min_val = array[min_idx];
+        if min_val == array[max_idx] {
+            // All elements equal
+            events.push(SortEvent::Done);
+            return;
+        }
+
+        // Phase 1: classification
+        let m = ((0.43 * n as f64) as usize).max(2);
+        let scale = (m - 1) as f64 / (array[max_idx] as i64 - min_val as i64) as f64;
+        let class_of = |value: i32| ((value as i64 - min_val as i64) as f64 * scale) as usize;
+
+        // class_end[k] is one past the last slot of class k
+        let class_end = zeroed(&mut scratch.indices, m);
+        for &value in array.iter() {
+            class_end[class_of(value)] += 1;
+        }
+        for k in 1..m {
+            class_end[k] += class_end[k - 1];
+        }
+
+        // Phase 2: permutation
+        if max_idx != 0 {
+            events.push(SortEvent::Swap { i: 0, j: max_idx });
+            array.swap(0, max_idx);
+        }
+
+        events.push(SortEvent::EnterRange { lo: 0, hi: n - 1 });
+
+        let mut moved = 0;
+        let mut j = 0;
+        let mut k = m - 1;
+        while moved < n - 1 {
+            // Skip elements already placed in their class region
+            while j + 1 > class_end[k] {
+                j += 1;
+                k = class_of(array[j]);
+            }
+
+            // Start a cycle with array[j] in hand
+            let mut flash = array[j];
+            while j != class_end[k] {
+                k = class_of(flash);
+                let dest = class_end[k] - 1;
+                let hold = array[dest];
+                if hold != flash {
+                    events.push(SortEvent::Overwrite {
+                        idx: dest,
+                        old_val: hold,
+                        new_val: flash,
+                    });
+                }
+                array[dest] = flash;
+                flash = hold;
+                class_end[k] -= 1;
+                moved += 1;
+            }
+        }
+
+        events.push(SortEvent::ExitRange { lo: 0, hi: n - 1 });
+
+        // Phase 3: insertion sort within (and across) classes
+        for i in 1..n {
+            let value = array[i];
+            let mut j = i;
+
+            while j > 0 {
+                events.push(SortEvent::Compare { i: j - 1, j });
+
+                if array[j - 1] > value {
+                    events.push(SortEvent::Overwrite {
+                        idx: j,
+                        old_val: array[j],
+                        new_val: array[j - 1],
+                    });
+                    array[j] = array[j - 1];
+                    j -= 1;
+                } else {
+                    break;
+                }
+            }
+
+            if j != i {
+                events.push(SortEvent::Overwrite {
+                    idx: j,
+                    old_val: array[j],
+                    new_val: value,
+                });
+                array[j] = value;
+            }
+        }
+
+        events.push(SortEvent::Done);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flash_sort_basic() {
+        let mut array = vec![5, 3, 8, 4, 2];
+        let events = FlashSort::sort(&mut array);
+
+        assert_eq!(array, vec![2, 3, 4, 5, 8]);
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_flash_sort_already_sorted() {
+        let mut array = vec![1, 2, 3, 4, 5];
+        FlashSort::sort(&mut array);
+
+        assert_eq!(array, vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_flash_sort_reverse() {
+        let mut array: Vec<i32> = (0..100).rev().collect();
+        FlashSort::sort(&mut array);
+
+        let expected: Vec<i32> = (0..100).collect();
+        assert_eq!(array, expected);
+    }
+
+    #[test]
+    fn test_flash_sort_empty() {
+        let mut array: Vec<i32> = vec![];
+        let events = FlashSort::sort(&mut array);
+
+        assert!(array.is_empty());
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_flash_sort_single() {
+        let mut array = vec![42];
+        let events = FlashSort::sort(&mut array);
+
+        assert_eq!(array, vec![42]);
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_flash_sort_all_equal() {
+        let mut array = vec![7, 7, 7, 7];
+        let events = FlashSort::sort(&mut array);
+
+        assert_eq!(array, vec![7, 7, 7, 7]);
+        assert!(!events.iter().any(|e| e.is_mutation()));
+    }
+
+    #[test]
+    fn test_flash_sort_duplicates_and_negatives() {
+        let mut array = vec![3, -1, 4, 1, -5, 9, 2, 6, 5, 3, 5, i32::MIN, i32::MAX];
+        FlashSort::sort(&mut array);
+
+        assert_eq!(array, vec![i32::MIN, -5, -1, 1, 2, 3, 3, 4, 5, 5, 6, 9, i32::MAX]);
+    }
+
+    #[test]
+    fn test_flash_sort_pseudo_random() {
+        let mut seed: u32 = 12345;
+        for n in [2, 3, 10, 57, 300] {
+            let mut array: Vec<i32> = (0..n)
+                .map(|_| {
+                    seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+                    (seed >> 8) as i32 % 1000 - 500
+                })
+                .collect();
+            let mut expected = array.clone();
+            expected.sort();
+
+            FlashSort::sort(&mut array);
+            assert_eq!(array, expected);
+        }
+    }
+
+    #[test]
+    fn test_flash_sort_events_replay() {
+        let original = vec![9, 1, 8, 2, 7, 3, 6, 4, 5, 0];
+        let mut array = original.clone();
+        let events = FlashSort::sort(&mut array);
+
+        // Overwrites must carry correct old values for replay
+        let mut replay = original;
+        for event in &events {
+            match *event {
+                SortEvent::Swap { i, j } => replay.swap(i, j),
+                SortEvent::Overwrite { idx, old_val, new_val } => {
+                    assert_eq!(replay[idx], old_val);
+                    replay[idx] = new_val;
+                }
+                _ => {}
+            }
+        }
+        assert_eq!(replay, array);
+    }
+}
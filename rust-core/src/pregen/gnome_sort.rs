@@ -1,26 +1,44 @@
 //! Gnome Sort implementation for V1 (Pregeneration) engine.
 //!
 //! Similar to insertion sort but moves elements by swapping adjacent pairs.
-//! Named after garden gnomes sorting flower pots.
+//! Named after garden gnomes sorting flower pots. The gnome's position is
+//! the "pos" cursor.
 
-use crate::events::SortEvent;
-use super::PregenSort;
+use crate::events::{Cursor, EventSink, SortEvent};
+use super::{estimate, AlgorithmInfo, PregenSort};
 
 pub struct GnomeSort;
 
 impl PregenSort for GnomeSort {
-    fn sort(array: &mut [i32]) -> Vec<SortEvent> {
-        let mut events = Vec::new();
+    const INFO: AlgorithmInfo = AlgorithmInfo {
+        display_name: "Gnome Sort",
+        best: "O(n)",
+        average: "O(n²)",
+        worst: "O(n²)",
+        space: "O(1)",
+        stable: true,
+        in_place: true,
+        adaptive: true,
+        description: "Swaps an element backwards until it is in order, then walks forward again.",
+    };
+
+    fn estimated_events(n: usize) -> usize {
+        estimate::quadratic(n, 1.0)
+    }
+
+    fn sort_into(array: &mut [i32], events: &mut impl EventSink) {
         let n = array.len();
 
         if n <= 1 {
             events.push(SortEvent::Done);
-            return events;
+            return;
         }
 
         let mut i = 0;
+        let mut pos = Cursor::new("pos");
 
         while i < n {
+            pos.set(Some(i), events);
             if i == 0 {
                 i += 1;
             } else {
@@ -38,8 +56,8 @@ impl PregenSort for GnomeSort {
             }
         }
 
+        pos.set(None, events);
         events.push(SortEvent::Done);
-        events
     }
 }
 
@@ -91,4 +109,20 @@ mod tests {
         assert_eq!(array, vec![42]);
         assert!(matches!(events.last(), Some(SortEvent::Done)));
     }
+
+    #[test]
+    fn test_gnome_sort_pos_cursor_follows_gnome() {
+        let mut array = vec![2, 1];
+        let events = GnomeSort::sort(&mut array);
+
+        let positions: Vec<Option<usize>> = events
+            .iter()
+            .filter_map(|e| match e {
+                SortEvent::SetCursor(c) if c.id == "pos" => Some(c.index),
+                _ => None,
+            })
+            .collect();
+        // Step forward, swap and step back, step forward again, then finish
+        assert_eq!(positions, vec![Some(0), Some(1), Some(0), Some(1), None]);
+    }
 }
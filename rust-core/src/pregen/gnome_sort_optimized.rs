@@ -0,0 +1,130 @@
+//! Optimized Gnome Sort implementation for V1 (Pregeneration) engine.
+//!
+//! Plain gnome sort walks back to where an element belongs, then walks
+//! forward again one comparison at a time over elements it already knows are
+//! in order. This variant remembers the position it started walking back
+//! from and jumps straight back to it once the element is placed, which
+//! makes it equivalent to insertion sort done with adjacent swaps.
+
+use crate::events::{EventSink, SortEvent};
+use super::{estimate, AlgorithmInfo, PregenSort};
+
+pub struct GnomeSortOptimized;
+
+impl PregenSort for GnomeSortOptimized {
+    const INFO: AlgorithmInfo = AlgorithmInfo {
+        display_name: "Optimized Gnome Sort",
+        best: "O(n)",
+        average: "O(n²)",
+        worst: "O(n²)",
+        space: "O(1)",
+        stable: true,
+        in_place: true,
+        adaptive: true,
+        description:
+            "Gnome sort that jumps back to where it started walking, like insertion sort with swaps.",
+    };
+
+    fn estimated_events(n: usize) -> usize {
+        estimate::quadratic(n, 0.5)
+    }
+
+    fn sort_into(array: &mut [i32], events: &mut impl EventSink) {
+        let n = array.len();
+
+        if n <= 1 {
+            events.push(SortEvent::Done);
+            return;
+        }
+
+        for upper in 1..n {
+            // Walk the new element back, remembering where to resume
+            let mut i = upper;
+
+            while i > 0 {
+                events.push(SortEvent::Compare { i: i - 1, j: i });
+
+                if array[i - 1] <= array[i] {
+                    break;
+                }
+
+                events.push(SortEvent::Swap { i: i - 1, j: i });
+                array.swap(i - 1, i);
+                i -= 1;
+            }
+        }
+
+        events.push(SortEvent::Done);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pregen::gnome_sort::GnomeSort;
+
+    fn compare_count(events: &[SortEvent]) -> usize {
+        events.iter().filter(|e| matches!(e, SortEvent::Compare { .. })).count()
+    }
+
+    #[test]
+    fn test_gnome_optimized_basic() {
+        let mut array = vec![5, 3, 8, 4, 2];
+        let events = GnomeSortOptimized::sort(&mut array);
+
+        assert_eq!(array, vec![2, 3, 4, 5, 8]);
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_gnome_optimized_already_sorted() {
+        let mut array = vec![1, 2, 3, 4, 5];
+        let events = GnomeSortOptimized::sort(&mut array);
+
+        assert_eq!(array, vec![1, 2, 3, 4, 5]);
+        assert!(!events.iter().any(|e| e.is_mutation()));
+    }
+
+    #[test]
+    fn test_gnome_optimized_reverse() {
+        let mut array = vec![5, 4, 3, 2, 1];
+        GnomeSortOptimized::sort(&mut array);
+
+        assert_eq!(array, vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_gnome_optimized_empty() {
+        let mut array: Vec<i32> = vec![];
+        let events = GnomeSortOptimized::sort(&mut array);
+
+        assert!(array.is_empty());
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_gnome_optimized_single() {
+        let mut array = vec![42];
+        let events = GnomeSortOptimized::sort(&mut array);
+
+        assert_eq!(array, vec![42]);
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_gnome_optimized_same_swaps_fewer_compares() {
+        let input: Vec<i32> = (0..60).map(|x| (x * 23) % 61).collect();
+
+        let mut plain = input.clone();
+        let plain_events = GnomeSort::sort(&mut plain);
+
+        let mut optimized = input;
+        let optimized_events = GnomeSortOptimized::sort(&mut optimized);
+
+        let swaps = |events: &[SortEvent]| events.iter().filter(|e| e.is_mutation()).count();
+
+        assert_eq!(plain, optimized);
+        assert_eq!(swaps(&plain_events), swaps(&optimized_events));
+        assert!(compare_count(&optimized_events) < compare_count(&plain_events));
+    }
+}
@@ -0,0 +1,149 @@
+//! Gravity (Bead) Sort implementation for V1 (Pregeneration) engine.
+//!
+//! Each value is a row of beads on vertical rods (value v puts beads on the
+//! first v rods). When the beads drop, every rod's beads pile up at the
+//! bottom, and each row ends up holding as many beads as there are rods
+//! reaching it. The simulation settles rows from the floor up, emitting one
+//! Overwrite per row whose bead count changes.
+//!
+//! Only meaningful for small non-negative integers. Inputs with negative
+//! values or values above `MAX_VALUE` are left untouched.
+
+use crate::prelude::*;
+use crate::events::{EventSink, SortEvent};
+use super::{estimate, AlgorithmInfo, PregenSort};
+
+pub struct GravitySort;
+
+/// Largest value (number of rods) accepted by the simulation.
+pub const MAX_VALUE: i32 = 4096;
+
+impl PregenSort for GravitySort {
+    const INFO: AlgorithmInfo = AlgorithmInfo {
+        display_name: "Gravity Sort",
+        best: "O(S)",
+        average: "O(S)",
+        worst: "O(S)",
+        space: "O(max)",
+        stable: false,
+        in_place: false,
+        adaptive: false,
+        description: "Bead sort: beads on rods fall under gravity; S is the sum of the values.",
+    };
+
+    fn estimated_events(n: usize) -> usize {
+        estimate::linear(n, 1.0)
+    }
+
+    fn sort_into(array: &mut [i32], events: &mut impl EventSink) {
+        let n = array.len();
+
+        if n <= 1 {
+            events.push(SortEvent::Done);
+            return;
+        }
+
+        // Input guard: beads need a small non-negative count per row
+        if array.iter().any(|&v| !(0..=MAX_VALUE).contains(&v)) {
+            events.push(SortEvent::Done);
+            return;
+        }
+
+        let max_val = *array.iter().max().unwrap() as usize;
+
+        // Number of beads threaded on each rod
+        let mut rod_heights = vec![0usize; max_val];
+        for &value in array.iter() {
+            for height in rod_heights.iter_mut().take(value as usize) {
+                *height += 1;
+            }
+        }
+
+        // Settle rows from the bottom: row `level` above the floor keeps one
+        // bead for every rod whose pile is taller than `level`
+        for level in 0..n {
+            let idx = n - 1 - level;
+            let beads = rod_heights.iter().filter(|&&h| h > level).count() as i32;
+            if array[idx] != beads {
+                events.push(SortEvent::Overwrite {
+                    idx,
+                    old_val: array[idx],
+                    new_val: beads,
+                });
+                array[idx] = beads;
+            }
+        }
+
+        events.push(SortEvent::Done);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gravity_sort_basic() {
+        let mut array = vec![5, 3, 8, 4, 2];
+        let events = GravitySort::sort(&mut array);
+
+        assert_eq!(array, vec![2, 3, 4, 5, 8]);
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_gravity_sort_already_sorted() {
+        let mut array = vec![1, 2, 3, 4, 5];
+        let events = GravitySort::sort(&mut array);
+
+        assert_eq!(array, vec![1, 2, 3, 4, 5]);
+        assert!(!events.iter().any(|e| e.is_mutation()));
+    }
+
+    #[test]
+    fn test_gravity_sort_reverse_with_zeros() {
+        let mut array = vec![5, 4, 3, 0, 2, 1, 0];
+        GravitySort::sort(&mut array);
+
+        assert_eq!(array, vec![0, 0, 1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_gravity_sort_duplicates() {
+        let mut array = vec![3, 1, 3, 2, 1];
+        GravitySort::sort(&mut array);
+
+        assert_eq!(array, vec![1, 1, 2, 3, 3]);
+    }
+
+    #[test]
+    fn test_gravity_sort_empty() {
+        let mut array: Vec<i32> = vec![];
+        let events = GravitySort::sort(&mut array);
+
+        assert!(array.is_empty());
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_gravity_sort_single() {
+        let mut array = vec![42];
+        let events = GravitySort::sort(&mut array);
+
+        assert_eq!(array, vec![42]);
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_gravity_sort_rejects_out_of_range() {
+        let mut array = vec![3, -1, 2];
+        let events = GravitySort::sort(&mut array);
+        assert_eq!(array, vec![3, -1, 2]);
+        assert_eq!(events, vec![SortEvent::Done]);
+
+        let mut array = vec![MAX_VALUE + 1, 0];
+        let events = GravitySort::sort(&mut array);
+        assert_eq!(array, vec![MAX_VALUE + 1, 0]);
+        assert_eq!(events, vec![SortEvent::Done]);
+    }
+}
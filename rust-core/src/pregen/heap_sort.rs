@@ -3,24 +3,39 @@
 //! Builds a max-heap and repeatedly extracts the maximum element.
 //! In-place with O(n log n) time complexity.
 
-use crate::events::SortEvent;
-use super::PregenSort;
+use crate::events::{EventSink, SortEvent};
+use super::{estimate, AlgorithmInfo, PregenSort};
 
 pub struct HeapSort;
 
 impl PregenSort for HeapSort {
-    fn sort(array: &mut [i32]) -> Vec<SortEvent> {
-        let mut events = Vec::new();
+    const INFO: AlgorithmInfo = AlgorithmInfo {
+        display_name: "Heap Sort",
+        best: "O(n log n)",
+        average: "O(n log n)",
+        worst: "O(n log n)",
+        space: "O(1)",
+        stable: false,
+        in_place: true,
+        adaptive: false,
+        description: "Builds a max-heap and repeatedly moves the maximum to the end.",
+    };
+
+    fn estimated_events(n: usize) -> usize {
+        estimate::n_log_n(n, 2.7)
+    }
+
+    fn sort_into(array: &mut [i32], events: &mut impl EventSink) {
         let n = array.len();
 
         if n <= 1 {
             events.push(SortEvent::Done);
-            return events;
+            return;
         }
 
         // Build max heap (heapify)
         for i in (0..n / 2).rev() {
-            sift_down(array, i, n, &mut events);
+            sift_down(array, i, n, events);
         }
 
         // Extract elements from heap one by one
@@ -30,17 +45,16 @@ impl PregenSort for HeapSort {
             array.swap(0, end);
 
             // Restore heap property for reduced heap
-            sift_down(array, 0, end, &mut events);
+            sift_down(array, 0, end, events);
         }
 
         events.push(SortEvent::Done);
-        events
     }
 }
 
 /// Sift down element at index `root` to maintain heap property.
 /// Only considers elements in range [0, end).
-fn sift_down(array: &mut [i32], root: usize, end: usize, events: &mut Vec<SortEvent>) {
+fn sift_down(array: &mut [i32], root: usize, end: usize, events: &mut impl EventSink) {
     let mut current = root;
 
     loop {
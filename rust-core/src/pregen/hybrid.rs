@@ -0,0 +1,363 @@
+//! Declarative hybrid sorts.
+//!
+//! A `HybridConfig` describes a hybrid such as "quicksort until depth 8 or
+//! size 32, then shell sort with Ciura gaps": a split stage that divides
+//! the array (quicksort partitioning or merge sort halving), the limits at
+//! which it stops, and the pregen algorithm that finishes each range it
+//! stops at. `sort_hybrid_into` drives the split stage with the existing
+//! partition and merge code and runs the finisher on each leaf range, with
+//! the finisher's trace shifted to array indices as in `subrange`.
+//!
+//! A Phase event marks every switch between stages, named after the stage
+//! that starts ("quicksort_lr", "shell", ...). Leaf ranges are wrapped in
+//! EnterRange/ExitRange like the split stage's own ranges.
+
+use serde::{Deserialize, Serialize};
+use crate::prelude::*;
+use crate::events::{expand_flips, EventSink, SortEvent};
+use crate::subrange::shift;
+use super::shell_sort::{gapped_insertion, Gaps};
+use super::{merge_sort, pregen_sort_into, quicksort_ll, quicksort_lr, Algorithm};
+
+/// How the split stage divides a range.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Split {
+    /// Hoare partitioning around the leftmost element, as in QuickSortLR.
+    QuickSortLR,
+    /// Lomuto partitioning around the rightmost element, as in QuickSortLL.
+    #[default]
+    QuickSortLL,
+    /// Top-down halving, merging the sorted halves, as in MergeSort.
+    Merge,
+}
+
+impl Split {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Split::QuickSortLR => "quicksort_lr",
+            Split::QuickSortLL => "quicksort_ll",
+            Split::Merge => "merge",
+        }
+    }
+
+    /// Parse split name from string.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Split> {
+        match s.to_lowercase().as_str() {
+            "quicksort_lr" | "quicksortlr" => Some(Split::QuickSortLR),
+            "quicksort_ll" | "quicksortll" => Some(Split::QuickSortLL),
+            "merge" | "mergesort" | "merge_sort" => Some(Split::Merge),
+            _ => None,
+        }
+    }
+}
+
+/// A hybrid sort: split until a limit, then finish each range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HybridConfig {
+    pub split: Split,
+    /// Ranges at this recursion depth are finished; the whole array is at
+    /// depth 0. None splits until the size cutoff.
+    pub max_depth: Option<usize>,
+    /// Ranges of at most this many elements are finished.
+    pub cutoff: usize,
+    pub finish: Algorithm,
+    /// Gap sequence when `finish` is Shell.
+    pub gaps: Gaps,
+}
+
+impl Default for HybridConfig {
+    fn default() -> Self {
+        Self {
+            split: Split::default(),
+            max_depth: None,
+            cutoff: 16,
+            finish: Algorithm::Insertion,
+            gaps: Gaps::default(),
+        }
+    }
+}
+
+/// A HybridConfig by name, as it arrives from JavaScript. Missing fields
+/// take the HybridConfig defaults.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HybridSpec {
+    pub split: String,
+    pub max_depth: Option<usize>,
+    pub cutoff: usize,
+    pub finish: String,
+    pub gaps: Option<String>,
+}
+
+impl Default for HybridSpec {
+    fn default() -> Self {
+        let config = HybridConfig::default();
+        Self {
+            split: config.split.as_str().to_string(),
+            max_depth: config.max_depth,
+            cutoff: config.cutoff,
+            finish: config.finish.as_str().to_string(),
+            gaps: None,
+        }
+    }
+}
+
+impl HybridConfig {
+    /// Resolve the names of a spec. Gaps are only accepted for a shell
+    /// sort finisher.
+    pub fn from_spec(spec: &HybridSpec) -> Result<Self, String> {
+        let split = Split::from_str(&spec.split)
+            .ok_or_else(|| format!("Unknown split: {}", spec.split))?;
+        let finish = Algorithm::from_str(&spec.finish)
+            .ok_or_else(|| format!("Unknown algorithm: {}", spec.finish))?;
+        let gaps = match spec.gaps.as_deref() {
+            Some(_) if finish != Algorithm::Shell => {
+                return Err(format!("Gaps only apply to shell, not {}", finish.as_str()));
+            }
+            Some(name) => Gaps::from_str(name).ok_or_else(|| format!("Unknown gaps: {}", name))?,
+            None => Gaps::default(),
+        };
+
+        Ok(Self { split, max_depth: spec.max_depth, cutoff: spec.cutoff, finish, gaps })
+    }
+
+    /// One-line description, e.g. "quicksort_lr until depth 8 or size 32,
+    /// then shell with ciura gaps".
+    pub fn describe(&self) -> String {
+        let until = match self.max_depth {
+            Some(depth) => format!("until depth {} or size {}", depth, self.cutoff),
+            None => format!("until size {}", self.cutoff),
+        };
+        let gaps = match self.finish {
+            Algorithm::Shell => format!(" with {} gaps", self.gaps.as_str()),
+            _ => String::new(),
+        };
+        format!("{} {}, then {}{}", self.split.as_str(), until, self.finish.as_str(), gaps)
+    }
+}
+
+/// Run a hybrid sort and return all events.
+pub fn pregen_sort_hybrid(config: &HybridConfig, array: &mut [i32]) -> Vec<SortEvent> {
+    let mut events = Vec::new();
+    sort_hybrid_into(config, array, &mut events);
+    events
+}
+
+/// Run a hybrid sort, appending its events to `events`.
+pub fn sort_hybrid_into(config: &HybridConfig, array: &mut [i32], events: &mut impl EventSink) {
+    let n = array.len();
+    if n > 1 {
+        let aux = match config.split {
+            Split::Merge => vec![0; n],
+            _ => Vec::new(),
+        };
+        let mut hybrid = Hybrid { config, array, aux, events, phase: None };
+        hybrid.sort(0, n - 1, 0);
+    }
+    events.push(SortEvent::Done);
+}
+
+struct Hybrid<'a, S: EventSink> {
+    config: &'a HybridConfig,
+    array: &'a mut [i32],
+    aux: Vec<i32>,
+    events: &'a mut S,
+    /// Stage of the last Phase event pushed.
+    phase: Option<&'static str>,
+}
+
+impl<S: EventSink> Hybrid<'_, S> {
+    fn enter_phase(&mut self, name: &'static str) {
+        if self.phase != Some(name) {
+            self.events.push(SortEvent::Phase { name: name.to_string() });
+            self.phase = Some(name);
+        }
+    }
+
+    /// Sort array[lo..=hi], a range at the given depth.
+    fn sort(&mut self, lo: usize, hi: usize, depth: usize) {
+        if lo >= hi {
+            return;
+        }
+        let at_limit = self.config.max_depth.is_some_and(|max| depth >= max);
+        if at_limit || hi - lo < self.config.cutoff {
+            self.finish(lo, hi);
+            return;
+        }
+
+        self.enter_phase(self.config.split.as_str());
+        self.events.push(SortEvent::EnterRange { lo, hi });
+        match self.config.split {
+            Split::QuickSortLR => {
+                let p = quicksort_lr::partition(self.array, lo, hi, self.events);
+                self.events.push(SortEvent::ExitRange { lo, hi });
+                self.sort(lo, p, depth + 1);
+                self.sort(p + 1, hi, depth + 1);
+            }
+            Split::QuickSortLL => {
+                let p = quicksort_ll::partition(self.array, lo, hi, self.events);
+                self.events.push(SortEvent::ExitRange { lo, hi });
+                if p > lo {
+                    self.sort(lo, p - 1, depth + 1);
+                }
+                self.sort(p + 1, hi, depth + 1);
+            }
+            Split::Merge => {
+                let mid = lo + (hi - lo) / 2;
+                self.sort(lo, mid, depth + 1);
+                self.sort(mid + 1, hi, depth + 1);
+                self.enter_phase(self.config.split.as_str());
+                merge_sort::merge(self.array, &mut self.aux, lo, mid, hi, self.events);
+                self.events.push(SortEvent::ExitRange { lo, hi });
+            }
+        }
+    }
+
+    /// Run the finisher on array[lo..=hi].
+    fn finish(&mut self, lo: usize, hi: usize) {
+        self.enter_phase(self.config.finish.as_str());
+        self.events.push(SortEvent::EnterRange { lo, hi });
+
+        let mut leaf = Vec::new();
+        let range = &mut self.array[lo..=hi];
+        match self.config.finish {
+            Algorithm::Shell => {
+                let gaps = self.config.gaps.sequence(range.len());
+                gapped_insertion(range, &gaps, &mut leaf);
+            }
+            algorithm => pregen_sort_into(algorithm, range, &mut leaf),
+        }
+        if lo > 0 {
+            leaf = expand_flips(&leaf);
+        }
+        for event in &leaf {
+            match event {
+                SortEvent::Done => {}
+                // The finisher's own phases; the next switch must be marked
+                SortEvent::Phase { .. } => {
+                    self.events.push(event.clone());
+                    self.phase = None;
+                }
+                _ => self.events.push(shift(event, lo, self.array)),
+            }
+        }
+
+        self.events.push(SortEvent::ExitRange { lo, hi });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patterns::{self, Pattern};
+    use crate::pregen::pregen_sort;
+
+    fn phases(events: &[SortEvent]) -> Vec<&str> {
+        events
+            .iter()
+            .filter_map(|e| match e {
+                SortEvent::Phase { name } => Some(name.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn replays_to_sorted(input: &[i32], events: &[SortEvent]) -> bool {
+        let mut array = input.to_vec();
+        for event in events {
+            if !event.in_bounds(array.len()) {
+                return false;
+            }
+            event.apply(&mut array);
+        }
+        array.windows(2).all(|w| w[0] <= w[1])
+    }
+
+    #[test]
+    fn test_quicksort_then_ciura_shell() {
+        let config = HybridConfig {
+            split: Split::QuickSortLR,
+            max_depth: Some(8),
+            cutoff: 32,
+            finish: Algorithm::Shell,
+            gaps: Gaps::Ciura,
+        };
+        let input = patterns::generate(Pattern::Random, 500, 3);
+        let mut array = input.clone();
+        let events = pregen_sort_hybrid(&config, &mut array);
+
+        assert!(array.windows(2).all(|w| w[0] <= w[1]));
+        assert!(replays_to_sorted(&input, &events));
+        assert_eq!(phases(&events)[..2], ["quicksort_lr", "shell"]);
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_every_split_and_finisher_sorts() {
+        let input = patterns::generate(Pattern::Random, 120, 9);
+        for split in [Split::QuickSortLR, Split::QuickSortLL, Split::Merge] {
+            for &finish in Algorithm::all() {
+                let config = HybridConfig { split, cutoff: 10, finish, ..Default::default() };
+                let events = pregen_sort_hybrid(&config, &mut input.clone());
+
+                assert!(replays_to_sorted(&input, &events), "{}", config.describe());
+            }
+        }
+    }
+
+    #[test]
+    fn test_phase_marks_every_switch() {
+        let config = HybridConfig { split: Split::Merge, cutoff: 4, ..Default::default() };
+        let events = pregen_sort_hybrid(&config, &mut (0..8).rev().collect::<Vec<_>>());
+
+        // Two leaves of 4, each finished, then one merge
+        assert_eq!(phases(&events), vec!["merge", "insertion", "merge"]);
+    }
+
+    #[test]
+    fn test_without_limits_matches_plain_split() {
+        let config = HybridConfig { cutoff: 0, ..Default::default() };
+        let input = patterns::generate(Pattern::Random, 60, 1);
+        let events = pregen_sort_hybrid(&config, &mut input.clone());
+        let plain = pregen_sort(Algorithm::QuickSortLL, &mut input.clone());
+
+        assert_eq!(events[0], SortEvent::Phase { name: "quicksort_ll".to_string() });
+        assert_eq!(events[1..], plain[..]);
+    }
+
+    #[test]
+    fn test_depth_zero_finishes_whole_array() {
+        let config =
+            HybridConfig { max_depth: Some(0), finish: Algorithm::HeapSort, ..Default::default() };
+        let input = vec![5, 1, 4, 2, 3];
+        let events = pregen_sort_hybrid(&config, &mut input.clone());
+        let plain = pregen_sort(Algorithm::HeapSort, &mut input.clone());
+
+        assert_eq!(phases(&events), vec!["heap"]);
+        assert_eq!(events[1], SortEvent::EnterRange { lo: 0, hi: 4 });
+        assert_eq!(events[2..events.len() - 2], plain[..plain.len() - 1]);
+    }
+
+    #[test]
+    fn test_from_spec() {
+        let spec = HybridSpec {
+            split: "quicksort_lr".to_string(),
+            max_depth: Some(8),
+            cutoff: 32,
+            finish: "shell".to_string(),
+            gaps: Some("ciura".to_string()),
+        };
+        let config = HybridConfig::from_spec(&spec).unwrap();
+        assert_eq!(
+            config.describe(),
+            "quicksort_lr until depth 8 or size 32, then shell with ciura gaps"
+        );
+        assert_eq!(HybridConfig::from_spec(&HybridSpec::default()), Ok(HybridConfig::default()));
+
+        let bad_gaps = HybridSpec { gaps: Some("ciura".to_string()), ..HybridSpec::default() };
+        assert!(HybridConfig::from_spec(&bad_gaps).is_err());
+        let bad_split = HybridSpec { split: "bogus".to_string(), ..HybridSpec::default() };
+        assert!(HybridConfig::from_spec(&bad_split).is_err());
+    }
+}
@@ -0,0 +1,122 @@
+//! Descriptive metadata for pregen algorithms.
+//!
+//! Each algorithm declares its `PregenSort::INFO` next to its implementation,
+//! so the UI's tooltips and comparisons read from the same place as the code.
+
+use serde::Serialize;
+
+/// Complexity classes and properties of an algorithm.
+///
+/// Complexities are written as big-O strings ("O(n log n)"); letters other
+/// than n are defined in the description.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct AlgorithmInfo {
+    pub display_name: &'static str,
+    pub best: &'static str,
+    pub average: &'static str,
+    pub worst: &'static str,
+    /// Auxiliary space, not counting the input.
+    pub space: &'static str,
+    /// Equal elements keep their relative order.
+    pub stable: bool,
+    /// Needs at most O(log n) or a fixed-size table of extra space.
+    pub in_place: bool,
+    /// Runs faster on presorted input.
+    pub adaptive: bool,
+    pub description: &'static str,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pregen::{algorithm_info, Algorithm, Category};
+
+    #[test]
+    fn test_every_algorithm_has_info() {
+        for &algorithm in Algorithm::all() {
+            let info = algorithm_info(algorithm);
+            assert!(!info.display_name.is_empty(), "{:?}", algorithm);
+            assert!(!info.description.is_empty(), "{:?}", algorithm);
+            for complexity in [info.best, info.average, info.worst, info.space] {
+                assert!(complexity.starts_with("O("), "{:?}: {}", algorithm, complexity);
+            }
+        }
+    }
+
+    #[test]
+    fn test_display_names_unique() {
+        let mut names: Vec<&str> =
+            Algorithm::all().iter().map(|&a| algorithm_info(a).display_name).collect();
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), Algorithm::all().len());
+    }
+
+    #[test]
+    fn test_known_properties() {
+        let merge = algorithm_info(Algorithm::MergeSort);
+        assert!(merge.stable && !merge.in_place);
+        assert_eq!(merge.worst, "O(n log n)");
+
+        let heap = algorithm_info(Algorithm::HeapSort);
+        assert!(!heap.stable && heap.in_place && !heap.adaptive);
+
+        let insertion = algorithm_info(Algorithm::Insertion);
+        assert!(insertion.adaptive);
+        assert_eq!(insertion.best, "O(n)");
+    }
+
+    #[test]
+    fn test_categories_partition_algorithms() {
+        let mut total = 0;
+        for &category in Category::all() {
+            let algorithms = category.algorithms();
+            let compiled_out = [
+                (Category::Distribution, cfg!(not(feature = "distribution"))),
+                (Category::Network, cfg!(not(feature = "networks"))),
+                (Category::Esoteric, cfg!(not(feature = "esoteric"))),
+            ]
+            .contains(&(category, true));
+            assert_eq!(algorithms.is_empty(), compiled_out, "{:?}", category);
+            assert!(algorithms.iter().all(|a| a.category() == category));
+            total += algorithms.len();
+        }
+        assert_eq!(total, Algorithm::all().len());
+
+        assert_eq!(Algorithm::QuickSortLL.category(), Category::Exchange);
+        #[cfg(feature = "networks")]
+        assert_eq!(Algorithm::Bitonic.category(), Category::Network);
+    }
+
+    #[test]
+    fn test_category_from_str() {
+        for &category in Category::all() {
+            assert_eq!(Category::from_str(category.as_str()), Some(category));
+        }
+        assert_eq!(Category::from_str("Hybrid"), Some(Category::Hybrid));
+        assert_eq!(Category::from_str("bogus"), None);
+    }
+
+    #[test]
+    fn test_aliases_parse_to_their_algorithm() {
+        let mut seen = std::collections::HashSet::new();
+        for &algorithm in Algorithm::all() {
+            assert_eq!(algorithm.aliases()[0], algorithm.as_str());
+            for alias in algorithm.aliases() {
+                assert!(seen.insert(*alias), "{} is ambiguous", alias);
+                assert_eq!(Algorithm::from_str(alias), Some(algorithm));
+                assert_eq!(Algorithm::from_str(&alias.to_uppercase()), Some(algorithm));
+            }
+        }
+    }
+
+    #[test]
+    fn test_strict_parsing() {
+        for &algorithm in Algorithm::all() {
+            assert_eq!(Algorithm::from_str_strict(algorithm.as_str()), Some(algorithm));
+        }
+        assert_eq!(Algorithm::from_str_strict("heap"), Some(Algorithm::HeapSort));
+        assert_eq!(Algorithm::from_str_strict("heapsort"), None);
+        assert_eq!(Algorithm::from_str_strict("Heap"), None);
+        assert_eq!(Algorithm::from_str("heapsort"), Some(Algorithm::HeapSort));
+    }
+}
@@ -1,18 +1,34 @@
 //! Insertion Sort implementation for V1 (Pregeneration) engine.
 
-use super::PregenSort;
-use crate::events::SortEvent;
+use super::{estimate, AlgorithmInfo, PregenSort};
+use crate::events::{EventSink, SortEvent};
 
 pub struct InsertionSort;
 
 impl PregenSort for InsertionSort {
-    fn sort(array: &mut [i32]) -> Vec<SortEvent> {
-        let mut events = Vec::new();
+    const INFO: AlgorithmInfo = AlgorithmInfo {
+        display_name: "Insertion Sort",
+        best: "O(n)",
+        average: "O(n²)",
+        worst: "O(n²)",
+        space: "O(1)",
+        stable: true,
+        in_place: true,
+        adaptive: true,
+        description:
+            "Inserts each element into the sorted prefix by shifting larger elements right.",
+    };
+
+    fn estimated_events(n: usize) -> usize {
+        estimate::quadratic(n, 0.5)
+    }
+
+    fn sort_into(array: &mut [i32], events: &mut impl EventSink) {
         let n = array.len();
 
         if n <= 1 {
             events.push(SortEvent::Done);
-            return events;
+            return;
         }
 
         for i in 1..n {
@@ -49,7 +65,6 @@ impl PregenSort for InsertionSort {
         }
 
         events.push(SortEvent::Done);
-        events
     }
 }
 
@@ -4,8 +4,8 @@
 //! heapsort when the recursion depth exceeds a level based on log(n).
 //! Falls back to insertion sort for small subarrays. Used in C++ STL.
 
-use crate::events::SortEvent;
-use super::PregenSort;
+use crate::events::{EventSink, SortEvent};
+use super::{estimate, AlgorithmInfo, PregenSort};
 
 pub struct IntroSort;
 
@@ -13,31 +13,59 @@ pub struct IntroSort;
 const INSERTION_THRESHOLD: usize = 16;
 
 impl PregenSort for IntroSort {
-    fn sort(array: &mut [i32]) -> Vec<SortEvent> {
-        let mut events = Vec::new();
+    const INFO: AlgorithmInfo = AlgorithmInfo {
+        display_name: "Introsort",
+        best: "O(n log n)",
+        average: "O(n log n)",
+        worst: "O(n log n)",
+        space: "O(log n)",
+        stable: false,
+        in_place: true,
+        adaptive: false,
+        description: "Quicksort that falls back to heapsort when recursion gets too deep.",
+    };
+
+    fn estimated_events(n: usize) -> usize {
+        estimate::n_log_n(n, 1.7)
+    }
+
+    fn sort_into(array: &mut [i32], events: &mut impl EventSink) {
         let n = array.len();
 
         if n <= 1 {
             events.push(SortEvent::Done);
-            return events;
+            return;
         }
 
-        // Maximum depth before switching to heapsort: 2 * floor(log2(n))
-        let max_depth = 2 * (n as f64).log2().floor() as usize;
-
-        introsort_recursive(array, 0, n - 1, max_depth, &mut events);
+        introsort_range(array, 0, n - 1, events);
 
         events.push(SortEvent::Done);
-        events
     }
 }
 
+/// Introsort the range [lo..=hi]. Used by hybrid sorts for small buckets.
+pub(super) fn introsort_range(
+    array: &mut [i32],
+    lo: usize,
+    hi: usize,
+    events: &mut impl EventSink,
+) {
+    if hi <= lo {
+        return;
+    }
+
+    // Maximum depth before switching to heapsort: 2 * floor(log2(n))
+    let max_depth = 2 * (hi - lo + 1).ilog2() as usize;
+
+    introsort_recursive(array, lo, hi, max_depth, events);
+}
+
 fn introsort_recursive(
     array: &mut [i32],
     lo: usize,
     hi: usize,
     depth_limit: usize,
-    events: &mut Vec<SortEvent>,
+    events: &mut impl EventSink,
 ) {
     let size = hi - lo + 1;
 
@@ -70,7 +98,7 @@ fn introsort_recursive(
 }
 
 /// Partition using median-of-three pivot selection.
-fn partition(array: &mut [i32], lo: usize, hi: usize, events: &mut Vec<SortEvent>) -> usize {
+fn partition(array: &mut [i32], lo: usize, hi: usize, events: &mut impl EventSink) -> usize {
     // Median-of-three pivot selection
     let mid = lo + (hi - lo) / 2;
 
@@ -142,7 +170,7 @@ fn partition(array: &mut [i32], lo: usize, hi: usize, events: &mut Vec<SortEvent
 }
 
 /// Insertion sort for a range.
-fn insertion_sort_range(array: &mut [i32], lo: usize, hi: usize, events: &mut Vec<SortEvent>) {
+fn insertion_sort_range(array: &mut [i32], lo: usize, hi: usize, events: &mut impl EventSink) {
     for i in (lo + 1)..=hi {
         let value = array[i];
         let mut j = i;
@@ -175,7 +203,7 @@ fn insertion_sort_range(array: &mut [i32], lo: usize, hi: usize, events: &mut Ve
 }
 
 /// Heapsort for a range.
-fn heapsort_range(array: &mut [i32], lo: usize, hi: usize, events: &mut Vec<SortEvent>) {
+pub(super) fn heapsort_range(array: &mut [i32], lo: usize, hi: usize, events: &mut impl EventSink) {
     let n = hi - lo + 1;
 
     // Build max heap
@@ -192,7 +220,7 @@ fn heapsort_range(array: &mut [i32], lo: usize, hi: usize, events: &mut Vec<Sort
 }
 
 /// Sift down for heapsort within a range.
-fn sift_down(array: &mut [i32], base: usize, root: usize, end: usize, events: &mut Vec<SortEvent>) {
+fn sift_down(array: &mut [i32], base: usize, root: usize, end: usize, events: &mut impl EventSink) {
     let mut current = root;
 
     loop {
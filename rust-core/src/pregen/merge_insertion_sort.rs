@@ -0,0 +1,241 @@
+//! Merge-Insertion (Ford–Johnson) Sort implementation for V1 (Pregeneration) engine.
+//!
+//! Designed to minimize comparisons rather than moves:
+//! 1. Pair up elements and compare each pair.
+//! 2. Recursively sort the larger element of every pair.
+//! 3. Binary-insert the smaller elements into the sorted chain in
+//!    Jacobsthal order, so each insertion searches a chain of length
+//!    2^k - 1 and never wastes a comparison.
+//!
+//! The algorithm works on element positions, so every Compare event refers
+//! to the untouched input array. The sorted order is written back with
+//! Overwrite events at the end.
+
+use alloc::collections::BTreeMap;
+
+use crate::prelude::*;
+use crate::events::{EventSink, SortEvent};
+use super::{estimate, AlgorithmInfo, PregenSort};
+
+pub struct MergeInsertionSort;
+
+impl PregenSort for MergeInsertionSort {
+    const INFO: AlgorithmInfo = AlgorithmInfo {
+        display_name: "Merge-Insertion Sort",
+        best: "O(n²)",
+        average: "O(n²)",
+        worst: "O(n²)",
+        space: "O(n)",
+        stable: false,
+        in_place: false,
+        adaptive: false,
+        description:
+            "Ford–Johnson sort: near-minimal comparisons (about n log n), at the cost of quadratic moves.",
+    };
+
+    fn estimated_events(n: usize) -> usize {
+        estimate::n_log_n(n, 1.0)
+    }
+
+    fn sort_into(array: &mut [i32], events: &mut impl EventSink) {
+        let n = array.len();
+
+        if n <= 1 {
+            events.push(SortEvent::Done);
+            return;
+        }
+
+        let order = merge_insertion(array, (0..n).collect(), events);
+
+        // Write the sorted order back into the array
+        let sorted: Vec<i32> = order.iter().map(|&i| array[i]).collect();
+        for (idx, value) in sorted.into_iter().enumerate() {
+            if array[idx] != value {
+                events.push(SortEvent::Overwrite {
+                    idx,
+                    old_val: array[idx],
+                    new_val: value,
+                });
+                array[idx] = value;
+            }
+        }
+
+        events.push(SortEvent::Done);
+    }
+}
+
+/// Returns true if array[a] > array[b], emitting a Compare event.
+fn greater(array: &[i32], a: usize, b: usize, events: &mut impl EventSink) -> bool {
+    events.push(SortEvent::Compare { i: a, j: b });
+    array[a] > array[b]
+}
+
+/// Sort a list of positions by their values using Ford–Johnson.
+fn merge_insertion(
+    array: &[i32],
+    items: Vec<usize>,
+    events: &mut impl EventSink,
+) -> Vec<usize> {
+    if items.len() <= 1 {
+        return items;
+    }
+
+    // Step 1: compare pairs, remembering each larger element's smaller partner
+    let mut larger = Vec::with_capacity(items.len() / 2);
+    let mut partner = BTreeMap::new();
+    for pair in items.chunks_exact(2) {
+        let (hi, lo) = if greater(array, pair[0], pair[1], events) {
+            (pair[0], pair[1])
+        } else {
+            (pair[1], pair[0])
+        };
+        larger.push(hi);
+        partner.insert(hi, lo);
+    }
+    let straggler = if items.len() % 2 == 1 { items.last().copied() } else { None };
+
+    // Step 2: recursively sort the larger elements
+    let sorted_larger = merge_insertion(array, larger, events);
+
+    // Main chain starts with b1 (free, it's below a1) followed by all a's
+    let mut chain = Vec::with_capacity(items.len());
+    chain.push(partner[&sorted_larger[0]]);
+    chain.extend_from_slice(&sorted_larger);
+
+    // Pending elements b2..bk, each bounded by its partner, then the straggler
+    let mut pending: Vec<(usize, Option<usize>)> = sorted_larger[1..]
+        .iter()
+        .map(|&a| (partner[&a], Some(a)))
+        .collect();
+    if let Some(s) = straggler {
+        pending.push((s, None));
+    }
+
+    // Step 3: insert in Jacobsthal groups, each group from its top down
+    let mut done = 0;
+    let mut prev_jacobsthal = 1;
+    let mut jacobsthal = 3;
+    while done < pending.len() {
+        let group_end = (jacobsthal - 1).min(pending.len());
+        for p in (done..group_end).rev() {
+            let (item, bound) = pending[p];
+            let limit = match bound {
+                Some(a) => chain.iter().position(|&c| c == a).unwrap(),
+                None => chain.len(),
+            };
+            let pos = binary_search(array, &chain[..limit], item, events);
+            chain.insert(pos, item);
+        }
+        done = group_end;
+        let next = jacobsthal + 2 * prev_jacobsthal;
+        prev_jacobsthal = jacobsthal;
+        jacobsthal = next;
+    }
+
+    chain
+}
+
+/// Position in `chain` before the first element greater than `item`.
+fn binary_search(
+    array: &[i32],
+    chain: &[usize],
+    item: usize,
+    events: &mut impl EventSink,
+) -> usize {
+    let mut lo = 0;
+    let mut hi = chain.len();
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if greater(array, chain[mid], item, events) {
+            hi = mid;
+        } else {
+            lo = mid + 1;
+        }
+    }
+    lo
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compare_count(events: &[SortEvent]) -> usize {
+        events.iter().filter(|e| matches!(e, SortEvent::Compare { .. })).count()
+    }
+
+    /// Ford–Johnson worst-case comparison count: sum of ceil(log2(3k/4)).
+    fn ford_johnson_bound(n: usize) -> usize {
+        (1..=n).map(|k| (3.0 * k as f64 / 4.0).log2().ceil().max(0.0) as usize).sum()
+    }
+
+    fn permutations(n: usize) -> Vec<Vec<i32>> {
+        if n == 0 {
+            return vec![vec![]];
+        }
+        let mut result = Vec::new();
+        for perm in permutations(n - 1) {
+            for pos in 0..=perm.len() {
+                let mut next = perm.clone();
+                next.insert(pos, n as i32);
+                result.push(next);
+            }
+        }
+        result
+    }
+
+    #[test]
+    fn test_merge_insertion_basic() {
+        let mut array = vec![5, 3, 8, 4, 2];
+        let events = MergeInsertionSort::sort(&mut array);
+
+        assert_eq!(array, vec![2, 3, 4, 5, 8]);
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_merge_insertion_reverse() {
+        let mut array: Vec<i32> = (0..100).rev().collect();
+        MergeInsertionSort::sort(&mut array);
+
+        let expected: Vec<i32> = (0..100).collect();
+        assert_eq!(array, expected);
+    }
+
+    #[test]
+    fn test_merge_insertion_duplicates() {
+        let mut array = vec![3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5];
+        MergeInsertionSort::sort(&mut array);
+
+        assert_eq!(array, vec![1, 1, 2, 3, 3, 4, 5, 5, 5, 6, 9]);
+    }
+
+    #[test]
+    fn test_merge_insertion_empty() {
+        let mut array: Vec<i32> = vec![];
+        let events = MergeInsertionSort::sort(&mut array);
+
+        assert!(array.is_empty());
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_merge_insertion_single() {
+        let mut array = vec![42];
+        let events = MergeInsertionSort::sort(&mut array);
+
+        assert_eq!(array, vec![42]);
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_merge_insertion_meets_comparison_bound() {
+        for n in 1..=7 {
+            let bound = ford_johnson_bound(n);
+            for mut array in permutations(n) {
+                let events = MergeInsertionSort::sort(&mut array);
+                assert!(array.windows(2).all(|w| w[0] <= w[1]));
+                assert!(compare_count(&events) <= bound, "n = {} exceeded {}", n, bound);
+            }
+        }
+    }
+}
@@ -3,26 +3,45 @@
 //! Classic divide-and-conquer algorithm with O(n log n) time complexity.
 //! Uses EnterRange/ExitRange events to visualize the recursive structure.
 
-use crate::events::SortEvent;
-use super::PregenSort;
+use crate::events::{EventSink, SortEvent};
+use super::context::{copied, Scratch};
+use super::{estimate, AlgorithmInfo, PregenSort};
 
 pub struct MergeSort;
 
 impl PregenSort for MergeSort {
-    fn sort(array: &mut [i32]) -> Vec<SortEvent> {
-        let mut events = Vec::new();
+    const INFO: AlgorithmInfo = AlgorithmInfo {
+        display_name: "Merge Sort",
+        best: "O(n log n)",
+        average: "O(n log n)",
+        worst: "O(n log n)",
+        space: "O(n)",
+        stable: true,
+        in_place: false,
+        adaptive: false,
+        description: "Top-down merge sort: sorts both halves recursively, then merges them.",
+    };
+
+    fn estimated_events(n: usize) -> usize {
+        estimate::n_log_n(n, 2.0)
+    }
+
+    fn sort_into(array: &mut [i32], events: &mut impl EventSink) {
+        Self::sort_into_with(array, events, &mut Scratch::new());
+    }
+
+    fn sort_into_with(array: &mut [i32], events: &mut impl EventSink, scratch: &mut Scratch) {
         let n = array.len();
 
         if n <= 1 {
             events.push(SortEvent::Done);
-            return events;
+            return;
         }
 
-        let mut aux = array.to_vec();
-        merge_sort_recursive(array, &mut aux, 0, n - 1, &mut events);
+        let aux = copied(&mut scratch.values, array);
+        merge_sort_recursive(array, aux, 0, n - 1, events);
 
         events.push(SortEvent::Done);
-        events
     }
 }
 
@@ -31,7 +50,7 @@ fn merge_sort_recursive(
     aux: &mut [i32],
     lo: usize,
     hi: usize,
-    events: &mut Vec<SortEvent>,
+    events: &mut impl EventSink,
 ) {
     if lo >= hi {
         return;
@@ -53,18 +72,18 @@ fn merge_sort_recursive(
     events.push(SortEvent::ExitRange { lo, hi });
 }
 
-fn merge(
+/// Merge sorted runs [lo..=mid] and [mid+1..=hi] through the aux buffer.
+#[allow(clippy::needless_range_loop)]
+pub(super) fn merge(
     array: &mut [i32],
     aux: &mut [i32],
     lo: usize,
     mid: usize,
     hi: usize,
-    events: &mut Vec<SortEvent>,
+    events: &mut impl EventSink,
 ) {
     // Copy to auxiliary array
-    for i in lo..=hi {
-        aux[i] = array[i];
-    }
+    aux[lo..=hi].copy_from_slice(&array[lo..=hi]);
 
     let mut i = lo;
     let mut j = mid + 1;
@@ -0,0 +1,141 @@
+//! Bottom-up Merge Sort implementation for V1 (Pregeneration) engine.
+//!
+//! Iterative variant of merge sort with no recursion. Merges runs of
+//! width 1, 2, 4, ... in flat passes over the array, which contrasts with
+//! the recursion tree of top-down merge sort.
+
+use crate::events::{EventSink, SortEvent};
+use super::merge_sort::merge;
+use super::context::{copied, Scratch};
+use super::{estimate, AlgorithmInfo, PregenSort};
+
+pub struct MergeSortBottomUp;
+
+impl PregenSort for MergeSortBottomUp {
+    const INFO: AlgorithmInfo = AlgorithmInfo {
+        display_name: "Bottom-up Merge Sort",
+        best: "O(n log n)",
+        average: "O(n log n)",
+        worst: "O(n log n)",
+        space: "O(n)",
+        stable: true,
+        in_place: false,
+        adaptive: false,
+        description: "Iterative merge sort that merges runs of width 1, 2, 4, ...",
+    };
+
+    fn estimated_events(n: usize) -> usize {
+        estimate::n_log_n(n, 2.0)
+    }
+
+    fn sort_into(array: &mut [i32], events: &mut impl EventSink) {
+        Self::sort_into_with(array, events, &mut Scratch::new());
+    }
+
+    fn sort_into_with(array: &mut [i32], events: &mut impl EventSink, scratch: &mut Scratch) {
+        let n = array.len();
+
+        if n <= 1 {
+            events.push(SortEvent::Done);
+            return;
+        }
+
+        let aux = copied(&mut scratch.values, array);
+
+        // Double the run width on every pass
+        let mut width = 1;
+        while width < n {
+            for lo in (0..n).step_by(2 * width) {
+                let mid = lo + width - 1;
+                if mid >= n - 1 {
+                    // Lone run at the end of the pass, nothing to merge
+                    break;
+                }
+                let hi = (lo + 2 * width - 1).min(n - 1);
+
+                events.push(SortEvent::EnterRange { lo, hi });
+                merge(array, aux, lo, mid, hi, events);
+                events.push(SortEvent::ExitRange { lo, hi });
+            }
+            width *= 2;
+        }
+
+        events.push(SortEvent::Done);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_bottom_up_basic() {
+        let mut array = vec![5, 3, 8, 4, 2];
+        let events = MergeSortBottomUp::sort(&mut array);
+
+        assert_eq!(array, vec![2, 3, 4, 5, 8]);
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_merge_bottom_up_already_sorted() {
+        let mut array = vec![1, 2, 3, 4, 5];
+        let events = MergeSortBottomUp::sort(&mut array);
+
+        assert_eq!(array, vec![1, 2, 3, 4, 5]);
+        let overwrite_count = events.iter().filter(|e| matches!(e, SortEvent::Overwrite { .. })).count();
+        assert_eq!(overwrite_count, 0);
+    }
+
+    #[test]
+    fn test_merge_bottom_up_reverse() {
+        let mut array: Vec<i32> = (0..37).rev().collect();
+        MergeSortBottomUp::sort(&mut array);
+
+        let expected: Vec<i32> = (0..37).collect();
+        assert_eq!(array, expected);
+    }
+
+    #[test]
+    fn test_merge_bottom_up_empty() {
+        let mut array: Vec<i32> = vec![];
+        let events = MergeSortBottomUp::sort(&mut array);
+
+        assert!(array.is_empty());
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_merge_bottom_up_single() {
+        let mut array = vec![42];
+        let events = MergeSortBottomUp::sort(&mut array);
+
+        assert_eq!(array, vec![42]);
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_merge_bottom_up_duplicates() {
+        let mut array = vec![3, 1, 3, 2, 1, 2];
+        MergeSortBottomUp::sort(&mut array);
+
+        assert_eq!(array, vec![1, 1, 2, 2, 3, 3]);
+    }
+
+    #[test]
+    fn test_merge_bottom_up_flat_ranges() {
+        let mut array = vec![4, 3, 2, 1];
+        let events = MergeSortBottomUp::sort(&mut array);
+
+        // Ranges are never nested: each Enter is immediately closed before the next
+        let ranges: Vec<&SortEvent> = events
+            .iter()
+            .filter(|e| matches!(e, SortEvent::EnterRange { .. } | SortEvent::ExitRange { .. }))
+            .collect();
+        for pair in ranges.chunks(2) {
+            assert!(matches!(pair[0], SortEvent::EnterRange { .. }));
+            assert_eq!(pair[1].inverse(), *pair[0]);
+        }
+        assert_eq!(ranges.len(), 6);
+    }
+}
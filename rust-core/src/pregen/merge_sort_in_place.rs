@@ -0,0 +1,212 @@
+//! In-place Merge Sort implementation for V1 (Pregeneration) engine.
+//!
+//! Top-down merge sort that merges without an auxiliary buffer. Each merge
+//! splits both runs around a binary-searched cut point and rotates the middle
+//! block into place with a single Rotate event, recursing on the two smaller
+//! merges. Uses O(log n) extra space at the cost of O(n log² n) time.
+
+use crate::events::{EventSink, SortEvent};
+use crate::rotation::rotate;
+use super::{estimate, AlgorithmInfo, PregenSort};
+
+pub struct MergeSortInPlace;
+
+impl PregenSort for MergeSortInPlace {
+    const INFO: AlgorithmInfo = AlgorithmInfo {
+        display_name: "In-place Merge Sort",
+        best: "O(n log n)",
+        average: "O(n log² n)",
+        worst: "O(n log² n)",
+        space: "O(log n)",
+        stable: true,
+        in_place: true,
+        adaptive: false,
+        description: "Merge sort that merges by rotating blocks instead of using a buffer.",
+    };
+
+    fn estimated_events(n: usize) -> usize {
+        estimate::n_log2_n(n, 0.4)
+    }
+
+    fn sort_into(array: &mut [i32], events: &mut impl EventSink) {
+        let n = array.len();
+
+        if n > 1 {
+            merge_sort_recursive(array, 0, n, events);
+        }
+
+        events.push(SortEvent::Done);
+    }
+}
+
+/// Sort the half-open range [lo, hi).
+fn merge_sort_recursive(array: &mut [i32], lo: usize, hi: usize, events: &mut impl EventSink) {
+    if hi - lo < 2 {
+        return;
+    }
+
+    events.push(SortEvent::EnterRange { lo, hi: hi - 1 });
+
+    let mid = lo + (hi - lo) / 2;
+    merge_sort_recursive(array, lo, mid, events);
+    merge_sort_recursive(array, mid, hi, events);
+    merge_in_place(array, lo, mid, hi, events);
+
+    events.push(SortEvent::ExitRange { lo, hi: hi - 1 });
+}
+
+/// Merge sorted runs [lo, mid) and [mid, hi) using rotations.
+fn merge_in_place(
+    array: &mut [i32],
+    lo: usize,
+    mid: usize,
+    hi: usize,
+    events: &mut impl EventSink,
+) {
+    let len1 = mid - lo;
+    let len2 = hi - mid;
+
+    if len1 == 0 || len2 == 0 {
+        return;
+    }
+
+    if len1 + len2 == 2 {
+        events.push(SortEvent::Compare { i: lo, j: mid });
+        if array[mid] < array[lo] {
+            events.push(SortEvent::Swap { i: lo, j: mid });
+            array.swap(lo, mid);
+        }
+        return;
+    }
+
+    // Already in order across the boundary, nothing to merge
+    events.push(SortEvent::Compare { i: mid - 1, j: mid });
+    if array[mid - 1] <= array[mid] {
+        return;
+    }
+
+    // Split the longer run in half and find the matching cut in the other run
+    let (cut1, cut2) = if len1 >= len2 {
+        let cut1 = lo + len1 / 2;
+        (cut1, lower_bound(array, mid, hi, cut1, events))
+    } else {
+        let cut2 = mid + len2 / 2;
+        (upper_bound(array, lo, mid, cut2, events), cut2)
+    };
+
+    rotate(array, cut1, mid, cut2, events);
+    let new_mid = cut1 + (cut2 - mid);
+
+    merge_in_place(array, lo, cut1, new_mid, events);
+    merge_in_place(array, new_mid, cut2, hi, events);
+}
+
+/// First index in [lo, hi) whose value is not less than array[key].
+fn lower_bound(
+    array: &[i32],
+    mut lo: usize,
+    mut hi: usize,
+    key: usize,
+    events: &mut impl EventSink,
+) -> usize {
+    while lo < hi {
+        let probe = lo + (hi - lo) / 2;
+        events.push(SortEvent::Compare { i: probe, j: key });
+        if array[probe] < array[key] {
+            lo = probe + 1;
+        } else {
+            hi = probe;
+        }
+    }
+    lo
+}
+
+/// First index in [lo, hi) whose value is greater than array[key].
+fn upper_bound(
+    array: &[i32],
+    mut lo: usize,
+    mut hi: usize,
+    key: usize,
+    events: &mut impl EventSink,
+) -> usize {
+    while lo < hi {
+        let probe = lo + (hi - lo) / 2;
+        events.push(SortEvent::Compare { i: probe, j: key });
+        if array[probe] <= array[key] {
+            lo = probe + 1;
+        } else {
+            hi = probe;
+        }
+    }
+    lo
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_in_place_basic() {
+        let mut array = vec![5, 3, 8, 4, 2];
+        let events = MergeSortInPlace::sort(&mut array);
+
+        assert_eq!(array, vec![2, 3, 4, 5, 8]);
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_merge_in_place_already_sorted() {
+        let mut array = vec![1, 2, 3, 4, 5];
+        let events = MergeSortInPlace::sort(&mut array);
+
+        assert_eq!(array, vec![1, 2, 3, 4, 5]);
+        let swap_count = events.iter().filter(|e| matches!(e, SortEvent::Swap { .. })).count();
+        assert_eq!(swap_count, 0);
+    }
+
+    #[test]
+    fn test_merge_in_place_reverse() {
+        let mut array: Vec<i32> = (0..50).rev().collect();
+        MergeSortInPlace::sort(&mut array);
+
+        let expected: Vec<i32> = (0..50).collect();
+        assert_eq!(array, expected);
+    }
+
+    #[test]
+    fn test_merge_in_place_empty() {
+        let mut array: Vec<i32> = vec![];
+        let events = MergeSortInPlace::sort(&mut array);
+
+        assert!(array.is_empty());
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_merge_in_place_single() {
+        let mut array = vec![42];
+        let events = MergeSortInPlace::sort(&mut array);
+
+        assert_eq!(array, vec![42]);
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_merge_in_place_duplicates() {
+        let mut array = vec![3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5];
+        MergeSortInPlace::sort(&mut array);
+
+        assert_eq!(array, vec![1, 1, 2, 3, 3, 4, 5, 5, 5, 6, 9]);
+    }
+
+    #[test]
+    fn test_merge_in_place_uses_rotations() {
+        let mut array = vec![7, 2, 9, 4, 1, 8, 3];
+        let events = MergeSortInPlace::sort(&mut array);
+
+        // No aux buffer, so every mutation is a swap or a rotation
+        let overwrite_count = events.iter().filter(|e| matches!(e, SortEvent::Overwrite { .. })).count();
+        assert_eq!(overwrite_count, 0);
+        assert!(events.iter().any(|e| matches!(e, SortEvent::Rotate(_))));
+    }
+}
@@ -0,0 +1,171 @@
+//! Natural Merge Sort implementation for V1 (Pregeneration) engine.
+//!
+//! Scans the array for existing ascending runs and merges neighbouring runs
+//! pairwise until one run remains. Each detected run is framed with an
+//! EnterRange/ExitRange pair, so presorted structure is visible up front and
+//! nearly-sorted inputs finish in very few passes.
+
+use crate::prelude::*;
+use crate::events::{EventSink, SortEvent};
+use super::merge_sort::merge;
+use super::context::{copied, Scratch};
+use super::{estimate, AlgorithmInfo, PregenSort};
+
+pub struct MergeSortNatural;
+
+impl PregenSort for MergeSortNatural {
+    const INFO: AlgorithmInfo = AlgorithmInfo {
+        display_name: "Natural Merge Sort",
+        best: "O(n)",
+        average: "O(n log n)",
+        worst: "O(n log n)",
+        space: "O(n)",
+        stable: true,
+        in_place: false,
+        adaptive: true,
+        description: "Merges the input's existing ascending runs pairwise until one remains.",
+    };
+
+    fn estimated_events(n: usize) -> usize {
+        estimate::n_log_n(n, 2.15)
+    }
+
+    fn sort_into(array: &mut [i32], events: &mut impl EventSink) {
+        Self::sort_into_with(array, events, &mut Scratch::new());
+    }
+
+    fn sort_into_with(array: &mut [i32], events: &mut impl EventSink, scratch: &mut Scratch) {
+        let n = array.len();
+
+        if n <= 1 {
+            events.push(SortEvent::Done);
+            return;
+        }
+
+        // Run boundaries as start indices, with n as the closing sentinel
+        let mut bounds = detect_runs(array, events);
+        let aux = copied(&mut scratch.values, array);
+
+        while bounds.len() > 2 {
+            let mut merged = Vec::with_capacity(bounds.len() / 2 + 2);
+
+            let mut r = 0;
+            while r + 2 < bounds.len() {
+                let lo = bounds[r];
+                let mid = bounds[r + 1] - 1;
+                let hi = bounds[r + 2] - 1;
+
+                events.push(SortEvent::EnterRange { lo, hi });
+                merge(array, aux, lo, mid, hi, events);
+                events.push(SortEvent::ExitRange { lo, hi });
+
+                merged.push(lo);
+                r += 2;
+            }
+
+            // Odd run out carries over to the next pass unchanged
+            if r + 1 < bounds.len() {
+                merged.push(bounds[r]);
+            }
+            merged.push(n);
+            bounds = merged;
+        }
+
+        events.push(SortEvent::Done);
+    }
+}
+
+/// Split the array into maximal non-descending runs.
+/// Returns run start indices followed by the array length.
+fn detect_runs(array: &[i32], events: &mut impl EventSink) -> Vec<usize> {
+    let n = array.len();
+    let mut bounds = vec![0];
+    let mut start = 0;
+
+    for i in 1..n {
+        events.push(SortEvent::Compare { i: i - 1, j: i });
+        if array[i - 1] > array[i] {
+            events.push(SortEvent::EnterRange { lo: start, hi: i - 1 });
+            events.push(SortEvent::ExitRange { lo: start, hi: i - 1 });
+            bounds.push(i);
+            start = i;
+        }
+    }
+
+    events.push(SortEvent::EnterRange { lo: start, hi: n - 1 });
+    events.push(SortEvent::ExitRange { lo: start, hi: n - 1 });
+    bounds.push(n);
+    bounds
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_natural_merge_basic() {
+        let mut array = vec![5, 3, 8, 4, 2];
+        let events = MergeSortNatural::sort(&mut array);
+
+        assert_eq!(array, vec![2, 3, 4, 5, 8]);
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_natural_merge_already_sorted() {
+        let mut array = vec![1, 2, 3, 4, 5];
+        let events = MergeSortNatural::sort(&mut array);
+
+        assert_eq!(array, vec![1, 2, 3, 4, 5]);
+        // One detection pass and nothing else
+        let compare_count = events.iter().filter(|e| matches!(e, SortEvent::Compare { .. })).count();
+        assert_eq!(compare_count, 4);
+        assert!(!events.iter().any(|e| e.is_mutation()));
+    }
+
+    #[test]
+    fn test_natural_merge_reverse() {
+        let mut array: Vec<i32> = (0..33).rev().collect();
+        MergeSortNatural::sort(&mut array);
+
+        let expected: Vec<i32> = (0..33).collect();
+        assert_eq!(array, expected);
+    }
+
+    #[test]
+    fn test_natural_merge_empty() {
+        let mut array: Vec<i32> = vec![];
+        let events = MergeSortNatural::sort(&mut array);
+
+        assert!(array.is_empty());
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_natural_merge_single() {
+        let mut array = vec![42];
+        let events = MergeSortNatural::sort(&mut array);
+
+        assert_eq!(array, vec![42]);
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_natural_merge_sawtooth() {
+        let mut array = vec![1, 4, 7, 2, 5, 8, 3, 6, 9];
+        let events = MergeSortNatural::sort(&mut array);
+
+        assert_eq!(array, vec![1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        // Three runs detected, then two merges
+        let enter_count = events.iter().filter(|e| matches!(e, SortEvent::EnterRange { .. })).count();
+        assert_eq!(enter_count, 5);
+    }
+
+    #[test]
+    fn test_natural_merge_duplicates() {
+        let mut array = vec![3, 1, 3, 2, 1, 2];
+        MergeSortNatural::sort(&mut array);
+
+        assert_eq!(array, vec![1, 1, 2, 2, 3, 3]);
+    }
+}
@@ -4,167 +4,831 @@
 //! and collect all events into a vector. Optimized for simplicity and
 //! timeline scrubbing, but uses O(N²) memory for events.
 
+#[cfg(feature = "distribution")]
+pub mod american_flag_sort;
 pub mod binary_insertion_sort;
+pub mod bingo_sort;
+#[cfg(feature = "networks")]
 pub mod bitonic_sort;
 pub mod bubble_sort;
+pub mod cartesian_tree_sort;
+pub mod circle_sort;
 pub mod cocktail_sort;
 pub mod comb_sort;
+pub mod context;
 pub mod cycle_sort;
+pub mod double_selection_sort;
+pub mod estimate;
+pub mod exchange_sort;
+#[cfg(feature = "distribution")]
+pub mod flash_sort;
 pub mod gnome_sort;
+pub mod gnome_sort_optimized;
+#[cfg(feature = "esoteric")]
+pub mod gravity_sort;
 pub mod heap_sort;
+pub mod heap_sort_bottom_up;
+pub mod hybrid;
+pub mod info;
 pub mod insertion_sort;
 pub mod intro_sort;
+pub mod merge_insertion_sort;
 pub mod merge_sort;
+pub mod merge_sort_bottom_up;
+pub mod merge_sort_in_place;
+pub mod merge_sort_natural;
+#[cfg(feature = "networks")]
+pub mod odd_even_merge_sort;
 pub mod odd_even_sort;
+#[cfg(feature = "networks")]
+pub mod pairwise_sort;
 pub mod pancake_sort;
+pub mod pdq_sort;
+#[cfg(feature = "distribution")]
+pub mod proxmap_sort;
 pub mod quicksort_ll;
 pub mod quicksort_lr;
+pub mod quicksort_stable;
+#[cfg(feature = "distribution")]
+pub mod radix_exchange_sort;
+#[cfg(feature = "distribution")]
 pub mod radix_lsd_sort;
+#[cfg(feature = "distribution")]
 pub mod radix_msd_sort;
+#[cfg(feature = "distribution")]
+pub mod sample_sort;
 pub mod selection_sort;
 pub mod shell_sort;
+#[cfg(feature = "esoteric")]
+pub mod sleep_sort;
+pub mod splay_sort;
+pub mod spread_sort;
+#[cfg(feature = "strings")]
+pub mod strings;
 pub mod timsort;
 
-use crate::events::SortEvent;
+use crate::prelude::*;
+use crate::coalesce;
+use crate::compact::EventLog;
+use crate::events::{EventSink, SortEvent};
+use crate::sweep;
+
+pub use context::Scratch;
+pub use info::AlgorithmInfo;
 
 /// Trait for pregeneration sorting algorithms.
 /// Algorithms run to completion and return all events.
 pub trait PregenSort {
+    /// Complexity, stability and description of the algorithm.
+    const INFO: AlgorithmInfo;
+
+    /// Sort the array, appending all events that occurred to `events`.
+    /// The array is modified in place.
+    fn sort_into(array: &mut [i32], events: &mut impl EventSink);
+
+    /// `sort_into`, taking aux arrays from `scratch` instead of allocating
+    /// them, so that repeated runs reuse them. Algorithms without O(n) aux
+    /// arrays keep this default, which ignores it.
+    fn sort_into_with(array: &mut [i32], events: &mut impl EventSink, scratch: &mut Scratch) {
+        let _ = scratch;
+        Self::sort_into(array, events);
+    }
+
+    /// Rough number of events produced when sorting n shuffled elements,
+    /// used to pre-size the event buffer. It does not need to be exact.
+    fn estimated_events(n: usize) -> usize {
+        estimate::n_log_n(n, 2.0)
+    }
+
     /// Sort the array and return all events that occurred.
     /// The array is modified in place.
-    fn sort(array: &mut [i32]) -> Vec<SortEvent>;
+    fn sort(array: &mut [i32]) -> Vec<SortEvent> {
+        let capacity = estimate::reserve_limit(Self::estimated_events(array.len()));
+        let mut events = Vec::with_capacity(capacity);
+        Self::sort_into(array, &mut events);
+        events
+    }
 }
 
 /// Available sorting algorithms for V1 engine.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Algorithm {
     Bubble,
+    Exchange,
+    Circle,
     Selection,
+    DoubleSelection,
+    Bingo,
     Insertion,
     BinaryInsertion,
+    MergeInsertion,
     Cocktail,
     OddEven,
     Gnome,
+    GnomeOptimized,
     Pancake,
     Shell,
     Comb,
     Cycle,
     QuickSortLL,
     QuickSortLR,
+    QuickSortStable,
     MergeSort,
+    MergeSortBottomUp,
+    MergeSortInPlace,
+    MergeSortNatural,
     HeapSort,
+    HeapSortBottomUp,
+    CartesianTree,
+    Splay,
     Timsort,
     IntroSort,
+    PdqSort,
+    #[cfg(feature = "distribution")]
     RadixLsd,
+    #[cfg(feature = "distribution")]
     RadixMsd,
+    #[cfg(feature = "distribution")]
+    AmericanFlag,
+    #[cfg(feature = "distribution")]
+    RadixExchange,
+    #[cfg(feature = "distribution")]
+    Flash,
+    #[cfg(feature = "distribution")]
+    Proxmap,
+    Spread,
+    #[cfg(feature = "esoteric")]
+    Gravity,
+    #[cfg(feature = "esoteric")]
+    Sleep,
+    #[cfg(feature = "distribution")]
+    Sample,
+    #[cfg(feature = "networks")]
     Bitonic,
+    #[cfg(feature = "networks")]
+    OddEvenMerge,
+    #[cfg(feature = "networks")]
+    Pairwise,
 }
 
 impl Algorithm {
     pub fn as_str(&self) -> &'static str {
         match self {
             Algorithm::Bubble => "bubble",
+            Algorithm::Exchange => "exchange",
+            Algorithm::Circle => "circle",
             Algorithm::Selection => "selection",
+            Algorithm::DoubleSelection => "double_selection",
+            Algorithm::Bingo => "bingo",
             Algorithm::Insertion => "insertion",
             Algorithm::BinaryInsertion => "binary_insertion",
+            Algorithm::MergeInsertion => "merge_insertion",
             Algorithm::Cocktail => "cocktail",
             Algorithm::OddEven => "odd_even",
             Algorithm::Gnome => "gnome",
+            Algorithm::GnomeOptimized => "gnome_optimized",
             Algorithm::Pancake => "pancake",
             Algorithm::Shell => "shell",
             Algorithm::Comb => "comb",
             Algorithm::Cycle => "cycle",
             Algorithm::QuickSortLL => "quicksort_ll",
             Algorithm::QuickSortLR => "quicksort_lr",
+            Algorithm::QuickSortStable => "quicksort_stable",
             Algorithm::MergeSort => "merge",
+            Algorithm::MergeSortBottomUp => "merge_bottom_up",
+            Algorithm::MergeSortInPlace => "merge_in_place",
+            Algorithm::MergeSortNatural => "merge_natural",
             Algorithm::HeapSort => "heap",
+            Algorithm::HeapSortBottomUp => "heap_bottom_up",
+            Algorithm::CartesianTree => "cartesian_tree",
+            Algorithm::Splay => "splay",
             Algorithm::Timsort => "timsort",
             Algorithm::IntroSort => "intro",
+            Algorithm::PdqSort => "pdq",
+            #[cfg(feature = "distribution")]
             Algorithm::RadixLsd => "radix_lsd",
+            #[cfg(feature = "distribution")]
             Algorithm::RadixMsd => "radix_msd",
+            #[cfg(feature = "distribution")]
+            Algorithm::AmericanFlag => "american_flag",
+            #[cfg(feature = "distribution")]
+            Algorithm::RadixExchange => "radix_exchange",
+            #[cfg(feature = "distribution")]
+            Algorithm::Flash => "flash",
+            #[cfg(feature = "distribution")]
+            Algorithm::Proxmap => "proxmap",
+            Algorithm::Spread => "spread",
+            #[cfg(feature = "esoteric")]
+            Algorithm::Gravity => "gravity",
+            #[cfg(feature = "esoteric")]
+            Algorithm::Sleep => "sleep",
+            #[cfg(feature = "distribution")]
+            Algorithm::Sample => "sample",
+            #[cfg(feature = "networks")]
             Algorithm::Bitonic => "bitonic",
+            #[cfg(feature = "networks")]
+            Algorithm::OddEvenMerge => "odd_even_merge",
+            #[cfg(feature = "networks")]
+            Algorithm::Pairwise => "pairwise",
         }
     }
 
     pub fn all() -> &'static [Algorithm] {
-        const ALGORITHMS: [Algorithm; 20] = [
+        const ALGORITHMS: &[Algorithm] = &[
             Algorithm::Bubble,
+            Algorithm::Exchange,
+            Algorithm::Circle,
             Algorithm::Selection,
+            Algorithm::DoubleSelection,
+            Algorithm::Bingo,
             Algorithm::Insertion,
             Algorithm::BinaryInsertion,
+            Algorithm::MergeInsertion,
             Algorithm::Cocktail,
             Algorithm::OddEven,
             Algorithm::Gnome,
+            Algorithm::GnomeOptimized,
             Algorithm::Pancake,
             Algorithm::Shell,
             Algorithm::Comb,
             Algorithm::Cycle,
             Algorithm::QuickSortLL,
             Algorithm::QuickSortLR,
+            Algorithm::QuickSortStable,
             Algorithm::MergeSort,
+            Algorithm::MergeSortBottomUp,
+            Algorithm::MergeSortInPlace,
+            Algorithm::MergeSortNatural,
             Algorithm::HeapSort,
+            Algorithm::HeapSortBottomUp,
+            Algorithm::CartesianTree,
+            Algorithm::Splay,
             Algorithm::Timsort,
             Algorithm::IntroSort,
+            Algorithm::PdqSort,
+            #[cfg(feature = "distribution")]
             Algorithm::RadixLsd,
+            #[cfg(feature = "distribution")]
             Algorithm::RadixMsd,
+            #[cfg(feature = "distribution")]
+            Algorithm::AmericanFlag,
+            #[cfg(feature = "distribution")]
+            Algorithm::RadixExchange,
+            #[cfg(feature = "distribution")]
+            Algorithm::Flash,
+            #[cfg(feature = "distribution")]
+            Algorithm::Proxmap,
+            Algorithm::Spread,
+            #[cfg(feature = "esoteric")]
+            Algorithm::Gravity,
+            #[cfg(feature = "esoteric")]
+            Algorithm::Sleep,
+            #[cfg(feature = "distribution")]
+            Algorithm::Sample,
+            #[cfg(feature = "networks")]
             Algorithm::Bitonic,
+            #[cfg(feature = "networks")]
+            Algorithm::OddEvenMerge,
+            #[cfg(feature = "networks")]
+            Algorithm::Pairwise,
         ];
-        &ALGORITHMS
+        ALGORITHMS
     }
 
-    /// Parse algorithm name from string.
+    /// Parse algorithm name from string. Accepts any alias in any case;
+    /// see `from_str_strict` to accept only canonical names.
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Option<Algorithm> {
-        match s.to_lowercase().as_str() {
-            "bubble" | "bubblesort" | "bubble_sort" => Some(Algorithm::Bubble),
-            "selection" | "selectionsort" | "selection_sort" => Some(Algorithm::Selection),
-            "insertion" | "insertionsort" | "insertion_sort" => Some(Algorithm::Insertion),
-            "binary_insertion" | "binaryinsertion" | "binary_insertion_sort" => {
-                Some(Algorithm::BinaryInsertion)
-            }
-            "cocktail" | "cocktailsort" | "cocktail_sort" => Some(Algorithm::Cocktail),
-            "odd_even" | "oddeven" | "odd_even_sort" => Some(Algorithm::OddEven),
-            "gnome" | "gnomesort" | "gnome_sort" => Some(Algorithm::Gnome),
-            "pancake" | "pancakesort" | "pancake_sort" => Some(Algorithm::Pancake),
-            "shell" | "shellsort" | "shell_sort" => Some(Algorithm::Shell),
-            "comb" | "combsort" | "comb_sort" => Some(Algorithm::Comb),
-            "cycle" | "cyclesort" | "cycle_sort" => Some(Algorithm::Cycle),
-            "quicksort_ll" | "quicksortll" | "quick_sort_ll" => Some(Algorithm::QuickSortLL),
-            "quicksort_lr" | "quicksortlr" | "quick_sort_lr" => Some(Algorithm::QuickSortLR),
-            "merge" | "mergesort" | "merge_sort" => Some(Algorithm::MergeSort),
-            "heap" | "heapsort" | "heap_sort" => Some(Algorithm::HeapSort),
-            "tim" | "timsort" | "tim_sort" => Some(Algorithm::Timsort),
-            "intro" | "introsort" | "intro_sort" => Some(Algorithm::IntroSort),
-            "radix_lsd" | "radixlsd" | "radix_lsd_sort" => Some(Algorithm::RadixLsd),
-            "radix_msd" | "radixmsd" | "radix_msd_sort" => Some(Algorithm::RadixMsd),
-            "bitonic" | "bitonicsort" | "bitonic_sort" => Some(Algorithm::Bitonic),
-            _ => None,
+        let name = s.to_lowercase();
+        Algorithm::all().iter().copied().find(|a| a.aliases().contains(&name.as_str()))
+    }
+
+    /// Parse only the canonical name, exactly as returned by `as_str`.
+    /// Use it to validate stored configurations, where an alias or a typo
+    /// that happens to match one should be caught rather than accepted.
+    pub fn from_str_strict(s: &str) -> Option<Algorithm> {
+        Algorithm::all().iter().copied().find(|a| a.as_str() == s)
+    }
+
+    /// All names `from_str` accepts for this algorithm (lowercase),
+    /// canonical name first.
+    pub fn aliases(&self) -> &'static [&'static str] {
+        match self {
+            Algorithm::Bubble => &["bubble", "bubblesort", "bubble_sort"],
+            Algorithm::Exchange => &["exchange", "exchangesort", "exchange_sort"],
+            Algorithm::Circle => &["circle", "circlesort", "circle_sort"],
+            Algorithm::Selection => &["selection", "selectionsort", "selection_sort"],
+            Algorithm::DoubleSelection => &[
+                "double_selection",
+                "doubleselection",
+                "double_selection_sort",
+                "min_max_selection",
+            ],
+            Algorithm::Bingo => &["bingo", "bingosort", "bingo_sort"],
+            Algorithm::Insertion => &["insertion", "insertionsort", "insertion_sort"],
+            Algorithm::BinaryInsertion => &[
+                "binary_insertion",
+                "binaryinsertion",
+                "binary_insertion_sort",
+            ],
+            Algorithm::MergeInsertion => &[
+                "merge_insertion",
+                "mergeinsertion",
+                "merge_insertion_sort",
+                "ford_johnson",
+                "fordjohnson",
+            ],
+            Algorithm::Cocktail => &["cocktail", "cocktailsort", "cocktail_sort"],
+            Algorithm::OddEven => &["odd_even", "oddeven", "odd_even_sort"],
+            Algorithm::Gnome => &["gnome", "gnomesort", "gnome_sort"],
+            Algorithm::GnomeOptimized => &[
+                "gnome_optimized",
+                "gnomeoptimized",
+                "gnome_sort_optimized",
+                "optimized_gnome",
+            ],
+            Algorithm::Pancake => &["pancake", "pancakesort", "pancake_sort"],
+            Algorithm::Shell => &["shell", "shellsort", "shell_sort"],
+            Algorithm::Comb => &["comb", "combsort", "comb_sort"],
+            Algorithm::Cycle => &["cycle", "cyclesort", "cycle_sort"],
+            Algorithm::QuickSortLL => &[
+                "quicksort_ll",
+                "quicksortll",
+                "quick_sort_ll",
+                "lomuto",
+                "quicksort",
+                "quick_sort",
+            ],
+            Algorithm::QuickSortLR => &["quicksort_lr", "quicksortlr", "quick_sort_lr", "hoare"],
+            Algorithm::QuickSortStable => &[
+                "quicksort_stable",
+                "quicksortstable",
+                "quick_sort_stable",
+                "stable_quicksort",
+            ],
+            Algorithm::MergeSort => &["merge", "mergesort", "merge_sort"],
+            Algorithm::MergeSortBottomUp => &[
+                "merge_bottom_up",
+                "mergebottomup",
+                "merge_sort_bottom_up",
+                "bottom_up_merge",
+            ],
+            Algorithm::MergeSortInPlace => &[
+                "merge_in_place",
+                "mergeinplace",
+                "merge_sort_in_place",
+                "in_place_merge",
+            ],
+            Algorithm::MergeSortNatural => &[
+                "merge_natural",
+                "mergenatural",
+                "natural_merge",
+                "natural_merge_sort",
+            ],
+            Algorithm::HeapSort => &["heap", "heapsort", "heap_sort"],
+            Algorithm::HeapSortBottomUp => &[
+                "heap_bottom_up",
+                "heapbottomup",
+                "heap_sort_bottom_up",
+                "bottom_up_heap",
+            ],
+            Algorithm::CartesianTree => &[
+                "cartesian_tree",
+                "cartesiantree",
+                "cartesian_tree_sort",
+                "cartesian",
+            ],
+            Algorithm::Splay => &["splay", "splaysort", "splay_sort"],
+            Algorithm::Timsort => &["timsort", "tim", "tim_sort"],
+            Algorithm::IntroSort => &["intro", "introsort", "intro_sort"],
+            Algorithm::PdqSort => &["pdq", "pdqsort", "pdq_sort"],
+            #[cfg(feature = "distribution")]
+            Algorithm::RadixLsd => &["radix_lsd", "radixlsd", "radix_lsd_sort"],
+            #[cfg(feature = "distribution")]
+            Algorithm::RadixMsd => &["radix_msd", "radixmsd", "radix_msd_sort"],
+            #[cfg(feature = "distribution")]
+            Algorithm::AmericanFlag => &["american_flag", "americanflag", "american_flag_sort"],
+            #[cfg(feature = "distribution")]
+            Algorithm::RadixExchange => &[
+                "radix_exchange",
+                "radixexchange",
+                "radix_exchange_sort",
+                "binary_radix",
+                "radix_msd_binary",
+            ],
+            #[cfg(feature = "distribution")]
+            Algorithm::Flash => &["flash", "flashsort", "flash_sort"],
+            #[cfg(feature = "distribution")]
+            Algorithm::Proxmap => &["proxmap", "proxmapsort", "proxmap_sort"],
+            Algorithm::Spread => &["spread", "spreadsort", "spread_sort"],
+            #[cfg(feature = "esoteric")]
+            Algorithm::Gravity => &[
+                "gravity",
+                "gravitysort",
+                "gravity_sort",
+                "bead",
+                "beadsort",
+                "bead_sort",
+            ],
+            #[cfg(feature = "esoteric")]
+            Algorithm::Sleep => &["sleep", "sleepsort", "sleep_sort"],
+            #[cfg(feature = "distribution")]
+            Algorithm::Sample => &["sample", "samplesort", "sample_sort"],
+            #[cfg(feature = "networks")]
+            Algorithm::Bitonic => &["bitonic", "bitonicsort", "bitonic_sort"],
+            #[cfg(feature = "networks")]
+            Algorithm::OddEvenMerge => &[
+                "odd_even_merge",
+                "oddevenmerge",
+                "odd_even_merge_sort",
+                "batcher",
+            ],
+            #[cfg(feature = "networks")]
+            Algorithm::Pairwise => &[
+                "pairwise",
+                "pairwisesort",
+                "pairwise_sort",
+                "pairwise_network",
+            ],
+        }
+    }
+
+    /// Family the algorithm belongs to, for grouping in the UI.
+    pub fn category(&self) -> Category {
+        match self {
+            Algorithm::Bubble
+            | Algorithm::Exchange
+            | Algorithm::Circle
+            | Algorithm::Cocktail
+            | Algorithm::OddEven
+            | Algorithm::Gnome
+            | Algorithm::GnomeOptimized
+            | Algorithm::Comb
+            | Algorithm::QuickSortLL
+            | Algorithm::QuickSortLR
+            | Algorithm::QuickSortStable => Category::Exchange,
+            Algorithm::Selection
+            | Algorithm::DoubleSelection
+            | Algorithm::Bingo
+            | Algorithm::Pancake
+            | Algorithm::Cycle
+            | Algorithm::HeapSort
+            | Algorithm::HeapSortBottomUp
+            | Algorithm::CartesianTree => Category::Selection,
+            Algorithm::Insertion
+            | Algorithm::BinaryInsertion
+            | Algorithm::MergeInsertion
+            | Algorithm::Shell
+            | Algorithm::Splay => Category::Insertion,
+            Algorithm::MergeSort
+            | Algorithm::MergeSortBottomUp
+            | Algorithm::MergeSortInPlace
+            | Algorithm::MergeSortNatural => Category::Merge,
+            #[cfg(feature = "distribution")]
+            Algorithm::RadixLsd
+            | Algorithm::RadixMsd
+            | Algorithm::AmericanFlag
+            | Algorithm::RadixExchange
+            | Algorithm::Flash
+            | Algorithm::Proxmap
+            | Algorithm::Sample => Category::Distribution,
+            #[cfg(feature = "networks")]
+            Algorithm::Bitonic | Algorithm::OddEvenMerge | Algorithm::Pairwise => Category::Network,
+            Algorithm::Timsort
+            | Algorithm::IntroSort
+            | Algorithm::PdqSort
+            | Algorithm::Spread => Category::Hybrid,
+            #[cfg(feature = "esoteric")]
+            Algorithm::Gravity | Algorithm::Sleep => Category::Esoteric,
         }
     }
 }
 
+/// Family of sorting algorithms. Partition sorts count as exchange sorts
+/// and heap/tree selection as selection sorts, following Knuth.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Category {
+    Exchange,
+    Selection,
+    Insertion,
+    Merge,
+    Distribution,
+    Network,
+    Hybrid,
+    Esoteric,
+}
+
+impl Category {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Category::Exchange => "exchange",
+            Category::Selection => "selection",
+            Category::Insertion => "insertion",
+            Category::Merge => "merge",
+            Category::Distribution => "distribution",
+            Category::Network => "network",
+            Category::Hybrid => "hybrid",
+            Category::Esoteric => "esoteric",
+        }
+    }
+
+    pub fn all() -> &'static [Category] {
+        const CATEGORIES: [Category; 8] = [
+            Category::Exchange,
+            Category::Selection,
+            Category::Insertion,
+            Category::Merge,
+            Category::Distribution,
+            Category::Network,
+            Category::Hybrid,
+            Category::Esoteric,
+        ];
+        &CATEGORIES
+    }
+
+    /// Parse category name from string.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Category> {
+        Category::all().iter().copied().find(|c| c.as_str() == s.to_lowercase())
+    }
+
+    /// Algorithms in this category, in registry order.
+    pub fn algorithms(&self) -> Vec<Algorithm> {
+        Algorithm::all().iter().copied().filter(|a| a.category() == *self).collect()
+    }
+}
+
 /// Run a pregeneration sort on the given array.
 /// Returns the sorted array and all events.
 pub fn pregen_sort(algorithm: Algorithm, array: &mut [i32]) -> Vec<SortEvent> {
+    let capacity = estimate::reserve_limit(estimated_events(algorithm, array.len()));
+    let mut events = Vec::with_capacity(capacity);
+    pregen_sort_into(algorithm, array, &mut events);
+    events
+}
+
+/// Run a pregeneration sort into an `EventLog`, which holds large traces in
+/// about half the memory of `pregen_sort`'s vector.
+pub fn pregen_sort_compact(algorithm: Algorithm, array: &mut [i32]) -> EventLog {
+    let mut events = EventLog::new();
+    pregen_sort_into(algorithm, array, &mut events);
+    events
+}
+
+/// Run a pregeneration sort and append a verification sweep before Done:
+/// Compare/MarkSorted events across the sorted array, see `sweep`.
+pub fn pregen_sort_with_sweep(algorithm: Algorithm, array: &mut [i32]) -> Vec<SortEvent> {
+    let mut events = pregen_sort(algorithm, array);
+    sweep::append_sweep(array, &mut events);
+    events
+}
+
+/// Run a pregeneration sort with insertion shifts coalesced into BlockMove
+/// events, see `coalesce`.
+pub fn pregen_sort_coalesced(algorithm: Algorithm, array: &mut [i32]) -> Vec<SortEvent> {
+    let input = array.to_vec();
+    let events = pregen_sort(algorithm, array);
+    coalesce::coalesce_shifts(&input, &events)
+}
+
+/// Run a pregeneration sort, appending its events to `events`.
+pub fn pregen_sort_into(algorithm: Algorithm, array: &mut [i32], events: &mut impl EventSink) {
+    match algorithm {
+        Algorithm::Bubble => bubble_sort::BubbleSort::sort_into(array, events),
+        Algorithm::Exchange => exchange_sort::ExchangeSort::sort_into(array, events),
+        Algorithm::Circle => circle_sort::CircleSort::sort_into(array, events),
+        Algorithm::Selection => selection_sort::SelectionSort::sort_into(array, events),
+        Algorithm::DoubleSelection => {
+            double_selection_sort::DoubleSelectionSort::sort_into(array, events)
+        }
+        Algorithm::Bingo => bingo_sort::BingoSort::sort_into(array, events),
+        Algorithm::Insertion => insertion_sort::InsertionSort::sort_into(array, events),
+        Algorithm::BinaryInsertion => {
+            binary_insertion_sort::BinaryInsertionSort::sort_into(array, events)
+        }
+        Algorithm::MergeInsertion => {
+            merge_insertion_sort::MergeInsertionSort::sort_into(array, events)
+        }
+        Algorithm::Cocktail => cocktail_sort::CocktailSort::sort_into(array, events),
+        Algorithm::OddEven => odd_even_sort::OddEvenSort::sort_into(array, events),
+        Algorithm::Gnome => gnome_sort::GnomeSort::sort_into(array, events),
+        Algorithm::GnomeOptimized => {
+            gnome_sort_optimized::GnomeSortOptimized::sort_into(array, events)
+        }
+        Algorithm::Pancake => pancake_sort::PancakeSort::sort_into(array, events),
+        Algorithm::Shell => shell_sort::ShellSort::sort_into(array, events),
+        Algorithm::Comb => comb_sort::CombSort::sort_into(array, events),
+        Algorithm::Cycle => cycle_sort::CycleSort::sort_into(array, events),
+        Algorithm::QuickSortLL => quicksort_ll::QuickSortLL::sort_into(array, events),
+        Algorithm::QuickSortLR => quicksort_lr::QuickSortLR::sort_into(array, events),
+        Algorithm::QuickSortStable => quicksort_stable::QuickSortStable::sort_into(array, events),
+        Algorithm::MergeSort => merge_sort::MergeSort::sort_into(array, events),
+        Algorithm::MergeSortBottomUp => {
+            merge_sort_bottom_up::MergeSortBottomUp::sort_into(array, events)
+        }
+        Algorithm::MergeSortInPlace => {
+            merge_sort_in_place::MergeSortInPlace::sort_into(array, events)
+        }
+        Algorithm::MergeSortNatural => {
+            merge_sort_natural::MergeSortNatural::sort_into(array, events)
+        }
+        Algorithm::HeapSort => heap_sort::HeapSort::sort_into(array, events),
+        Algorithm::HeapSortBottomUp => {
+            heap_sort_bottom_up::HeapSortBottomUp::sort_into(array, events)
+        }
+        Algorithm::CartesianTree => {
+            cartesian_tree_sort::CartesianTreeSort::sort_into(array, events)
+        }
+        Algorithm::Splay => splay_sort::SplaySort::sort_into(array, events),
+        Algorithm::Timsort => timsort::Timsort::sort_into(array, events),
+        Algorithm::IntroSort => intro_sort::IntroSort::sort_into(array, events),
+        Algorithm::PdqSort => pdq_sort::PdqSort::sort_into(array, events),
+        #[cfg(feature = "distribution")]
+        Algorithm::RadixLsd => radix_lsd_sort::RadixLsdSort::sort_into(array, events),
+        #[cfg(feature = "distribution")]
+        Algorithm::RadixMsd => radix_msd_sort::RadixMsdSort::sort_into(array, events),
+        #[cfg(feature = "distribution")]
+        Algorithm::AmericanFlag => american_flag_sort::AmericanFlagSort::sort_into(array, events),
+        #[cfg(feature = "distribution")]
+        Algorithm::RadixExchange => {
+            radix_exchange_sort::RadixExchangeSort::sort_into(array, events)
+        }
+        #[cfg(feature = "distribution")]
+        Algorithm::Flash => flash_sort::FlashSort::sort_into(array, events),
+        #[cfg(feature = "distribution")]
+        Algorithm::Proxmap => proxmap_sort::ProxmapSort::sort_into(array, events),
+        Algorithm::Spread => spread_sort::SpreadSort::sort_into(array, events),
+        #[cfg(feature = "esoteric")]
+        Algorithm::Gravity => gravity_sort::GravitySort::sort_into(array, events),
+        #[cfg(feature = "esoteric")]
+        Algorithm::Sleep => sleep_sort::SleepSort::sort_into(array, events),
+        #[cfg(feature = "distribution")]
+        Algorithm::Sample => sample_sort::SampleSort::sort_into(array, events),
+        #[cfg(feature = "networks")]
+        Algorithm::Bitonic => bitonic_sort::BitonicSort::sort_into(array, events),
+        #[cfg(feature = "networks")]
+        Algorithm::OddEvenMerge => odd_even_merge_sort::OddEvenMergeSort::sort_into(array, events),
+        #[cfg(feature = "networks")]
+        Algorithm::Pairwise => pairwise_sort::PairwiseSort::sort_into(array, events),
+    }
+}
+
+/// Run a pregeneration sort, appending its events to `events` and taking
+/// aux arrays from `scratch`; see `PregenSort::sort_into_with`.
+pub fn pregen_sort_into_with(
+    algorithm: Algorithm,
+    array: &mut [i32],
+    events: &mut impl EventSink,
+    scratch: &mut Scratch,
+) {
+    match algorithm {
+        Algorithm::MergeSort => merge_sort::MergeSort::sort_into_with(array, events, scratch),
+        Algorithm::MergeSortBottomUp => {
+            merge_sort_bottom_up::MergeSortBottomUp::sort_into_with(array, events, scratch)
+        }
+        Algorithm::MergeSortNatural => {
+            merge_sort_natural::MergeSortNatural::sort_into_with(array, events, scratch)
+        }
+        Algorithm::Timsort => timsort::Timsort::sort_into_with(array, events, scratch),
+        #[cfg(feature = "distribution")]
+        Algorithm::RadixLsd => radix_lsd_sort::RadixLsdSort::sort_into_with(array, events, scratch),
+        #[cfg(feature = "distribution")]
+        Algorithm::RadixMsd => radix_msd_sort::RadixMsdSort::sort_into_with(array, events, scratch),
+        #[cfg(feature = "distribution")]
+        Algorithm::Flash => flash_sort::FlashSort::sort_into_with(array, events, scratch),
+        #[cfg(feature = "distribution")]
+        Algorithm::Sample => sample_sort::SampleSort::sort_into_with(array, events, scratch),
+        algorithm => pregen_sort_into(algorithm, array, events),
+    }
+}
+
+/// Expected number of events for sorting n elements with `algorithm`.
+/// See `PregenSort::estimated_events`.
+pub fn estimated_events(algorithm: Algorithm, n: usize) -> usize {
+    match algorithm {
+        Algorithm::Bubble => bubble_sort::BubbleSort::estimated_events(n),
+        Algorithm::Exchange => exchange_sort::ExchangeSort::estimated_events(n),
+        Algorithm::Circle => circle_sort::CircleSort::estimated_events(n),
+        Algorithm::Selection => selection_sort::SelectionSort::estimated_events(n),
+        Algorithm::DoubleSelection => {
+            double_selection_sort::DoubleSelectionSort::estimated_events(n)
+        }
+        Algorithm::Bingo => bingo_sort::BingoSort::estimated_events(n),
+        Algorithm::Insertion => insertion_sort::InsertionSort::estimated_events(n),
+        Algorithm::BinaryInsertion => {
+            binary_insertion_sort::BinaryInsertionSort::estimated_events(n)
+        }
+        Algorithm::MergeInsertion => merge_insertion_sort::MergeInsertionSort::estimated_events(n),
+        Algorithm::Cocktail => cocktail_sort::CocktailSort::estimated_events(n),
+        Algorithm::OddEven => odd_even_sort::OddEvenSort::estimated_events(n),
+        Algorithm::Gnome => gnome_sort::GnomeSort::estimated_events(n),
+        Algorithm::GnomeOptimized => gnome_sort_optimized::GnomeSortOptimized::estimated_events(n),
+        Algorithm::Pancake => pancake_sort::PancakeSort::estimated_events(n),
+        Algorithm::Shell => shell_sort::ShellSort::estimated_events(n),
+        Algorithm::Comb => comb_sort::CombSort::estimated_events(n),
+        Algorithm::Cycle => cycle_sort::CycleSort::estimated_events(n),
+        Algorithm::QuickSortLL => quicksort_ll::QuickSortLL::estimated_events(n),
+        Algorithm::QuickSortLR => quicksort_lr::QuickSortLR::estimated_events(n),
+        Algorithm::QuickSortStable => quicksort_stable::QuickSortStable::estimated_events(n),
+        Algorithm::MergeSort => merge_sort::MergeSort::estimated_events(n),
+        Algorithm::MergeSortBottomUp => {
+            merge_sort_bottom_up::MergeSortBottomUp::estimated_events(n)
+        }
+        Algorithm::MergeSortInPlace => merge_sort_in_place::MergeSortInPlace::estimated_events(n),
+        Algorithm::MergeSortNatural => merge_sort_natural::MergeSortNatural::estimated_events(n),
+        Algorithm::HeapSort => heap_sort::HeapSort::estimated_events(n),
+        Algorithm::HeapSortBottomUp => heap_sort_bottom_up::HeapSortBottomUp::estimated_events(n),
+        Algorithm::CartesianTree => cartesian_tree_sort::CartesianTreeSort::estimated_events(n),
+        Algorithm::Splay => splay_sort::SplaySort::estimated_events(n),
+        Algorithm::Timsort => timsort::Timsort::estimated_events(n),
+        Algorithm::IntroSort => intro_sort::IntroSort::estimated_events(n),
+        Algorithm::PdqSort => pdq_sort::PdqSort::estimated_events(n),
+        #[cfg(feature = "distribution")]
+        Algorithm::RadixLsd => radix_lsd_sort::RadixLsdSort::estimated_events(n),
+        #[cfg(feature = "distribution")]
+        Algorithm::RadixMsd => radix_msd_sort::RadixMsdSort::estimated_events(n),
+        #[cfg(feature = "distribution")]
+        Algorithm::AmericanFlag => american_flag_sort::AmericanFlagSort::estimated_events(n),
+        #[cfg(feature = "distribution")]
+        Algorithm::RadixExchange => radix_exchange_sort::RadixExchangeSort::estimated_events(n),
+        #[cfg(feature = "distribution")]
+        Algorithm::Flash => flash_sort::FlashSort::estimated_events(n),
+        #[cfg(feature = "distribution")]
+        Algorithm::Proxmap => proxmap_sort::ProxmapSort::estimated_events(n),
+        Algorithm::Spread => spread_sort::SpreadSort::estimated_events(n),
+        #[cfg(feature = "esoteric")]
+        Algorithm::Gravity => gravity_sort::GravitySort::estimated_events(n),
+        #[cfg(feature = "esoteric")]
+        Algorithm::Sleep => sleep_sort::SleepSort::estimated_events(n),
+        #[cfg(feature = "distribution")]
+        Algorithm::Sample => sample_sort::SampleSort::estimated_events(n),
+        #[cfg(feature = "networks")]
+        Algorithm::Bitonic => bitonic_sort::BitonicSort::estimated_events(n),
+        #[cfg(feature = "networks")]
+        Algorithm::OddEvenMerge => odd_even_merge_sort::OddEvenMergeSort::estimated_events(n),
+        #[cfg(feature = "networks")]
+        Algorithm::Pairwise => pairwise_sort::PairwiseSort::estimated_events(n),
+    }
+}
+
+/// Metadata of `algorithm`: complexity classes, stability, description.
+pub fn algorithm_info(algorithm: Algorithm) -> AlgorithmInfo {
     match algorithm {
-        Algorithm::Bubble => bubble_sort::BubbleSort::sort(array),
-        Algorithm::Selection => selection_sort::SelectionSort::sort(array),
-        Algorithm::Insertion => insertion_sort::InsertionSort::sort(array),
-        Algorithm::BinaryInsertion => binary_insertion_sort::BinaryInsertionSort::sort(array),
-        Algorithm::Cocktail => cocktail_sort::CocktailSort::sort(array),
-        Algorithm::OddEven => odd_even_sort::OddEvenSort::sort(array),
-        Algorithm::Gnome => gnome_sort::GnomeSort::sort(array),
-        Algorithm::Pancake => pancake_sort::PancakeSort::sort(array),
-        Algorithm::Shell => shell_sort::ShellSort::sort(array),
-        Algorithm::Comb => comb_sort::CombSort::sort(array),
-        Algorithm::Cycle => cycle_sort::CycleSort::sort(array),
-        Algorithm::QuickSortLL => quicksort_ll::QuickSortLL::sort(array),
-        Algorithm::QuickSortLR => quicksort_lr::QuickSortLR::sort(array),
-        Algorithm::MergeSort => merge_sort::MergeSort::sort(array),
-        Algorithm::HeapSort => heap_sort::HeapSort::sort(array),
-        Algorithm::Timsort => timsort::Timsort::sort(array),
-        Algorithm::IntroSort => intro_sort::IntroSort::sort(array),
-        Algorithm::RadixLsd => radix_lsd_sort::RadixLsdSort::sort(array),
-        Algorithm::RadixMsd => radix_msd_sort::RadixMsdSort::sort(array),
-        Algorithm::Bitonic => bitonic_sort::BitonicSort::sort(array),
+        Algorithm::Bubble => bubble_sort::BubbleSort::INFO,
+        Algorithm::Exchange => exchange_sort::ExchangeSort::INFO,
+        Algorithm::Circle => circle_sort::CircleSort::INFO,
+        Algorithm::Selection => selection_sort::SelectionSort::INFO,
+        Algorithm::DoubleSelection => double_selection_sort::DoubleSelectionSort::INFO,
+        Algorithm::Bingo => bingo_sort::BingoSort::INFO,
+        Algorithm::Insertion => insertion_sort::InsertionSort::INFO,
+        Algorithm::BinaryInsertion => binary_insertion_sort::BinaryInsertionSort::INFO,
+        Algorithm::MergeInsertion => merge_insertion_sort::MergeInsertionSort::INFO,
+        Algorithm::Cocktail => cocktail_sort::CocktailSort::INFO,
+        Algorithm::OddEven => odd_even_sort::OddEvenSort::INFO,
+        Algorithm::Gnome => gnome_sort::GnomeSort::INFO,
+        Algorithm::GnomeOptimized => gnome_sort_optimized::GnomeSortOptimized::INFO,
+        Algorithm::Pancake => pancake_sort::PancakeSort::INFO,
+        Algorithm::Shell => shell_sort::ShellSort::INFO,
+        Algorithm::Comb => comb_sort::CombSort::INFO,
+        Algorithm::Cycle => cycle_sort::CycleSort::INFO,
+        Algorithm::QuickSortLL => quicksort_ll::QuickSortLL::INFO,
+        Algorithm::QuickSortLR => quicksort_lr::QuickSortLR::INFO,
+        Algorithm::QuickSortStable => quicksort_stable::QuickSortStable::INFO,
+        Algorithm::MergeSort => merge_sort::MergeSort::INFO,
+        Algorithm::MergeSortBottomUp => merge_sort_bottom_up::MergeSortBottomUp::INFO,
+        Algorithm::MergeSortInPlace => merge_sort_in_place::MergeSortInPlace::INFO,
+        Algorithm::MergeSortNatural => merge_sort_natural::MergeSortNatural::INFO,
+        Algorithm::HeapSort => heap_sort::HeapSort::INFO,
+        Algorithm::HeapSortBottomUp => heap_sort_bottom_up::HeapSortBottomUp::INFO,
+        Algorithm::CartesianTree => cartesian_tree_sort::CartesianTreeSort::INFO,
+        Algorithm::Splay => splay_sort::SplaySort::INFO,
+        Algorithm::Timsort => timsort::Timsort::INFO,
+        Algorithm::IntroSort => intro_sort::IntroSort::INFO,
+        Algorithm::PdqSort => pdq_sort::PdqSort::INFO,
+        #[cfg(feature = "distribution")]
+        Algorithm::RadixLsd => radix_lsd_sort::RadixLsdSort::INFO,
+        #[cfg(feature = "distribution")]
+        Algorithm::RadixMsd => radix_msd_sort::RadixMsdSort::INFO,
+        #[cfg(feature = "distribution")]
+        Algorithm::AmericanFlag => american_flag_sort::AmericanFlagSort::INFO,
+        #[cfg(feature = "distribution")]
+        Algorithm::RadixExchange => radix_exchange_sort::RadixExchangeSort::INFO,
+        #[cfg(feature = "distribution")]
+        Algorithm::Flash => flash_sort::FlashSort::INFO,
+        #[cfg(feature = "distribution")]
+        Algorithm::Proxmap => proxmap_sort::ProxmapSort::INFO,
+        Algorithm::Spread => spread_sort::SpreadSort::INFO,
+        #[cfg(feature = "esoteric")]
+        Algorithm::Gravity => gravity_sort::GravitySort::INFO,
+        #[cfg(feature = "esoteric")]
+        Algorithm::Sleep => sleep_sort::SleepSort::INFO,
+        #[cfg(feature = "distribution")]
+        Algorithm::Sample => sample_sort::SampleSort::INFO,
+        #[cfg(feature = "networks")]
+        Algorithm::Bitonic => bitonic_sort::BitonicSort::INFO,
+        #[cfg(feature = "networks")]
+        Algorithm::OddEvenMerge => odd_even_merge_sort::OddEvenMergeSort::INFO,
+        #[cfg(feature = "networks")]
+        Algorithm::Pairwise => pairwise_sort::PairwiseSort::INFO,
     }
 }
@@ -0,0 +1,169 @@
+//! Batcher's Odd-Even Merge Sort implementation for V1 (Pregeneration) engine.
+//!
+//! A sorting network built from recursive odd-even merges. Like bitonic sort,
+//! the network is defined for power-of-2 lengths, so arrays are padded to the
+//! next power of 2 with i32::MAX sentinels. Every comparator moves the minimum
+//! to the lower index, so sentinels never leave the padded tail and any
+//! comparator touching the padding is a no-op that can simply be skipped.
+
+use crate::prelude::*;
+use crate::events::{EventSink, SortEvent};
+use super::{estimate, AlgorithmInfo, PregenSort};
+
+pub struct OddEvenMergeSort;
+
+impl PregenSort for OddEvenMergeSort {
+    const INFO: AlgorithmInfo = AlgorithmInfo {
+        display_name: "Odd-Even Merge Sort",
+        best: "O(n log² n)",
+        average: "O(n log² n)",
+        worst: "O(n log² n)",
+        space: "O(1)",
+        stable: false,
+        in_place: true,
+        adaptive: false,
+        description: "Batcher's sorting network built from recursive odd-even merges.",
+    };
+
+    fn estimated_events(n: usize) -> usize {
+        estimate::n_log2_n(n, 0.375)
+    }
+
+    fn sort_into(array: &mut [i32], events: &mut impl EventSink) {
+        let n = array.len();
+
+        if n <= 1 {
+            events.push(SortEvent::Done);
+            return;
+        }
+
+        for round in rounds(n) {
+            for (i, j) in round {
+                events.push(SortEvent::Compare { i, j });
+                if array[i] > array[j] {
+                    events.push(SortEvent::Swap { i, j });
+                    array.swap(i, j);
+                }
+            }
+        }
+
+        events.push(SortEvent::Done);
+    }
+}
+
+/// Comparators of the odd-even merge network for `n` elements, grouped into
+/// rounds. Comparators within a round touch disjoint indices and can run in
+/// parallel. Each pair is (low, high) with the minimum going to `low`.
+pub fn rounds(n: usize) -> Vec<Vec<(usize, usize)>> {
+    let mut rounds = Vec::new();
+    if n <= 1 {
+        return rounds;
+    }
+
+    let padded_len = n.next_power_of_two();
+
+    let mut p = 1;
+    while p < padded_len {
+        let mut k = p;
+        while k >= 1 {
+            let mut round = Vec::new();
+            let mut j = k % p;
+            while j + k < padded_len {
+                for i in 0..k.min(padded_len - j - k) {
+                    let a = i + j;
+                    let b = i + j + k;
+                    // Only merge elements belonging to the same 2p-sized block
+                    if a / (2 * p) == b / (2 * p) && b < n {
+                        round.push((a, b));
+                    }
+                }
+                j += 2 * k;
+            }
+            if !round.is_empty() {
+                rounds.push(round);
+            }
+            k /= 2;
+        }
+        p *= 2;
+    }
+
+    rounds
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_odd_even_merge_sort_basic() {
+        let mut array = vec![5, 3, 8, 4, 2, 7, 1, 6];
+        let events = OddEvenMergeSort::sort(&mut array);
+
+        assert_eq!(array, vec![1, 2, 3, 4, 5, 6, 7, 8]);
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_odd_even_merge_sort_non_power_of_2() {
+        for n in 2..40 {
+            let mut array: Vec<i32> = (0..n).map(|x| (x * 37 + 11) % 23).collect();
+            let mut expected = array.clone();
+            expected.sort();
+
+            let events = OddEvenMergeSort::sort(&mut array);
+            assert_eq!(array, expected, "failed for n = {}", n);
+            // No correction overwrites needed after pruning the padding
+            assert!(!events.iter().any(|e| matches!(e, SortEvent::Overwrite { .. })));
+        }
+    }
+
+    #[test]
+    fn test_odd_even_merge_sort_reverse() {
+        let mut array = vec![8, 7, 6, 5, 4, 3, 2, 1];
+        OddEvenMergeSort::sort(&mut array);
+
+        assert_eq!(array, vec![1, 2, 3, 4, 5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn test_odd_even_merge_sort_empty() {
+        let mut array: Vec<i32> = vec![];
+        let events = OddEvenMergeSort::sort(&mut array);
+
+        assert!(array.is_empty());
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_odd_even_merge_sort_single() {
+        let mut array = vec![42];
+        let events = OddEvenMergeSort::sort(&mut array);
+
+        assert_eq!(array, vec![42]);
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_odd_even_merge_sort_duplicates() {
+        let mut array = vec![5, 3, 5, 1, 3, 5, 1, 3];
+        OddEvenMergeSort::sort(&mut array);
+
+        assert_eq!(array, vec![1, 1, 3, 3, 3, 5, 5, 5]);
+    }
+
+    #[test]
+    fn test_odd_even_merge_rounds_are_disjoint() {
+        for round in rounds(16) {
+            let mut seen = [false; 16];
+            for (a, b) in round {
+                assert!(a < b);
+                assert!(!seen[a] && !seen[b]);
+                seen[a] = true;
+                seen[b] = true;
+            }
+        }
+        // Batcher's network for 16 inputs has 63 comparators in 10 rounds
+        assert_eq!(rounds(16).len(), 10);
+        assert_eq!(rounds(16).iter().map(Vec::len).sum::<usize>(), 63);
+    }
+}
@@ -3,19 +3,34 @@
 //! Compares and swaps adjacent pairs, alternating between odd-even and even-odd pairs.
 //! Originally designed for parallel processors.
 
-use crate::events::SortEvent;
-use super::PregenSort;
+use crate::events::{EventSink, SortEvent};
+use super::{estimate, AlgorithmInfo, PregenSort};
 
 pub struct OddEvenSort;
 
 impl PregenSort for OddEvenSort {
-    fn sort(array: &mut [i32]) -> Vec<SortEvent> {
-        let mut events = Vec::new();
+    const INFO: AlgorithmInfo = AlgorithmInfo {
+        display_name: "Odd-Even Sort",
+        best: "O(n)",
+        average: "O(n²)",
+        worst: "O(n²)",
+        space: "O(1)",
+        stable: true,
+        in_place: true,
+        adaptive: true,
+        description: "Alternates compare-swaps of odd-even and even-odd adjacent pairs.",
+    };
+
+    fn estimated_events(n: usize) -> usize {
+        estimate::quadratic(n, 0.75)
+    }
+
+    fn sort_into(array: &mut [i32], events: &mut impl EventSink) {
         let n = array.len();
 
         if n <= 1 {
             events.push(SortEvent::Done);
-            return events;
+            return;
         }
 
         let mut sorted = false;
@@ -47,7 +62,6 @@ impl PregenSort for OddEvenSort {
         }
 
         events.push(SortEvent::Done);
-        events
     }
 }
 
@@ -0,0 +1,181 @@
+//! Pairwise Sorting Network implementation for V1 (Pregeneration) engine.
+//!
+//! Parberry's pairwise network: first sorts pairs, pairs of pairs and so on
+//! (the same comparators as the first half of a bitonic/odd-even network),
+//! then repairs the result with a cascade of shrinking-distance comparators.
+//! Uses the same number of comparators as Batcher's odd-even merge sort but
+//! with a different wiring. The construction handles any length directly,
+//! generating only comparators whose endpoints are inside the array.
+
+use crate::prelude::*;
+use crate::events::{EventSink, SortEvent};
+use super::{estimate, AlgorithmInfo, PregenSort};
+
+pub struct PairwiseSort;
+
+impl PregenSort for PairwiseSort {
+    const INFO: AlgorithmInfo = AlgorithmInfo {
+        display_name: "Pairwise Sorting Network",
+        best: "O(n log² n)",
+        average: "O(n log² n)",
+        worst: "O(n log² n)",
+        space: "O(1)",
+        stable: false,
+        in_place: true,
+        adaptive: false,
+        description:
+            "Parberry's network: sorts pairs of pairs, then repairs with shrinking comparators.",
+    };
+
+    fn estimated_events(n: usize) -> usize {
+        estimate::n_log2_n(n, 0.36)
+    }
+
+    fn sort_into(array: &mut [i32], events: &mut impl EventSink) {
+        let n = array.len();
+
+        if n <= 1 {
+            events.push(SortEvent::Done);
+            return;
+        }
+
+        for round in rounds(n) {
+            for (i, j) in round {
+                events.push(SortEvent::Compare { i, j });
+                if array[i] > array[j] {
+                    events.push(SortEvent::Swap { i, j });
+                    array.swap(i, j);
+                }
+            }
+        }
+
+        events.push(SortEvent::Done);
+    }
+}
+
+/// Comparators of the pairwise network for `n` elements, grouped into
+/// rounds. Comparators within a round touch disjoint indices and can run in
+/// parallel. Each pair is (low, high) with the minimum going to `low`.
+pub fn rounds(n: usize) -> Vec<Vec<(usize, usize)>> {
+    let mut rounds = Vec::new();
+    if n <= 1 {
+        return rounds;
+    }
+
+    // Phase 1: sort pairs at distance 1, 2, 4, ...
+    let mut a = 1;
+    while a < n {
+        let mut round = Vec::new();
+        let mut b = a;
+        let mut c = 0;
+        while b < n {
+            round.push((b - a, b));
+            b += 1;
+            c = (c + 1) % a;
+            if c == 0 {
+                b += a;
+            }
+        }
+        rounds.push(round);
+        a *= 2;
+    }
+
+    // Phase 2: merge with cascades of comparators at distance d * a
+    a /= 4;
+    let mut e = 1;
+    while a > 0 {
+        let mut d = e;
+        while d > 0 {
+            let mut round = Vec::new();
+            let mut b = (d + 1) * a;
+            let mut c = 0;
+            while b < n {
+                round.push((b - d * a, b));
+                b += 1;
+                c = (c + 1) % a;
+                if c == 0 {
+                    b += a;
+                }
+            }
+            if !round.is_empty() {
+                rounds.push(round);
+            }
+            d /= 2;
+        }
+        a /= 2;
+        e = 2 * e + 1;
+    }
+
+    rounds
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pairwise_sort_basic() {
+        let mut array = vec![5, 3, 8, 4, 2, 7, 1, 6];
+        let events = PairwiseSort::sort(&mut array);
+
+        assert_eq!(array, vec![1, 2, 3, 4, 5, 6, 7, 8]);
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_pairwise_sort_all_zero_one_inputs() {
+        // Zero-one principle: sorting every 0/1 input proves the network sorts
+        for n in 2..=12usize {
+            for bits in 0u32..(1 << n) {
+                let mut array: Vec<i32> = (0..n).map(|i| ((bits >> i) & 1) as i32).collect();
+                PairwiseSort::sort(&mut array);
+                assert!(array.windows(2).all(|w| w[0] <= w[1]), "failed for n = {}", n);
+            }
+        }
+    }
+
+    #[test]
+    fn test_pairwise_sort_non_power_of_2() {
+        for n in 2..40 {
+            let mut array: Vec<i32> = (0..n).map(|x| (x * 37 + 11) % 23).collect();
+            let mut expected = array.clone();
+            expected.sort();
+
+            PairwiseSort::sort(&mut array);
+            assert_eq!(array, expected, "failed for n = {}", n);
+        }
+    }
+
+    #[test]
+    fn test_pairwise_sort_empty() {
+        let mut array: Vec<i32> = vec![];
+        let events = PairwiseSort::sort(&mut array);
+
+        assert!(array.is_empty());
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_pairwise_sort_single() {
+        let mut array = vec![42];
+        let events = PairwiseSort::sort(&mut array);
+
+        assert_eq!(array, vec![42]);
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_pairwise_rounds_are_disjoint() {
+        for round in rounds(16) {
+            let mut seen = [false; 16];
+            for (a, b) in round {
+                assert!(a < b);
+                assert!(!seen[a] && !seen[b]);
+                seen[a] = true;
+                seen[b] = true;
+            }
+        }
+        // Same comparator count as Batcher's odd-even merge sort
+        assert_eq!(rounds(16).iter().map(Vec::len).sum::<usize>(), 63);
+    }
+}
@@ -1,21 +1,38 @@
 //! Pancake Sort implementation for V1 (Pregeneration) engine.
 //!
 //! Sorts by repeatedly flipping (reversing) prefixes of the array.
-//! The only allowed operation is a "flip" which reverses elements from 0 to k.
+//! The only allowed operation is a "flip" which reverses elements from 0 to k,
+//! emitted as one Flip event; `events::expand_flips` turns it into Swaps.
 
-use crate::events::SortEvent;
-use super::PregenSort;
+use crate::events::{EventSink, SortEvent};
+use super::{estimate, AlgorithmInfo, PregenSort};
 
 pub struct PancakeSort;
 
 impl PregenSort for PancakeSort {
-    fn sort(array: &mut [i32]) -> Vec<SortEvent> {
-        let mut events = Vec::new();
+    const INFO: AlgorithmInfo = AlgorithmInfo {
+        display_name: "Pancake Sort",
+        best: "O(n²)",
+        average: "O(n²)",
+        worst: "O(n²)",
+        space: "O(1)",
+        stable: false,
+        in_place: true,
+        adaptive: false,
+        description:
+            "Sorts using only prefix reversals, flipping the maximum to the front and then to its place.",
+    };
+
+    fn estimated_events(n: usize) -> usize {
+        estimate::quadratic(n, 0.875)
+    }
+
+    fn sort_into(array: &mut [i32], events: &mut impl EventSink) {
         let n = array.len();
 
         if n <= 1 {
             events.push(SortEvent::Done);
-            return events;
+            return;
         }
 
         // Start from the full array and reduce the unsorted portion
@@ -36,34 +53,27 @@ impl PregenSort for PancakeSort {
 
             // Flip max to front (if not already there)
             if max_idx > 0 {
-                flip(array, max_idx, &mut events);
+                flip(array, max_idx, events);
             }
 
             // Flip max to its final position
-            flip(array, size - 1, &mut events);
+            flip(array, size - 1, events);
         }
 
         events.push(SortEvent::Done);
-        events
     }
 }
 
 /// Reverse elements from index 0 to k (inclusive).
-fn flip(array: &mut [i32], k: usize, events: &mut Vec<SortEvent>) {
-    let mut left = 0;
-    let mut right = k;
-
-    while left < right {
-        events.push(SortEvent::Swap { i: left, j: right });
-        array.swap(left, right);
-        left += 1;
-        right -= 1;
-    }
+fn flip(array: &mut [i32], k: usize, events: &mut impl EventSink) {
+    events.push(SortEvent::Flip { k });
+    array[..=k].reverse();
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::events::expand_flips;
 
     #[test]
     fn test_pancake_sort_basic() {
@@ -80,9 +90,8 @@ mod tests {
         let events = PancakeSort::sort(&mut array);
 
         assert_eq!(array, vec![1, 2, 3, 4, 5]);
-        // Pancake sort still does comparisons but no swaps when sorted
-        let swap_count = events.iter().filter(|e| matches!(e, SortEvent::Swap { .. })).count();
-        assert_eq!(swap_count, 0);
+        // Pancake sort still does comparisons but no flips when sorted
+        assert!(!events.iter().any(|e| e.is_mutation()));
     }
 
     #[test]
@@ -111,6 +120,23 @@ mod tests {
         assert!(matches!(events.last(), Some(SortEvent::Done)));
     }
 
+    #[test]
+    fn test_pancake_sort_emits_flips() {
+        let input = vec![3, 1, 4, 1, 5, 9, 2, 6];
+        let events = PancakeSort::sort(&mut input.clone());
+
+        let mut mutations = events.iter().filter(|e| e.is_mutation());
+        assert!(mutations.all(|e| matches!(e, SortEvent::Flip { .. })));
+
+        // The expanded trace leaves the array in the same state
+        let mut replayed = input.clone();
+        for event in expand_flips(&events) {
+            assert!(!matches!(event, SortEvent::Flip { .. }));
+            event.apply(&mut replayed);
+        }
+        assert_eq!(replayed, vec![1, 1, 2, 3, 4, 5, 6, 9]);
+    }
+
     #[test]
     fn test_pancake_sort_two_elements() {
         let mut array = vec![2, 1];
@@ -0,0 +1,509 @@
+//! Pattern-defeating Quicksort (pdqsort) implementation for V1 (Pregeneration) engine.
+//!
+//! Quicksort with heuristics that detect and exploit common input patterns:
+//! - Median-of-three pivots, or Tukey's ninther for large ranges.
+//! - If a partition needed no swaps, the range is probably sorted: a bounded
+//!   insertion sort is tried on both sides and the sort stops if it succeeds.
+//! - If the pivot equals its left neighbour (a previous pivot), the range is
+//!   full of duplicates: equal elements are partitioned out in one pass and
+//!   marked with an EqualRange event.
+//! - Highly unbalanced partitions shuffle a few elements to break patterns,
+//!   and after log(n) of them the range falls back to heapsort.
+//!
+//! Emits a Phase event whenever one of these heuristics kicks in.
+
+use crate::prelude::*;
+use crate::events::{EventSink, SortEvent};
+use super::intro_sort::heapsort_range;
+use super::{estimate, AlgorithmInfo, PregenSort};
+
+pub struct PdqSort;
+
+/// Ranges smaller than this are insertion sorted.
+const INSERTION_SORT_THRESHOLD: usize = 24;
+
+/// Ranges larger than this use Tukey's ninther for pivot selection.
+const NINTHER_THRESHOLD: usize = 128;
+
+/// Maximum number of moves allowed in a partial insertion sort.
+const PARTIAL_INSERTION_SORT_LIMIT: usize = 8;
+
+pub const PHASE_PARTIAL_INSERTION: &str = "partial_insertion_sort";
+pub const PHASE_EQUAL_PARTITION: &str = "equal_partition";
+pub const PHASE_SHUFFLE: &str = "bad_partition_shuffle";
+pub const PHASE_HEAPSORT: &str = "heapsort_fallback";
+
+impl PregenSort for PdqSort {
+    const INFO: AlgorithmInfo = AlgorithmInfo {
+        display_name: "Pattern-defeating Quicksort",
+        best: "O(n)",
+        average: "O(n log n)",
+        worst: "O(n log n)",
+        space: "O(log n)",
+        stable: false,
+        in_place: true,
+        adaptive: true,
+        description: "Quicksort with heuristics for sorted runs, duplicates and bad pivots.",
+    };
+
+    fn estimated_events(n: usize) -> usize {
+        estimate::n_log_n(n, 1.9)
+    }
+
+    fn sort_into(array: &mut [i32], events: &mut impl EventSink) {
+        let n = array.len();
+
+        if n <= 1 {
+            events.push(SortEvent::Done);
+            return;
+        }
+
+        // Number of bad partitions allowed before switching to heapsort
+        let bad_allowed = n.ilog2() as usize;
+
+        pdqsort_loop(array, 0, n, bad_allowed, true, events);
+
+        events.push(SortEvent::Done);
+    }
+}
+
+fn phase(name: &str, events: &mut impl EventSink) {
+    events.push(SortEvent::Phase {
+        name: name.to_string(),
+    });
+}
+
+/// Sort array[begin..end].
+fn pdqsort_loop(
+    array: &mut [i32],
+    mut begin: usize,
+    end: usize,
+    mut bad_allowed: usize,
+    mut leftmost: bool,
+    events: &mut impl EventSink,
+) {
+    loop {
+        let size = end - begin;
+
+        if size < INSERTION_SORT_THRESHOLD {
+            insertion_sort_range(array, begin, end, events);
+            return;
+        }
+
+        // Choose pivot as median of 3 or pseudomedian of 9, moved to begin
+        let s2 = size / 2;
+        if size > NINTHER_THRESHOLD {
+            sort3(array, begin, begin + s2, end - 1, events);
+            sort3(array, begin + 1, begin + s2 - 1, end - 2, events);
+            sort3(array, begin + 2, begin + s2 + 1, end - 3, events);
+            sort3(array, begin + s2 - 1, begin + s2, begin + s2 + 1, events);
+            events.push(SortEvent::Swap { i: begin, j: begin + s2 });
+            array.swap(begin, begin + s2);
+        } else {
+            sort3(array, begin + s2, begin, end - 1, events);
+        }
+
+        // A pivot equal to the previous pivot means many duplicates:
+        // put everything equal to it on the left and skip over them
+        if !leftmost {
+            events.push(SortEvent::Compare { i: begin - 1, j: begin });
+            if array[begin - 1] >= array[begin] {
+                phase(PHASE_EQUAL_PARTITION, events);
+                events.push(SortEvent::EnterRange { lo: begin, hi: end - 1 });
+                let pivot_pos = partition_left(array, begin, end, events);
+                // Everything up to the pivot is at least the previous pivot,
+                // and so equal to it
+                if pivot_pos > begin {
+                    events.push(SortEvent::EqualRange { lo: begin, hi: pivot_pos });
+                }
+                events.push(SortEvent::ExitRange { lo: begin, hi: end - 1 });
+                begin = pivot_pos + 1;
+                continue;
+            }
+        }
+
+        events.push(SortEvent::EnterRange { lo: begin, hi: end - 1 });
+        let (pivot_pos, already_partitioned) = partition_right(array, begin, end, events);
+        events.push(SortEvent::ExitRange { lo: begin, hi: end - 1 });
+
+        let l_size = pivot_pos - begin;
+        let r_size = end - (pivot_pos + 1);
+        let highly_unbalanced = l_size < size / 8 || r_size < size / 8;
+
+        if highly_unbalanced {
+            // Too many bad partitions: guarantee O(n log n) with heapsort
+            bad_allowed -= 1;
+            if bad_allowed == 0 {
+                phase(PHASE_HEAPSORT, events);
+                heapsort_range(array, begin, end - 1, events);
+                return;
+            }
+
+            phase(PHASE_SHUFFLE, events);
+            if l_size >= INSERTION_SORT_THRESHOLD {
+                swap(array, begin, begin + l_size / 4, events);
+                swap(array, pivot_pos - 1, pivot_pos - l_size / 4, events);
+
+                if l_size > NINTHER_THRESHOLD {
+                    swap(array, begin + 1, begin + (l_size / 4 + 1), events);
+                    swap(array, begin + 2, begin + (l_size / 4 + 2), events);
+                    swap(array, pivot_pos - 2, pivot_pos - (l_size / 4 + 1), events);
+                    swap(array, pivot_pos - 3, pivot_pos - (l_size / 4 + 2), events);
+                }
+            }
+
+            if r_size >= INSERTION_SORT_THRESHOLD {
+                swap(array, pivot_pos + 1, pivot_pos + (1 + r_size / 4), events);
+                swap(array, end - 1, end - r_size / 4, events);
+
+                if r_size > NINTHER_THRESHOLD {
+                    swap(array, pivot_pos + 2, pivot_pos + (2 + r_size / 4), events);
+                    swap(array, pivot_pos + 3, pivot_pos + (3 + r_size / 4), events);
+                    swap(array, end - 2, end - (1 + r_size / 4), events);
+                    swap(array, end - 3, end - (2 + r_size / 4), events);
+                }
+            }
+        } else if already_partitioned {
+            // No swaps were needed: the range is likely sorted already
+            phase(PHASE_PARTIAL_INSERTION, events);
+            if partial_insertion_sort(array, begin, pivot_pos, events)
+                && partial_insertion_sort(array, pivot_pos + 1, end, events)
+            {
+                return;
+            }
+        }
+
+        // Recurse into the left part, loop on the right part
+        pdqsort_loop(array, begin, pivot_pos, bad_allowed, leftmost, events);
+        begin = pivot_pos + 1;
+        leftmost = false;
+    }
+}
+
+fn swap(array: &mut [i32], i: usize, j: usize, events: &mut impl EventSink) {
+    events.push(SortEvent::Swap { i, j });
+    array.swap(i, j);
+}
+
+/// Order array[a] <= array[b].
+fn sort2(array: &mut [i32], a: usize, b: usize, events: &mut impl EventSink) {
+    events.push(SortEvent::Compare { i: a, j: b });
+    if array[b] < array[a] {
+        swap(array, a, b, events);
+    }
+}
+
+/// Order array[a] <= array[b] <= array[c].
+fn sort3(array: &mut [i32], a: usize, b: usize, c: usize, events: &mut impl EventSink) {
+    sort2(array, a, b, events);
+    sort2(array, b, c, events);
+    sort2(array, a, b, events);
+}
+
+/// Partition array[begin..end] around the pivot at begin, with elements
+/// equal to the pivot on the right. Returns the final pivot position and
+/// whether the range was already partitioned (no swaps needed).
+fn partition_right(
+    array: &mut [i32],
+    begin: usize,
+    end: usize,
+    events: &mut impl EventSink,
+) -> (usize, bool) {
+    let pivot = array[begin];
+    let mut first = begin + 1;
+    let mut last = end;
+
+    // Find the first element >= pivot
+    while first < end {
+        events.push(SortEvent::Compare { i: first, j: begin });
+        if array[first] >= pivot {
+            break;
+        }
+        first += 1;
+    }
+
+    // Find the last element < pivot
+    while last > first {
+        last -= 1;
+        events.push(SortEvent::Compare { i: last, j: begin });
+        if array[last] < pivot {
+            break;
+        }
+    }
+
+    let already_partitioned = first >= last;
+
+    // Swap out-of-place pairs until the scans cross
+    while first < last {
+        swap(array, first, last, events);
+
+        loop {
+            first += 1;
+            events.push(SortEvent::Compare { i: first, j: begin });
+            if array[first] >= pivot {
+                break;
+            }
+        }
+
+        loop {
+            last -= 1;
+            events.push(SortEvent::Compare { i: last, j: begin });
+            if array[last] < pivot {
+                break;
+            }
+        }
+    }
+
+    // Move the pivot into its final place
+    let pivot_pos = first - 1;
+    if pivot_pos != begin {
+        swap(array, begin, pivot_pos, events);
+    }
+
+    (pivot_pos, already_partitioned)
+}
+
+/// Partition array[begin..end] around the pivot at begin, with elements
+/// equal to the pivot on the left. Returns the final pivot position.
+fn partition_left(
+    array: &mut [i32],
+    begin: usize,
+    end: usize,
+    events: &mut impl EventSink,
+) -> usize {
+    let pivot = array[begin];
+    let mut first = begin;
+    let mut last = end;
+
+    // Find the last element <= pivot (the pivot itself stops the scan)
+    loop {
+        last -= 1;
+        events.push(SortEvent::Compare { i: begin, j: last });
+        if pivot >= array[last] {
+            break;
+        }
+    }
+
+    // Find the first element > pivot
+    while first < last {
+        first += 1;
+        if first == last {
+            break;
+        }
+        events.push(SortEvent::Compare { i: begin, j: first });
+        if pivot < array[first] {
+            break;
+        }
+    }
+
+    while first < last {
+        swap(array, first, last, events);
+
+        loop {
+            last -= 1;
+            events.push(SortEvent::Compare { i: begin, j: last });
+            if pivot >= array[last] {
+                break;
+            }
+        }
+
+        loop {
+            first += 1;
+            if first >= last {
+                break;
+            }
+            events.push(SortEvent::Compare { i: begin, j: first });
+            if pivot < array[first] {
+                break;
+            }
+        }
+    }
+
+    // Move the pivot to the end of the equal block
+    let pivot_pos = last;
+    if pivot_pos != begin {
+        swap(array, begin, pivot_pos, events);
+    }
+
+    pivot_pos
+}
+
+/// Insertion sort for array[begin..end].
+fn insertion_sort_range(array: &mut [i32], begin: usize, end: usize, events: &mut impl EventSink) {
+    partial_insertion_sort_limited(array, begin, end, usize::MAX, events);
+}
+
+/// Insertion sort that gives up after PARTIAL_INSERTION_SORT_LIMIT moves.
+/// Returns true if the range was fully sorted.
+fn partial_insertion_sort(
+    array: &mut [i32],
+    begin: usize,
+    end: usize,
+    events: &mut impl EventSink,
+) -> bool {
+    partial_insertion_sort_limited(array, begin, end, PARTIAL_INSERTION_SORT_LIMIT, events)
+}
+
+fn partial_insertion_sort_limited(
+    array: &mut [i32],
+    begin: usize,
+    end: usize,
+    limit: usize,
+    events: &mut impl EventSink,
+) -> bool {
+    let mut moves = 0;
+
+    for i in (begin + 1)..end {
+        let value = array[i];
+        let mut j = i;
+
+        while j > begin {
+            events.push(SortEvent::Compare { i: j - 1, j });
+
+            if array[j - 1] > value {
+                events.push(SortEvent::Overwrite {
+                    idx: j,
+                    old_val: array[j],
+                    new_val: array[j - 1],
+                });
+                array[j] = array[j - 1];
+                j -= 1;
+            } else {
+                break;
+            }
+        }
+
+        if j != i {
+            events.push(SortEvent::Overwrite {
+                idx: j,
+                old_val: array[j],
+                new_val: value,
+            });
+            array[j] = value;
+            moves += i - j;
+        }
+
+        if moves > limit {
+            return false;
+        }
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn has_phase(events: &[SortEvent], name: &str) -> bool {
+        events
+            .iter()
+            .any(|e| matches!(e, SortEvent::Phase { name: n } if n == name))
+    }
+
+    fn compare_count(events: &[SortEvent]) -> usize {
+        events.iter().filter(|e| matches!(e, SortEvent::Compare { .. })).count()
+    }
+
+    #[test]
+    fn test_pdq_sort_basic() {
+        let mut array = vec![5, 3, 8, 4, 2];
+        let events = PdqSort::sort(&mut array);
+
+        assert_eq!(array, vec![2, 3, 4, 5, 8]);
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_pdq_sort_empty() {
+        let mut array: Vec<i32> = vec![];
+        let events = PdqSort::sort(&mut array);
+
+        assert!(array.is_empty());
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_pdq_sort_single() {
+        let mut array = vec![42];
+        let events = PdqSort::sort(&mut array);
+
+        assert_eq!(array, vec![42]);
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_pdq_sort_pseudorandom() {
+        let input: Vec<i32> = (0..1000).map(|x| (x * 7919 + 13) % 1009).collect();
+        let mut array = input.clone();
+        PdqSort::sort(&mut array);
+
+        let mut expected = input;
+        expected.sort();
+        assert_eq!(array, expected);
+    }
+
+    #[test]
+    fn test_pdq_sort_sorted_is_linear() {
+        let mut array: Vec<i32> = (0..1000).collect();
+        let events = PdqSort::sort(&mut array);
+
+        let expected: Vec<i32> = (0..1000).collect();
+        assert_eq!(array, expected);
+        assert!(has_phase(&events, PHASE_PARTIAL_INSERTION));
+        assert!(compare_count(&events) < 3 * 1000);
+    }
+
+    #[test]
+    fn test_pdq_sort_duplicates_use_equal_partition() {
+        let input: Vec<i32> = (0..500).map(|x| (x * 31) % 4).collect();
+        let mut array = input.clone();
+        let events = PdqSort::sort(&mut array);
+
+        let mut expected = input;
+        expected.sort();
+        assert_eq!(array, expected);
+        assert!(has_phase(&events, PHASE_EQUAL_PARTITION));
+    }
+
+    #[test]
+    fn test_pdq_sort_equal_partition_marks_equal_range() {
+        let input: Vec<i32> = (0..500).map(|x| (x * 31) % 4).collect();
+        let events = PdqSort::sort(&mut input.clone());
+
+        let mut replayed = input.clone();
+        let mut ranges = 0;
+        for event in &events {
+            event.apply(&mut replayed);
+            if let SortEvent::EqualRange { lo, hi } = *event {
+                assert!(replayed[lo..=hi].iter().all(|&v| v == replayed[lo]), "{}..={}", lo, hi);
+                ranges += 1;
+            }
+        }
+        assert!(ranges > 0);
+    }
+
+    #[test]
+    fn test_pdq_sort_organ_pipe_and_reverse() {
+        let mut organ: Vec<i32> = (0..300).chain((0..300).rev()).collect();
+        PdqSort::sort(&mut organ);
+        assert!(organ.windows(2).all(|w| w[0] <= w[1]));
+
+        let mut reverse: Vec<i32> = (0..700).rev().collect();
+        PdqSort::sort(&mut reverse);
+        let expected: Vec<i32> = (0..700).collect();
+        assert_eq!(reverse, expected);
+    }
+
+    #[test]
+    fn test_pdq_sort_range_events_balanced() {
+        let mut array: Vec<i32> = (0..400).map(|x| (x * 37) % 401).collect();
+        let events = PdqSort::sort(&mut array);
+
+        let enter_count = events.iter().filter(|e| matches!(e, SortEvent::EnterRange { .. })).count();
+        let exit_count = events.iter().filter(|e| matches!(e, SortEvent::ExitRange { .. })).count();
+
+        assert!(enter_count > 0);
+        assert_eq!(enter_count, exit_count);
+    }
+}
@@ -0,0 +1,170 @@
+//! Proxmap Sort implementation for V1 (Pregeneration) engine.
+//!
+//! Distribution sort that predicts where each element will land:
+//! 1. A mapping key splits the value range into n subarrays and a
+//!    histogram ("hit counts") records how many elements map to each.
+//! 2. Prefix sums of the hit counts give the proximity map: the start
+//!    position of every subarray in the output.
+//! 3. Elements are inserted in input order into their subarray, using
+//!    insertion sort to keep each subarray ordered.
+//!
+//! The input is copied to a temporary buffer first, so the main array acts
+//! as the output and fills up subarray by subarray.
+
+use crate::prelude::*;
+use crate::events::{EventSink, SortEvent};
+use super::{estimate, AlgorithmInfo, PregenSort};
+
+pub struct ProxmapSort;
+
+impl PregenSort for ProxmapSort {
+    const INFO: AlgorithmInfo = AlgorithmInfo {
+        display_name: "Proxmap Sort",
+        best: "O(n)",
+        average: "O(n)",
+        worst: "O(n²)",
+        space: "O(n)",
+        stable: true,
+        in_place: false,
+        adaptive: false,
+        description:
+            "Predicts each element's position from its value and insertion-sorts within subarrays.",
+    };
+
+    fn estimated_events(n: usize) -> usize {
+        estimate::linear(n, 2.0)
+    }
+
+    fn sort_into(array: &mut [i32], events: &mut impl EventSink) {
+        let n = array.len();
+
+        if n <= 1 {
+            events.push(SortEvent::Done);
+            return;
+        }
+
+        let min_val = *array.iter().min().unwrap() as i64;
+        let max_val = *array.iter().max().unwrap() as i64;
+        let range = (max_val - min_val + 1) as f64;
+        let map_key = |value: i32| (((value as i64 - min_val) as f64 / range) * n as f64) as usize;
+
+        // Phase 1: hit counts per mapping key
+        let mut hits = vec![0usize; n];
+        for &value in array.iter() {
+            hits[map_key(value)] += 1;
+        }
+
+        // Phase 2: proximity map (start of each subarray)
+        let mut prox = vec![0usize; n];
+        for k in 1..n {
+            prox[k] = prox[k - 1] + hits[k - 1];
+        }
+
+        // Phase 3: insert each element into its subarray
+        let input = array.to_vec();
+        let mut filled = vec![0usize; n];
+
+        for &value in input.iter() {
+            let k = map_key(value);
+            let start = prox[k];
+            let mut j = start + filled[k];
+            filled[k] += 1;
+
+            // Shift larger elements of the subarray right to open a slot
+            while j > start {
+                events.push(SortEvent::Compare { i: j - 1, j });
+
+                if array[j - 1] > value {
+                    events.push(SortEvent::Overwrite {
+                        idx: j,
+                        old_val: array[j],
+                        new_val: array[j - 1],
+                    });
+                    array[j] = array[j - 1];
+                    j -= 1;
+                } else {
+                    break;
+                }
+            }
+
+            if array[j] != value {
+                events.push(SortEvent::Overwrite {
+                    idx: j,
+                    old_val: array[j],
+                    new_val: value,
+                });
+                array[j] = value;
+            }
+        }
+
+        events.push(SortEvent::Done);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_proxmap_sort_basic() {
+        let mut array = vec![5, 3, 8, 4, 2];
+        let events = ProxmapSort::sort(&mut array);
+
+        assert_eq!(array, vec![2, 3, 4, 5, 8]);
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_proxmap_sort_already_sorted() {
+        let mut array: Vec<i32> = (0..50).collect();
+        let events = ProxmapSort::sort(&mut array);
+
+        let expected: Vec<i32> = (0..50).collect();
+        assert_eq!(array, expected);
+        // Uniform keys map one element per subarray: nothing moves
+        assert!(!events.iter().any(|e| e.is_mutation()));
+    }
+
+    #[test]
+    fn test_proxmap_sort_reverse() {
+        let mut array: Vec<i32> = (0..100).rev().collect();
+        ProxmapSort::sort(&mut array);
+
+        let expected: Vec<i32> = (0..100).collect();
+        assert_eq!(array, expected);
+    }
+
+    #[test]
+    fn test_proxmap_sort_skewed_and_negative() {
+        let mut array = vec![1000, -3, 2, 1, 0, -1, i32::MIN, i32::MAX, 2];
+        ProxmapSort::sort(&mut array);
+
+        assert_eq!(array, vec![i32::MIN, -3, -1, 0, 1, 2, 2, 1000, i32::MAX]);
+    }
+
+    #[test]
+    fn test_proxmap_sort_empty() {
+        let mut array: Vec<i32> = vec![];
+        let events = ProxmapSort::sort(&mut array);
+
+        assert!(array.is_empty());
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_proxmap_sort_single() {
+        let mut array = vec![42];
+        let events = ProxmapSort::sort(&mut array);
+
+        assert_eq!(array, vec![42]);
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_proxmap_sort_duplicates() {
+        let mut array = vec![3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5];
+        ProxmapSort::sort(&mut array);
+
+        assert_eq!(array, vec![1, 1, 2, 3, 3, 4, 5, 5, 5, 6, 9]);
+    }
+}
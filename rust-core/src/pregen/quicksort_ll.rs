@@ -4,26 +4,40 @@
 //! Single pointer moves left-to-right, swapping elements smaller than pivot.
 //! Emits EnterRange/ExitRange events to visualize recursive subarrays.
 
-use crate::events::SortEvent;
-use super::PregenSort;
+use crate::events::{EventSink, SortEvent};
+use super::{estimate, AlgorithmInfo, PregenSort};
 
 pub struct QuickSortLL;
 
 impl PregenSort for QuickSortLL {
-    fn sort(array: &mut [i32]) -> Vec<SortEvent> {
-        let mut events = Vec::new();
+    const INFO: AlgorithmInfo = AlgorithmInfo {
+        display_name: "QuickSort (LL pointers)",
+        best: "O(n log n)",
+        average: "O(n log n)",
+        worst: "O(n²)",
+        space: "O(log n)",
+        stable: false,
+        in_place: true,
+        adaptive: false,
+        description: "Quicksort with Lomuto partitioning around the rightmost element.",
+    };
+
+    fn estimated_events(n: usize) -> usize {
+        estimate::n_log_n(n, 1.9)
+    }
+
+    fn sort_into(array: &mut [i32], events: &mut impl EventSink) {
         let n = array.len();
 
         if n > 1 {
-            quicksort_recursive(array, 0, n - 1, &mut events);
+            quicksort_recursive(array, 0, n - 1, events);
         }
 
         events.push(SortEvent::Done);
-        events
     }
 }
 
-fn quicksort_recursive(array: &mut [i32], lo: usize, hi: usize, events: &mut Vec<SortEvent>) {
+fn quicksort_recursive(array: &mut [i32], lo: usize, hi: usize, events: &mut impl EventSink) {
     if lo >= hi {
         return;
     }
@@ -49,7 +63,12 @@ fn quicksort_recursive(array: &mut [i32], lo: usize, hi: usize, events: &mut Vec
 
 /// Lomuto partition scheme with rightmost pivot.
 /// Returns the final position of the pivot.
-fn partition(array: &mut [i32], lo: usize, hi: usize, events: &mut Vec<SortEvent>) -> usize {
+pub(super) fn partition(
+    array: &mut [i32],
+    lo: usize,
+    hi: usize,
+    events: &mut impl EventSink,
+) -> usize {
     let pivot = array[hi];
     let mut i = lo;
 
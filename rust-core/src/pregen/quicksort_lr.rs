@@ -4,26 +4,40 @@
 //! Two pointers move toward each other from both ends.
 //! Emits EnterRange/ExitRange events to visualize recursive subarrays.
 
-use crate::events::SortEvent;
-use super::PregenSort;
+use crate::events::{EventSink, SortEvent};
+use super::{estimate, AlgorithmInfo, PregenSort};
 
 pub struct QuickSortLR;
 
 impl PregenSort for QuickSortLR {
-    fn sort(array: &mut [i32]) -> Vec<SortEvent> {
-        let mut events = Vec::new();
+    const INFO: AlgorithmInfo = AlgorithmInfo {
+        display_name: "QuickSort (LR pointers)",
+        best: "O(n log n)",
+        average: "O(n log n)",
+        worst: "O(n²)",
+        space: "O(log n)",
+        stable: false,
+        in_place: true,
+        adaptive: false,
+        description: "Quicksort with Hoare partitioning: two pointers move towards each other.",
+    };
+
+    fn estimated_events(n: usize) -> usize {
+        estimate::n_log_n(n, 1.7)
+    }
+
+    fn sort_into(array: &mut [i32], events: &mut impl EventSink) {
         let n = array.len();
 
         if n > 1 {
-            quicksort_recursive(array, 0, n - 1, &mut events);
+            quicksort_recursive(array, 0, n - 1, events);
         }
 
         events.push(SortEvent::Done);
-        events
     }
 }
 
-fn quicksort_recursive(array: &mut [i32], lo: usize, hi: usize, events: &mut Vec<SortEvent>) {
+fn quicksort_recursive(array: &mut [i32], lo: usize, hi: usize, events: &mut impl EventSink) {
     if lo >= hi {
         return;
     }
@@ -50,7 +64,12 @@ fn quicksort_recursive(array: &mut [i32], lo: usize, hi: usize, events: &mut Vec
 /// Hoare partition scheme with leftmost pivot.
 /// Two pointers move toward each other from both ends.
 /// Returns the partition index.
-fn partition(array: &mut [i32], lo: usize, hi: usize, events: &mut Vec<SortEvent>) -> usize {
+pub(super) fn partition(
+    array: &mut [i32],
+    lo: usize,
+    hi: usize,
+    events: &mut impl EventSink,
+) -> usize {
     let pivot = array[lo];
     let mut left = lo;
     let mut right = hi;
@@ -82,9 +101,7 @@ fn partition(array: &mut [i32], lo: usize, hi: usize, events: &mut Vec<SortEvent
 
         // Move pointers inward to continue
         left += 1;
-        if right > 0 {
-            right -= 1;
-        }
+        right = right.saturating_sub(1);
     }
 }
 
@@ -166,4 +183,15 @@ mod tests {
         assert_eq!(array, vec![1, 2]);
         assert!(matches!(events.last(), Some(SortEvent::Done)));
     }
+
+    #[test]
+    fn test_quicksort_lr_registered() {
+        use crate::pregen::Algorithm;
+
+        assert_eq!(Algorithm::from_str("hoare"), Some(Algorithm::QuickSortLR));
+        assert_eq!(Algorithm::from_str("lomuto"), Some(Algorithm::QuickSortLL));
+        assert_eq!(Algorithm::from_str("quicksort"), Some(Algorithm::QuickSortLL));
+        assert_eq!(Algorithm::from_str(Algorithm::QuickSortLR.as_str()), Some(Algorithm::QuickSortLR));
+        assert!(Algorithm::all().contains(&Algorithm::QuickSortLR));
+    }
 }
@@ -0,0 +1,226 @@
+//! Stable QuickSort implementation for V1 (Pregeneration) engine.
+//!
+//! Partitions out of place: one left-to-right scan compares every element
+//! with the pivot and appends it to a "less", "equal" or "greater" buffer,
+//! then the buffers are written back in that order. Appending preserves the
+//! relative order of equal elements, which in-place partition schemes don't.
+//! Only the less and greater parts are recursed on; an equal block of more
+//! than one element is marked with an EqualRange event.
+//!
+//! `stable_order` exposes the resulting permutation of input positions.
+
+use crate::prelude::*;
+use crate::events::{EventSink, SortEvent};
+use super::{estimate, AlgorithmInfo, PregenSort};
+
+pub struct QuickSortStable;
+
+impl PregenSort for QuickSortStable {
+    const INFO: AlgorithmInfo = AlgorithmInfo {
+        display_name: "Stable QuickSort",
+        best: "O(n log n)",
+        average: "O(n log n)",
+        worst: "O(n²)",
+        space: "O(n)",
+        stable: true,
+        in_place: false,
+        adaptive: false,
+        description: "Quicksort that partitions out of place into less, equal and greater buffers.",
+    };
+
+    fn estimated_events(n: usize) -> usize {
+        estimate::n_log_n(n, 2.35)
+    }
+
+    fn sort_into(array: &mut [i32], events: &mut impl EventSink) {
+        stable_quicksort(array, events);
+    }
+}
+
+/// Input positions in the order the sort places them.
+/// Equal values always appear in increasing position order.
+pub fn stable_order(array: &[i32]) -> Vec<usize> {
+    let mut copy = array.to_vec();
+    let mut events = Vec::new();
+    stable_quicksort(&mut copy, &mut events)
+}
+
+/// Sort the array, returning the original position of every output element.
+fn stable_quicksort(array: &mut [i32], events: &mut impl EventSink) -> Vec<usize> {
+    let n = array.len();
+    let mut ids: Vec<usize> = (0..n).collect();
+
+    if n > 1 {
+        quicksort_recursive(array, &mut ids, 0, n - 1, events);
+    }
+
+    events.push(SortEvent::Done);
+    ids
+}
+
+fn quicksort_recursive(
+    array: &mut [i32],
+    ids: &mut [usize],
+    lo: usize,
+    hi: usize,
+    events: &mut impl EventSink,
+) {
+    if lo >= hi {
+        return;
+    }
+
+    events.push(SortEvent::EnterRange { lo, hi });
+
+    let (less_end, greater_start) = partition(array, ids, lo, hi, events);
+    if greater_start - less_end > 1 {
+        events.push(SortEvent::EqualRange { lo: less_end, hi: greater_start - 1 });
+    }
+
+    events.push(SortEvent::ExitRange { lo, hi });
+
+    if less_end > lo {
+        quicksort_recursive(array, ids, lo, less_end - 1, events);
+    }
+    if greater_start < hi {
+        quicksort_recursive(array, ids, greater_start, hi, events);
+    }
+}
+
+/// Stable three-way partition of [lo..=hi] around the middle element.
+/// Returns (start of equal block, start of greater block).
+fn partition(
+    array: &mut [i32],
+    ids: &mut [usize],
+    lo: usize,
+    hi: usize,
+    events: &mut impl EventSink,
+) -> (usize, usize) {
+    let pivot_idx = lo + (hi - lo) / 2;
+    let pivot = array[pivot_idx];
+
+    let mut less = Vec::new();
+    let mut equal = Vec::new();
+    let mut greater = Vec::new();
+
+    for i in lo..=hi {
+        if i != pivot_idx {
+            events.push(SortEvent::Compare { i, j: pivot_idx });
+        }
+        let entry = (array[i], ids[i]);
+        if array[i] < pivot {
+            less.push(entry);
+        } else if array[i] > pivot {
+            greater.push(entry);
+        } else {
+            equal.push(entry);
+        }
+    }
+
+    let equal_start = lo + less.len();
+    let greater_start = equal_start + equal.len();
+
+    // Write the buffers back in order
+    let merged = less.into_iter().chain(equal).chain(greater);
+    for (offset, (value, id)) in merged.enumerate() {
+        let idx = lo + offset;
+        if array[idx] != value {
+            events.push(SortEvent::Overwrite {
+                idx,
+                old_val: array[idx],
+                new_val: value,
+            });
+            array[idx] = value;
+        }
+        ids[idx] = id;
+    }
+
+    (equal_start, greater_start)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quicksort_stable_basic() {
+        let mut array = vec![5, 3, 8, 4, 2];
+        let events = QuickSortStable::sort(&mut array);
+
+        assert_eq!(array, vec![2, 3, 4, 5, 8]);
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_quicksort_stable_already_sorted() {
+        let mut array = vec![1, 2, 3, 4, 5];
+        let events = QuickSortStable::sort(&mut array);
+
+        assert_eq!(array, vec![1, 2, 3, 4, 5]);
+        assert!(!events.iter().any(|e| e.is_mutation()));
+    }
+
+    #[test]
+    fn test_quicksort_stable_reverse() {
+        let mut array: Vec<i32> = (0..100).rev().collect();
+        QuickSortStable::sort(&mut array);
+
+        let expected: Vec<i32> = (0..100).collect();
+        assert_eq!(array, expected);
+    }
+
+    #[test]
+    fn test_quicksort_stable_empty() {
+        let mut array: Vec<i32> = vec![];
+        let events = QuickSortStable::sort(&mut array);
+
+        assert!(array.is_empty());
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_quicksort_stable_single() {
+        let mut array = vec![42];
+        let events = QuickSortStable::sort(&mut array);
+
+        assert_eq!(array, vec![42]);
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_quicksort_stable_duplicates() {
+        let mut array = vec![3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5];
+        QuickSortStable::sort(&mut array);
+
+        assert_eq!(array, vec![1, 1, 2, 3, 3, 4, 5, 5, 5, 6, 9]);
+    }
+
+    #[test]
+    fn test_quicksort_stable_marks_equal_blocks() {
+        let input = vec![3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5];
+        let events = QuickSortStable::sort(&mut input.clone());
+
+        let mut replayed = input.clone();
+        let mut ranges = 0;
+        for event in &events {
+            event.apply(&mut replayed);
+            if let SortEvent::EqualRange { lo, hi } = *event {
+                assert!(replayed[lo..=hi].iter().all(|&v| v == replayed[lo]), "{}..={}", lo, hi);
+                ranges += 1;
+            }
+        }
+        assert!(ranges > 0);
+    }
+
+    #[test]
+    fn test_quicksort_stable_preserves_equal_order() {
+        let array: Vec<i32> = (0..60).map(|x| (x * 17) % 7).collect();
+        let order = stable_order(&array);
+
+        for w in order.windows(2) {
+            assert!(array[w[0]] <= array[w[1]]);
+            if array[w[0]] == array[w[1]] {
+                assert!(w[0] < w[1]);
+            }
+        }
+    }
+}
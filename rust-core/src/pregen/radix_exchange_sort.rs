@@ -0,0 +1,181 @@
+//! Radix Exchange Sort (binary MSD radix) implementation for V1 (Pregeneration) engine.
+//!
+//! Partitions the range by the most significant bit, quicksort style: a left
+//! pointer looks for elements with the bit set, a right pointer for elements
+//! with the bit clear, and the two are swapped. Each half is then partitioned
+//! by the next bit down. Bit inspections are emitted as self-Compare events.
+//!
+//! Keys are flipped on the sign bit, so negative numbers sort correctly.
+
+use crate::events::{EventSink, SortEvent};
+use super::{estimate, AlgorithmInfo, PregenSort};
+
+pub struct RadixExchangeSort;
+
+impl PregenSort for RadixExchangeSort {
+    const INFO: AlgorithmInfo = AlgorithmInfo {
+        display_name: "Radix Exchange Sort",
+        best: "O(n·k)",
+        average: "O(n·k)",
+        worst: "O(n·k)",
+        space: "O(k)",
+        stable: false,
+        in_place: true,
+        adaptive: false,
+        description: "Binary MSD radix sort that partitions on each bit, quicksort style.",
+    };
+
+    fn estimated_events(n: usize) -> usize {
+        estimate::n_log_n(n, 1.7)
+    }
+
+    fn sort_into(array: &mut [i32], events: &mut impl EventSink) {
+        let n = array.len();
+
+        if n <= 1 {
+            events.push(SortEvent::Done);
+            return;
+        }
+
+        // Bits above the highest one where min and max differ are shared by all keys
+        let min_key = key(*array.iter().min().unwrap());
+        let max_key = key(*array.iter().max().unwrap());
+        let diff = min_key ^ max_key;
+
+        if diff != 0 {
+            let top_bit = 31 - diff.leading_zeros();
+            radix_exchange(array, 0, n, top_bit, events);
+        }
+
+        events.push(SortEvent::Done);
+    }
+}
+
+/// Order-preserving unsigned key for a signed value.
+fn key(value: i32) -> u32 {
+    (value as u32) ^ 0x8000_0000
+}
+
+fn bit_set(value: i32, bit: u32) -> bool {
+    (key(value) >> bit) & 1 == 1
+}
+
+/// Partition array[lo..hi] on `bit`, then recurse on both halves with the next bit.
+fn radix_exchange(array: &mut [i32], lo: usize, hi: usize, bit: u32, events: &mut impl EventSink) {
+    if hi <= lo + 1 {
+        return;
+    }
+
+    events.push(SortEvent::EnterRange { lo, hi: hi - 1 });
+
+    // Invariant: [lo, left) has the bit clear, [right, hi) has it set
+    let mut left = lo;
+    let mut right = hi;
+    loop {
+        while left < right {
+            events.push(SortEvent::Compare { i: left, j: left });
+            if bit_set(array[left], bit) {
+                break;
+            }
+            left += 1;
+        }
+        while left < right {
+            events.push(SortEvent::Compare { i: right - 1, j: right - 1 });
+            if !bit_set(array[right - 1], bit) {
+                break;
+            }
+            right -= 1;
+        }
+        if left >= right {
+            break;
+        }
+
+        events.push(SortEvent::Swap { i: left, j: right - 1 });
+        array.swap(left, right - 1);
+        left += 1;
+        right -= 1;
+    }
+
+    events.push(SortEvent::ExitRange { lo, hi: hi - 1 });
+
+    if bit > 0 {
+        radix_exchange(array, lo, left, bit - 1, events);
+        radix_exchange(array, left, hi, bit - 1, events);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_radix_exchange_basic() {
+        let mut array = vec![5, 3, 8, 4, 2];
+        let events = RadixExchangeSort::sort(&mut array);
+
+        assert_eq!(array, vec![2, 3, 4, 5, 8]);
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_radix_exchange_already_sorted() {
+        let mut array = vec![1, 2, 3, 4, 5];
+        let events = RadixExchangeSort::sort(&mut array);
+
+        assert_eq!(array, vec![1, 2, 3, 4, 5]);
+        assert!(!events.iter().any(|e| e.is_mutation()));
+    }
+
+    #[test]
+    fn test_radix_exchange_reverse() {
+        let mut array: Vec<i32> = (0..100).rev().collect();
+        RadixExchangeSort::sort(&mut array);
+
+        let expected: Vec<i32> = (0..100).collect();
+        assert_eq!(array, expected);
+    }
+
+    #[test]
+    fn test_radix_exchange_negative_and_extremes() {
+        let mut array = vec![3, -1, i32::MAX, 0, i32::MIN, -7, 3];
+        RadixExchangeSort::sort(&mut array);
+
+        assert_eq!(array, vec![i32::MIN, -7, -1, 0, 3, 3, i32::MAX]);
+    }
+
+    #[test]
+    fn test_radix_exchange_empty() {
+        let mut array: Vec<i32> = vec![];
+        let events = RadixExchangeSort::sort(&mut array);
+
+        assert!(array.is_empty());
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_radix_exchange_single() {
+        let mut array = vec![42];
+        let events = RadixExchangeSort::sort(&mut array);
+
+        assert_eq!(array, vec![42]);
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_radix_exchange_all_equal() {
+        let mut array = vec![9, 9, 9];
+        let events = RadixExchangeSort::sort(&mut array);
+
+        assert_eq!(array, vec![9, 9, 9]);
+        assert_eq!(events, vec![SortEvent::Done]);
+    }
+
+    #[test]
+    fn test_radix_exchange_uses_only_swaps() {
+        let mut array = vec![6, 1, 7, 0, 5, 2, 4, 3];
+        let events = RadixExchangeSort::sort(&mut array);
+
+        assert_eq!(array, vec![0, 1, 2, 3, 4, 5, 6, 7]);
+        assert!(!events.iter().any(|e| matches!(e, SortEvent::Overwrite { .. })));
+    }
+}
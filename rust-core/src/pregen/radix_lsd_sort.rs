@@ -4,21 +4,43 @@
 //! Uses counting sort as a stable subroutine for each digit.
 //! Only works with non-negative integers.
 
-use crate::events::SortEvent;
-use super::PregenSort;
+use crate::prelude::*;
+use crate::events::{EventSink, SortEvent};
+use super::context::{zeroed, Scratch};
+use super::{estimate, AlgorithmInfo, PregenSort};
 
 pub struct RadixLsdSort;
 
 const RADIX: i32 = 10;
 
 impl PregenSort for RadixLsdSort {
-    fn sort(array: &mut [i32]) -> Vec<SortEvent> {
-        let mut events = Vec::new();
+    const INFO: AlgorithmInfo = AlgorithmInfo {
+        display_name: "Radix Sort (LSD)",
+        best: "O(n·k)",
+        average: "O(n·k)",
+        worst: "O(n·k)",
+        space: "O(n + b)",
+        stable: true,
+        in_place: false,
+        adaptive: false,
+        description:
+            "Counting sort on each digit from least significant to most; k digits in base b.",
+    };
+
+    fn estimated_events(n: usize) -> usize {
+        estimate::linear(n, 8.0)
+    }
+
+    fn sort_into(array: &mut [i32], events: &mut impl EventSink) {
+        Self::sort_into_with(array, events, &mut Scratch::new());
+    }
+
+    fn sort_into_with(array: &mut [i32], events: &mut impl EventSink, scratch: &mut Scratch) {
         let n = array.len();
 
         if n <= 1 {
             events.push(SortEvent::Done);
-            return events;
+            return;
         }
 
         // Find maximum value to determine number of digits
@@ -26,25 +48,30 @@ impl PregenSort for RadixLsdSort {
         if max_val < 0 {
             // Radix sort LSD only works with non-negative integers
             events.push(SortEvent::Done);
-            return events;
+            return;
         }
 
         // Process each digit position
+        let output = zeroed(&mut scratch.values, n);
         let mut exp = 1;
         while max_val / exp > 0 {
-            counting_sort_by_digit(array, exp, &mut events);
+            counting_sort_by_digit(array, exp, output, events);
             exp *= RADIX;
         }
 
         events.push(SortEvent::Done);
-        events
     }
 }
 
-/// Counting sort based on digit at position exp (1, 10, 100, ...)
-fn counting_sort_by_digit(array: &mut [i32], exp: i32, events: &mut Vec<SortEvent>) {
+/// Counting sort based on digit at position exp (1, 10, 100, ...), through
+/// `output`, which is as long as the array.
+fn counting_sort_by_digit(
+    array: &mut [i32],
+    exp: i32,
+    output: &mut [i32],
+    events: &mut impl EventSink,
+) {
     let n = array.len();
-    let mut output = vec![0; n];
     let mut count = vec![0usize; RADIX as usize];
 
     // Count occurrences of each digit
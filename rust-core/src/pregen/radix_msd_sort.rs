@@ -3,21 +3,41 @@
 //! Processes digits from most significant to least significant.
 //! Recursively sorts each bucket. Only works with non-negative integers.
 
-use crate::events::SortEvent;
-use super::PregenSort;
+use crate::events::{EventSink, SortEvent};
+use super::context::{zeroed, Scratch};
+use super::{estimate, AlgorithmInfo, PregenSort};
 
 pub struct RadixMsdSort;
 
 const RADIX: usize = 10;
 
 impl PregenSort for RadixMsdSort {
-    fn sort(array: &mut [i32]) -> Vec<SortEvent> {
-        let mut events = Vec::new();
+    const INFO: AlgorithmInfo = AlgorithmInfo {
+        display_name: "Radix Sort (MSD)",
+        best: "O(n·k)",
+        average: "O(n·k)",
+        worst: "O(n·k)",
+        space: "O(n + b)",
+        stable: true,
+        in_place: false,
+        adaptive: false,
+        description: "Buckets by the most significant digit, then sorts each bucket recursively.",
+    };
+
+    fn estimated_events(n: usize) -> usize {
+        estimate::linear(n, 8.0)
+    }
+
+    fn sort_into(array: &mut [i32], events: &mut impl EventSink) {
+        Self::sort_into_with(array, events, &mut Scratch::new());
+    }
+
+    fn sort_into_with(array: &mut [i32], events: &mut impl EventSink, scratch: &mut Scratch) {
         let n = array.len();
 
         if n <= 1 {
             events.push(SortEvent::Done);
-            return events;
+            return;
         }
 
         // Find maximum value to determine number of digits
@@ -25,7 +45,7 @@ impl PregenSort for RadixMsdSort {
         if max_val < 0 {
             // Radix sort MSD only works with non-negative integers
             events.push(SortEvent::Done);
-            return events;
+            return;
         }
 
         // Calculate the highest digit position
@@ -35,15 +55,22 @@ impl PregenSort for RadixMsdSort {
         }
 
         // Start recursive MSD sort
-        msd_sort(array, 0, n, max_exp, &mut events);
+        msd_sort(array, 0, n, max_exp, scratch, events);
 
         events.push(SortEvent::Done);
-        events
     }
 }
 
 /// Recursively sort array[lo..hi] by digit at position exp
-fn msd_sort(array: &mut [i32], lo: usize, hi: usize, exp: i32, events: &mut Vec<SortEvent>) {
+#[allow(clippy::needless_range_loop)]
+fn msd_sort(
+    array: &mut [i32],
+    lo: usize,
+    hi: usize,
+    exp: i32,
+    scratch: &mut Scratch,
+    events: &mut impl EventSink,
+) {
     if hi <= lo + 1 || exp == 0 {
         return;
     }
@@ -52,7 +79,7 @@ fn msd_sort(array: &mut [i32], lo: usize, hi: usize, exp: i32, events: &mut Vec<
     events.push(SortEvent::EnterRange { lo, hi: hi - 1 });
 
     // Count occurrences of each digit
-    let mut count = vec![0usize; RADIX + 1];
+    let mut count = [0usize; RADIX + 1];
     for i in lo..hi {
         let digit = ((array[i] / exp) % RADIX as i32) as usize;
         count[digit + 1] += 1;
@@ -64,7 +91,7 @@ fn msd_sort(array: &mut [i32], lo: usize, hi: usize, exp: i32, events: &mut Vec<
     }
 
     // Store original positions for stable distribution
-    let mut temp = vec![0; hi - lo];
+    let temp = zeroed(&mut scratch.values, hi - lo);
     for i in lo..hi {
         let digit = ((array[i] / exp) % RADIX as i32) as usize;
         temp[count[digit]] = array[i];
@@ -73,10 +100,8 @@ fn msd_sort(array: &mut [i32], lo: usize, hi: usize, exp: i32, events: &mut Vec<
 
     // Copy back with Overwrite events
     // Reset count for tracking bucket boundaries
-    let mut bucket_ends = vec![0usize; RADIX + 1];
-    for i in 0..RADIX {
-        bucket_ends[i + 1] = count[i];
-    }
+    let mut bucket_ends = [0usize; RADIX + 1];
+    bucket_ends[1..].copy_from_slice(&count[..RADIX]);
 
     for i in 0..(hi - lo) {
         let idx = lo + i;
@@ -99,7 +124,7 @@ fn msd_sort(array: &mut [i32], lo: usize, hi: usize, exp: i32, events: &mut Vec<
         let next_exp = exp / RADIX as i32;
 
         // Recalculate bucket boundaries from scratch
-        let mut count = vec![0usize; RADIX + 1];
+        let mut count = [0usize; RADIX + 1];
         for i in lo..hi {
             let digit = ((array[i] / exp) % RADIX as i32) as usize;
             count[digit + 1] += 1;
@@ -112,7 +137,7 @@ fn msd_sort(array: &mut [i32], lo: usize, hi: usize, exp: i32, events: &mut Vec<
             let bucket_lo = lo + count[d];
             let bucket_hi = lo + count[d + 1];
             if bucket_hi > bucket_lo + 1 {
-                msd_sort(array, bucket_lo, bucket_hi, next_exp, events);
+                msd_sort(array, bucket_lo, bucket_hi, next_exp, scratch, events);
             }
         }
     }
@@ -0,0 +1,257 @@
+//! Sample Sort implementation for V1 (Pregeneration) engine.
+//!
+//! Generalization of quicksort to many pivots, used in parallel and
+//! distributed sorting. An evenly spaced sample is sorted to pick k-1
+//! splitters, every element is classified into one of k buckets by binary
+//! search over the splitters, and the buckets are scattered into place and
+//! sorted independently (recursively, or with insertion sort when small).
+
+use crate::prelude::*;
+use crate::events::{EventSink, SortEvent};
+use super::context::{zeroed, Scratch};
+use super::{estimate, AlgorithmInfo, PregenSort};
+
+pub struct SampleSort;
+
+/// Buckets at or below this size are finished with insertion sort.
+const INSERTION_THRESHOLD: usize = 16;
+
+/// Sample elements drawn per bucket when choosing splitters.
+const OVERSAMPLING: usize = 3;
+
+/// Upper bound on the number of buckets per level.
+const MAX_BUCKETS: usize = 16;
+
+impl PregenSort for SampleSort {
+    const INFO: AlgorithmInfo = AlgorithmInfo {
+        display_name: "Sample Sort",
+        best: "O(n log n)",
+        average: "O(n log n)",
+        worst: "O(n²)",
+        space: "O(n)",
+        stable: false,
+        in_place: false,
+        adaptive: false,
+        description: "Quicksort with many pivots picked from a sorted sample of the input.",
+    };
+
+    fn estimated_events(n: usize) -> usize {
+        estimate::n_log_n(n, 2.0)
+    }
+
+    fn sort_into(array: &mut [i32], events: &mut impl EventSink) {
+        Self::sort_into_with(array, events, &mut Scratch::new());
+    }
+
+    fn sort_into_with(array: &mut [i32], events: &mut impl EventSink, scratch: &mut Scratch) {
+        let n = array.len();
+
+        if n > 1 {
+            sample_sort(array, 0, n, scratch, events);
+        }
+
+        events.push(SortEvent::Done);
+    }
+}
+
+/// Sort the half-open range [lo, hi).
+fn sample_sort(
+    array: &mut [i32],
+    lo: usize,
+    hi: usize,
+    scratch: &mut Scratch,
+    events: &mut impl EventSink,
+) {
+    let len = hi - lo;
+    if len <= INSERTION_THRESHOLD {
+        insertion_sort_range(array, lo, hi, events);
+        return;
+    }
+
+    let buckets = len.isqrt().clamp(2, MAX_BUCKETS);
+
+    // Sort an evenly spaced sample of positions by value (no mutation)
+    let sample_size = buckets * OVERSAMPLING;
+    let mut sample: Vec<usize> = (0..sample_size).map(|s| lo + s * len / sample_size).collect();
+    for s in 1..sample.len() {
+        let mut t = s;
+        while t > 0 {
+            events.push(SortEvent::Compare { i: sample[t - 1], j: sample[t] });
+            if array[sample[t - 1]] > array[sample[t]] {
+                sample.swap(t - 1, t);
+                t -= 1;
+            } else {
+                break;
+            }
+        }
+    }
+
+    // Every OVERSAMPLING-th sample becomes a splitter
+    let splitters: Vec<usize> = (1..buckets).map(|b| sample[b * OVERSAMPLING]).collect();
+
+    // Classify each element by binary search over the splitters
+    let bucket_of = zeroed(&mut scratch.indices, len);
+    let mut counts = vec![0usize; buckets];
+    for idx in lo..hi {
+        let mut left = 0;
+        let mut right = splitters.len();
+        while left < right {
+            let mid = left + (right - left) / 2;
+            events.push(SortEvent::Compare { i: idx, j: splitters[mid] });
+            if array[idx] < array[splitters[mid]] {
+                right = mid;
+            } else {
+                left = mid + 1;
+            }
+        }
+        bucket_of[idx - lo] = left;
+        counts[left] += 1;
+    }
+
+    // All elements in one bucket (e.g. many duplicates): no progress possible
+    if counts.contains(&len) {
+        insertion_sort_range(array, lo, hi, events);
+        return;
+    }
+
+    // Scatter elements into their buckets through an aux buffer
+    let mut starts = vec![lo; buckets + 1];
+    for b in 0..buckets {
+        starts[b + 1] = starts[b] + counts[b];
+    }
+    let mut next = starts.clone();
+    let aux = zeroed(&mut scratch.values, len);
+    for idx in lo..hi {
+        let b = bucket_of[idx - lo];
+        aux[next[b] - lo] = array[idx];
+        next[b] += 1;
+    }
+
+    events.push(SortEvent::EnterRange { lo, hi: hi - 1 });
+    for (offset, &value) in aux.iter().enumerate() {
+        let idx = lo + offset;
+        if array[idx] != value {
+            events.push(SortEvent::Overwrite {
+                idx,
+                old_val: array[idx],
+                new_val: value,
+            });
+            array[idx] = value;
+        }
+    }
+    events.push(SortEvent::ExitRange { lo, hi: hi - 1 });
+
+    // Sort each bucket independently
+    for b in 0..buckets {
+        let (bucket_lo, bucket_hi) = (starts[b], starts[b + 1]);
+        if bucket_hi - bucket_lo > 1 {
+            events.push(SortEvent::EnterRange { lo: bucket_lo, hi: bucket_hi - 1 });
+            sample_sort(array, bucket_lo, bucket_hi, scratch, events);
+            events.push(SortEvent::ExitRange { lo: bucket_lo, hi: bucket_hi - 1 });
+        }
+    }
+}
+
+/// Insertion sort for the half-open range [lo, hi).
+fn insertion_sort_range(array: &mut [i32], lo: usize, hi: usize, events: &mut impl EventSink) {
+    for i in (lo + 1)..hi {
+        let value = array[i];
+        let mut j = i;
+
+        while j > lo {
+            events.push(SortEvent::Compare { i: j - 1, j });
+
+            if array[j - 1] > value {
+                events.push(SortEvent::Overwrite {
+                    idx: j,
+                    old_val: array[j],
+                    new_val: array[j - 1],
+                });
+                array[j] = array[j - 1];
+                j -= 1;
+            } else {
+                break;
+            }
+        }
+
+        if j != i {
+            events.push(SortEvent::Overwrite {
+                idx: j,
+                old_val: array[j],
+                new_val: value,
+            });
+            array[j] = value;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample_sort_basic() {
+        let mut array = vec![5, 3, 8, 4, 2];
+        let events = SampleSort::sort(&mut array);
+
+        assert_eq!(array, vec![2, 3, 4, 5, 8]);
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_sample_sort_large_reverse() {
+        let mut array: Vec<i32> = (0..500).rev().collect();
+        SampleSort::sort(&mut array);
+
+        let expected: Vec<i32> = (0..500).collect();
+        assert_eq!(array, expected);
+    }
+
+    #[test]
+    fn test_sample_sort_empty() {
+        let mut array: Vec<i32> = vec![];
+        let events = SampleSort::sort(&mut array);
+
+        assert!(array.is_empty());
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_sample_sort_single() {
+        let mut array = vec![42];
+        let events = SampleSort::sort(&mut array);
+
+        assert_eq!(array, vec![42]);
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_sample_sort_few_unique() {
+        let mut array: Vec<i32> = (0..200).map(|x| (x * 7) % 3).collect();
+        SampleSort::sort(&mut array);
+
+        assert!(array.windows(2).all(|w| w[0] <= w[1]));
+        assert_eq!(array.iter().filter(|&&v| v == 0).count(), 67);
+    }
+
+    #[test]
+    fn test_sample_sort_all_equal() {
+        let mut array = vec![4; 100];
+        SampleSort::sort(&mut array);
+
+        assert_eq!(array, vec![4; 100]);
+    }
+
+    #[test]
+    fn test_sample_sort_emits_bucket_ranges() {
+        let mut array: Vec<i32> = (0..100).map(|x| (x * 37) % 101).collect();
+        let events = SampleSort::sort(&mut array);
+
+        let enter_count = events.iter().filter(|e| matches!(e, SortEvent::EnterRange { .. })).count();
+        let exit_count = events.iter().filter(|e| matches!(e, SortEvent::ExitRange { .. })).count();
+
+        assert!(enter_count > 2);
+        assert_eq!(enter_count, exit_count);
+        assert!(array.windows(2).all(|w| w[0] <= w[1]));
+    }
+}
@@ -1,22 +1,41 @@
 //! Selection Sort implementation for V1 (Pregeneration) engine.
+//!
+//! Tracks the current minimum candidate with the "min" cursor.
 
-use crate::events::SortEvent;
-use super::PregenSort;
+use crate::events::{Cursor, EventSink, SortEvent};
+use super::{estimate, AlgorithmInfo, PregenSort};
 
 pub struct SelectionSort;
 
 impl PregenSort for SelectionSort {
-    fn sort(array: &mut [i32]) -> Vec<SortEvent> {
-        let mut events = Vec::new();
+    const INFO: AlgorithmInfo = AlgorithmInfo {
+        display_name: "Selection Sort",
+        best: "O(n²)",
+        average: "O(n²)",
+        worst: "O(n²)",
+        space: "O(1)",
+        stable: false,
+        in_place: true,
+        adaptive: false,
+        description: "Selects the minimum of the unsorted part and swaps it into place.",
+    };
+
+    fn estimated_events(n: usize) -> usize {
+        estimate::quadratic(n, 0.5)
+    }
+
+    fn sort_into(array: &mut [i32], events: &mut impl EventSink) {
         let n = array.len();
 
         if n <= 1 {
             events.push(SortEvent::Done);
-            return events;
+            return;
         }
 
+        let mut min = Cursor::new("min");
         for i in 0..n - 1 {
             let mut min_idx = i;
+            min.set(Some(min_idx), events);
 
             // Find minimum element in unsorted portion
             for j in (i + 1)..n {
@@ -24,6 +43,7 @@ impl PregenSort for SelectionSort {
 
                 if array[j] < array[min_idx] {
                     min_idx = j;
+                    min.set(Some(min_idx), events);
                 }
             }
 
@@ -34,8 +54,8 @@ impl PregenSort for SelectionSort {
             }
         }
 
+        min.set(None, events);
         events.push(SortEvent::Done);
-        events
     }
 }
 
@@ -88,4 +108,23 @@ mod tests {
         assert_eq!(array, vec![42]);
         assert!(matches!(events.last(), Some(SortEvent::Done)));
     }
+
+    #[test]
+    fn test_selection_sort_tracks_min_cursor() {
+        let mut array = vec![3, 1, 2];
+        let events = SelectionSort::sort(&mut array);
+
+        let moves: Vec<(Option<usize>, Option<usize>)> = events
+            .iter()
+            .filter_map(|e| match e {
+                SortEvent::SetCursor(c) if c.id == "min" => Some((c.prev, c.index)),
+                _ => None,
+            })
+            .collect();
+        // Pass 1 starts at 0 and finds 1; pass 2 starts at 1 and finds 2
+        assert_eq!(
+            moves,
+            vec![(None, Some(0)), (Some(0), Some(1)), (Some(1), Some(2)), (Some(2), None)]
+        );
+    }
 }
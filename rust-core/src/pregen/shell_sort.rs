@@ -2,65 +2,162 @@
 //!
 //! Generalization of insertion sort that allows exchange of far apart elements.
 //! Uses a gap sequence that decreases to 1. This implementation uses the
-//! original Shell sequence (n/2, n/4, ..., 1).
+//! original Shell sequence (n/2, n/4, ..., 1); `Gaps` names the other
+//! sequences, which hybrids can pick. A GapChange event starts every gap
+//! and a last one back to 0 ends the passes; each gapped insertion starts by
+//! highlighting the gap's endpoints with the "gap" tag.
 
-use crate::events::SortEvent;
-use super::PregenSort;
+use crate::prelude::*;
+use crate::events::{EventSink, SortEvent};
+use super::{estimate, AlgorithmInfo, PregenSort};
 
 pub struct ShellSort;
 
 impl PregenSort for ShellSort {
-    fn sort(array: &mut [i32]) -> Vec<SortEvent> {
-        let mut events = Vec::new();
+    const INFO: AlgorithmInfo = AlgorithmInfo {
+        display_name: "Shell Sort",
+        best: "O(n log n)",
+        average: "O(n^1.5)",
+        worst: "O(n²)",
+        space: "O(1)",
+        stable: false,
+        in_place: true,
+        adaptive: true,
+        description: "Gapped insertion sort with Shell's gaps n/2, n/4, ..., 1.",
+    };
+
+    fn estimated_events(n: usize) -> usize {
+        estimate::n_sqrt_n(n, 1.5)
+    }
+
+    fn sort_into(array: &mut [i32], events: &mut impl EventSink) {
         let n = array.len();
 
         if n <= 1 {
             events.push(SortEvent::Done);
-            return events;
+            return;
+        }
+
+        gapped_insertion(array, &Gaps::Shell.sequence(n), events);
+
+        events.push(SortEvent::Done);
+    }
+}
+
+/// Gap sequences for shell sort.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Gaps {
+    /// Shell's n/2, n/4, ..., 1.
+    #[default]
+    Shell,
+    /// Knuth's (3^k - 1) / 2: 1, 4, 13, 40, ...
+    Knuth,
+    /// Ciura's 1, 4, 10, 23, 57, 132, 301, 701, extended by a factor 2.25.
+    Ciura,
+}
+
+impl Gaps {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Gaps::Shell => "shell",
+            Gaps::Knuth => "knuth",
+            Gaps::Ciura => "ciura",
+        }
+    }
+
+    /// Parse gap sequence name from string.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Gaps> {
+        match s.to_lowercase().as_str() {
+            "shell" => Some(Gaps::Shell),
+            "knuth" => Some(Gaps::Knuth),
+            "ciura" => Some(Gaps::Ciura),
+            _ => None,
         }
+    }
+
+    /// Gaps below n, largest first, ending with 1. Empty for n <= 1.
+    pub fn sequence(&self, n: usize) -> Vec<usize> {
+        const CIURA: [usize; 8] = [1, 4, 10, 23, 57, 132, 301, 701];
 
-        // Start with a large gap, then reduce
-        let mut gap = n / 2;
-
-        while gap > 0 {
-            // Perform gapped insertion sort
-            for i in gap..n {
-                let value = array[i];
-                let mut j = i;
-
-                // Shift earlier gap-sorted elements up until correct position found
-                while j >= gap {
-                    events.push(SortEvent::Compare { i: j - gap, j });
-
-                    if array[j - gap] > value {
-                        events.push(SortEvent::Overwrite {
-                            idx: j,
-                            old_val: array[j],
-                            new_val: array[j - gap],
-                        });
-                        array[j] = array[j - gap];
-                        j -= gap;
-                    } else {
-                        break;
-                    }
+        let mut gaps = Vec::new();
+        match self {
+            Gaps::Shell => {
+                let mut gap = n / 2;
+                while gap > 0 {
+                    gaps.push(gap);
+                    gap /= 2;
                 }
+                return gaps;
+            }
+            Gaps::Knuth => {
+                let mut gap = 1;
+                while gap < n {
+                    gaps.push(gap);
+                    gap = gap * 3 + 1;
+                }
+            }
+            Gaps::Ciura => {
+                gaps.extend(CIURA.iter().copied().take_while(|&gap| gap < n));
+                let mut gap = CIURA[CIURA.len() - 1] * 9 / 4;
+                while gaps.len() >= CIURA.len() && gap < n {
+                    gaps.push(gap);
+                    gap = gap * 9 / 4;
+                }
+            }
+        }
+        gaps.reverse();
+        gaps
+    }
+}
+
+/// Gapped insertion passes over the whole array, one per gap in order.
+/// Pushes no Done, so callers can run it as one stage of a larger sort.
+pub(super) fn gapped_insertion(array: &mut [i32], gaps: &[usize], events: &mut impl EventSink) {
+    let n = array.len();
+    let mut prev = 0;
 
-                // Place value at its correct position
-                if j != i {
+    for &gap in gaps {
+        events.push(SortEvent::GapChange { gap, prev });
+        prev = gap;
+
+        // Perform gapped insertion sort
+        for i in gap..n {
+            events.push(SortEvent::highlight(vec![i - gap, i], "gap"));
+            let value = array[i];
+            let mut j = i;
+
+            // Shift earlier gap-sorted elements up until correct position found
+            while j >= gap {
+                events.push(SortEvent::Compare { i: j - gap, j });
+
+                if array[j - gap] > value {
                     events.push(SortEvent::Overwrite {
                         idx: j,
                         old_val: array[j],
-                        new_val: value,
+                        new_val: array[j - gap],
                     });
-                    array[j] = value;
+                    array[j] = array[j - gap];
+                    j -= gap;
+                } else {
+                    break;
                 }
             }
 
-            gap /= 2;
+            // Place value at its correct position
+            if j != i {
+                events.push(SortEvent::Overwrite {
+                    idx: j,
+                    old_val: array[j],
+                    new_val: value,
+                });
+                array[j] = value;
+            }
         }
+    }
 
-        events.push(SortEvent::Done);
-        events
+    if prev > 0 {
+        events.push(SortEvent::GapChange { gap: 0, prev });
     }
 }
 
@@ -131,4 +228,60 @@ mod tests {
         assert_eq!(array, vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
         assert!(matches!(events.last(), Some(SortEvent::Done)));
     }
+
+    #[test]
+    fn test_shell_sort_highlights_gap_endpoints() {
+        let mut array = vec![4, 3, 2, 1];
+        let events = ShellSort::sort(&mut array);
+
+        let gaps: Vec<Vec<usize>> = events
+            .iter()
+            .filter_map(|e| match e {
+                SortEvent::Highlight(h) if h.tag == "gap" => Some(h.indices.clone()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(gaps, vec![vec![0, 2], vec![1, 3], vec![0, 1], vec![1, 2], vec![2, 3]]);
+    }
+
+    #[test]
+    fn test_shell_sort_announces_gaps() {
+        let mut array: Vec<i32> = (0..10).rev().collect();
+        let events = ShellSort::sort(&mut array);
+
+        let changes: Vec<(usize, usize)> = events
+            .iter()
+            .filter_map(|e| match *e {
+                SortEvent::GapChange { gap, prev } => Some((gap, prev)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(changes, vec![(5, 0), (2, 5), (1, 2), (0, 1)]);
+        assert_eq!(events[0], SortEvent::GapChange { gap: 5, prev: 0 });
+    }
+
+    #[test]
+    fn test_gap_sequences() {
+        assert_eq!(Gaps::Shell.sequence(10), vec![5, 2, 1]);
+        assert_eq!(Gaps::Knuth.sequence(41), vec![40, 13, 4, 1]);
+        assert_eq!(Gaps::Ciura.sequence(100), vec![57, 23, 10, 4, 1]);
+        assert_eq!(Gaps::Ciura.sequence(4000)[..3], [3548, 1577, 701]);
+        for gaps in [Gaps::Shell, Gaps::Knuth, Gaps::Ciura] {
+            assert!(gaps.sequence(1).is_empty());
+            assert_eq!(Gaps::from_str(gaps.as_str()), Some(gaps));
+        }
+    }
+
+    #[test]
+    fn test_gapped_insertion_sorts_with_any_sequence() {
+        let input: Vec<i32> = (0..50).map(|k| (k * 37) % 50).collect();
+        for gaps in [Gaps::Knuth, Gaps::Ciura] {
+            let mut array = input.clone();
+            let mut events = Vec::new();
+            gapped_insertion(&mut array, &gaps.sequence(50), &mut events);
+
+            assert!(array.windows(2).all(|w| w[0] <= w[1]));
+            assert!(!events.contains(&SortEvent::Done));
+        }
+    }
 }
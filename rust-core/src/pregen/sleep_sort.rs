@@ -0,0 +1,149 @@
+//! Sleep Sort implementation for V1 (Pregeneration) engine.
+//!
+//! The joke algorithm: start one timer per element that sleeps for a time
+//! proportional to its value, and append each element to the output when its
+//! timer fires. This is a deterministic simulation on a virtual timeline, so
+//! no real timers are involved. Each element wakes at (value - min); ties
+//! wake in input order. Placements are emitted as Overwrite events in wake
+//! order, filling the array from the left.
+
+use core::cmp::Reverse;
+use alloc::collections::BinaryHeap;
+
+use crate::prelude::*;
+use crate::events::{EventSink, SortEvent};
+use super::{estimate, AlgorithmInfo, PregenSort};
+
+pub struct SleepSort;
+
+impl PregenSort for SleepSort {
+    const INFO: AlgorithmInfo = AlgorithmInfo {
+        display_name: "Sleep Sort",
+        best: "O(n log n)",
+        average: "O(n log n)",
+        worst: "O(n log n)",
+        space: "O(n)",
+        stable: true,
+        in_place: false,
+        adaptive: false,
+        description:
+            "Each element waits for a time proportional to its value; simulated, not real timers.",
+    };
+
+    fn estimated_events(n: usize) -> usize {
+        estimate::linear(n, 1.0)
+    }
+
+    fn sort_into(array: &mut [i32], events: &mut impl EventSink) {
+        let n = array.len();
+
+        if n <= 1 {
+            events.push(SortEvent::Done);
+            return;
+        }
+
+        for (idx, value) in wake_order(array).into_iter().enumerate() {
+            if array[idx] != value {
+                events.push(SortEvent::Overwrite {
+                    idx,
+                    old_val: array[idx],
+                    new_val: value,
+                });
+                array[idx] = value;
+            }
+        }
+
+        events.push(SortEvent::Done);
+    }
+}
+
+/// Virtual wake-up time of every element, in input order.
+pub fn wake_times(array: &[i32]) -> Vec<u64> {
+    let min_val = array.iter().copied().min().unwrap_or(0) as i64;
+    array.iter().map(|&v| (v as i64 - min_val) as u64).collect()
+}
+
+/// Run the timeline and return values in the order their timers fire.
+fn wake_order(array: &[i32]) -> Vec<i32> {
+    // Event queue of pending timers, earliest first
+    let mut timers: BinaryHeap<Reverse<(u64, usize)>> = wake_times(array)
+        .into_iter()
+        .enumerate()
+        .map(|(idx, time)| Reverse((time, idx)))
+        .collect();
+
+    let mut output = Vec::with_capacity(array.len());
+    while let Some(Reverse((_, idx))) = timers.pop() {
+        output.push(array[idx]);
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sleep_sort_basic() {
+        let mut array = vec![5, 3, 8, 4, 2];
+        let events = SleepSort::sort(&mut array);
+
+        assert_eq!(array, vec![2, 3, 4, 5, 8]);
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_sleep_sort_negative_and_extremes() {
+        let mut array = vec![0, i32::MAX, -7, i32::MIN, 3];
+        SleepSort::sort(&mut array);
+
+        assert_eq!(array, vec![i32::MIN, -7, 0, 3, i32::MAX]);
+    }
+
+    #[test]
+    fn test_sleep_sort_duplicates() {
+        let mut array = vec![3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5];
+        SleepSort::sort(&mut array);
+
+        assert_eq!(array, vec![1, 1, 2, 3, 3, 4, 5, 5, 5, 6, 9]);
+    }
+
+    #[test]
+    fn test_sleep_sort_empty() {
+        let mut array: Vec<i32> = vec![];
+        let events = SleepSort::sort(&mut array);
+
+        assert!(array.is_empty());
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_sleep_sort_single() {
+        let mut array = vec![42];
+        let events = SleepSort::sort(&mut array);
+
+        assert_eq!(array, vec![42]);
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_sleep_sort_placements_in_timeline_order() {
+        let mut array = vec![30, 10, 20];
+        let events = SleepSort::sort(&mut array);
+
+        let placed: Vec<(usize, i32)> = events
+            .iter()
+            .filter_map(|e| match e {
+                SortEvent::Overwrite { idx, new_val, .. } => Some((*idx, *new_val)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(placed, vec![(0, 10), (1, 20), (2, 30)]);
+    }
+
+    #[test]
+    fn test_sleep_sort_wake_times() {
+        assert_eq!(wake_times(&[5, -2, 0]), vec![7, 0, 2]);
+        assert!(wake_times(&[]).is_empty());
+    }
+}
@@ -0,0 +1,286 @@
+//! Splaysort implementation for V1 (Pregeneration) engine.
+//!
+//! Inserts elements one by one into a splay tree, splaying each new node to
+//! the root, then writes the tree back in order. Recently inserted keys stay
+//! near the root, so inputs with clustered or nearly sorted values need only
+//! shallow searches. The tree stores positions of the untouched input, so
+//! every Compare event is meaningful; the sorted order is written back with
+//! Overwrite events at the end.
+//!
+//! `insertion_depths` reports how deep each insertion had to search.
+
+use crate::prelude::*;
+use crate::events::{EventSink, SortEvent};
+use super::{estimate, AlgorithmInfo, PregenSort};
+
+pub struct SplaySort;
+
+impl PregenSort for SplaySort {
+    const INFO: AlgorithmInfo = AlgorithmInfo {
+        display_name: "Splaysort",
+        best: "O(n)",
+        average: "O(n log n)",
+        worst: "O(n log n)",
+        space: "O(n)",
+        stable: false,
+        in_place: false,
+        adaptive: true,
+        description: "Inserts every element into a splay tree, then writes the tree back in order.",
+    };
+
+    fn estimated_events(n: usize) -> usize {
+        estimate::n_log_n(n, 1.3)
+    }
+
+    fn sort_into(array: &mut [i32], events: &mut impl EventSink) {
+        splay_sort(array, events);
+    }
+}
+
+/// Number of comparisons each insertion needed, in input order.
+/// Runs the sort on a copy of `array`.
+pub fn insertion_depths(array: &[i32]) -> Vec<usize> {
+    let mut copy = array.to_vec();
+    let mut events = Vec::new();
+    splay_sort(&mut copy, &mut events)
+}
+
+#[derive(Clone, Copy)]
+struct Node {
+    left: Option<usize>,
+    right: Option<usize>,
+    parent: Option<usize>,
+}
+
+/// Splay tree over array positions. Node ids equal array positions.
+struct SplayTree {
+    nodes: Vec<Node>,
+    root: Option<usize>,
+}
+
+impl SplayTree {
+    fn new(len: usize) -> Self {
+        let empty = Node {
+            left: None,
+            right: None,
+            parent: None,
+        };
+        Self {
+            nodes: vec![empty; len],
+            root: None,
+        }
+    }
+
+    /// Insert position `x`, returning the number of comparisons made.
+    fn insert(&mut self, array: &[i32], x: usize, events: &mut impl EventSink) -> usize {
+        let mut depth = 0;
+        let mut current = match self.root {
+            Some(root) => root,
+            None => {
+                self.root = Some(x);
+                return depth;
+            }
+        };
+
+        loop {
+            events.push(SortEvent::Compare { i: x, j: current });
+            depth += 1;
+
+            // Equal keys go right, keeping the sort stable
+            let go_left = array[x] < array[current];
+            let child = if go_left {
+                self.nodes[current].left
+            } else {
+                self.nodes[current].right
+            };
+
+            match child {
+                Some(next) => current = next,
+                None => {
+                    if go_left {
+                        self.nodes[current].left = Some(x);
+                    } else {
+                        self.nodes[current].right = Some(x);
+                    }
+                    self.nodes[x].parent = Some(current);
+                    break;
+                }
+            }
+        }
+
+        self.splay(x);
+        depth
+    }
+
+    /// Rotate `x` above its parent.
+    fn rotate(&mut self, x: usize) {
+        let p = self.nodes[x].parent.unwrap();
+        let g = self.nodes[p].parent;
+
+        if self.nodes[p].left == Some(x) {
+            let b = self.nodes[x].right;
+            self.nodes[p].left = b;
+            if let Some(b) = b {
+                self.nodes[b].parent = Some(p);
+            }
+            self.nodes[x].right = Some(p);
+        } else {
+            let b = self.nodes[x].left;
+            self.nodes[p].right = b;
+            if let Some(b) = b {
+                self.nodes[b].parent = Some(p);
+            }
+            self.nodes[x].left = Some(p);
+        }
+        self.nodes[p].parent = Some(x);
+        self.nodes[x].parent = g;
+
+        match g {
+            Some(g) => {
+                if self.nodes[g].left == Some(p) {
+                    self.nodes[g].left = Some(x);
+                } else {
+                    self.nodes[g].right = Some(x);
+                }
+            }
+            None => self.root = Some(x),
+        }
+    }
+
+    /// Move `x` to the root with zig, zig-zig and zig-zag steps.
+    fn splay(&mut self, x: usize) {
+        while let Some(p) = self.nodes[x].parent {
+            if let Some(g) = self.nodes[p].parent {
+                let zig_zig = (self.nodes[g].left == Some(p)) == (self.nodes[p].left == Some(x));
+                if zig_zig {
+                    self.rotate(p);
+                } else {
+                    self.rotate(x);
+                }
+            }
+            self.rotate(x);
+        }
+    }
+
+    /// Positions in sorted order.
+    fn in_order(&self) -> Vec<usize> {
+        let mut order = Vec::with_capacity(self.nodes.len());
+        let mut stack = Vec::new();
+        let mut current = self.root;
+
+        while current.is_some() || !stack.is_empty() {
+            while let Some(node) = current {
+                stack.push(node);
+                current = self.nodes[node].left;
+            }
+            let node = stack.pop().unwrap();
+            order.push(node);
+            current = self.nodes[node].right;
+        }
+
+        order
+    }
+}
+
+/// Sort the array, returning the comparison depth of every insertion.
+fn splay_sort(array: &mut [i32], events: &mut impl EventSink) -> Vec<usize> {
+    let n = array.len();
+    let mut depths = Vec::with_capacity(n);
+
+    if n > 1 {
+        let mut tree = SplayTree::new(n);
+        for x in 0..n {
+            depths.push(tree.insert(array, x, events));
+        }
+
+        // Write the sorted order back into the array
+        let sorted: Vec<i32> = tree.in_order().iter().map(|&i| array[i]).collect();
+        for (idx, value) in sorted.into_iter().enumerate() {
+            if array[idx] != value {
+                events.push(SortEvent::Overwrite {
+                    idx,
+                    old_val: array[idx],
+                    new_val: value,
+                });
+                array[idx] = value;
+            }
+        }
+    } else {
+        depths.resize(n, 0);
+    }
+
+    events.push(SortEvent::Done);
+    depths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_splay_sort_basic() {
+        let mut array = vec![5, 3, 8, 4, 2];
+        let events = SplaySort::sort(&mut array);
+
+        assert_eq!(array, vec![2, 3, 4, 5, 8]);
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_splay_sort_reverse() {
+        let mut array: Vec<i32> = (0..200).rev().collect();
+        SplaySort::sort(&mut array);
+
+        let expected: Vec<i32> = (0..200).collect();
+        assert_eq!(array, expected);
+    }
+
+    #[test]
+    fn test_splay_sort_duplicates() {
+        let mut array = vec![3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5];
+        SplaySort::sort(&mut array);
+
+        assert_eq!(array, vec![1, 1, 2, 3, 3, 4, 5, 5, 5, 6, 9]);
+    }
+
+    #[test]
+    fn test_splay_sort_empty() {
+        let mut array: Vec<i32> = vec![];
+        let events = SplaySort::sort(&mut array);
+
+        assert!(array.is_empty());
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_splay_sort_single() {
+        let mut array = vec![42];
+        let events = SplaySort::sort(&mut array);
+
+        assert_eq!(array, vec![42]);
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_splay_sort_sorted_input_is_shallow() {
+        let sorted: Vec<i32> = (0..100).collect();
+        let depths = insertion_depths(&sorted);
+
+        // Each new maximum lands right below the previous one at the root
+        assert_eq!(depths[0], 0);
+        assert!(depths[1..].iter().all(|&d| d == 1));
+    }
+
+    #[test]
+    fn test_splay_sort_depths_match_compares() {
+        let array = vec![7, 3, 9, 1, 5, 8, 2];
+        let depths = insertion_depths(&array);
+
+        let mut copy = array.clone();
+        let events = SplaySort::sort(&mut copy);
+        let compare_count = events.iter().filter(|e| matches!(e, SortEvent::Compare { .. })).count();
+
+        assert_eq!(depths.len(), array.len());
+        assert_eq!(depths.iter().sum::<usize>(), compare_count);
+    }
+}
@@ -0,0 +1,225 @@
+//! Spreadsort implementation for V1 (Pregeneration) engine.
+//!
+//! Simplified version of the hybrid radix/comparison sort from Boost:
+//! 1. Find the minimum and maximum of the range.
+//! 2. Split the value range into up to 2^MAX_SPLITS bins by the top bits of
+//!    (value - min), and permute elements into their bins in place.
+//! 3. Recurse on every bin. Bins small enough for comparison sorting to win
+//!    are handed to introsort instead.
+//!
+//! Each level removes the bits it split on, so recursion depth is bounded by
+//! the key width. Bins whose values are all equal are skipped.
+
+use crate::prelude::*;
+use crate::events::{EventSink, SortEvent};
+use super::intro_sort::introsort_range;
+use super::{estimate, AlgorithmInfo, PregenSort};
+
+pub struct SpreadSort;
+
+/// Bins at or below this size are sorted with introsort.
+const COMPARISON_THRESHOLD: usize = 32;
+
+/// Maximum number of bits split on per level.
+const MAX_SPLITS: u32 = 11;
+
+impl PregenSort for SpreadSort {
+    const INFO: AlgorithmInfo = AlgorithmInfo {
+        display_name: "Spreadsort",
+        best: "O(n)",
+        average: "O(n log n)",
+        worst: "O(n log n)",
+        space: "O(2^s)",
+        stable: false,
+        in_place: true,
+        adaptive: false,
+        description: "Hybrid radix sort that splits by top bits and hands small bins to introsort.",
+    };
+
+    fn estimated_events(n: usize) -> usize {
+        estimate::linear(n, 5.0)
+    }
+
+    fn sort_into(array: &mut [i32], events: &mut impl EventSink) {
+        let n = array.len();
+
+        if n <= 1 {
+            events.push(SortEvent::Done);
+            return;
+        }
+
+        spread_sort(array, 0, n - 1, events);
+
+        events.push(SortEvent::Done);
+    }
+}
+
+/// Sort the range [lo..=hi].
+fn spread_sort(array: &mut [i32], lo: usize, hi: usize, events: &mut impl EventSink) {
+    let size = hi - lo + 1;
+
+    if size <= COMPARISON_THRESHOLD {
+        introsort_range(array, lo, hi, events);
+        return;
+    }
+
+    events.push(SortEvent::EnterRange { lo, hi });
+
+    // Find min and max positions
+    let mut min_idx = lo;
+    let mut max_idx = lo;
+    for i in (lo + 1)..=hi {
+        events.push(SortEvent::Compare { i, j: min_idx });
+        if array[i] < array[min_idx] {
+            min_idx = i;
+            continue;
+        }
+        events.push(SortEvent::Compare { i, j: max_idx });
+        if array[i] > array[max_idx] {
+            max_idx = i;
+        }
+    }
+
+    let min_val = array[min_idx];
+    let key_range = key(array[max_idx], min_val);
+    if key_range == 0 {
+        // All values equal
+        events.push(SortEvent::ExitRange { lo, hi });
+        return;
+    }
+
+    // Split on the top bits of the key range
+    let range_bits = 64 - key_range.leading_zeros();
+    let size_bits = usize::BITS - 1 - size.leading_zeros();
+    let bin_bits = range_bits.min(size_bits).min(MAX_SPLITS);
+    let shift = range_bits - bin_bits;
+    let bin_count = 1usize << bin_bits;
+    let bin_of = |value: i32| (key(value, min_val) >> shift) as usize;
+
+    // Count elements per bin
+    let mut count = vec![0usize; bin_count];
+    for i in lo..=hi {
+        events.push(SortEvent::Compare { i, j: i });
+        count[bin_of(array[i])] += 1;
+    }
+
+    // Bin boundaries: bin b occupies [start[b], start[b + 1])
+    let mut start = vec![lo; bin_count + 1];
+    for b in 0..bin_count {
+        start[b + 1] = start[b] + count[b];
+    }
+
+    // Permute in place: next[b] is the first unplaced slot of bin b
+    let mut next = start.clone();
+    for b in 0..bin_count {
+        while next[b] < start[b + 1] {
+            let i = next[b];
+            let target = bin_of(array[i]);
+            if target == b {
+                next[b] += 1;
+            } else {
+                let j = next[target];
+                events.push(SortEvent::Swap { i, j });
+                array.swap(i, j);
+                next[target] += 1;
+            }
+        }
+    }
+
+    events.push(SortEvent::ExitRange { lo, hi });
+
+    // Every value in a bin shares the top bits; with no bits left they are equal
+    if shift == 0 {
+        return;
+    }
+
+    for b in 0..bin_count {
+        if start[b + 1] > start[b] + 1 {
+            spread_sort(array, start[b], start[b + 1] - 1, events);
+        }
+    }
+}
+
+/// Non-negative key of a value relative to the range minimum.
+fn key(value: i32, min_val: i32) -> u64 {
+    (value as i64 - min_val as i64) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spread_sort_basic() {
+        let mut array = vec![5, 3, 8, 4, 2];
+        let events = SpreadSort::sort(&mut array);
+
+        assert_eq!(array, vec![2, 3, 4, 5, 8]);
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_spread_sort_reverse() {
+        let mut array: Vec<i32> = (0..500).rev().collect();
+        SpreadSort::sort(&mut array);
+
+        let expected: Vec<i32> = (0..500).collect();
+        assert_eq!(array, expected);
+    }
+
+    #[test]
+    fn test_spread_sort_wide_range() {
+        let mut array: Vec<i32> = (0..300)
+            .map(|x: i64| ((x * 2_654_435_761) % (u32::MAX as i64) - i32::MAX as i64) as i32)
+            .collect();
+        array.push(i32::MIN);
+        array.push(i32::MAX);
+        SpreadSort::sort(&mut array);
+
+        assert!(array.windows(2).all(|w| w[0] <= w[1]));
+        assert_eq!(array[0], i32::MIN);
+        assert_eq!(array[array.len() - 1], i32::MAX);
+    }
+
+    #[test]
+    fn test_spread_sort_many_duplicates() {
+        let mut array: Vec<i32> = (0..200).map(|x| (x * 7) % 5).collect();
+        SpreadSort::sort(&mut array);
+
+        let mut expected: Vec<i32> = (0..200).map(|x| (x * 7) % 5).collect();
+        expected.sort();
+        assert_eq!(array, expected);
+    }
+
+    #[test]
+    fn test_spread_sort_empty() {
+        let mut array: Vec<i32> = vec![];
+        let events = SpreadSort::sort(&mut array);
+
+        assert!(array.is_empty());
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_spread_sort_single() {
+        let mut array = vec![42];
+        let events = SpreadSort::sort(&mut array);
+
+        assert_eq!(array, vec![42]);
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_spread_sort_small_input_uses_introsort() {
+        let input = vec![9, 4, 7, 1, 8, 2, 6, 3, 5, 0];
+
+        let mut spread = input.clone();
+        let spread_events = SpreadSort::sort(&mut spread);
+
+        let mut intro = input;
+        let intro_events = crate::pregen::intro_sort::IntroSort::sort(&mut intro);
+
+        assert_eq!(spread, intro);
+        assert_eq!(spread_events, intro_events);
+    }
+}
@@ -0,0 +1,396 @@
+//! Burstsort implementation for V1 (Pregeneration) engine.
+//!
+//! Cache-conscious string sort built on a burst trie:
+//! 1. Strings are inserted one by one. Trie nodes branch on one character;
+//!    below them, strings collect in small unsorted buckets.
+//! 2. When a bucket grows past BURST_THRESHOLD it "bursts" into a new trie
+//!    node, and its strings are redistributed by their next character.
+//! 3. An in-order traversal visits the buckets in lexicographic order; each
+//!    is insertion sorted on the characters after its trie prefix.
+//!
+//! The trie holds positions of the untouched array, so event indices stay
+//! meaningful until the end. Every bucket assignment emits a Highlight of
+//! the assigned position tagged "bucket:<id>", where the id names the
+//! bucket (see `bucket_id`), under a Phase naming the trie level it
+//! happened on ("trie_level_0", "trie_level_1", ...). Compare
+//! events are only the string comparisons of bucket sorting, which runs in
+//! the "bucket_sort" phase. The final order is then applied with Swap
+//! events, one EnterRange/ExitRange per bucket.
+
+use crate::prelude::*;
+use crate::events::SortEvent;
+use super::PregenStringSort;
+
+pub struct Burstsort;
+
+/// Buckets holding more strings than this burst into a trie node.
+const BURST_THRESHOLD: usize = 8;
+
+/// Children per trie node: one per byte, plus one for "string ended here".
+const FANOUT: usize = 257;
+
+/// Highlight tag prefix of bucket assignment events.
+pub const BUCKET_TAG: &str = "bucket";
+
+/// Id of bucket `c` of trie node `node`, unique within one sort.
+pub fn bucket_id(node: usize, c: usize) -> usize {
+    node * FANOUT + c
+}
+
+/// Assignment of the string at `pos` to a bucket.
+fn assignment(pos: usize, node: usize, c: usize) -> SortEvent {
+    SortEvent::highlight(vec![pos], &format!("{}:{}", BUCKET_TAG, bucket_id(node, c)))
+}
+
+/// Phase of the bucket sorting pass.
+pub const PHASE_BUCKET_SORT: &str = "bucket_sort";
+
+impl PregenStringSort for Burstsort {
+    fn sort(array: &mut [String]) -> Vec<SortEvent> {
+        let mut events = Vec::new();
+        let n = array.len();
+
+        if n > 1 {
+            let mut trie = BurstTrie::new();
+            for pos in 0..n {
+                trie.insert(array, pos, &mut events);
+            }
+
+            events.push(SortEvent::Phase { name: PHASE_BUCKET_SORT.to_string() });
+            let buckets = trie.sorted_buckets(array, &mut events);
+            apply_order(array, &buckets, &mut events);
+        }
+
+        events.push(SortEvent::Done);
+        events
+    }
+}
+
+enum Child {
+    Bucket(Vec<usize>),
+    Node(usize),
+}
+
+struct Node {
+    children: Vec<Child>,
+}
+
+impl Node {
+    fn new() -> Self {
+        Self {
+            children: (0..FANOUT).map(|_| Child::Bucket(Vec::new())).collect(),
+        }
+    }
+}
+
+struct BurstTrie {
+    nodes: Vec<Node>,
+    /// Trie level of the last Phase event emitted.
+    level: Option<usize>,
+}
+
+/// Child slot for the character at `depth`: 0 past the end, else byte + 1.
+fn slot(s: &str, depth: usize) -> usize {
+    s.as_bytes().get(depth).map_or(0, |&b| b as usize + 1)
+}
+
+impl BurstTrie {
+    fn new() -> Self {
+        Self {
+            nodes: vec![Node::new()],
+            level: None,
+        }
+    }
+
+    /// Emit a Phase for trie level `depth` unless it is the current one.
+    fn enter_level(&mut self, depth: usize, events: &mut Vec<SortEvent>) {
+        if self.level != Some(depth) {
+            self.level = Some(depth);
+            events.push(SortEvent::Phase { name: format!("trie_level_{}", depth) });
+        }
+    }
+
+    /// Assign the string at `pos` to a bucket, bursting it if it overflows.
+    fn insert(&mut self, array: &[String], pos: usize, events: &mut Vec<SortEvent>) {
+        let mut node = 0;
+        let mut depth = 0;
+
+        loop {
+            let c = slot(&array[pos], depth);
+            if let Child::Node(next) = self.nodes[node].children[c] {
+                node = next;
+                depth += 1;
+                continue;
+            }
+
+            self.enter_level(depth, events);
+            if let Child::Bucket(bucket) = &mut self.nodes[node].children[c] {
+                bucket.push(pos);
+                events.push(assignment(pos, node, c));
+                // The end-of-string bucket holds equal strings and never bursts
+                if c != 0 && bucket.len() > BURST_THRESHOLD {
+                    self.burst(array, node, c, depth + 1, events);
+                }
+            }
+            return;
+        }
+    }
+
+    /// Replace bucket `c` of `node` with a new node one character deeper.
+    fn burst(
+        &mut self,
+        array: &[String],
+        node: usize,
+        c: usize,
+        depth: usize,
+        events: &mut Vec<SortEvent>,
+    ) {
+        let new_id = self.nodes.len();
+        let old = core::mem::replace(&mut self.nodes[node].children[c], Child::Node(new_id));
+        let mut new_node = Node::new();
+
+        if let Child::Bucket(bucket) = old {
+            self.enter_level(depth, events);
+            for pos in bucket {
+                let c = slot(&array[pos], depth);
+                if let Child::Bucket(b) = &mut new_node.children[c] {
+                    b.push(pos);
+                    events.push(assignment(pos, new_id, c));
+                }
+            }
+        }
+
+        self.nodes.push(new_node);
+
+        // A bucket can still overflow if every string shares the next character
+        for c in 1..FANOUT {
+            let overflowing = matches!(
+                &self.nodes[new_id].children[c],
+                Child::Bucket(b) if b.len() > BURST_THRESHOLD
+            );
+            if overflowing {
+                self.burst(array, new_id, c, depth + 1, events);
+            }
+        }
+    }
+
+    /// Traverse the trie in order, sorting each bucket.
+    /// Returns the non-empty buckets in lexicographic order.
+    fn sorted_buckets(&mut self, array: &[String], events: &mut Vec<SortEvent>) -> Vec<Vec<usize>> {
+        let mut buckets = Vec::new();
+        self.collect(0, 0, array, &mut buckets, events);
+        buckets
+    }
+
+    fn collect(
+        &mut self,
+        node: usize,
+        depth: usize,
+        array: &[String],
+        buckets: &mut Vec<Vec<usize>>,
+        events: &mut Vec<SortEvent>,
+    ) {
+        for c in 0..FANOUT {
+            match &mut self.nodes[node].children[c] {
+                Child::Node(next) => {
+                    let next = *next;
+                    self.collect(next, depth + 1, array, buckets, events);
+                }
+                Child::Bucket(bucket) if !bucket.is_empty() => {
+                    let mut bucket = core::mem::take(bucket);
+                    // Strings in the end-of-string bucket are all equal
+                    if c != 0 {
+                        sort_bucket(array, &mut bucket, depth + 1, events);
+                    }
+                    buckets.push(bucket);
+                }
+                Child::Bucket(_) => {}
+            }
+        }
+    }
+}
+
+/// Insertion sort bucket positions by their suffixes from `depth`.
+fn sort_bucket(array: &[String], bucket: &mut [usize], depth: usize, events: &mut Vec<SortEvent>) {
+    for i in 1..bucket.len() {
+        let mut j = i;
+        while j > 0 {
+            events.push(SortEvent::Compare {
+                i: bucket[j - 1],
+                j: bucket[j],
+            });
+            if array[bucket[j - 1]].as_bytes()[depth..] <= array[bucket[j]].as_bytes()[depth..] {
+                break;
+            }
+            bucket.swap(j - 1, j);
+            j -= 1;
+        }
+    }
+}
+
+/// Move strings into bucket order with swaps, one range per bucket.
+fn apply_order(array: &mut [String], buckets: &[Vec<usize>], events: &mut Vec<SortEvent>) {
+    let n = array.len();
+    // where_is[p]: current index of the string originally at p; who_is[i]: the reverse
+    let mut where_is: Vec<usize> = (0..n).collect();
+    let mut who_is: Vec<usize> = (0..n).collect();
+    let mut target = 0;
+
+    for bucket in buckets {
+        let lo = target;
+        events.push(SortEvent::EnterRange { lo, hi: lo + bucket.len() - 1 });
+
+        for &pos in bucket {
+            let current = where_is[pos];
+            if current != target {
+                events.push(SortEvent::Swap { i: target, j: current });
+                array.swap(target, current);
+
+                let displaced = who_is[target];
+                where_is[displaced] = current;
+                who_is[current] = displaced;
+                where_is[pos] = target;
+                who_is[target] = pos;
+            }
+            target += 1;
+        }
+
+        events.push(SortEvent::ExitRange { lo, hi: target - 1 });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(words: &[&str]) -> Vec<String> {
+        words.iter().map(|w| w.to_string()).collect()
+    }
+
+    fn sorted(words: &[String]) -> Vec<String> {
+        let mut expected = words.to_vec();
+        expected.sort();
+        expected
+    }
+
+    #[test]
+    fn test_burstsort_basic() {
+        let mut array = strings(&["she", "sells", "seashells", "by", "the", "sea", "shore"]);
+        let expected = sorted(&array);
+        let events = Burstsort::sort(&mut array);
+
+        assert_eq!(array, expected);
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_burstsort_bursts_large_buckets() {
+        let mut array: Vec<String> = (0..300).map(|i| format!("key{}", (i * 7919) % 1000)).collect();
+        let expected = sorted(&array);
+        let events = Burstsort::sort(&mut array);
+
+        assert_eq!(array, expected);
+        let bucket_count = events.iter().filter(|e| matches!(e, SortEvent::EnterRange { .. })).count();
+        assert!(bucket_count > 1);
+    }
+
+    #[test]
+    fn test_burstsort_assignment_events() {
+        let mut array: Vec<String> = (0..300).map(|i| format!("key{}", (i * 7919) % 1000)).collect();
+        let events = Burstsort::sort(&mut array);
+
+        // Compares are real string comparisons, never self-compares
+        assert!(events.iter().all(|e| !matches!(e, SortEvent::Compare { i, j } if i == j)));
+
+        // Every string is assigned at least once, each event naming its bucket
+        let assignments: Vec<(usize, &str)> = events
+            .iter()
+            .filter_map(|e| match e {
+                SortEvent::Highlight(h) => {
+                    assert_eq!(h.indices.len(), 1);
+                    Some((h.indices[0], h.tag.strip_prefix("bucket:")?))
+                }
+                _ => None,
+            })
+            .collect();
+        let assigned: std::collections::HashSet<usize> =
+            assignments.iter().map(|&(pos, _)| pos).collect();
+        assert_eq!(assigned.len(), array.len());
+        let buckets: std::collections::HashSet<&str> =
+            assignments.iter().map(|&(_, id)| id).collect();
+        assert!(buckets.len() > 1 && buckets.len() < assignments.len());
+
+        let phases: Vec<&str> = events
+            .iter()
+            .filter_map(|e| match e {
+                SortEvent::Phase { name } => Some(name.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(phases[0], "trie_level_0");
+        assert!(phases.contains(&"trie_level_1"));
+        assert_eq!(phases.last(), Some(&PHASE_BUCKET_SORT));
+    }
+
+    #[test]
+    fn test_burstsort_duplicates_keep_trace_linear() {
+        let n = 20_000;
+        let mut array = vec!["same".to_string(); n];
+        let events = Burstsort::sort(&mut array);
+
+        // The end-of-string bucket never bursts; its assignments must not
+        // repeat the whole bucket
+        let highlighted: usize = events
+            .iter()
+            .map(|e| match e {
+                SortEvent::Highlight(h) => h.indices.len(),
+                _ => 0,
+            })
+            .sum();
+        assert!(highlighted <= 2 * n, "{}", highlighted);
+        assert!(events.len() <= 3 * n, "{}", events.len());
+    }
+
+    #[test]
+    fn test_burstsort_shared_prefixes_and_empty() {
+        let mut array: Vec<String> = (0..40).map(|i| "a".repeat(i % 13)).collect();
+        let expected = sorted(&array);
+        Burstsort::sort(&mut array);
+
+        assert_eq!(array, expected);
+    }
+
+    #[test]
+    fn test_burstsort_empty() {
+        let mut array: Vec<String> = vec![];
+        let events = Burstsort::sort(&mut array);
+
+        assert!(array.is_empty());
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_burstsort_single() {
+        let mut array = strings(&["only"]);
+        let events = Burstsort::sort(&mut array);
+
+        assert_eq!(array, strings(&["only"]));
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_burstsort_events_replay_to_sorted() {
+        let input: Vec<String> = (0..100).map(|i| format!("{:x}", (i * 2654435761u64) % 4096)).collect();
+        let mut array = input.clone();
+        let events = Burstsort::sort(&mut array);
+
+        let mut replay = input;
+        for event in &events {
+            if let SortEvent::Swap { i, j } = event {
+                replay.swap(*i, *j);
+            }
+        }
+        assert_eq!(replay, array);
+        assert_eq!(array, sorted(&array));
+    }
+}
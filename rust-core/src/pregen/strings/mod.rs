@@ -0,0 +1,62 @@
+//! String sorting algorithms for the V1 (Pregeneration) engine.
+//!
+//! Events refer to positions in the string array, so only index-based
+//! events are emitted (Compare, Swap, EnterRange, ExitRange, Done).
+//! Overwrite carries i32 values and is never used here.
+
+pub mod burstsort;
+pub mod multikey_quicksort;
+
+use crate::prelude::*;
+use crate::events::SortEvent;
+
+/// Trait for pregeneration string sorting algorithms.
+pub trait PregenStringSort {
+    /// Sort the strings lexicographically (by bytes) and return all events.
+    /// The array is modified in place.
+    fn sort(array: &mut [String]) -> Vec<SortEvent>;
+}
+
+/// Available string sorting algorithms for V1 engine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StringAlgorithm {
+    MultikeyQuicksort,
+    Burstsort,
+}
+
+impl StringAlgorithm {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StringAlgorithm::MultikeyQuicksort => "multikey_quicksort",
+            StringAlgorithm::Burstsort => "burstsort",
+        }
+    }
+
+    pub fn all() -> &'static [StringAlgorithm] {
+        const ALGORITHMS: [StringAlgorithm; 2] = [
+            StringAlgorithm::MultikeyQuicksort,
+            StringAlgorithm::Burstsort,
+        ];
+        &ALGORITHMS
+    }
+
+    /// Parse algorithm name from string.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<StringAlgorithm> {
+        match s.to_lowercase().as_str() {
+            "multikey_quicksort" | "multikey" | "mkqs" | "three_way_radix_quicksort" => {
+                Some(StringAlgorithm::MultikeyQuicksort)
+            }
+            "burstsort" | "burst" | "burst_sort" => Some(StringAlgorithm::Burstsort),
+            _ => None,
+        }
+    }
+}
+
+/// Run a pregeneration string sort on the given array.
+pub fn pregen_sort_strings(algorithm: StringAlgorithm, array: &mut [String]) -> Vec<SortEvent> {
+    match algorithm {
+        StringAlgorithm::MultikeyQuicksort => multikey_quicksort::MultikeyQuicksort::sort(array),
+        StringAlgorithm::Burstsort => burstsort::Burstsort::sort(array),
+    }
+}
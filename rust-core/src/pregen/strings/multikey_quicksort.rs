@@ -0,0 +1,206 @@
+//! Multi-key Quicksort implementation for V1 (Pregeneration) engine.
+//!
+//! Bentley–Sedgewick three-way radix quicksort for strings. Each pass looks
+//! at a single character position (depth) and partitions around the pivot
+//! string's character into less / equal / greater parts. The less and
+//! greater parts are sorted at the same depth; the equal part moves on to
+//! the next character, so common prefixes are never compared twice.
+//!
+//! Every character comparison emits a Compare event against the pivot,
+//! which sits at the start of the range during partitioning.
+
+use crate::prelude::*;
+use crate::events::SortEvent;
+use super::PregenStringSort;
+
+pub struct MultikeyQuicksort;
+
+impl PregenStringSort for MultikeyQuicksort {
+    fn sort(array: &mut [String]) -> Vec<SortEvent> {
+        let mut events = Vec::new();
+        let n = array.len();
+
+        if n > 1 {
+            multikey_sort(array, 0, n, 0, &mut events);
+        }
+
+        events.push(SortEvent::Done);
+        events
+    }
+}
+
+/// Character at `depth`, or -1 past the end of the string.
+fn char_at(s: &str, depth: usize) -> i32 {
+    s.as_bytes().get(depth).map_or(-1, |&b| b as i32)
+}
+
+fn swap(array: &mut [String], i: usize, j: usize, events: &mut Vec<SortEvent>) {
+    if i != j {
+        events.push(SortEvent::Swap { i, j });
+        array.swap(i, j);
+    }
+}
+
+/// Swap the blocks [a, a + len) and [b, b + len).
+fn swap_block(array: &mut [String], a: usize, b: usize, len: usize, events: &mut Vec<SortEvent>) {
+    for k in 0..len {
+        swap(array, a + k, b + k, events);
+    }
+}
+
+/// Sort array[lo..hi], where all strings share their first `depth` characters.
+fn multikey_sort(
+    array: &mut [String],
+    lo: usize,
+    hi: usize,
+    depth: usize,
+    events: &mut Vec<SortEvent>,
+) {
+    if hi <= lo + 1 {
+        return;
+    }
+
+    events.push(SortEvent::EnterRange { lo, hi: hi - 1 });
+
+    // Middle string as pivot, moved to the front
+    swap(array, lo, lo + (hi - lo) / 2, events);
+    let pivot = char_at(&array[lo], depth);
+
+    // Invariant: [lo, a) and (d, hi) equal the pivot, [a, b) less, (c, d] greater
+    let mut a = lo + 1;
+    let mut b = lo + 1;
+    let mut c = hi - 1;
+    let mut d = hi - 1;
+
+    loop {
+        while b <= c {
+            events.push(SortEvent::Compare { i: b, j: lo });
+            let ch = char_at(&array[b], depth);
+            if ch > pivot {
+                break;
+            }
+            if ch == pivot {
+                swap(array, a, b, events);
+                a += 1;
+            }
+            b += 1;
+        }
+
+        while b <= c {
+            events.push(SortEvent::Compare { i: c, j: lo });
+            let ch = char_at(&array[c], depth);
+            if ch < pivot {
+                break;
+            }
+            if ch == pivot {
+                swap(array, c, d, events);
+                d -= 1;
+            }
+            c -= 1;
+        }
+
+        if b > c {
+            break;
+        }
+
+        swap(array, b, c, events);
+        b += 1;
+        c -= 1;
+    }
+
+    // Move the equal blocks from both ends into the middle
+    let left = (a - lo).min(b - a);
+    swap_block(array, lo, b - left, left, events);
+    let right = (d - c).min(hi - 1 - d);
+    swap_block(array, b, hi - right, right, events);
+
+    let less_end = lo + (b - a);
+    let greater_start = hi - (d - c);
+
+    events.push(SortEvent::ExitRange { lo, hi: hi - 1 });
+
+    multikey_sort(array, lo, less_end, depth, events);
+    // Strings that ended at this depth are all equal; only longer ones continue
+    if pivot >= 0 {
+        multikey_sort(array, less_end, greater_start, depth + 1, events);
+    }
+    multikey_sort(array, greater_start, hi, depth, events);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(words: &[&str]) -> Vec<String> {
+        words.iter().map(|w| w.to_string()).collect()
+    }
+
+    fn sorted(words: &[&str]) -> Vec<String> {
+        let mut expected = strings(words);
+        expected.sort();
+        expected
+    }
+
+    #[test]
+    fn test_multikey_basic() {
+        let words = ["she", "sells", "seashells", "by", "the", "sea", "shore"];
+        let mut array = strings(&words);
+        let events = MultikeyQuicksort::sort(&mut array);
+
+        assert_eq!(array, sorted(&words));
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_multikey_prefixes_and_empty_strings() {
+        let words = ["abc", "", "ab", "a", "abcd", "", "b", "abc"];
+        let mut array = strings(&words);
+        MultikeyQuicksort::sort(&mut array);
+
+        assert_eq!(array, sorted(&words));
+    }
+
+    #[test]
+    fn test_multikey_many_duplicates() {
+        let words: Vec<&str> = (0..100).map(|i| ["cat", "car", "cart", "dog"][i % 4]).collect();
+        let mut array = strings(&words);
+        MultikeyQuicksort::sort(&mut array);
+
+        assert_eq!(array, sorted(&words));
+    }
+
+    #[test]
+    fn test_multikey_empty() {
+        let mut array: Vec<String> = vec![];
+        let events = MultikeyQuicksort::sort(&mut array);
+
+        assert!(array.is_empty());
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_multikey_single() {
+        let mut array = strings(&["only"]);
+        let events = MultikeyQuicksort::sort(&mut array);
+
+        assert_eq!(array, strings(&["only"]));
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_multikey_events_replay_to_sorted() {
+        let words = ["delta", "alpha", "charlie", "bravo", "alpha", "echo", "al"];
+        let mut array = strings(&words);
+        let events = MultikeyQuicksort::sort(&mut array);
+
+        // Only index-based events, so replaying swaps on the input reproduces the output
+        let mut replay = strings(&words);
+        for event in &events {
+            assert!(!matches!(event, SortEvent::Overwrite { .. }));
+            if let SortEvent::Swap { i, j } = event {
+                replay.swap(*i, *j);
+            }
+        }
+        assert_eq!(replay, array);
+    }
+}
@@ -1,178 +1,627 @@
 //! Tim Sort implementation for V1 (Pregeneration) engine.
 //!
-//! Hybrid sorting algorithm derived from merge sort and insertion sort.
-//! Used in Python's sort() and Java's Arrays.sort(). Divides the array
-//! into small "runs" which are sorted with insertion sort, then merged.
+//! The merge sort of Python's list.sort() and Java's Arrays.sort() for
+//! objects, following Java's TimSort:
+//! - The array is scanned for natural runs. Strictly descending runs are
+//!   reversed in place; runs shorter than minrun (16..=32, chosen so n/minrun
+//!   is close to a power of two) are extended with binary insertion sort.
+//! - Runs are pushed on a stack whose lengths must keep growing like the
+//!   Fibonacci numbers from the top down (len[i] > len[i+1] + len[i+2] and
+//!   len[i+1] > len[i+2]); adjacent runs are merged until that holds again.
+//! - A merge first gallops to skip the prefix of the left run and the suffix
+//!   of the right run that are already in place, then copies the smaller run
+//!   out and merges from that end. When one side keeps winning, the merge
+//!   switches to galloping mode and copies whole blocks at a time.
+//!
+//! Phase events mark run detection ("run"), merging ("merge") and galloping
+//! mode ("gallop").
 
-use super::PregenSort;
-use crate::events::SortEvent;
+use super::context::{copied, Scratch};
+use super::{estimate, AlgorithmInfo, PregenSort};
+use crate::prelude::*;
+use crate::events::{EventSink, SortEvent};
+use crate::rotation;
 
 pub struct Timsort;
 
-/// Minimum run size. Smaller runs use insertion sort.
-const MIN_RUN: usize = 32;
+/// Arrays shorter than this are sorted with one binary insertion sort.
+const MIN_MERGE: usize = 32;
+
+/// Initial number of consecutive wins that switches a merge to galloping.
+const MIN_GALLOP: usize = 7;
 
 impl PregenSort for Timsort {
-    fn sort(array: &mut [i32]) -> Vec<SortEvent> {
-        let mut events = Vec::new();
+    const INFO: AlgorithmInfo = AlgorithmInfo {
+        display_name: "Timsort",
+        best: "O(n)",
+        average: "O(n log n)",
+        worst: "O(n log n)",
+        space: "O(n)",
+        stable: true,
+        in_place: false,
+        adaptive: true,
+        description: "Merges natural runs under stack invariants, galloping through long streaks.",
+    };
+
+    fn estimated_events(n: usize) -> usize {
+        estimate::n_log_n(n, 2.1)
+    }
+
+    fn sort_into(array: &mut [i32], events: &mut impl EventSink) {
+        Self::sort_into_with(array, events, &mut Scratch::new());
+    }
+
+    fn sort_into_with(array: &mut [i32], events: &mut impl EventSink, scratch: &mut Scratch) {
         let n = array.len();
 
-        if n <= 1 {
-            events.push(SortEvent::Done);
-            return events;
+        if n > 1 {
+            let mut sort = TimSortState {
+                array,
+                events,
+                tmp: &mut scratch.values,
+                runs: Vec::new(),
+                min_gallop: MIN_GALLOP,
+                phase: None,
+            };
+            sort.sort();
+        }
+
+        events.push(SortEvent::Done);
+    }
+}
+
+/// A run on the merge stack: `len` sorted elements starting at `base`.
+#[derive(Debug, Clone, Copy)]
+struct Run {
+    base: usize,
+    len: usize,
+}
+
+struct TimSortState<'a, S: EventSink> {
+    array: &'a mut [i32],
+    events: &'a mut S,
+    /// Buffer the smaller run of a merge is copied out to.
+    tmp: &'a mut Vec<i32>,
+    runs: Vec<Run>,
+    /// Wins in a row that switch to galloping; adapts to the data.
+    min_gallop: usize,
+    phase: Option<&'static str>,
+}
+
+impl<S: EventSink> TimSortState<'_, S> {
+    fn sort(&mut self) {
+        let n = self.array.len();
+
+        self.enter_phase("run");
+        if n < MIN_MERGE {
+            let run_len = self.count_run_and_make_ascending(0, n);
+            self.binary_sort(0, n, run_len);
+            return;
         }
 
-        // Sort small runs with insertion sort
         let min_run = min_run_length(n);
+        let mut lo = 0;
+        while lo < n {
+            self.enter_phase("run");
+            let mut run_len = self.count_run_and_make_ascending(lo, n);
+
+            // Extend short runs to min(min_run, what is left)
+            if run_len < min_run {
+                let force = min_run.min(n - lo);
+                self.binary_sort(lo, lo + force, lo + run_len);
+                run_len = force;
+            }
+
+            self.runs.push(Run { base: lo, len: run_len });
+            self.merge_collapse();
+            lo += run_len;
+        }
+
+        self.merge_force_collapse();
+        debug_assert_eq!(self.runs.len(), 1);
+    }
+
+    fn enter_phase(&mut self, name: &'static str) {
+        if self.phase != Some(name) {
+            self.events.push(SortEvent::Phase { name: name.to_string() });
+            self.phase = Some(name);
+        }
+    }
+
+    fn compare(&mut self, i: usize, j: usize) {
+        self.events.push(SortEvent::Compare { i, j });
+    }
+
+    fn write(&mut self, idx: usize, value: i32) {
+        if self.array[idx] != value {
+            self.events.push(SortEvent::Overwrite {
+                idx,
+                old_val: self.array[idx],
+                new_val: value,
+            });
+            self.array[idx] = value;
+        }
+    }
+
+    /// Copy `count` elements from `src` to `dst` within the array, in the
+    /// direction that doesn't clobber the source.
+    fn move_within(&mut self, src: usize, dst: usize, count: usize) {
+        if dst < src {
+            for k in 0..count {
+                self.write(dst + k, self.array[src + k]);
+            }
+        } else {
+            for k in (0..count).rev() {
+                self.write(dst + k, self.array[src + k]);
+            }
+        }
+    }
 
-        for start in (0..n).step_by(min_run) {
-            let end = (start + min_run - 1).min(n - 1);
-            insertion_sort_range(array, start, end, &mut events);
-        }
-
-        // Merge runs
-        let mut size = min_run;
-        while size < n {
-            for left in (0..n).step_by(2 * size) {
-                let mid = (left + size - 1).min(n - 1);
-                let right = (left + 2 * size - 1).min(n - 1);
-
-                if mid < right {
-                    events.push(SortEvent::EnterRange {
-                        lo: left,
-                        hi: right,
-                    });
-                    merge(array, left, mid, right, &mut events);
-                    events.push(SortEvent::ExitRange {
-                        lo: left,
-                        hi: right,
-                    });
+    /// Length of the run starting at lo, reversing it if it is strictly
+    /// descending. Strictness keeps the reversal stable.
+    fn count_run_and_make_ascending(&mut self, lo: usize, hi: usize) -> usize {
+        let mut run_hi = lo + 1;
+        if run_hi == hi {
+            return 1;
+        }
+
+        self.compare(lo, run_hi);
+        if self.array[run_hi] < self.array[lo] {
+            run_hi += 1;
+            while run_hi < hi {
+                self.compare(run_hi - 1, run_hi);
+                if self.array[run_hi] >= self.array[run_hi - 1] {
+                    break;
+                }
+                run_hi += 1;
+            }
+            rotation::reverse(self.array, lo, run_hi, self.events);
+        } else {
+            run_hi += 1;
+            while run_hi < hi {
+                self.compare(run_hi - 1, run_hi);
+                if self.array[run_hi] < self.array[run_hi - 1] {
+                    break;
                 }
+                run_hi += 1;
             }
-            size *= 2;
         }
 
-        events.push(SortEvent::Done);
-        events
+        run_hi - lo
     }
-}
 
-/// Calculate minimum run length.
-fn min_run_length(mut n: usize) -> usize {
-    let mut r = 0;
-    while n >= MIN_RUN {
-        r |= n & 1;
-        n >>= 1;
+    /// Binary insertion sort of [lo, hi), whose prefix [lo, start) is sorted.
+    fn binary_sort(&mut self, lo: usize, hi: usize, start: usize) {
+        for i in start.max(lo + 1)..hi {
+            let pivot = self.array[i];
+
+            // Rightmost position keeps equal elements in order
+            let (mut left, mut right) = (lo, i);
+            while left < right {
+                let mid = left + (right - left) / 2;
+                self.compare(i, mid);
+                if pivot < self.array[mid] {
+                    right = mid;
+                } else {
+                    left = mid + 1;
+                }
+            }
+
+            for j in (left..i).rev() {
+                self.write(j + 1, self.array[j]);
+            }
+            self.write(left, pivot);
+        }
     }
-    n + r
-}
 
-/// Insertion sort for a range [lo, hi].
-fn insertion_sort_range(array: &mut [i32], lo: usize, hi: usize, events: &mut Vec<SortEvent>) {
-    for i in (lo + 1)..=hi {
-        let value = array[i];
-        let mut j = i;
-
-        while j > lo {
-            events.push(SortEvent::Compare { i: j - 1, j });
-
-            if array[j - 1] > value {
-                events.push(SortEvent::Overwrite {
-                    idx: j,
-                    old_val: array[j],
-                    new_val: array[j - 1],
-                });
-                array[j] = array[j - 1];
-                j -= 1;
-            } else {
+    /// Merge adjacent runs until the stack invariants hold again. Checks the
+    /// top four runs, the fix from "OpenJDK's java.utils.Collection.sort() is
+    /// broken" (de Gouw et al., 2015).
+    fn merge_collapse(&mut self) {
+        while self.runs.len() > 1 {
+            let mut n = self.runs.len() - 2;
+            let len = |k: usize| self.runs[k].len;
+            if (n > 0 && len(n - 1) <= len(n) + len(n + 1))
+                || (n > 1 && len(n - 2) <= len(n - 1) + len(n))
+            {
+                if len(n - 1) < len(n + 1) {
+                    n -= 1;
+                }
+            } else if len(n) > len(n + 1) {
                 break;
             }
+            self.merge_at(n);
         }
+        debug_assert!(self.invariants_hold(), "{:?}", self.runs);
+    }
 
-        if j != i {
-            events.push(SortEvent::Overwrite {
-                idx: j,
-                old_val: array[j],
-                new_val: value,
-            });
-            array[j] = value;
+    /// Whether every run is longer than the next, and than the next two
+    /// together.
+    fn invariants_hold(&self) -> bool {
+        let lens: Vec<usize> = self.runs.iter().map(|run| run.len).collect();
+        lens.windows(2).all(|w| w[0] > w[1]) && lens.windows(3).all(|w| w[0] > w[1] + w[2])
+    }
+
+    /// Merge everything left on the stack, once all runs have been found.
+    fn merge_force_collapse(&mut self) {
+        while self.runs.len() > 1 {
+            let mut n = self.runs.len() - 2;
+            if n > 0 && self.runs[n - 1].len < self.runs[n + 1].len {
+                n -= 1;
+            }
+            self.merge_at(n);
         }
     }
-}
 
-/// Merge two sorted subarrays [lo..mid] and [mid+1..hi].
-fn merge(array: &mut [i32], lo: usize, mid: usize, hi: usize, events: &mut Vec<SortEvent>) {
-    let left: Vec<i32> = array[lo..=mid].to_vec();
-    let right: Vec<i32> = array[mid + 1..=hi].to_vec();
-
-    let mut i = 0;
-    let mut j = 0;
-    let mut k = lo;
-
-    while i < left.len() && j < right.len() {
-        // Compare indices in original array for visualization
-        let left_idx = lo + i;
-        let right_idx = mid + 1 + j;
-        events.push(SortEvent::Compare {
-            i: left_idx.min(hi),
-            j: right_idx.min(hi),
-        });
-
-        if left[i] <= right[j] {
-            if array[k] != left[i] {
-                events.push(SortEvent::Overwrite {
-                    idx: k,
-                    old_val: array[k],
-                    new_val: left[i],
-                });
+    /// Merge runs i and i + 1 of the stack.
+    fn merge_at(&mut self, i: usize) {
+        let Run { base: mut base1, len: mut len1 } = self.runs[i];
+        let Run { base: base2, len: mut len2 } = self.runs[i + 1];
+        self.runs[i].len = len1 + len2;
+        self.runs.remove(i + 1);
+
+        self.enter_phase("merge");
+        let (lo, hi) = (base1, base2 + len2 - 1);
+        self.events.push(SortEvent::EnterRange { lo, hi });
+
+        // Elements of run 1 that are <= run 2's first are already in place
+        let key = self.array[base2];
+        let k = gallop(key, base2, &self.array[base1..base1 + len1], base1, 0, true, self.events);
+        base1 += k;
+        len1 -= k;
+
+        // As are elements of run 2 that are >= run 1's last
+        if len1 > 0 {
+            let key_idx = base1 + len1 - 1;
+            let key = self.array[key_idx];
+            let run2 = &self.array[base2..base2 + len2];
+            len2 = gallop(key, key_idx, run2, base2, len2 - 1, false, self.events);
+
+            if len2 > 0 {
+                let mut tmp = core::mem::take(self.tmp);
+                if len1 <= len2 {
+                    copied(&mut tmp, &self.array[base1..base1 + len1]);
+                    self.merge_lo(&tmp, base1, len1, base2, len2);
+                } else {
+                    copied(&mut tmp, &self.array[base2..base2 + len2]);
+                    self.merge_hi(&tmp, base1, len1, base2, len2);
+                }
+                *self.tmp = tmp;
+            }
+        }
+
+        self.events.push(SortEvent::ExitRange { lo, hi });
+    }
+
+    /// Merge with run 1 copied out to `tmp`, filling from the left.
+    /// Requires len1 <= len2, array[base2] < array[base1] and run 1's last
+    /// element greater than run 2's last.
+    fn merge_lo(
+        &mut self,
+        tmp: &[i32],
+        base1: usize,
+        mut len1: usize,
+        base2: usize,
+        mut len2: usize,
+    ) {
+        // Slot each buffered element came from, for Compare events
+        let origin = |k: usize| base1 + k;
+        let (mut cursor1, mut cursor2, mut dest) = (0, base2, base1);
+
+        self.write(dest, self.array[cursor2]);
+        dest += 1;
+        cursor2 += 1;
+        len2 -= 1;
+        if len2 == 0 {
+            self.copy_from(&tmp[cursor1..cursor1 + len1], dest);
+            return;
+        }
+        if len1 == 1 {
+            self.move_within(cursor2, dest, len2);
+            self.write(dest + len2, tmp[cursor1]);
+            return;
+        }
+
+        let mut min_gallop = self.min_gallop as isize;
+        'outer: loop {
+            let (mut count1, mut count2) = (0, 0);
+
+            // One element at a time until one run wins min_gallop times in a row
+            loop {
+                self.compare(cursor2, origin(cursor1));
+                if self.array[cursor2] < tmp[cursor1] {
+                    self.write(dest, self.array[cursor2]);
+                    dest += 1;
+                    cursor2 += 1;
+                    count2 += 1;
+                    count1 = 0;
+                    len2 -= 1;
+                    if len2 == 0 {
+                        break 'outer;
+                    }
+                } else {
+                    self.write(dest, tmp[cursor1]);
+                    dest += 1;
+                    cursor1 += 1;
+                    count1 += 1;
+                    count2 = 0;
+                    len1 -= 1;
+                    if len1 == 1 {
+                        break 'outer;
+                    }
+                }
+                if (count1 | count2) as isize >= min_gallop {
+                    break;
+                }
+            }
+
+            // Galloping: copy whole blocks while either run keeps winning
+            self.enter_phase("gallop");
+            loop {
+                let key = self.array[cursor2];
+                let run1 = &tmp[cursor1..cursor1 + len1];
+                count1 = gallop(key, cursor2, run1, origin(cursor1), 0, true, self.events);
+                if count1 != 0 {
+                    self.copy_from(&tmp[cursor1..cursor1 + count1], dest);
+                    dest += count1;
+                    cursor1 += count1;
+                    len1 -= count1;
+                    if len1 <= 1 {
+                        break 'outer;
+                    }
+                }
+                self.write(dest, self.array[cursor2]);
+                dest += 1;
+                cursor2 += 1;
+                len2 -= 1;
+                if len2 == 0 {
+                    break 'outer;
+                }
+
+                let run2 = &self.array[cursor2..cursor2 + len2];
+                let key = tmp[cursor1];
+                count2 = gallop(key, origin(cursor1), run2, cursor2, 0, false, self.events);
+                if count2 != 0 {
+                    self.move_within(cursor2, dest, count2);
+                    dest += count2;
+                    cursor2 += count2;
+                    len2 -= count2;
+                    if len2 == 0 {
+                        break 'outer;
+                    }
+                }
+                self.write(dest, tmp[cursor1]);
+                dest += 1;
+                cursor1 += 1;
+                len1 -= 1;
+                if len1 == 1 {
+                    break 'outer;
+                }
+
+                min_gallop -= 1;
+                if count1 < MIN_GALLOP && count2 < MIN_GALLOP {
+                    break;
+                }
             }
-            array[k] = left[i];
-            i += 1;
+            min_gallop = min_gallop.max(0) + 2;
+            self.enter_phase("merge");
+        }
+        self.min_gallop = min_gallop.max(1) as usize;
+        self.enter_phase("merge");
+
+        if len1 == 1 {
+            self.move_within(cursor2, dest, len2);
+            self.write(dest + len2, tmp[cursor1]);
         } else {
-            if array[k] != right[j] {
-                events.push(SortEvent::Overwrite {
-                    idx: k,
-                    old_val: array[k],
-                    new_val: right[j],
-                });
+            // len1 == 0 can't happen with a consistent order
+            self.copy_from(&tmp[cursor1..cursor1 + len1], dest);
+        }
+    }
+
+    /// Merge with run 2 copied out to `tmp`, filling from the right.
+    /// Requires len1 > len2, array[base2] < array[base1] and run 1's last
+    /// element greater than run 2's last.
+    fn merge_hi(
+        &mut self,
+        tmp: &[i32],
+        base1: usize,
+        mut len1: usize,
+        base2: usize,
+        mut len2: usize,
+    ) {
+        let origin = |k: usize| base2 + k;
+        // Exclusive ends: run 1 is [base1, end1), the buffer tmp[..len2],
+        // and the next write goes to dest - 1
+        let mut end1 = base1 + len1;
+        let mut dest = base2 + len2;
+
+        end1 -= 1;
+        dest -= 1;
+        self.write(dest, self.array[end1]);
+        len1 -= 1;
+        if len1 == 0 {
+            self.copy_from(&tmp[..len2], dest - len2);
+            return;
+        }
+        if len2 == 1 {
+            dest -= len1;
+            end1 -= len1;
+            self.move_within(end1, dest, len1);
+            self.write(dest - 1, tmp[0]);
+            return;
+        }
+
+        let mut min_gallop = self.min_gallop as isize;
+        'outer: loop {
+            let (mut count1, mut count2) = (0, 0);
+
+            loop {
+                self.compare(origin(len2 - 1), end1 - 1);
+                if tmp[len2 - 1] < self.array[end1 - 1] {
+                    end1 -= 1;
+                    dest -= 1;
+                    self.write(dest, self.array[end1]);
+                    count1 += 1;
+                    count2 = 0;
+                    len1 -= 1;
+                    if len1 == 0 {
+                        break 'outer;
+                    }
+                } else {
+                    dest -= 1;
+                    self.write(dest, tmp[len2 - 1]);
+                    count2 += 1;
+                    count1 = 0;
+                    len2 -= 1;
+                    if len2 == 1 {
+                        break 'outer;
+                    }
+                }
+                if (count1 | count2) as isize >= min_gallop {
+                    break;
+                }
             }
-            array[k] = right[j];
-            j += 1;
+
+            self.enter_phase("gallop");
+            loop {
+                let key = tmp[len2 - 1];
+                let run1 = &self.array[base1..end1];
+                let at = gallop(key, origin(len2 - 1), run1, base1, len1 - 1, true, self.events);
+                count1 = len1 - at;
+                if count1 != 0 {
+                    dest -= count1;
+                    end1 -= count1;
+                    len1 -= count1;
+                    self.move_within(end1, dest, count1);
+                    if len1 == 0 {
+                        break 'outer;
+                    }
+                }
+                dest -= 1;
+                self.write(dest, tmp[len2 - 1]);
+                len2 -= 1;
+                if len2 == 1 {
+                    break 'outer;
+                }
+
+                let key = self.array[end1 - 1];
+                let run2 = &tmp[..len2];
+                let at = gallop(key, end1 - 1, run2, origin(0), len2 - 1, false, self.events);
+                count2 = len2 - at;
+                if count2 != 0 {
+                    dest -= count2;
+                    len2 -= count2;
+                    self.copy_from(&tmp[len2..len2 + count2], dest);
+                    if len2 <= 1 {
+                        break 'outer;
+                    }
+                }
+                end1 -= 1;
+                dest -= 1;
+                self.write(dest, self.array[end1]);
+                len1 -= 1;
+                if len1 == 0 {
+                    break 'outer;
+                }
+
+                min_gallop -= 1;
+                if count1 < MIN_GALLOP && count2 < MIN_GALLOP {
+                    break;
+                }
+            }
+            min_gallop = min_gallop.max(0) + 2;
+            self.enter_phase("merge");
+        }
+        self.min_gallop = min_gallop.max(1) as usize;
+        self.enter_phase("merge");
+
+        if len2 == 1 {
+            dest -= len1;
+            end1 -= len1;
+            self.move_within(end1, dest, len1);
+            self.write(dest - 1, tmp[0]);
+        } else {
+            // len2 == 0 can't happen with a consistent order
+            self.copy_from(&tmp[..len2], dest - len2);
         }
-        k += 1;
     }
 
-    // Copy remaining elements
-    while i < left.len() {
-        if array[k] != left[i] {
-            events.push(SortEvent::Overwrite {
-                idx: k,
-                old_val: array[k],
-                new_val: left[i],
-            });
+    /// Write buffered values to consecutive slots starting at `dest`.
+    fn copy_from(&mut self, values: &[i32], dest: usize) {
+        for (k, &value) in values.iter().enumerate() {
+            self.write(dest + k, value);
         }
-        array[k] = left[i];
-        i += 1;
-        k += 1;
     }
+}
 
-    while j < right.len() {
-        if array[k] != right[j] {
-            events.push(SortEvent::Overwrite {
-                idx: k,
-                old_val: array[k],
-                new_val: right[j],
-            });
+/// Where `key` goes in the sorted `run`, searching outward from `hint` in
+/// steps of 1, 3, 7, ... and then by bisection. With `after_equal` the
+/// position is after any equal elements (Java's gallopRight), otherwise
+/// before them (gallopLeft). `key_idx` and `origin` (the slot of run[0])
+/// are only used for the Compare events.
+fn gallop(
+    key: i32,
+    key_idx: usize,
+    run: &[i32],
+    origin: usize,
+    hint: usize,
+    after_equal: bool,
+    events: &mut impl EventSink,
+) -> usize {
+    let mut goes_after = |k: usize| {
+        events.push(SortEvent::Compare { i: key_idx, j: origin + k });
+        if after_equal {
+            key >= run[k]
+        } else {
+            key > run[k]
         }
-        array[k] = right[j];
-        j += 1;
-        k += 1;
+    };
+
+    // Bracket the answer in [lo, hi]
+    let (mut last_ofs, mut ofs) = (0, 1);
+    let (mut lo, mut hi) = if goes_after(hint) {
+        let max_ofs = run.len() - hint;
+        while ofs < max_ofs && goes_after(hint + ofs) {
+            last_ofs = ofs;
+            ofs = 2 * ofs + 1;
+        }
+        (hint + last_ofs + 1, hint + ofs.min(max_ofs))
+    } else {
+        let max_ofs = hint + 1;
+        while ofs < max_ofs && !goes_after(hint - ofs) {
+            last_ofs = ofs;
+            ofs = 2 * ofs + 1;
+        }
+        (hint + 1 - ofs.min(max_ofs), hint - last_ofs)
+    };
+
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if goes_after(mid) {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+    hi
+}
+
+/// Minimum run length: n itself below MIN_MERGE, otherwise a length in
+/// MIN_MERGE/2..=MIN_MERGE such that n / min_run is a power of two or
+/// slightly less than one.
+fn min_run_length(mut n: usize) -> usize {
+    let mut r = 0;
+    while n >= MIN_MERGE {
+        r |= n & 1;
+        n >>= 1;
     }
+    n + r
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::patterns::{self, Pattern};
 
     #[test]
     fn test_timsort_basic() {
@@ -227,6 +676,80 @@ mod tests {
         assert!(matches!(events.last(), Some(SortEvent::Done)));
     }
 
+    fn replay(input: &[i32], events: &[SortEvent]) -> Vec<i32> {
+        let mut array = input.to_vec();
+        for event in events {
+            event.apply(&mut array);
+        }
+        array
+    }
+
+    #[test]
+    fn test_timsort_all_patterns() {
+        for &pattern in Pattern::all() {
+            for n in [31, 32, 65, 200, 1000] {
+                for seed in 0..3 {
+                    let input = patterns::generate(pattern, n, seed);
+                    let mut array = input.clone();
+                    let events = Timsort::sort(&mut array);
+
+                    let mut expected = input.clone();
+                    expected.sort();
+                    assert_eq!(array, expected, "{:?} n={} seed={}", pattern, n, seed);
+                    assert_eq!(replay(&input, &events), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_timsort_reverses_descending_runs() {
+        let mut array: Vec<i32> = (0..100).rev().collect();
+        let events = Timsort::sort(&mut array);
+
+        // One strictly descending run: reversed with swaps, nothing to merge
+        let mut mutations = events.iter().filter(|e| e.is_mutation());
+        assert!(mutations.all(|e| matches!(e, SortEvent::Swap { .. })));
+        let swaps = events.iter().filter(|e| matches!(e, SortEvent::Swap { .. })).count();
+        assert_eq!(swaps, 50);
+    }
+
+    #[test]
+    fn test_timsort_sorted_input_is_one_scan() {
+        let mut array: Vec<i32> = (0..500).collect();
+        let events = Timsort::sort(&mut array);
+
+        let compares = events.iter().filter(|e| matches!(e, SortEvent::Compare { .. })).count();
+        assert_eq!(compares, 499);
+        assert!(!events.iter().any(|e| e.is_mutation()));
+    }
+
+    #[test]
+    fn test_timsort_phases() {
+        // Two interleaved ascending halves: long runs whose merge gallops
+        let mut array: Vec<i32> = (0..400).map(|x| if x < 200 { 2 * x } else { x / 8 }).collect();
+        let events = Timsort::sort(&mut array);
+
+        assert!(array.windows(2).all(|w| w[0] <= w[1]));
+        let phases: Vec<&str> = events
+            .iter()
+            .filter_map(|e| match e {
+                SortEvent::Phase { name } => Some(name.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(phases[..2], ["run", "merge"]);
+        assert!(phases.contains(&"gallop"));
+    }
+
+    #[test]
+    fn test_min_run_length() {
+        assert_eq!(min_run_length(31), 31);
+        assert_eq!(min_run_length(64), 16);
+        assert_eq!(min_run_length(65), 17);
+        assert!((16..=32).contains(&min_run_length(1000)));
+    }
+
     #[test]
     fn test_timsort_duplicates() {
         let mut array = vec![3, 1, 3, 2, 1];
@@ -0,0 +1,7 @@
+//! The alloc types the std prelude would provide, so that the core
+//! modules build the same with and without std.
+
+pub use alloc::boxed::Box;
+pub use alloc::string::{String, ToString};
+pub use alloc::vec::Vec;
+pub use alloc::{format, vec};
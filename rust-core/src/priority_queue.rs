@@ -0,0 +1,309 @@
+//! Priority queue (binary max-heap) operations on a visible array.
+//!
+//! The heap occupies the prefix [0, len) of a fixed-size array; everything
+//! after it stays visible but is not part of the heap. Each operation
+//! returns the events it produced, so single heap operations can be played
+//! back before moving on to the full heapsort:
+//! - `heapify` turns the whole array into a heap with sift-downs.
+//! - `push` writes a value into the first free slot and sifts it up.
+//! - `pop` swaps the root behind the heap and sifts the new root down,
+//!   leaving popped values in descending order after the heap, exactly as
+//!   heapsort does.
+//!
+//! Every operation is wrapped in an EnterRange/ExitRange over the heap.
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+use crate::events::SortEvent;
+
+pub struct PriorityQueue {
+    array: Vec<i32>,
+    len: usize,
+}
+
+impl PriorityQueue {
+    /// Create an empty heap over `array`. Its slots are the heap capacity.
+    pub fn new(array: Vec<i32>) -> Self {
+        Self { array, len: 0 }
+    }
+
+    /// Number of elements in the heap.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Number of slots in the visible array.
+    pub fn capacity(&self) -> usize {
+        self.array.len()
+    }
+
+    /// The visible array, including slots outside the heap.
+    pub fn array(&self) -> &[i32] {
+        &self.array
+    }
+
+    /// Largest element, if any.
+    pub fn peek(&self) -> Option<i32> {
+        if self.len > 0 {
+            Some(self.array[0])
+        } else {
+            None
+        }
+    }
+
+    /// Make the whole array a heap, bottom-up.
+    pub fn heapify(&mut self) -> Vec<SortEvent> {
+        let mut events = Vec::new();
+        self.len = self.array.len();
+
+        if self.len == 0 {
+            return events;
+        }
+
+        events.push(SortEvent::EnterRange { lo: 0, hi: self.len - 1 });
+        for i in (0..self.len / 2).rev() {
+            self.sift_down(i, &mut events);
+        }
+        events.push(SortEvent::ExitRange { lo: 0, hi: self.len - 1 });
+
+        events
+    }
+
+    /// Insert a value. Returns None if the heap is full.
+    pub fn push(&mut self, value: i32) -> Option<Vec<SortEvent>> {
+        if self.len == self.array.len() {
+            return None;
+        }
+
+        let mut events = Vec::new();
+        let idx = self.len;
+        self.len += 1;
+
+        events.push(SortEvent::EnterRange { lo: 0, hi: self.len - 1 });
+        if self.array[idx] != value {
+            events.push(SortEvent::Overwrite {
+                idx,
+                old_val: self.array[idx],
+                new_val: value,
+            });
+            self.array[idx] = value;
+        }
+        self.sift_up(idx, &mut events);
+        events.push(SortEvent::ExitRange { lo: 0, hi: self.len - 1 });
+
+        Some(events)
+    }
+
+    /// Remove the largest element, leaving it just behind the heap.
+    /// Returns None if the heap is empty.
+    pub fn pop(&mut self) -> Option<(i32, Vec<SortEvent>)> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let mut events = Vec::new();
+        let top = self.array[0];
+        let last = self.len - 1;
+
+        events.push(SortEvent::EnterRange { lo: 0, hi: last });
+        if last != 0 {
+            events.push(SortEvent::Swap { i: 0, j: last });
+            self.array.swap(0, last);
+        }
+        self.len -= 1;
+        self.sift_down(0, &mut events);
+        events.push(SortEvent::ExitRange { lo: 0, hi: last });
+
+        Some((top, events))
+    }
+
+    fn sift_up(&mut self, mut idx: usize, events: &mut Vec<SortEvent>) {
+        while idx > 0 {
+            let parent = (idx - 1) / 2;
+            events.push(SortEvent::Compare { i: parent, j: idx });
+
+            if self.array[idx] > self.array[parent] {
+                events.push(SortEvent::Swap { i: parent, j: idx });
+                self.array.swap(parent, idx);
+                idx = parent;
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn sift_down(&mut self, root: usize, events: &mut Vec<SortEvent>) {
+        let mut current = root;
+
+        loop {
+            let left = 2 * current + 1;
+            let right = 2 * current + 2;
+            let mut largest = current;
+
+            if left < self.len {
+                events.push(SortEvent::Compare { i: largest, j: left });
+                if self.array[left] > self.array[largest] {
+                    largest = left;
+                }
+            }
+
+            if right < self.len {
+                events.push(SortEvent::Compare { i: largest, j: right });
+                if self.array[right] > self.array[largest] {
+                    largest = right;
+                }
+            }
+
+            if largest != current {
+                events.push(SortEvent::Swap { i: current, j: largest });
+                self.array.swap(current, largest);
+                current = largest;
+            } else {
+                break;
+            }
+        }
+    }
+}
+
+/// Wasm-exposed priority queue for interactive heap demos.
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub struct LivePriorityQueue {
+    inner: PriorityQueue,
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl LivePriorityQueue {
+    /// Create an empty heap over the given array.
+    #[wasm_bindgen(constructor)]
+    pub fn new(array: JsValue) -> Result<LivePriorityQueue, JsValue> {
+        let arr: Vec<i32> = crate::events::js_to_array(array)?;
+        Ok(LivePriorityQueue {
+            inner: PriorityQueue::new(arr),
+        })
+    }
+
+    /// Make the whole array a heap, return events generated.
+    pub fn heapify(&mut self) -> Result<JsValue, JsValue> {
+        crate::events::events_to_js(&self.inner.heapify())
+    }
+
+    /// Insert a value, return events generated.
+    pub fn push(&mut self, value: i32) -> Result<JsValue, JsValue> {
+        let events = self
+            .inner
+            .push(value)
+            .ok_or_else(|| JsValue::from_str("Priority queue is full"))?;
+        crate::events::events_to_js(&events)
+    }
+
+    /// Remove the largest element, return events generated.
+    /// The removed value is left at index `len()`.
+    pub fn pop(&mut self) -> Result<JsValue, JsValue> {
+        let (_, events) = self
+            .inner
+            .pop()
+            .ok_or_else(|| JsValue::from_str("Priority queue is empty"))?;
+        crate::events::events_to_js(&events)
+    }
+
+    /// Number of elements in the heap.
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Check whether the heap is empty.
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Get current array state.
+    pub fn get_array(&self) -> Result<JsValue, JsValue> {
+        serde_wasm_bindgen::to_value(self.inner.array())
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_max_heap(array: &[i32]) -> bool {
+        (1..array.len()).all(|i| array[(i - 1) / 2] >= array[i])
+    }
+
+    #[test]
+    fn test_heapify_builds_heap() {
+        let mut pq = PriorityQueue::new(vec![3, 9, 2, 7, 5, 8, 1]);
+        let events = pq.heapify();
+
+        assert_eq!(pq.len(), 7);
+        assert!(is_max_heap(pq.array()));
+        assert_eq!(pq.peek(), Some(9));
+        assert!(matches!(events.first(), Some(SortEvent::EnterRange { lo: 0, hi: 6 })));
+    }
+
+    #[test]
+    fn test_push_sifts_up() {
+        let mut pq = PriorityQueue::new(vec![0; 5]);
+        for value in [4, 1, 7, 3, 9] {
+            assert!(pq.push(value).is_some());
+            assert!(is_max_heap(&pq.array()[..pq.len()]));
+        }
+
+        assert_eq!(pq.peek(), Some(9));
+        assert!(pq.push(10).is_none());
+    }
+
+    #[test]
+    fn test_pop_returns_descending() {
+        let mut pq = PriorityQueue::new(vec![5, 3, 8, 4, 2, 8]);
+        pq.heapify();
+
+        let mut popped = Vec::new();
+        while let Some((value, _)) = pq.pop() {
+            popped.push(value);
+        }
+
+        assert_eq!(popped, vec![8, 8, 5, 4, 3, 2]);
+        // Popped values stay behind the heap, so the array ends up sorted
+        assert_eq!(pq.array(), &[2, 3, 4, 5, 8, 8]);
+        assert!(pq.is_empty());
+    }
+
+    #[test]
+    fn test_events_replay_to_array() {
+        let initial = vec![0, 0, 0, 0];
+        let mut pq = PriorityQueue::new(initial.clone());
+        let mut events = Vec::new();
+        for value in [2, 6, 4] {
+            events.extend(pq.push(value).unwrap());
+        }
+        events.extend(pq.pop().unwrap().1);
+
+        let mut replay = initial;
+        for event in &events {
+            match event {
+                SortEvent::Swap { i, j } => replay.swap(*i, *j),
+                SortEvent::Overwrite { idx, new_val, .. } => replay[*idx] = *new_val,
+                _ => {}
+            }
+        }
+        assert_eq!(replay, pq.array());
+    }
+
+    #[test]
+    fn test_empty_queue() {
+        let mut pq = PriorityQueue::new(vec![]);
+
+        assert!(pq.heapify().is_empty());
+        assert!(pq.pop().is_none());
+        assert!(pq.push(1).is_none());
+        assert_eq!(pq.peek(), None);
+    }
+}
@@ -0,0 +1,167 @@
+//! Wall-clock profiling of trace generation.
+//!
+//! The trace is generated in chunks of a fixed number of events and each
+//! chunk is timed, so slow stretches of an algorithm show up as slow
+//! chunks. The clock is `performance.now()` (or `Date.now()`) in wasm and
+//! `Instant` natively.
+//!
+//! Only the live engine generates in steps. Algorithms without a live
+//! implementation run in the pregen engine, which produces the whole trace
+//! in one call: their profile is a single chunk covering every event.
+
+use serde::Serialize;
+use crate::engine::Engine;
+use crate::events::SortEvent;
+use crate::live::LiveSort;
+use crate::pregen::{pregen_sort, Algorithm};
+
+/// Timing of one chunk of events.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ChunkTiming {
+    /// Index of the chunk's first event in the trace.
+    pub start: usize,
+    pub len: usize,
+    pub millis: f64,
+    /// The last Phase event up to the end of the chunk, if any.
+    pub phase: Option<String>,
+}
+
+/// Result of `profile_sort`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Profile {
+    pub engine: Engine,
+    pub chunk_size: usize,
+    pub chunks: Vec<ChunkTiming>,
+    pub total_millis: f64,
+}
+
+/// Milliseconds on a monotonic clock.
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+pub(crate) fn now_millis() -> f64 {
+    use wasm_bindgen::JsCast;
+
+    // performance.now() where there is one (pages and workers), else Date
+    let performance = js_sys::Reflect::get(&js_sys::global(), &"performance".into()).ok();
+    let now = performance.as_ref().and_then(|performance| {
+        let now = js_sys::Reflect::get(performance, &"now".into()).ok()?;
+        now.dyn_into::<js_sys::Function>().ok()?.call0(performance).ok()?.as_f64()
+    });
+    now.unwrap_or_else(js_sys::Date::now)
+}
+
+/// Milliseconds on a monotonic clock.
+#[cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+pub(crate) fn now_millis() -> f64 {
+    use std::sync::OnceLock;
+    use std::time::Instant;
+
+    static START: OnceLock<Instant> = OnceLock::new();
+    START.get_or_init(Instant::now).elapsed().as_secs_f64() * 1000.0
+}
+
+/// Sort a copy of array, timing each chunk of `chunk_size` events. Uses the
+/// live engine when the algorithm has one, the pregen engine otherwise.
+pub fn profile_sort(
+    algorithm: &str,
+    array: &[i32],
+    chunk_size: usize,
+) -> Result<(Vec<SortEvent>, Profile), String> {
+    if chunk_size == 0 {
+        return Err("Chunk size must be at least 1".to_string());
+    }
+
+    let mut events = Vec::new();
+    let mut chunks = Vec::new();
+    let mut phase = None;
+    let mut record = |events: &[SortEvent], start: usize, millis: f64| {
+        for event in &events[start..] {
+            if let SortEvent::Phase { name } = event {
+                phase = Some(name.clone());
+            }
+        }
+        chunks.push(ChunkTiming { start, len: events.len() - start, millis, phase: phase.clone() });
+    };
+
+    let engine = match LiveSort::new(algorithm, array.to_vec()) {
+        Some(mut live) => {
+            // Step at least once: a stepper that starts out done still
+            // emits its Done on the first step
+            loop {
+                let start = events.len();
+                let begin = now_millis();
+                events.extend(live.step(chunk_size));
+                record(&events, start, now_millis() - begin);
+                if live.is_done() {
+                    break;
+                }
+            }
+            Engine::Live
+        }
+        None => {
+            let algo = Algorithm::from_str(algorithm)
+                .ok_or_else(|| format!("Unknown algorithm: {}", algorithm))?;
+            let begin = now_millis();
+            events = pregen_sort(algo, &mut array.to_vec());
+            record(&events, 0, now_millis() - begin);
+            Engine::Pregen
+        }
+    };
+
+    let total_millis = chunks.iter().map(|chunk| chunk.millis).sum();
+    Ok((events, Profile { engine, chunk_size, chunks, total_millis }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patterns::{self, Pattern};
+
+    #[test]
+    fn test_live_chunks_cover_trace() {
+        let input = patterns::generate(Pattern::Uniform, 100, 1);
+        let (events, profile) = profile_sort("bubble", &input, 64).unwrap();
+
+        assert_eq!(profile.engine, Engine::Live);
+        assert!(profile.chunks.len() > 1);
+        let mut next = 0;
+        for chunk in &profile.chunks {
+            assert_eq!(chunk.start, next);
+            assert!(chunk.len <= 64 && chunk.millis >= 0.0);
+            next += chunk.len;
+        }
+        assert_eq!(next, events.len());
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_pregen_is_one_chunk() {
+        let input = patterns::generate(Pattern::Uniform, 100, 1);
+        let (events, profile) = profile_sort("heap", &input, 64).unwrap();
+
+        assert_eq!(profile.engine, Engine::Pregen);
+        assert_eq!(profile.chunks.len(), 1);
+        assert_eq!(profile.chunks[0].len, events.len());
+        assert_eq!(profile.total_millis, profile.chunks[0].millis);
+    }
+
+    #[test]
+    fn test_chunks_carry_last_phase() {
+        let input: Vec<i32> = (0..300).map(|x| (x * 31) % 4).collect();
+        let (_, profile) = profile_sort("pdq", &input, 64).unwrap();
+        assert!(profile.chunks[0].phase.is_some());
+    }
+
+    #[test]
+    fn test_live_matches_plain_run() {
+        let input = patterns::generate(Pattern::Random, 50, 2);
+        let (events, _) = profile_sort("quicksort_ll", &input, 7).unwrap();
+        let mut live = LiveSort::new("quicksort_ll", input).unwrap();
+        assert_eq!(events, live.step(1_000_000));
+    }
+
+    #[test]
+    fn test_rejects_bad_arguments() {
+        assert!(profile_sort("bubble", &[1], 0).is_err());
+        assert!(profile_sort("nope", &[1], 8).is_err());
+    }
+}
@@ -0,0 +1,199 @@
+//! Ghost race: two algorithms on the same input, in one trace.
+//!
+//! Both algorithms sort their own copy of the input. Their traces are
+//! merged into a single sequence of events tagged with a lane (0 or 1), so a
+//! race can be replayed deterministically from one artifact. Each lane's
+//! events keep their order; only the interleaving depends on the schedule.
+
+use serde::Serialize;
+use crate::events::SortEvent;
+use crate::pregen::{pregen_sort, Algorithm};
+
+/// How the two traces are interleaved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RaceSchedule {
+    /// Slots are given in proportion to each lane's event count, so both
+    /// lanes progress at the same relative rate and finish together.
+    Proportional,
+    /// One event from each lane in turn; the lane with fewer events
+    /// finishes first, like a real race.
+    Lockstep,
+}
+
+impl RaceSchedule {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RaceSchedule::Proportional => "proportional",
+            RaceSchedule::Lockstep => "lockstep",
+        }
+    }
+
+    pub fn all() -> &'static [RaceSchedule] {
+        &[RaceSchedule::Proportional, RaceSchedule::Lockstep]
+    }
+
+    /// Parse schedule name from string.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<RaceSchedule> {
+        match s.to_lowercase().as_str() {
+            "proportional" => Some(RaceSchedule::Proportional),
+            "lockstep" | "alternate" => Some(RaceSchedule::Lockstep),
+            _ => None,
+        }
+    }
+}
+
+/// An event of one lane. Serializes flat, e.g. `{"lane":1,"type":"Swap",...}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LaneEvent {
+    pub lane: u8,
+    #[serde(flatten)]
+    pub event: SortEvent,
+}
+
+/// A complete race: the algorithms by lane, the shared input and the
+/// interleaved events.
+#[derive(Debug, Clone, Serialize)]
+pub struct GhostRace {
+    pub algorithms: [&'static str; 2],
+    pub input: Vec<i32>,
+    pub events: Vec<LaneEvent>,
+}
+
+/// Run both algorithms on copies of input and interleave their traces.
+/// With the `parallel` feature the two sorts run on the thread pool.
+pub fn ghost_race(algorithms: [Algorithm; 2], input: &[i32], schedule: RaceSchedule) -> GhostRace {
+    let sort = |algorithm: Algorithm| pregen_sort(algorithm, &mut input.to_vec());
+    #[cfg(feature = "parallel")]
+    let traces = {
+        let (a, b) = rayon::join(|| sort(algorithms[0]), || sort(algorithms[1]));
+        [a, b]
+    };
+    #[cfg(not(feature = "parallel"))]
+    let traces = algorithms.map(sort);
+
+    GhostRace {
+        algorithms: algorithms.map(|algorithm| algorithm.as_str()),
+        input: input.to_vec(),
+        events: interleave(traces, schedule),
+    }
+}
+
+/// Merge two traces into one, preserving each trace's order.
+pub fn interleave(traces: [Vec<SortEvent>; 2], schedule: RaceSchedule) -> Vec<LaneEvent> {
+    let [a, b] = traces;
+    let (len_a, len_b) = (a.len(), b.len());
+    let mut merged = Vec::with_capacity(len_a + len_b);
+    let mut a = a.into_iter();
+    let mut b = b.into_iter();
+    let (mut taken_a, mut taken_b) = (0, 0);
+
+    while taken_a < len_a || taken_b < len_b {
+        let take_a = if taken_a == len_a {
+            false
+        } else if taken_b == len_b {
+            true
+        } else {
+            match schedule {
+                // Lane with the smaller completed fraction goes next:
+                // (taken_a + 1) / len_a <= (taken_b + 1) / len_b, ties to lane 0
+                RaceSchedule::Proportional => (taken_a + 1) * len_b <= (taken_b + 1) * len_a,
+                RaceSchedule::Lockstep => taken_a <= taken_b,
+            }
+        };
+
+        let (lane, event) = if take_a {
+            taken_a += 1;
+            (0, a.next())
+        } else {
+            taken_b += 1;
+            (1, b.next())
+        };
+        merged.push(LaneEvent {
+            lane,
+            event: event.expect("taken count tracks the trace length"),
+        });
+    }
+
+    merged
+}
+
+/// Events of one lane, in order.
+pub fn lane_events(events: &[LaneEvent], lane: u8) -> Vec<SortEvent> {
+    events
+        .iter()
+        .filter(|e| e.lane == lane)
+        .map(|e| e.event.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lanes(events: &[LaneEvent]) -> Vec<u8> {
+        events.iter().map(|e| e.lane).collect()
+    }
+
+    #[test]
+    fn test_race_preserves_each_trace() {
+        let input = vec![5, 3, 8, 1, 9, 2, 7];
+        let algorithms = [Algorithm::Bubble, Algorithm::MergeSort];
+
+        for &schedule in RaceSchedule::all() {
+            let race = ghost_race(algorithms, &input, schedule);
+            assert_eq!(race.algorithms, ["bubble", "merge"]);
+
+            for (lane, &algorithm) in algorithms.iter().enumerate() {
+                let expected = pregen_sort(algorithm, &mut input.clone());
+                assert_eq!(lane_events(&race.events, lane as u8), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_proportional_schedule() {
+        let a = vec![SortEvent::Done; 2];
+        let b = vec![SortEvent::Done; 4];
+        let merged = interleave([a, b], RaceSchedule::Proportional);
+
+        // Lane 1 has twice the events, so it gets two slots per lane 0 slot
+        assert_eq!(lanes(&merged), vec![1, 0, 1, 1, 0, 1]);
+    }
+
+    #[test]
+    fn test_lockstep_schedule() {
+        let a = vec![SortEvent::Done; 2];
+        let b = vec![SortEvent::Done; 4];
+        let merged = interleave([a, b], RaceSchedule::Lockstep);
+
+        assert_eq!(lanes(&merged), vec![0, 1, 0, 1, 1, 1]);
+    }
+
+    #[test]
+    fn test_race_deterministic() {
+        let input = vec![4, 2, 6, 1, 3];
+        let algorithms = [Algorithm::QuickSortLL, Algorithm::HeapSort];
+        let first = ghost_race(algorithms, &input, RaceSchedule::Proportional);
+        let second = ghost_race(algorithms, &input, RaceSchedule::Proportional);
+        assert_eq!(first.events, second.events);
+    }
+
+    #[test]
+    fn test_schedule_from_str() {
+        for &schedule in RaceSchedule::all() {
+            assert_eq!(RaceSchedule::from_str(schedule.as_str()), Some(schedule));
+        }
+        assert_eq!(RaceSchedule::from_str("bogus"), None);
+    }
+
+    #[cfg(feature = "native")]
+    #[test]
+    fn test_lane_event_serializes_flat() {
+        let event = LaneEvent { lane: 1, event: SortEvent::Swap { i: 2, j: 3 } };
+        assert_eq!(
+            serde_json::to_string(&event).unwrap(),
+            r#"{"lane":1,"type":"Swap","i":2,"j":3}"#
+        );
+    }
+}
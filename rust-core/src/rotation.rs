@@ -0,0 +1,318 @@
+//! Array rotation and block-swap primitives.
+//!
+//! These are the building blocks of the in-place merges: moving one block
+//! of elements past another without an auxiliary buffer. Each primitive
+//! works on half-open ranges and appends its events to a caller-supplied
+//! list, so sorts can reuse them directly.
+//!
+//! Rotating [first, last) around middle makes array[middle] the first
+//! element, i.e. a left rotation by k = middle - first. `rotate` emits the
+//! whole rotation as one Rotate event, which is what sorts use so that a
+//! rotation renders as one operation. Three element-level strategies are
+//! available as standalone demos of how a rotation is actually done:
+//! - Reversal: reverse both blocks, then the whole range. Only Swaps.
+//! - Juggling: follow the gcd(n, k) cycles of the permutation, moving each
+//!   element once. Uses Overwrites, with one value held aside per cycle.
+//! - BlockSwap: Gries-Mills; repeatedly swap the shorter block with the
+//!   matching end of the longer one. Only Swaps.
+
+use crate::prelude::*;
+use crate::events::{EventSink, SortEvent};
+
+/// Available rotation strategies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RotationAlgorithm {
+    /// A single Rotate event.
+    Direct,
+    Reversal,
+    Juggling,
+    BlockSwap,
+}
+
+impl RotationAlgorithm {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RotationAlgorithm::Direct => "direct",
+            RotationAlgorithm::Reversal => "reversal",
+            RotationAlgorithm::Juggling => "juggling",
+            RotationAlgorithm::BlockSwap => "block_swap",
+        }
+    }
+
+    pub fn all() -> &'static [RotationAlgorithm] {
+        const ALGORITHMS: [RotationAlgorithm; 4] = [
+            RotationAlgorithm::Direct,
+            RotationAlgorithm::Reversal,
+            RotationAlgorithm::Juggling,
+            RotationAlgorithm::BlockSwap,
+        ];
+        &ALGORITHMS
+    }
+
+    /// Parse algorithm name from string.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<RotationAlgorithm> {
+        match s.to_lowercase().as_str() {
+            "direct" | "rotate" => Some(RotationAlgorithm::Direct),
+            "reversal" | "triple_reverse" | "three_reversal" => Some(RotationAlgorithm::Reversal),
+            "juggling" | "dolphin" => Some(RotationAlgorithm::Juggling),
+            "block_swap" | "blockswap" | "gries_mills" => Some(RotationAlgorithm::BlockSwap),
+            _ => None,
+        }
+    }
+}
+
+/// Rotate the whole array left by k (k is taken modulo the length).
+pub fn pregen_rotate(algorithm: RotationAlgorithm, array: &mut [i32], k: usize) -> Vec<SortEvent> {
+    let mut events = Vec::new();
+    let n = array.len();
+
+    if n == 0 {
+        return events;
+    }
+
+    let middle = k % n;
+    events.push(SortEvent::EnterRange { lo: 0, hi: n - 1 });
+    match algorithm {
+        RotationAlgorithm::Direct => rotate(array, 0, middle, n, &mut events),
+        RotationAlgorithm::Reversal => rotate_reversal(array, 0, middle, n, &mut events),
+        RotationAlgorithm::Juggling => rotate_juggling(array, 0, middle, n, &mut events),
+        RotationAlgorithm::BlockSwap => rotate_block_swap(array, 0, middle, n, &mut events),
+    }
+    events.push(SortEvent::ExitRange { lo: 0, hi: n - 1 });
+
+    events
+}
+
+/// Rotate [first, last) so that array[middle] becomes the first element,
+/// as a single Rotate event.
+pub fn rotate(
+    array: &mut [i32],
+    first: usize,
+    middle: usize,
+    last: usize,
+    events: &mut impl EventSink,
+) {
+    if first == middle || middle == last {
+        return;
+    }
+    events.push(SortEvent::rotate(first, last - 1, middle - first));
+    array[first..last].rotate_left(middle - first);
+}
+
+/// Reverse the half-open range [lo, hi).
+pub fn reverse(array: &mut [i32], lo: usize, hi: usize, events: &mut impl EventSink) {
+    if hi - lo < 2 {
+        return;
+    }
+    let mut left = lo;
+    let mut right = hi - 1;
+    while left < right {
+        events.push(SortEvent::Swap { i: left, j: right });
+        array.swap(left, right);
+        left += 1;
+        right -= 1;
+    }
+}
+
+/// Swap the non-overlapping blocks [a, a + len) and [b, b + len).
+pub fn block_swap(array: &mut [i32], a: usize, b: usize, len: usize, events: &mut impl EventSink) {
+    for offset in 0..len {
+        events.push(SortEvent::Swap { i: a + offset, j: b + offset });
+        array.swap(a + offset, b + offset);
+    }
+}
+
+/// Rotate [first, last) so that array[middle] becomes the first element.
+/// Implemented as three reversals, each emitted as Swap events.
+pub fn rotate_reversal(
+    array: &mut [i32],
+    first: usize,
+    middle: usize,
+    last: usize,
+    events: &mut impl EventSink,
+) {
+    if first == middle || middle == last {
+        return;
+    }
+    reverse(array, first, middle, events);
+    reverse(array, middle, last, events);
+    reverse(array, first, last, events);
+}
+
+/// Rotate [first, last) so that array[middle] becomes the first element,
+/// moving every element exactly once along the cycles of the rotation.
+pub fn rotate_juggling(
+    array: &mut [i32],
+    first: usize,
+    middle: usize,
+    last: usize,
+    events: &mut impl EventSink,
+) {
+    if first == middle || middle == last {
+        return;
+    }
+
+    let n = last - first;
+    let k = middle - first;
+
+    for cycle in 0..gcd(n, k) {
+        // Hold the cycle's first value aside, then pull each slot's
+        // successor into it until the cycle closes
+        let start = first + cycle;
+        let held = array[start];
+        let mut slot = start;
+
+        loop {
+            let next = first + (slot - first + k) % n;
+            if next == start {
+                break;
+            }
+            overwrite(array, slot, array[next], events);
+            slot = next;
+        }
+        overwrite(array, slot, held, events);
+    }
+}
+
+/// Rotate [first, last) so that array[middle] becomes the first element.
+/// Gries-Mills: swap the shorter block into its final place, then continue
+/// on what is left.
+pub fn rotate_block_swap(
+    array: &mut [i32],
+    mut first: usize,
+    middle: usize,
+    mut last: usize,
+    events: &mut impl EventSink,
+) {
+    while first < middle && middle < last {
+        let left = middle - first;
+        let right = last - middle;
+
+        if left <= right {
+            // A B1 B2 with |B2| = |A|  ->  B2 B1 A, A is done
+            block_swap(array, first, last - left, left, events);
+            last -= left;
+        } else {
+            // A1 A2 B with |A1| = |B|  ->  B A2 A1, B is done
+            block_swap(array, first, middle, right, events);
+            first += right;
+        }
+    }
+}
+
+fn overwrite(array: &mut [i32], idx: usize, value: i32, events: &mut impl EventSink) {
+    if array[idx] != value {
+        events.push(SortEvent::Overwrite {
+            idx,
+            old_val: array[idx],
+            new_val: value,
+        });
+        array[idx] = value;
+    }
+}
+
+fn gcd(mut a: usize, mut b: usize) -> usize {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rotated(n: i32, k: usize) -> Vec<i32> {
+        let mut expected: Vec<i32> = (0..n).collect();
+        if n > 0 {
+            expected.rotate_left(k % n as usize);
+        }
+        expected
+    }
+
+    #[test]
+    fn test_rotate_all_algorithms() {
+        for &algorithm in RotationAlgorithm::all() {
+            for n in 0..12 {
+                for k in 0..=n as usize {
+                    let mut array: Vec<i32> = (0..n).collect();
+                    pregen_rotate(algorithm, &mut array, k);
+                    assert_eq!(array, rotated(n, k), "{:?} n={} k={}", algorithm, n, k);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_rotate_subrange() {
+        let mut array = vec![9, 1, 2, 3, 4, 5, 9];
+        let mut events = Vec::new();
+        rotate_block_swap(&mut array, 1, 3, 6, &mut events);
+
+        assert_eq!(array, vec![9, 3, 4, 5, 1, 2, 9]);
+        assert!(events.iter().all(|e| match e {
+            SortEvent::Swap { i, j } => (1..6).contains(i) && (1..6).contains(j),
+            _ => false,
+        }));
+    }
+
+    #[test]
+    fn test_juggling_moves_each_element_once() {
+        let mut array: Vec<i32> = (0..12).collect();
+        let events = pregen_rotate(RotationAlgorithm::Juggling, &mut array, 4);
+
+        let overwrites = events.iter().filter(|e| matches!(e, SortEvent::Overwrite { .. })).count();
+        assert_eq!(overwrites, 12);
+        assert!(!events.iter().any(|e| matches!(e, SortEvent::Swap { .. })));
+    }
+
+    #[test]
+    fn test_swap_counts() {
+        let mut array: Vec<i32> = (0..10).collect();
+        let reversal = pregen_rotate(RotationAlgorithm::Reversal, &mut array, 3);
+        let mut array: Vec<i32> = (0..10).collect();
+        let block = pregen_rotate(RotationAlgorithm::BlockSwap, &mut array, 5);
+
+        let swaps = |events: &[SortEvent]| {
+            events.iter().filter(|e| matches!(e, SortEvent::Swap { .. })).count()
+        };
+        // Reversals: 1 + 3 + 5 swaps; equal halves: one block swap of 5
+        assert_eq!(swaps(&reversal), 9);
+        assert_eq!(swaps(&block), 5);
+    }
+
+    #[test]
+    fn test_direct_rotation_is_one_event() {
+        let mut array = vec![9, 1, 2, 3, 4, 5, 9];
+        let mut events = Vec::new();
+        rotate(&mut array, 1, 3, 6, &mut events);
+
+        assert_eq!(array, vec![9, 3, 4, 5, 1, 2, 9]);
+        assert_eq!(events, vec![SortEvent::rotate(1, 5, 2)]);
+
+        // Empty rotations emit nothing
+        rotate(&mut array, 2, 2, 6, &mut events);
+        assert_eq!(events.len(), 1);
+    }
+
+    #[test]
+    fn test_reverse_and_block_swap() {
+        let mut array = vec![1, 2, 3, 4, 5, 6];
+        let mut events = Vec::new();
+        reverse(&mut array, 1, 5, &mut events);
+        assert_eq!(array, vec![1, 5, 4, 3, 2, 6]);
+
+        block_swap(&mut array, 0, 4, 2, &mut events);
+        assert_eq!(array, vec![2, 6, 4, 3, 1, 5]);
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(RotationAlgorithm::from_str("gries_mills"), Some(RotationAlgorithm::BlockSwap));
+        for &algorithm in RotationAlgorithm::all() {
+            assert_eq!(RotationAlgorithm::from_str(algorithm.as_str()), Some(algorithm));
+        }
+        assert_eq!(RotationAlgorithm::from_str("bogus"), None);
+    }
+}
@@ -0,0 +1,118 @@
+//! Binary search implementation.
+//!
+//! Probes the middle of the current window and discards the half that
+//! cannot contain the target. Searches for the leftmost match, so the window
+//! keeps narrowing after a hit until only one candidate remains.
+
+use crate::events::SortEvent;
+use super::PregenSearch;
+
+pub struct BinarySearch;
+
+impl PregenSearch for BinarySearch {
+    fn search(array: &[i32], target: i32) -> Vec<SortEvent> {
+        let mut events = Vec::new();
+
+        // Window [lo, hi): the first position with value >= target lies in it
+        let mut lo = 0;
+        let mut hi = array.len();
+
+        while lo < hi {
+            events.push(SortEvent::EnterRange { lo, hi: hi - 1 });
+
+            let mid = lo + (hi - lo) / 2;
+            events.push(SortEvent::Compare { i: mid, j: mid });
+
+            events.push(SortEvent::ExitRange { lo, hi: hi - 1 });
+
+            if array[mid] < target {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+
+        if lo < array.len() && array[lo] == target {
+            events.push(SortEvent::Found { idx: lo });
+        }
+
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::found_index;
+
+    fn probe_count(events: &[SortEvent]) -> usize {
+        events.iter().filter(|e| matches!(e, SortEvent::Compare { .. })).count()
+    }
+
+    #[test]
+    fn test_binary_search_found() {
+        let array = vec![1, 3, 5, 7, 9, 11];
+        let events = BinarySearch::search(&array, 7);
+
+        assert_eq!(found_index(&events), Some(3));
+    }
+
+    #[test]
+    fn test_binary_search_not_found() {
+        let array = vec![1, 3, 5, 7, 9, 11];
+
+        for target in [0, 4, 12] {
+            let events = BinarySearch::search(&array, target);
+            assert_eq!(found_index(&events), None);
+        }
+    }
+
+    #[test]
+    fn test_binary_search_leftmost_duplicate() {
+        let array = vec![1, 2, 2, 2, 2, 3];
+        let events = BinarySearch::search(&array, 2);
+
+        assert_eq!(found_index(&events), Some(1));
+    }
+
+    #[test]
+    fn test_binary_search_every_element() {
+        let array: Vec<i32> = (0..100).map(|x| x * 2).collect();
+
+        for (idx, &value) in array.iter().enumerate() {
+            let events = BinarySearch::search(&array, value);
+            assert_eq!(found_index(&events), Some(idx));
+        }
+    }
+
+    #[test]
+    fn test_binary_search_empty() {
+        let events = BinarySearch::search(&[], 5);
+
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn test_binary_search_logarithmic_probes() {
+        let array: Vec<i32> = (0..1024).collect();
+        let events = BinarySearch::search(&array, 700);
+
+        assert_eq!(probe_count(&events), 10);
+    }
+
+    #[test]
+    fn test_binary_search_windows_shrink() {
+        let array: Vec<i32> = (0..50).collect();
+        let events = BinarySearch::search(&array, 31);
+
+        let widths: Vec<usize> = events
+            .iter()
+            .filter_map(|e| match e {
+                SortEvent::EnterRange { lo, hi } => Some(hi - lo + 1),
+                _ => None,
+            })
+            .collect();
+        assert!(widths.windows(2).all(|w| w[1] < w[0]));
+        assert!(!events.iter().any(|e| e.is_mutation()));
+    }
+}
@@ -0,0 +1,138 @@
+//! Exponential search implementation.
+//!
+//! Two phases, each emitted as its own group of events:
+//! 1. Galloping: probe positions 0, 1, 3, 7, 15, ... (bound doubling) until
+//!    one is not smaller than the target. Shown as self-Compare probes under
+//!    an EnterRange over the whole array.
+//! 2. Binary refinement: binary search between the last two probes, with
+//!    the usual narrowing EnterRange/ExitRange pairs.
+//!
+//! Takes O(log i) probes, where i is the target's position, so it beats
+//! plain binary search when the target is near the front.
+
+use crate::events::SortEvent;
+use super::PregenSearch;
+
+pub struct ExponentialSearch;
+
+impl PregenSearch for ExponentialSearch {
+    fn search(array: &[i32], target: i32) -> Vec<SortEvent> {
+        let mut events = Vec::new();
+        let n = array.len();
+
+        if n == 0 {
+            return events;
+        }
+
+        // Phase 1: double the bound until it passes the target
+        events.push(SortEvent::EnterRange { lo: 0, hi: n - 1 });
+        let mut lo = 0;
+        let mut bound = 1;
+        loop {
+            let probe = (bound - 1).min(n - 1);
+            events.push(SortEvent::Compare { i: probe, j: probe });
+
+            if array[probe] >= target || probe == n - 1 {
+                break;
+            }
+            lo = probe + 1;
+            bound *= 2;
+        }
+        events.push(SortEvent::ExitRange { lo: 0, hi: n - 1 });
+
+        // Phase 2: binary search for the leftmost match in [lo, hi)
+        let mut hi = bound.min(n);
+        while lo < hi {
+            events.push(SortEvent::EnterRange { lo, hi: hi - 1 });
+
+            let mid = lo + (hi - lo) / 2;
+            events.push(SortEvent::Compare { i: mid, j: mid });
+
+            events.push(SortEvent::ExitRange { lo, hi: hi - 1 });
+
+            if array[mid] < target {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+
+        if lo < n && array[lo] == target {
+            events.push(SortEvent::Found { idx: lo });
+        }
+
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::binary_search::BinarySearch;
+    use crate::search::found_index;
+
+    fn probe_count(events: &[SortEvent]) -> usize {
+        events.iter().filter(|e| matches!(e, SortEvent::Compare { .. })).count()
+    }
+
+    #[test]
+    fn test_exponential_search_found() {
+        let array = vec![1, 3, 5, 7, 9, 11];
+        let events = ExponentialSearch::search(&array, 7);
+
+        assert_eq!(found_index(&events), Some(3));
+    }
+
+    #[test]
+    fn test_exponential_search_not_found() {
+        let array = vec![1, 3, 5, 7, 9, 11];
+
+        for target in [0, 4, 12] {
+            let events = ExponentialSearch::search(&array, target);
+            assert_eq!(found_index(&events), None);
+        }
+    }
+
+    #[test]
+    fn test_exponential_search_every_element_and_duplicates() {
+        let array: Vec<i32> = (0..97).map(|x| x / 3).collect();
+
+        for value in 0..33 {
+            let events = ExponentialSearch::search(&array, value);
+            assert_eq!(found_index(&events), Some(value as usize * 3));
+        }
+    }
+
+    #[test]
+    fn test_exponential_search_empty() {
+        assert!(ExponentialSearch::search(&[], 5).is_empty());
+    }
+
+    #[test]
+    fn test_exponential_search_beats_binary_near_front() {
+        let array: Vec<i32> = (0..100000).collect();
+
+        let exponential = ExponentialSearch::search(&array, 5);
+        let binary = BinarySearch::search(&array, 5);
+
+        assert_eq!(found_index(&exponential), Some(5));
+        assert!(probe_count(&exponential) < probe_count(&binary));
+    }
+
+    #[test]
+    fn test_exponential_search_gallop_positions() {
+        let array: Vec<i32> = (0..100).collect();
+        let events = ExponentialSearch::search(&array, 50);
+
+        // Probes of the galloping phase, up to its ExitRange
+        let gallop: Vec<usize> = events
+            .iter()
+            .take_while(|e| !matches!(e, SortEvent::ExitRange { .. }))
+            .filter_map(|e| match e {
+                SortEvent::Compare { i, .. } => Some(*i),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(gallop, vec![0, 1, 3, 7, 15, 31, 63]);
+    }
+}
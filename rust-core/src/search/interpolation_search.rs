@@ -0,0 +1,161 @@
+//! Interpolation search implementation.
+//!
+//! Instead of probing the middle, estimates where the target should be from
+//! the values at both ends of the window, assuming they are evenly spread:
+//! pos = lo + (target - a[lo]) * (hi - lo) / (a[hi] - a[lo]).
+//! On uniform data this needs O(log log n) probes; on skewed data the
+//! estimates can be far off and it degrades towards a linear scan.
+//!
+//! Each step emits a Compare between the window ends (the values feeding the
+//! estimate), then a self-Compare at the computed probe position. A hit is
+//! confirmed as the leftmost match by one extra probe of its left neighbour.
+
+use crate::events::SortEvent;
+use super::PregenSearch;
+
+pub struct InterpolationSearch;
+
+impl PregenSearch for InterpolationSearch {
+    fn search(array: &[i32], target: i32) -> Vec<SortEvent> {
+        let mut events = Vec::new();
+
+        if array.is_empty() {
+            return events;
+        }
+
+        let mut lo = 0;
+        let mut hi = array.len() - 1;
+
+        while lo <= hi {
+            events.push(SortEvent::EnterRange { lo, hi });
+            events.push(SortEvent::Compare { i: lo, j: hi });
+
+            // Target outside the window's value range
+            if target < array[lo] || target > array[hi] {
+                events.push(SortEvent::ExitRange { lo, hi });
+                break;
+            }
+
+            // Everything before lo is smaller, so a match here is leftmost
+            if array[lo] == target {
+                events.push(SortEvent::ExitRange { lo, hi });
+                events.push(SortEvent::Found { idx: lo });
+                break;
+            }
+
+            // Now a[lo] < target <= a[hi], so the estimate lands in (lo, hi]
+            let pos = probe_position(array, lo, hi, target);
+            events.push(SortEvent::Compare { i: pos, j: pos });
+
+            if array[pos] == target {
+                // A hit is leftmost if its left neighbour is smaller
+                events.push(SortEvent::Compare { i: pos - 1, j: pos - 1 });
+                if array[pos - 1] < target {
+                    events.push(SortEvent::ExitRange { lo, hi });
+                    events.push(SortEvent::Found { idx: pos });
+                    break;
+                }
+            }
+
+            events.push(SortEvent::ExitRange { lo, hi });
+
+            if array[pos] < target {
+                lo = pos + 1;
+            } else {
+                hi = pos - 1;
+            }
+        }
+
+        events
+    }
+}
+
+/// Estimated position of target in [lo..=hi], given a[lo] < target <= a[hi].
+pub fn probe_position(array: &[i32], lo: usize, hi: usize, target: i32) -> usize {
+    let span = array[hi] as i64 - array[lo] as i64;
+    let offset = target as i64 - array[lo] as i64;
+    lo + (offset * (hi - lo) as i64 / span) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::binary_search::BinarySearch;
+    use crate::search::found_index;
+
+    fn probe_count(events: &[SortEvent]) -> usize {
+        events
+            .iter()
+            .filter(|e| matches!(e, SortEvent::Compare { i, j } if i == j))
+            .count()
+    }
+
+    #[test]
+    fn test_interpolation_search_found() {
+        let array = vec![1, 3, 5, 7, 9, 11];
+        let events = InterpolationSearch::search(&array, 7);
+
+        assert_eq!(found_index(&events), Some(3));
+    }
+
+    #[test]
+    fn test_interpolation_search_not_found() {
+        let array = vec![1, 3, 5, 7, 9, 11];
+
+        for target in [0, 4, 12] {
+            let events = InterpolationSearch::search(&array, target);
+            assert_eq!(found_index(&events), None);
+        }
+    }
+
+    #[test]
+    fn test_interpolation_search_leftmost_duplicate() {
+        let array = vec![1, 2, 2, 2, 2, 3, 9];
+        let events = InterpolationSearch::search(&array, 2);
+
+        assert_eq!(found_index(&events), Some(1));
+    }
+
+    #[test]
+    fn test_interpolation_search_every_element() {
+        let array: Vec<i32> = (0..100).map(|x| x * x).collect();
+
+        for (idx, &value) in array.iter().enumerate() {
+            let events = InterpolationSearch::search(&array, value);
+            assert_eq!(found_index(&events), Some(idx));
+        }
+    }
+
+    #[test]
+    fn test_interpolation_search_extreme_values() {
+        let array = vec![i32::MIN, -5, 0, 5, i32::MAX];
+
+        for (idx, &value) in array.iter().enumerate() {
+            let events = InterpolationSearch::search(&array, value);
+            assert_eq!(found_index(&events), Some(idx));
+        }
+    }
+
+    #[test]
+    fn test_interpolation_search_empty() {
+        assert!(InterpolationSearch::search(&[], 5).is_empty());
+    }
+
+    #[test]
+    fn test_interpolation_search_probes_uniform_vs_skewed() {
+        let uniform: Vec<i32> = (0..1024).map(|x| x * 3).collect();
+        let uniform_events = InterpolationSearch::search(&uniform, 2100);
+        let binary_events = BinarySearch::search(&uniform, 2100);
+
+        // Uniform data: the first estimate lands right on the target,
+        // plus one probe to confirm it is the leftmost match
+        assert_eq!(probe_count(&uniform_events), 2);
+        assert_eq!(probe_count(&binary_events), 10);
+
+        // Skewed data: estimates crawl towards the dense end
+        let skewed: Vec<i32> = (0..1024).map(|x| if x < 1023 { x } else { 1_000_000 }).collect();
+        let skewed_events = InterpolationSearch::search(&skewed, 1000);
+        assert_eq!(found_index(&skewed_events), Some(1000));
+        assert!(probe_count(&skewed_events) > probe_count(&BinarySearch::search(&skewed, 1000)));
+    }
+}
@@ -0,0 +1,132 @@
+//! Jump search implementation.
+//!
+//! Two phases, each emitted as its own group of events:
+//! 1. Block probes: check the last element of each block of sqrt(n)
+//!    elements until one is not smaller than the target. Shown as
+//!    self-Compare probes under an EnterRange over the whole array.
+//! 2. Linear refinement: scan the block found in phase 1 from the left,
+//!    under an EnterRange over that block.
+//!
+//! Takes O(sqrt(n)) probes and only ever steps backwards once.
+
+use crate::events::SortEvent;
+use super::PregenSearch;
+
+pub struct JumpSearch;
+
+impl PregenSearch for JumpSearch {
+    #[allow(clippy::needless_range_loop)]
+    fn search(array: &[i32], target: i32) -> Vec<SortEvent> {
+        let mut events = Vec::new();
+        let n = array.len();
+
+        if n == 0 {
+            return events;
+        }
+
+        let step = ((n as f64).sqrt() as usize).max(1);
+
+        // Phase 1: jump block by block
+        events.push(SortEvent::EnterRange { lo: 0, hi: n - 1 });
+        let mut block_lo = 0;
+        loop {
+            let block_hi = (block_lo + step).min(n) - 1;
+            events.push(SortEvent::Compare { i: block_hi, j: block_hi });
+
+            if array[block_hi] >= target || block_hi == n - 1 {
+                break;
+            }
+            block_lo = block_hi + 1;
+        }
+        events.push(SortEvent::ExitRange { lo: 0, hi: n - 1 });
+
+        // Phase 2: linear scan within the block
+        let block_hi = (block_lo + step).min(n) - 1;
+        events.push(SortEvent::EnterRange { lo: block_lo, hi: block_hi });
+        let mut found = None;
+        for i in block_lo..=block_hi {
+            events.push(SortEvent::Compare { i, j: i });
+            if array[i] >= target {
+                if array[i] == target {
+                    found = Some(i);
+                }
+                break;
+            }
+        }
+        events.push(SortEvent::ExitRange { lo: block_lo, hi: block_hi });
+
+        if let Some(idx) = found {
+            events.push(SortEvent::Found { idx });
+        }
+
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::found_index;
+
+    fn probe_count(events: &[SortEvent]) -> usize {
+        events.iter().filter(|e| matches!(e, SortEvent::Compare { .. })).count()
+    }
+
+    #[test]
+    fn test_jump_search_found() {
+        let array = vec![1, 3, 5, 7, 9, 11];
+        let events = JumpSearch::search(&array, 7);
+
+        assert_eq!(found_index(&events), Some(3));
+    }
+
+    #[test]
+    fn test_jump_search_not_found() {
+        let array = vec![1, 3, 5, 7, 9, 11];
+
+        for target in [0, 4, 12] {
+            let events = JumpSearch::search(&array, target);
+            assert_eq!(found_index(&events), None);
+        }
+    }
+
+    #[test]
+    fn test_jump_search_every_element_and_duplicates() {
+        let array: Vec<i32> = (0..97).map(|x| x / 3).collect();
+
+        for value in 0..33 {
+            let events = JumpSearch::search(&array, value);
+            assert_eq!(found_index(&events), Some(value as usize * 3));
+        }
+    }
+
+    #[test]
+    fn test_jump_search_empty() {
+        assert!(JumpSearch::search(&[], 5).is_empty());
+    }
+
+    #[test]
+    fn test_jump_search_sqrt_probes() {
+        let array: Vec<i32> = (0..10000).collect();
+        let events = JumpSearch::search(&array, 9999);
+
+        // At most n / step block probes plus step linear probes
+        assert!(probe_count(&events) <= 200);
+        assert_eq!(found_index(&events), Some(9999));
+    }
+
+    #[test]
+    fn test_jump_search_two_phase_ranges() {
+        let array: Vec<i32> = (0..100).collect();
+        let events = JumpSearch::search(&array, 42);
+
+        let ranges: Vec<(usize, usize)> = events
+            .iter()
+            .filter_map(|e| match e {
+                SortEvent::EnterRange { lo, hi } => Some((*lo, *hi)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(ranges, vec![(0, 99), (40, 49)]);
+    }
+}
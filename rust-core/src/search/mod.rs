@@ -0,0 +1,85 @@
+//! Search algorithms over sorted arrays.
+//!
+//! Searches never mutate the array. Each probe is a self-Compare on the
+//! probed position (the target is not an array element), and the shrinking
+//! search window is shown with EnterRange/ExitRange pairs. If the target is
+//! present, the event list ends with a Found event at its leftmost position.
+
+pub mod binary_search;
+pub mod exponential_search;
+pub mod interpolation_search;
+pub mod jump_search;
+
+use crate::events::SortEvent;
+
+/// Trait for search algorithms.
+pub trait PregenSearch {
+    /// Search the sorted array for target and return all events.
+    fn search(array: &[i32], target: i32) -> Vec<SortEvent>;
+}
+
+/// Available search algorithms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchAlgorithm {
+    Binary,
+    Interpolation,
+    Jump,
+    Exponential,
+}
+
+impl SearchAlgorithm {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SearchAlgorithm::Binary => "binary",
+            SearchAlgorithm::Interpolation => "interpolation",
+            SearchAlgorithm::Jump => "jump",
+            SearchAlgorithm::Exponential => "exponential",
+        }
+    }
+
+    pub fn all() -> &'static [SearchAlgorithm] {
+        const ALGORITHMS: [SearchAlgorithm; 4] = [
+            SearchAlgorithm::Binary,
+            SearchAlgorithm::Interpolation,
+            SearchAlgorithm::Jump,
+            SearchAlgorithm::Exponential,
+        ];
+        &ALGORITHMS
+    }
+
+    /// Parse algorithm name from string.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<SearchAlgorithm> {
+        match s.to_lowercase().as_str() {
+            "binary" | "binarysearch" | "binary_search" => Some(SearchAlgorithm::Binary),
+            "interpolation" | "interpolationsearch" | "interpolation_search" => {
+                Some(SearchAlgorithm::Interpolation)
+            }
+            "jump" | "jumpsearch" | "jump_search" => Some(SearchAlgorithm::Jump),
+            "exponential" | "exponentialsearch" | "exponential_search" | "galloping" => {
+                Some(SearchAlgorithm::Exponential)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Run a search for target on the given sorted array.
+pub fn pregen_search(algorithm: SearchAlgorithm, array: &[i32], target: i32) -> Vec<SortEvent> {
+    match algorithm {
+        SearchAlgorithm::Binary => binary_search::BinarySearch::search(array, target),
+        SearchAlgorithm::Interpolation => {
+            interpolation_search::InterpolationSearch::search(array, target)
+        }
+        SearchAlgorithm::Jump => jump_search::JumpSearch::search(array, target),
+        SearchAlgorithm::Exponential => exponential_search::ExponentialSearch::search(array, target),
+    }
+}
+
+/// Position reported by the Found event of a search, if any.
+pub fn found_index(events: &[SortEvent]) -> Option<usize> {
+    match events.last() {
+        Some(SortEvent::Found { idx }) => Some(*idx),
+        _ => None,
+    }
+}
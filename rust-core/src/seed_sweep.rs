@@ -0,0 +1,207 @@
+//! Seed sweeps: one algorithm over many inputs from the same pattern.
+//!
+//! A single run's comparison count is an anecdote; how far it moves with
+//! the input is what a plot of an algorithm's behaviour needs. A sweep runs
+//! the algorithm on S seeds of one generator pattern and size, and reports
+//! every run alongside the min, median, 95th percentile and max of its
+//! comparisons, swaps and main-array writes.
+//!
+//! Like tournaments, runs are counted as their events are emitted and go on
+//! the batch thread pool.
+
+use serde::{Deserialize, Serialize};
+use crate::batch;
+use crate::patterns::{self, Pattern};
+use crate::pregen::{pregen_sort_into, Algorithm};
+use crate::tournament::Tally;
+
+/// What to run. Missing fields take the defaults: 32 seeds from 0 of
+/// random inputs of 100 elements. The algorithm is required.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SeedSweepRequest {
+    pub algorithm: String,
+    pub pattern: String,
+    pub n: usize,
+    /// Number of seeds, run as first_seed, first_seed + 1, ...
+    pub seeds: usize,
+    pub first_seed: u64,
+}
+
+impl Default for SeedSweepRequest {
+    fn default() -> Self {
+        Self {
+            algorithm: String::new(),
+            pattern: "random".to_string(),
+            n: 100,
+            seeds: 32,
+            first_seed: 0,
+        }
+    }
+}
+
+/// Counts of one seed's run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SeedRun {
+    pub seed: u64,
+    pub comparisons: u64,
+    pub swaps: u64,
+    pub writes: u64,
+}
+
+/// Spread of one count over the runs. Percentiles are nearest-rank, so
+/// each is a count some run actually made.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Distribution {
+    pub min: u64,
+    pub median: u64,
+    pub p95: u64,
+    pub max: u64,
+    pub mean: f64,
+}
+
+impl Distribution {
+    /// Distribution of a non-empty set of values.
+    pub fn of(values: &[u64]) -> Self {
+        let mut sorted = values.to_vec();
+        sorted.sort_unstable();
+        let rank = |p: f64| sorted[((p * sorted.len() as f64).ceil() as usize).max(1) - 1];
+
+        Self {
+            min: sorted[0],
+            median: rank(0.5),
+            p95: rank(0.95),
+            max: sorted[sorted.len() - 1],
+            mean: sorted.iter().sum::<u64>() as f64 / sorted.len() as f64,
+        }
+    }
+}
+
+/// Result of `SeedSweepRequest::run`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SeedSweep {
+    pub algorithm: &'static str,
+    pub pattern: &'static str,
+    pub n: usize,
+    /// In seed order.
+    pub runs: Vec<SeedRun>,
+    pub comparisons: Distribution,
+    pub swaps: Distribution,
+    pub writes: Distribution,
+}
+
+impl SeedSweepRequest {
+    pub fn run(&self) -> Result<SeedSweep, String> {
+        let algorithm = Algorithm::from_str(&self.algorithm)
+            .ok_or_else(|| format!("Unknown algorithm: {}", self.algorithm))?;
+        let pattern = Pattern::from_str(&self.pattern)
+            .ok_or_else(|| format!("Unknown pattern: {}", self.pattern))?;
+        if self.seeds == 0 {
+            return Err("A seed sweep needs at least one seed".to_string());
+        }
+
+        let seeds: Vec<u64> =
+            (0..self.seeds as u64).map(|k| self.first_seed.wrapping_add(k)).collect();
+        let runs = batch::map_in_order(&seeds, |&seed| {
+            let mut array = patterns::generate(pattern, self.n, seed);
+            let mut tally = Tally::default();
+            pregen_sort_into(algorithm, &mut array, &mut tally);
+            SeedRun {
+                seed,
+                comparisons: tally.comparisons,
+                swaps: tally.swaps,
+                writes: tally.writes,
+            }
+        });
+
+        let distribution = |count: fn(&SeedRun) -> u64| {
+            Distribution::of(&runs.iter().map(count).collect::<Vec<_>>())
+        };
+        Ok(SeedSweep {
+            algorithm: algorithm.as_str(),
+            pattern: pattern.as_str(),
+            n: self.n,
+            comparisons: distribution(|r| r.comparisons),
+            swaps: distribution(|r| r.swaps),
+            writes: distribution(|r| r.writes),
+            runs,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{main_writes, EventCounts};
+    use crate::pregen::pregen_sort;
+
+    fn request(algorithm: &str) -> SeedSweepRequest {
+        SeedSweepRequest { algorithm: algorithm.to_string(), ..SeedSweepRequest::default() }
+    }
+
+    #[test]
+    fn test_distribution_nearest_rank() {
+        let values: Vec<u64> = (1..=20).rev().collect();
+        let d = Distribution::of(&values);
+        assert_eq!((d.min, d.median, d.p95, d.max), (1, 10, 19, 20));
+        assert_eq!(d.mean, 10.5);
+
+        let single = Distribution::of(&[7]);
+        assert_eq!((single.min, single.median, single.p95, single.max), (7, 7, 7, 7));
+    }
+
+    #[test]
+    fn test_runs_match_traces() {
+        let mut request = request("heap");
+        request.seeds = 4;
+        request.first_seed = 10;
+        let sweep = request.run().unwrap();
+
+        let seeds: Vec<u64> = sweep.runs.iter().map(|r| r.seed).collect();
+        assert_eq!(seeds, vec![10, 11, 12, 13]);
+        for run in &sweep.runs {
+            let mut input = patterns::generate(Pattern::Random, 100, run.seed);
+            let events = pregen_sort(Algorithm::HeapSort, &mut input);
+            let counts = EventCounts::from_events(&events);
+            assert_eq!(run.comparisons, counts.compares as u64);
+            assert_eq!(run.swaps, counts.swaps as u64);
+            assert_eq!(run.writes, main_writes(&events) as u64);
+        }
+    }
+
+    #[test]
+    fn test_random_inputs_spread() {
+        let sweep = request("quicksort_ll").run().unwrap();
+        let c = &sweep.comparisons;
+
+        assert_eq!(sweep.runs.len(), 32);
+        assert!(c.min <= c.median && c.median <= c.p95 && c.p95 <= c.max);
+        assert!(c.min < c.max);
+        assert!(c.min as f64 <= c.mean && c.mean <= c.max as f64);
+    }
+
+    #[test]
+    fn test_fixed_input_has_no_spread() {
+        let mut request = request("merge");
+        request.pattern = "sorted".to_string();
+        let sweep = request.run().unwrap();
+
+        assert_eq!(sweep.comparisons.min, sweep.comparisons.max);
+        assert_eq!(sweep.writes.min, sweep.writes.max);
+        assert_eq!(sweep.swaps.max, 0);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(SeedSweepRequest::default().run().is_err());
+        assert!(request("bogus").run().is_err());
+
+        let mut no_seeds = request("heap");
+        no_seeds.seeds = 0;
+        assert!(no_seeds.run().is_err());
+
+        let mut bad_pattern = request("heap");
+        bad_pattern.pattern = "zigzag-ish".to_string();
+        assert!(bad_pattern.run().is_err());
+    }
+}
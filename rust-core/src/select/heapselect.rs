@@ -0,0 +1,180 @@
+//! Heapselect implementation.
+//!
+//! Streaming selection: keeps a max-heap of the k + 1 smallest elements seen
+//! so far at the front of the array.
+//! 1. Push the first k + 1 elements onto the heap (sift up).
+//! 2. For each later element smaller than the heap root, pop the root and
+//!    push the element in its place (swap with the root, then sift down).
+//! 3. The root is now the k-th smallest; swap it to position k.
+//!
+//! Needs only one pass over the input, so it also works when elements
+//! arrive one by one. The heap region is wrapped in EnterRange/ExitRange.
+
+use crate::events::SortEvent;
+use super::PregenSelect;
+
+pub struct Heapselect;
+
+impl PregenSelect for Heapselect {
+    fn select(array: &mut [i32], k: usize) -> Vec<SortEvent> {
+        assert!(k < array.len(), "k out of bounds");
+
+        let mut events = Vec::new();
+        let heap_len = k + 1;
+
+        events.push(SortEvent::EnterRange { lo: 0, hi: k });
+
+        // Push the first k + 1 elements
+        for i in 1..heap_len {
+            sift_up(array, i, &mut events);
+        }
+
+        // Stream the rest: replace the root whenever a smaller element arrives
+        for i in heap_len..array.len() {
+            events.push(SortEvent::Compare { i, j: 0 });
+            if array[i] < array[0] {
+                events.push(SortEvent::Swap { i, j: 0 });
+                array.swap(i, 0);
+                sift_down(array, 0, heap_len, &mut events);
+            }
+        }
+
+        events.push(SortEvent::ExitRange { lo: 0, hi: k });
+
+        // The largest of the k + 1 smallest is the k-th smallest
+        if k != 0 {
+            events.push(SortEvent::Swap { i: 0, j: k });
+            array.swap(0, k);
+        }
+
+        events.push(SortEvent::Found { idx: k });
+        events
+    }
+}
+
+/// Move the element at `idx` up until its parent is not smaller.
+fn sift_up(array: &mut [i32], mut idx: usize, events: &mut Vec<SortEvent>) {
+    while idx > 0 {
+        let parent = (idx - 1) / 2;
+        events.push(SortEvent::Compare { i: parent, j: idx });
+
+        if array[idx] > array[parent] {
+            events.push(SortEvent::Swap { i: parent, j: idx });
+            array.swap(parent, idx);
+            idx = parent;
+        } else {
+            break;
+        }
+    }
+}
+
+/// Sift down for a max heap occupying [0, end).
+fn sift_down(array: &mut [i32], root: usize, end: usize, events: &mut Vec<SortEvent>) {
+    let mut current = root;
+
+    loop {
+        let left = 2 * current + 1;
+        let right = 2 * current + 2;
+        let mut largest = current;
+
+        if left < end {
+            events.push(SortEvent::Compare { i: largest, j: left });
+            if array[left] > array[largest] {
+                largest = left;
+            }
+        }
+
+        if right < end {
+            events.push(SortEvent::Compare { i: largest, j: right });
+            if array[right] > array[largest] {
+                largest = right;
+            }
+        }
+
+        if largest != current {
+            events.push(SortEvent::Swap { i: current, j: largest });
+            array.swap(current, largest);
+            current = largest;
+        } else {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kth(array: &[i32], k: usize) -> i32 {
+        let mut sorted = array.to_vec();
+        sorted.sort();
+        sorted[k]
+    }
+
+    #[test]
+    fn test_heapselect_basic() {
+        let mut array = vec![5, 3, 8, 4, 2];
+        let events = Heapselect::select(&mut array, 2);
+
+        assert_eq!(array[2], 4);
+        assert_eq!(events.last(), Some(&SortEvent::Found { idx: 2 }));
+    }
+
+    #[test]
+    fn test_heapselect_every_k() {
+        let input: Vec<i32> = (0..50).map(|x| (x * 31 + 7) % 17).collect();
+
+        for k in 0..input.len() {
+            let mut array = input.clone();
+            Heapselect::select(&mut array, k);
+
+            let value = array[k];
+            assert_eq!(value, kth(&input, k));
+            assert!(array[..k].iter().all(|&x| x <= value));
+            assert!(array[k + 1..].iter().all(|&x| x >= value));
+        }
+    }
+
+    #[test]
+    fn test_heapselect_single() {
+        let mut array = vec![42];
+        let events = Heapselect::select(&mut array, 0);
+
+        assert_eq!(array, vec![42]);
+        assert_eq!(events.last(), Some(&SortEvent::Found { idx: 0 }));
+    }
+
+    #[test]
+    fn test_heapselect_heap_stays_at_front() {
+        let mut array: Vec<i32> = (0..100).rev().collect();
+        let events = Heapselect::select(&mut array, 4);
+
+        // Every swap touches the heap region [0, 4] or pulls an element into it
+        for event in &events {
+            if let SortEvent::Swap { i, j } = event {
+                assert!(*i <= 4 || *j <= 4);
+            }
+        }
+        assert_eq!(array[4], 4);
+    }
+
+    #[test]
+    fn test_heapselect_single_pass() {
+        let mut array: Vec<i32> = (0..500).map(|x| (x * 7919) % 503).collect();
+        let events = Heapselect::select(&mut array, 9);
+
+        // Each streamed element is compared with the root exactly once
+        let root_compares = events
+            .iter()
+            .filter(|e| matches!(e, SortEvent::Compare { i, j: 0 } if *i >= 10))
+            .count();
+        assert_eq!(root_compares, 490);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_heapselect_k_out_of_bounds() {
+        let mut array = vec![1, 2, 3];
+        Heapselect::select(&mut array, 3);
+    }
+}
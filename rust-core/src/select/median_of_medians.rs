@@ -0,0 +1,248 @@
+//! Median of Medians (BFPRT) selection.
+//!
+//! Quickselect with a deterministic pivot that guarantees linear time:
+//! 1. Split the range into groups of five and sort each group.
+//! 2. Gather the group medians at the front of the range.
+//! 3. Recursively select the median of those medians as the pivot.
+//!
+//! The pivot is larger than about 30% of the elements and smaller than
+//! another 30%, so each partition discards a constant fraction of the range.
+//! Every group of five is wrapped in EnterRange/ExitRange events.
+
+use crate::events::SortEvent;
+use super::PregenSelect;
+
+pub struct MedianOfMedians;
+
+/// Elements per group.
+const GROUP_SIZE: usize = 5;
+
+impl PregenSelect for MedianOfMedians {
+    fn select(array: &mut [i32], k: usize) -> Vec<SortEvent> {
+        assert!(k < array.len(), "k out of bounds");
+
+        let mut events = Vec::new();
+        select_range(array, 0, array.len() - 1, k, &mut events);
+
+        events.push(SortEvent::Found { idx: k });
+        events
+    }
+}
+
+/// Place the k-th smallest element of [lo..=hi] at position k.
+fn select_range(
+    array: &mut [i32],
+    mut lo: usize,
+    mut hi: usize,
+    k: usize,
+    events: &mut Vec<SortEvent>,
+) {
+    while lo < hi {
+        let pivot_idx = pivot(array, lo, hi, events);
+
+        events.push(SortEvent::EnterRange { lo, hi });
+        let (lt, gt) = partition(array, lo, hi, pivot_idx, events);
+        events.push(SortEvent::ExitRange { lo, hi });
+
+        if k < lt {
+            hi = lt - 1;
+        } else if k > gt {
+            lo = gt + 1;
+        } else {
+            return;
+        }
+    }
+}
+
+/// Choose the median of medians of [lo..=hi]. Returns its position.
+fn pivot(array: &mut [i32], lo: usize, hi: usize, events: &mut Vec<SortEvent>) -> usize {
+    if hi - lo < GROUP_SIZE {
+        insertion_sort_range(array, lo, hi, events);
+        return lo + (hi - lo) / 2;
+    }
+
+    // Sort each group of five and move its median to the front
+    let mut medians_end = lo;
+    let mut group_lo = lo;
+    while group_lo <= hi {
+        let group_hi = (group_lo + GROUP_SIZE - 1).min(hi);
+
+        events.push(SortEvent::EnterRange { lo: group_lo, hi: group_hi });
+        insertion_sort_range(array, group_lo, group_hi, events);
+        events.push(SortEvent::ExitRange { lo: group_lo, hi: group_hi });
+
+        let median = group_lo + (group_hi - group_lo) / 2;
+        if median != medians_end {
+            events.push(SortEvent::Swap { i: median, j: medians_end });
+            array.swap(median, medians_end);
+        }
+        medians_end += 1;
+        group_lo += GROUP_SIZE;
+    }
+
+    // Recursively select the median of the medians
+    let mid = lo + (medians_end - 1 - lo) / 2;
+    select_range(array, lo, medians_end - 1, mid, events);
+    mid
+}
+
+/// Three-way partition of [lo..=hi] around array[pivot_idx].
+/// Returns the inclusive range of positions holding pivot-equal elements,
+/// so runs of duplicates cannot make the partition unbalanced.
+fn partition(
+    array: &mut [i32],
+    lo: usize,
+    hi: usize,
+    pivot_idx: usize,
+    events: &mut Vec<SortEvent>,
+) -> (usize, usize) {
+    // Move pivot to the front
+    if pivot_idx != lo {
+        events.push(SortEvent::Swap { i: pivot_idx, j: lo });
+        array.swap(pivot_idx, lo);
+    }
+
+    // Invariant: [lo, lt) < pivot, [lt, i) == pivot, (gt, hi] > pivot
+    let pivot = array[lo];
+    let mut lt = lo;
+    let mut i = lo + 1;
+    let mut gt = hi;
+
+    while i <= gt {
+        events.push(SortEvent::Compare { i, j: lt });
+
+        if array[i] < pivot {
+            events.push(SortEvent::Swap { i, j: lt });
+            array.swap(i, lt);
+            lt += 1;
+            i += 1;
+        } else if array[i] > pivot {
+            if i != gt {
+                events.push(SortEvent::Swap { i, j: gt });
+                array.swap(i, gt);
+            }
+            gt -= 1;
+        } else {
+            i += 1;
+        }
+    }
+
+    (lt, gt)
+}
+
+/// Insertion sort for a range.
+fn insertion_sort_range(array: &mut [i32], lo: usize, hi: usize, events: &mut Vec<SortEvent>) {
+    for i in (lo + 1)..=hi {
+        let mut j = i;
+
+        while j > lo {
+            events.push(SortEvent::Compare { i: j - 1, j });
+
+            if array[j - 1] > array[j] {
+                events.push(SortEvent::Swap { i: j - 1, j });
+                array.swap(j - 1, j);
+                j -= 1;
+            } else {
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::select::quickselect::Quickselect;
+
+    fn kth(array: &[i32], k: usize) -> i32 {
+        let mut sorted = array.to_vec();
+        sorted.sort();
+        sorted[k]
+    }
+
+    #[test]
+    fn test_median_of_medians_basic() {
+        let mut array = vec![5, 3, 8, 4, 2];
+        let events = MedianOfMedians::select(&mut array, 2);
+
+        assert_eq!(array[2], 4);
+        assert_eq!(events.last(), Some(&SortEvent::Found { idx: 2 }));
+    }
+
+    #[test]
+    fn test_median_of_medians_every_k() {
+        let input: Vec<i32> = (0..73).map(|x| (x * 31 + 7) % 29).collect();
+
+        for k in 0..input.len() {
+            let mut array = input.clone();
+            MedianOfMedians::select(&mut array, k);
+
+            let value = array[k];
+            assert_eq!(value, kth(&input, k));
+            assert!(array[..k].iter().all(|&x| x <= value));
+            assert!(array[k + 1..].iter().all(|&x| x >= value));
+        }
+    }
+
+    #[test]
+    fn test_median_of_medians_single() {
+        let mut array = vec![42];
+        let events = MedianOfMedians::select(&mut array, 0);
+
+        assert_eq!(array, vec![42]);
+        assert_eq!(events, vec![SortEvent::Found { idx: 0 }]);
+    }
+
+    #[test]
+    fn test_median_of_medians_linear_compares() {
+        for n in [100usize, 1000, 10000] {
+            let input: Vec<i32> = (0..n as i32).collect();
+            let mut array = input.clone();
+            let events = MedianOfMedians::select(&mut array, n / 2);
+
+            let compare_count = events.iter().filter(|e| matches!(e, SortEvent::Compare { .. })).count();
+            assert_eq!(array[n / 2], kth(&input, n / 2));
+            // Worst-case bound is a constant times n, regardless of input order
+            assert!(compare_count < 30 * n, "n = {}: {} compares", n, compare_count);
+        }
+    }
+
+    #[test]
+    fn test_median_of_medians_all_equal_is_linear() {
+        let mut array = vec![7; 2000];
+        let events = MedianOfMedians::select(&mut array, 1000);
+
+        let compare_count = events.iter().filter(|e| matches!(e, SortEvent::Compare { .. })).count();
+        assert_eq!(array[1000], 7);
+        assert!(compare_count < 30 * 2000);
+    }
+
+    #[test]
+    fn test_median_of_medians_group_ranges() {
+        let mut array: Vec<i32> = (0..25).rev().collect();
+        let events = MedianOfMedians::select(&mut array, 12);
+
+        // The first pass sorts five groups of five
+        let groups: Vec<(usize, usize)> = events
+            .iter()
+            .filter_map(|e| match e {
+                SortEvent::EnterRange { lo, hi } if hi - lo == 4 => Some((*lo, *hi)),
+                _ => None,
+            })
+            .take(5)
+            .collect();
+        assert_eq!(groups, vec![(0, 4), (5, 9), (10, 14), (15, 19), (20, 24)]);
+    }
+
+    #[test]
+    fn test_median_of_medians_matches_quickselect() {
+        let input: Vec<i32> = (0..200).map(|x| (x * 7919) % 211).collect();
+
+        let mut a = input.clone();
+        MedianOfMedians::select(&mut a, 77);
+        let mut b = input;
+        Quickselect::select(&mut b, 77);
+
+        assert_eq!(a[77], b[77]);
+    }
+}
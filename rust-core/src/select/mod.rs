@@ -0,0 +1,73 @@
+//! Selection algorithms: find the k-th smallest element (0-based).
+//!
+//! Selections run to completion like the V1 pregen sorts and emit the same
+//! Compare/Swap/Range events. Instead of Done, the event list ends with a
+//! Found event pointing at position k, which then holds the k-th smallest
+//! element.
+//!
+//! `partial_sort::partial_sort` sorts just the k smallest elements and ends
+//! with Done like the full sorts.
+
+pub mod heapselect;
+pub mod median_of_medians;
+pub mod partial_sort;
+pub mod quickselect;
+
+use crate::events::SortEvent;
+
+/// Trait for selection algorithms.
+pub trait PregenSelect {
+    /// Rearrange the array so that position k holds the k-th smallest
+    /// element, and return all events that occurred.
+    /// Panics if k is out of bounds.
+    fn select(array: &mut [i32], k: usize) -> Vec<SortEvent>;
+}
+
+/// Available selection algorithms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelectAlgorithm {
+    Quickselect,
+    MedianOfMedians,
+    Heapselect,
+}
+
+impl SelectAlgorithm {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SelectAlgorithm::Quickselect => "quickselect",
+            SelectAlgorithm::MedianOfMedians => "median_of_medians",
+            SelectAlgorithm::Heapselect => "heapselect",
+        }
+    }
+
+    pub fn all() -> &'static [SelectAlgorithm] {
+        const ALGORITHMS: [SelectAlgorithm; 3] = [
+            SelectAlgorithm::Quickselect,
+            SelectAlgorithm::MedianOfMedians,
+            SelectAlgorithm::Heapselect,
+        ];
+        &ALGORITHMS
+    }
+
+    /// Parse algorithm name from string.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<SelectAlgorithm> {
+        match s.to_lowercase().as_str() {
+            "quickselect" | "quick_select" | "hoare_select" => Some(SelectAlgorithm::Quickselect),
+            "median_of_medians" | "medianofmedians" | "bfprt" | "mom" => {
+                Some(SelectAlgorithm::MedianOfMedians)
+            }
+            "heapselect" | "heap_select" => Some(SelectAlgorithm::Heapselect),
+            _ => None,
+        }
+    }
+}
+
+/// Run a selection for the k-th smallest element on the given array.
+pub fn pregen_select(algorithm: SelectAlgorithm, array: &mut [i32], k: usize) -> Vec<SortEvent> {
+    match algorithm {
+        SelectAlgorithm::Quickselect => quickselect::Quickselect::select(array, k),
+        SelectAlgorithm::MedianOfMedians => median_of_medians::MedianOfMedians::select(array, k),
+        SelectAlgorithm::Heapselect => heapselect::Heapselect::select(array, k),
+    }
+}
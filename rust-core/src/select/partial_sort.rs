@@ -0,0 +1,162 @@
+//! Partial sort (top-k) implementation.
+//!
+//! Sorts only the k smallest elements into positions [0, k); the order of
+//! the rest is unspecified. Works like C++ `std::partial_sort`:
+//! 1. Build a max-heap over the first k elements.
+//! 2. Stream over the remaining elements; any element smaller than the heap
+//!    root replaces it and is sifted down.
+//! 3. Heapsort the k survivors.
+//!
+//! Takes O(n log k) comparisons instead of O(n log n) for a full sort.
+//! The heap region is wrapped in EnterRange/ExitRange events.
+
+use crate::events::SortEvent;
+
+/// Sort the k smallest elements of `array` into its first k positions.
+/// k larger than the array length sorts the whole array.
+pub fn partial_sort(array: &mut [i32], k: usize) -> Vec<SortEvent> {
+    let mut events = Vec::new();
+    let k = k.min(array.len());
+
+    if k == 0 {
+        events.push(SortEvent::Done);
+        return events;
+    }
+
+    events.push(SortEvent::EnterRange { lo: 0, hi: k - 1 });
+
+    // Build max heap over the first k elements
+    for i in (0..k / 2).rev() {
+        sift_down(array, i, k, &mut events);
+    }
+
+    // Keep the k smallest seen so far in the heap
+    for i in k..array.len() {
+        events.push(SortEvent::Compare { i, j: 0 });
+        if array[i] < array[0] {
+            events.push(SortEvent::Swap { i, j: 0 });
+            array.swap(i, 0);
+            sift_down(array, 0, k, &mut events);
+        }
+    }
+
+    // Sort the heap
+    for end in (1..k).rev() {
+        events.push(SortEvent::Swap { i: 0, j: end });
+        array.swap(0, end);
+        sift_down(array, 0, end, &mut events);
+    }
+
+    events.push(SortEvent::ExitRange { lo: 0, hi: k - 1 });
+    events.push(SortEvent::Done);
+    events
+}
+
+/// Sift down for a max heap occupying [0, end).
+fn sift_down(array: &mut [i32], root: usize, end: usize, events: &mut Vec<SortEvent>) {
+    let mut current = root;
+
+    loop {
+        let left = 2 * current + 1;
+        let right = 2 * current + 2;
+        let mut largest = current;
+
+        if left < end {
+            events.push(SortEvent::Compare { i: largest, j: left });
+            if array[left] > array[largest] {
+                largest = left;
+            }
+        }
+
+        if right < end {
+            events.push(SortEvent::Compare { i: largest, j: right });
+            if array[right] > array[largest] {
+                largest = right;
+            }
+        }
+
+        if largest != current {
+            events.push(SortEvent::Swap { i: current, j: largest });
+            array.swap(current, largest);
+            current = largest;
+        } else {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pregen::heap_sort::HeapSort;
+    use crate::pregen::PregenSort;
+
+    fn compare_count(events: &[SortEvent]) -> usize {
+        events.iter().filter(|e| matches!(e, SortEvent::Compare { .. })).count()
+    }
+
+    #[test]
+    fn test_partial_sort_basic() {
+        let mut array = vec![9, 4, 7, 1, 8, 2, 6, 3, 5, 0];
+        let events = partial_sort(&mut array, 3);
+
+        assert_eq!(&array[..3], &[0, 1, 2]);
+        let mut rest = array[3..].to_vec();
+        rest.sort();
+        assert_eq!(rest, vec![3, 4, 5, 6, 7, 8, 9]);
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_partial_sort_every_k() {
+        let input: Vec<i32> = (0..40).map(|x| (x * 17 + 3) % 13).collect();
+        let mut sorted = input.clone();
+        sorted.sort();
+
+        for k in 0..=input.len() {
+            let mut array = input.clone();
+            partial_sort(&mut array, k);
+            assert_eq!(&array[..k], &sorted[..k], "k = {}", k);
+        }
+    }
+
+    #[test]
+    fn test_partial_sort_k_larger_than_len() {
+        let mut array = vec![3, 1, 2];
+        partial_sort(&mut array, 10);
+
+        assert_eq!(array, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_partial_sort_zero_k() {
+        let mut array = vec![3, 1, 2];
+        let events = partial_sort(&mut array, 0);
+
+        assert_eq!(array, vec![3, 1, 2]);
+        assert_eq!(events, vec![SortEvent::Done]);
+    }
+
+    #[test]
+    fn test_partial_sort_empty() {
+        let mut array: Vec<i32> = vec![];
+        let events = partial_sort(&mut array, 5);
+
+        assert!(array.is_empty());
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_partial_sort_top_10_cheaper_than_full_sort() {
+        let input: Vec<i32> = (0..2000).map(|x| (x * 7919) % 2003).collect();
+
+        let mut partial = input.clone();
+        let partial_events = partial_sort(&mut partial, 10);
+
+        let mut full = input;
+        let full_events = HeapSort::sort(&mut full);
+
+        assert_eq!(&partial[..10], &full[..10]);
+        assert!(compare_count(&partial_events) * 4 < compare_count(&full_events));
+    }
+}
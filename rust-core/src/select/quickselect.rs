@@ -0,0 +1,161 @@
+//! Quickselect implementation.
+//!
+//! Partitions like quicksort, but only continues into the side that
+//! contains position k. Expected linear time; the pivot is picked
+//! pseudo-randomly from a fixed seed so traces are reproducible.
+//! Emits EnterRange/ExitRange events for every partitioned range.
+
+use crate::events::SortEvent;
+use super::PregenSelect;
+
+pub struct Quickselect;
+
+/// Seed for pivot selection.
+const SEED: u64 = 0x9E37_79B9_7F4A_7C15;
+
+impl PregenSelect for Quickselect {
+    fn select(array: &mut [i32], k: usize) -> Vec<SortEvent> {
+        assert!(k < array.len(), "k out of bounds");
+
+        let mut events = Vec::new();
+        let mut rng = SEED;
+        let mut lo = 0;
+        let mut hi = array.len() - 1;
+
+        while lo < hi {
+            events.push(SortEvent::EnterRange { lo, hi });
+
+            let pivot_idx = lo + (next_random(&mut rng) % (hi - lo + 1) as u64) as usize;
+            let p = partition(array, lo, hi, pivot_idx, &mut events);
+
+            events.push(SortEvent::ExitRange { lo, hi });
+
+            if k < p {
+                hi = p - 1;
+            } else if k > p {
+                lo = p + 1;
+            } else {
+                break;
+            }
+        }
+
+        events.push(SortEvent::Found { idx: k });
+        events
+    }
+}
+
+/// Xorshift64 step.
+fn next_random(state: &mut u64) -> u64 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+    *state
+}
+
+/// Lomuto partition of [lo..=hi] around array[pivot_idx].
+/// Returns the pivot's final position.
+fn partition(
+    array: &mut [i32],
+    lo: usize,
+    hi: usize,
+    pivot_idx: usize,
+    events: &mut Vec<SortEvent>,
+) -> usize {
+    // Move pivot to the end
+    if pivot_idx != hi {
+        events.push(SortEvent::Swap { i: pivot_idx, j: hi });
+        array.swap(pivot_idx, hi);
+    }
+
+    let pivot = array[hi];
+    let mut store = lo;
+
+    for i in lo..hi {
+        events.push(SortEvent::Compare { i, j: hi });
+
+        if array[i] < pivot {
+            if i != store {
+                events.push(SortEvent::Swap { i, j: store });
+                array.swap(i, store);
+            }
+            store += 1;
+        }
+    }
+
+    if store != hi {
+        events.push(SortEvent::Swap { i: store, j: hi });
+        array.swap(store, hi);
+    }
+
+    store
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kth(array: &[i32], k: usize) -> i32 {
+        let mut sorted = array.to_vec();
+        sorted.sort();
+        sorted[k]
+    }
+
+    #[test]
+    fn test_quickselect_basic() {
+        let mut array = vec![5, 3, 8, 4, 2];
+        let events = Quickselect::select(&mut array, 2);
+
+        assert_eq!(array[2], 4);
+        assert_eq!(events.last(), Some(&SortEvent::Found { idx: 2 }));
+    }
+
+    #[test]
+    fn test_quickselect_every_k() {
+        let input: Vec<i32> = (0..50).map(|x| (x * 31 + 7) % 17).collect();
+
+        for k in 0..input.len() {
+            let mut array = input.clone();
+            Quickselect::select(&mut array, k);
+
+            let value = array[k];
+            assert_eq!(value, kth(&input, k));
+            assert!(array[..k].iter().all(|&x| x <= value));
+            assert!(array[k + 1..].iter().all(|&x| x >= value));
+        }
+    }
+
+    #[test]
+    fn test_quickselect_single() {
+        let mut array = vec![42];
+        let events = Quickselect::select(&mut array, 0);
+
+        assert_eq!(array, vec![42]);
+        assert_eq!(events, vec![SortEvent::Found { idx: 0 }]);
+    }
+
+    #[test]
+    fn test_quickselect_all_equal() {
+        let mut array = vec![7; 20];
+        Quickselect::select(&mut array, 13);
+
+        assert_eq!(array[13], 7);
+    }
+
+    #[test]
+    fn test_quickselect_fewer_compares_than_sorting() {
+        let input: Vec<i32> = (0..1000).map(|x| (x * 7919) % 1009).collect();
+        let mut array = input.clone();
+        let events = Quickselect::select(&mut array, 500);
+
+        let compare_count = events.iter().filter(|e| matches!(e, SortEvent::Compare { .. })).count();
+        assert_eq!(array[500], kth(&input, 500));
+        assert!(compare_count < 1000 * 10);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_quickselect_k_out_of_bounds() {
+        let mut array = vec![1, 2, 3];
+        Quickselect::select(&mut array, 3);
+    }
+}
@@ -0,0 +1,213 @@
+//! Shareable sessions.
+//!
+//! A `SessionBundle` records everything needed to reproduce a run: the
+//! request (algorithm, explicit array or generator pattern and seed, and
+//! options), the crate version that made it and, optionally, the trace
+//! itself. Serialized, it is a single blob for a link or a bug report;
+//! `replay` reruns it and reports whether the events came out identical.
+
+use serde::{Deserialize, Serialize};
+use crate::batch::{BatchRequest, BatchResult};
+use crate::events::SortEvent;
+
+/// Value of `SessionBundle::format`, identifying a sort-forge session.
+pub const SESSION_FORMAT: &str = "sort-forge-session";
+
+/// Bumped whenever the session layout changes incompatibly.
+pub const SESSION_FORMAT_VERSION: u32 = 1;
+
+/// A run packaged for sharing.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionBundle {
+    pub format: String,
+    pub format_version: u32,
+    /// Version of the crate that exported the session.
+    pub crate_version: String,
+    pub request: BatchRequest,
+    /// The events of the exported run, when it was exported with its trace.
+    #[serde(default)]
+    pub events: Option<Vec<SortEvent>>,
+}
+
+/// Outcome of replaying a SessionBundle.
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionReplay {
+    pub result: BatchResult,
+    /// Whether the replayed events equal the recorded ones; None when the
+    /// bundle carries no trace.
+    pub matches_trace: Option<bool>,
+    /// Index of the first event that differs from the recorded trace, or
+    /// the shorter length when one trace is a prefix of the other.
+    pub first_mismatch: Option<usize>,
+    /// Whether the replaying crate is the version that exported the session.
+    pub same_version: bool,
+}
+
+impl SessionBundle {
+    /// Bundle a request, running it to record its trace if `include_trace`.
+    /// A recorded trace turns the request's `events` option on, so a replay
+    /// has events to compare.
+    pub fn export(mut request: BatchRequest, include_trace: bool) -> Result<Self, String> {
+        let events = if include_trace {
+            request.options.events = true;
+            request.run()?.events
+        } else {
+            request.validate()?;
+            None
+        };
+
+        Ok(Self {
+            format: SESSION_FORMAT.to_string(),
+            format_version: SESSION_FORMAT_VERSION,
+            crate_version: env!("CARGO_PKG_VERSION").to_string(),
+            request,
+            events,
+        })
+    }
+
+    /// Check that this is a session this crate can replay.
+    pub fn check_format(&self) -> Result<(), String> {
+        if self.format != SESSION_FORMAT {
+            return Err(format!("Not a sort-forge session: {}", self.format));
+        }
+        if self.format_version > SESSION_FORMAT_VERSION {
+            return Err(format!(
+                "Session format version {} is newer than supported ({})",
+                self.format_version, SESSION_FORMAT_VERSION
+            ));
+        }
+        Ok(())
+    }
+
+    /// Rerun the request and compare against the recorded trace, if any.
+    pub fn replay(&self) -> Result<SessionReplay, String> {
+        self.check_format()?;
+        let result = self.request.run()?;
+
+        let first_mismatch = match (&self.events, &result.events) {
+            (Some(recorded), Some(replayed)) => first_mismatch(recorded, replayed),
+            (Some(_), None) => Some(0),
+            _ => None,
+        };
+
+        Ok(SessionReplay {
+            matches_trace: self.events.as_ref().map(|_| first_mismatch.is_none()),
+            first_mismatch,
+            same_version: self.crate_version == env!("CARGO_PKG_VERSION"),
+            result,
+        })
+    }
+}
+
+/// Index of the first differing event, None when the traces are equal.
+fn first_mismatch(a: &[SortEvent], b: &[SortEvent]) -> Option<usize> {
+    match a.iter().zip(b).position(|(x, y)| x != y) {
+        Some(i) => Some(i),
+        None if a.len() != b.len() => Some(a.len().min(b.len())),
+        None => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::batch::BatchOptions;
+
+    fn pattern_request(algorithm: &str) -> BatchRequest {
+        BatchRequest {
+            algorithm: algorithm.to_string(),
+            array: None,
+            pattern: Some("random".to_string()),
+            n: Some(40),
+            seed: 7,
+            options: BatchOptions::default(),
+        }
+    }
+
+    #[test]
+    fn test_export_records_trace() {
+        let bundle = SessionBundle::export(pattern_request("quicksort_lr"), true).unwrap();
+
+        assert_eq!(bundle.format, SESSION_FORMAT);
+        assert_eq!(bundle.format_version, SESSION_FORMAT_VERSION);
+        assert_eq!(bundle.crate_version, env!("CARGO_PKG_VERSION"));
+        assert!(matches!(bundle.events.as_deref().unwrap().last(), Some(SortEvent::Done)));
+    }
+
+    #[test]
+    fn test_export_without_trace_validates() {
+        let bundle = SessionBundle::export(pattern_request("heap"), false).unwrap();
+        assert_eq!(bundle.events, None);
+
+        assert!(SessionBundle::export(pattern_request("bogus"), false).is_err());
+        let mut request = pattern_request("heap");
+        request.n = None;
+        assert!(SessionBundle::export(request, true).is_err());
+    }
+
+    #[test]
+    fn test_replay_matches_trace() {
+        let mut request = pattern_request("merge");
+        request.options.events = false;
+        request.options.sweep = true;
+        let bundle = SessionBundle::export(request, true).unwrap();
+        assert!(bundle.request.options.events);
+
+        let replay = bundle.replay().unwrap();
+        assert_eq!(replay.matches_trace, Some(true));
+        assert_eq!(replay.first_mismatch, None);
+        assert!(replay.same_version);
+        assert_eq!(replay.result.events, bundle.events);
+    }
+
+    #[test]
+    fn test_replay_explicit_array() {
+        let mut request = pattern_request("insertion");
+        request.pattern = None;
+        request.array = Some(vec![3, 1, 2]);
+        let bundle = SessionBundle::export(request, false).unwrap();
+
+        let replay = bundle.replay().unwrap();
+        assert_eq!(replay.matches_trace, None);
+        assert_eq!(replay.result.sorted_array, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_replay_reports_divergence() {
+        let mut bundle = SessionBundle::export(pattern_request("bubble"), true).unwrap();
+        let events = bundle.events.as_mut().unwrap();
+        events[2] = SortEvent::Compare { i: 0, j: 39 };
+        events.pop();
+
+        let replay = bundle.replay().unwrap();
+        assert_eq!(replay.matches_trace, Some(false));
+        assert_eq!(replay.first_mismatch, Some(2));
+
+        assert_eq!(first_mismatch(&[SortEvent::Done], &[]), Some(0));
+    }
+
+    #[test]
+    fn test_replay_rejects_foreign_formats() {
+        let mut bundle = SessionBundle::export(pattern_request("bubble"), false).unwrap();
+        bundle.crate_version = "0.0.0".to_string();
+        assert!(!bundle.replay().unwrap().same_version);
+
+        bundle.format_version = SESSION_FORMAT_VERSION + 1;
+        assert!(bundle.replay().is_err());
+        bundle.format_version = SESSION_FORMAT_VERSION;
+        bundle.format = "sort-forge-trace".to_string();
+        assert!(bundle.replay().is_err());
+    }
+
+    #[cfg(feature = "native")]
+    #[test]
+    fn test_session_json_round_trip() {
+        let bundle = SessionBundle::export(pattern_request("shell"), true).unwrap();
+
+        let json = serde_json::to_string(&bundle).unwrap();
+        assert!(json.starts_with(r#"{"format":"sort-forge-session""#));
+        let restored: SessionBundle = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, bundle);
+        assert_eq!(restored.replay().unwrap().matches_trace, Some(true));
+    }
+}
@@ -0,0 +1,139 @@
+//! Opt-in Snapshot events in traces.
+//!
+//! A trace is a chain of deltas: one lost or corrupted event and every
+//! frame after it is wrong, and jumping to a phase means replaying
+//! everything before it. Snapshots of the whole array at chosen points
+//! give frontends hard synchronization points to restore from and seek to.
+//! They cost n values each, so they are off unless a policy asks for them.
+
+use crate::prelude::*;
+use crate::events::SortEvent;
+use crate::pregen::{pregen_sort, Algorithm};
+
+/// Where to insert snapshots.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SnapshotPolicy {
+    /// After every Phase event.
+    pub at_phases: bool,
+    /// After every this many events, if set.
+    pub every: Option<usize>,
+}
+
+/// Replay events on input and return them with Snapshot events inserted
+/// where the policy asks. No snapshot follows the final Done.
+pub fn insert_snapshots(
+    input: &[i32],
+    events: &[SortEvent],
+    policy: SnapshotPolicy,
+) -> Vec<SortEvent> {
+    let mut array = input.to_vec();
+    let mut out = Vec::with_capacity(events.len());
+    for (n, event) in events.iter().enumerate() {
+        event.apply(&mut array);
+        out.push(event.clone());
+
+        let at_phase = policy.at_phases && matches!(event, SortEvent::Phase { .. });
+        let periodic = policy.every.is_some_and(|every| every > 0 && (n + 1) % every == 0);
+        if (at_phase || periodic) && !matches!(event, SortEvent::Done) {
+            out.push(SortEvent::snapshot(&array));
+        }
+    }
+    out
+}
+
+/// Run a pregeneration sort with snapshots inserted by policy.
+pub fn pregen_sort_with_snapshots(
+    algorithm: Algorithm,
+    array: &mut [i32],
+    policy: SnapshotPolicy,
+) -> Vec<SortEvent> {
+    let input = array.to_vec();
+    let events = pregen_sort(algorithm, array);
+    insert_snapshots(&input, &events, policy)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pregen::pdq_sort::PHASE_EQUAL_PARTITION;
+
+    fn snapshots(events: &[SortEvent]) -> Vec<(usize, Vec<i32>)> {
+        events
+            .iter()
+            .enumerate()
+            .filter_map(|(at, e)| match e {
+                SortEvent::Snapshot(s) => Some((at, s.array.clone())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_default_policy_adds_nothing() {
+        let input = vec![3, 1, 2];
+        let events = pregen_sort(Algorithm::Bubble, &mut input.clone());
+        assert_eq!(insert_snapshots(&input, &events, SnapshotPolicy::default()), events);
+    }
+
+    #[test]
+    fn test_snapshots_match_replay() {
+        let input: Vec<i32> = (0..300).map(|x| (x * 31) % 4).collect();
+        let policy = SnapshotPolicy { at_phases: true, every: Some(50) };
+        let events = pregen_sort_with_snapshots(Algorithm::PdqSort, &mut input.clone(), policy);
+
+        let mut array = input.clone();
+        let taken = snapshots(&events);
+        assert!(!taken.is_empty());
+        for (at, event) in events.iter().enumerate() {
+            if let SortEvent::Snapshot(s) = event {
+                assert_eq!(array, s.array, "snapshot at {}", at);
+            }
+            event.apply(&mut array);
+        }
+    }
+
+    #[test]
+    fn test_snapshot_follows_each_phase() {
+        let input: Vec<i32> = (0..300).map(|x| (x * 31) % 4).collect();
+        let policy = SnapshotPolicy { at_phases: true, every: None };
+        let events = pregen_sort_with_snapshots(Algorithm::PdqSort, &mut input.clone(), policy);
+
+        let phases: Vec<usize> = events
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| matches!(e, SortEvent::Phase { name } if name == PHASE_EQUAL_PARTITION))
+            .map(|(at, _)| at)
+            .collect();
+        assert!(!phases.is_empty());
+        for at in phases {
+            assert!(matches!(events[at + 1], SortEvent::Snapshot(_)));
+        }
+    }
+
+    #[test]
+    fn test_periodic_snapshots_recover_corruption() {
+        let input = vec![5, 4, 3, 2, 1, 0];
+        let policy = SnapshotPolicy { at_phases: false, every: Some(4) };
+        let events = pregen_sort_with_snapshots(Algorithm::Bubble, &mut input.clone(), policy);
+        let (at, _) = snapshots(&events)[0];
+
+        // Drop a swap before the first snapshot; replay is right again after it
+        let first_swap = events.iter().position(|e| matches!(e, SortEvent::Swap { .. })).unwrap();
+        assert!(first_swap < at);
+        let mut array = input.clone();
+        for (n, event) in events.iter().enumerate() {
+            if n != first_swap {
+                event.apply(&mut array);
+            }
+        }
+        assert_eq!(array, vec![0, 1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_no_snapshot_after_done() {
+        let policy = SnapshotPolicy { at_phases: false, every: Some(1) };
+        let events = pregen_sort_with_snapshots(Algorithm::Insertion, &mut [2, 1], policy);
+        assert!(matches!(events.last(), Some(SortEvent::Done)));
+        assert_eq!(snapshots(&events).len(), events.len() / 2);
+    }
+}
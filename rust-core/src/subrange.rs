@@ -0,0 +1,130 @@
+//! Sorting one slice of an array.
+//!
+//! `pregen_sort_range` sorts only `[lo, hi)` and leaves the rest of the
+//! array untouched, so a demo can let the user select a region and sort it
+//! in place. The algorithm runs on the slice and every index in its trace
+//! is shifted by `lo`, so the events address the whole array and play back
+//! on it directly. Snapshots are widened to the whole array, and Flips,
+//! which always reverse a prefix, become Swaps unless the range starts at 0.
+
+use crate::prelude::*;
+use crate::events::{expand_flips, SortEvent};
+use crate::pregen::{pregen_sort, Algorithm};
+
+/// Sort `array[lo..hi]` in place, returning events with absolute indices.
+/// Fails if the range isn't within the array.
+pub fn pregen_sort_range(
+    algorithm: Algorithm,
+    array: &mut [i32],
+    lo: usize,
+    hi: usize,
+) -> Result<Vec<SortEvent>, String> {
+    if lo > hi || hi > array.len() {
+        return Err(format!("Range {}..{} is outside an array of {}", lo, hi, array.len()));
+    }
+
+    let before = array.to_vec();
+    let mut events = pregen_sort(algorithm, &mut array[lo..hi]);
+    if lo > 0 {
+        events = expand_flips(&events);
+    }
+    Ok(events.iter().map(|event| shift(event, lo, &before)).collect())
+}
+
+/// Shift an event of the slice starting at `lo` into array coordinates.
+/// `array` supplies the untouched elements around a Snapshot.
+pub(crate) fn shift(event: &SortEvent, lo: usize, array: &[i32]) -> SortEvent {
+    let at = |idx: usize| lo + idx;
+    match event {
+        SortEvent::Swap { i, j } => SortEvent::Swap { i: at(*i), j: at(*j) },
+        SortEvent::Overwrite { idx, old_val, new_val } => SortEvent::Overwrite {
+            idx: at(*idx),
+            old_val: *old_val,
+            new_val: *new_val,
+        },
+        SortEvent::Compare { i, j } => SortEvent::Compare { i: at(*i), j: at(*j) },
+        SortEvent::EnterRange { lo: l, hi: h } => SortEvent::EnterRange { lo: at(*l), hi: at(*h) },
+        SortEvent::ExitRange { lo: l, hi: h } => SortEvent::ExitRange { lo: at(*l), hi: at(*h) },
+        SortEvent::Found { idx } => SortEvent::Found { idx: at(*idx) },
+        SortEvent::MarkSorted { idx } => SortEvent::MarkSorted { idx: at(*idx) },
+        SortEvent::Highlight(h) => {
+            SortEvent::highlight(h.indices.iter().map(|&idx| at(idx)).collect(), &h.tag)
+        }
+        SortEvent::BlockMove(m) => SortEvent::block_move(at(m.from), at(m.to), m.len),
+        SortEvent::Rotate(r) => SortEvent::rotate(at(r.lo), at(r.hi), r.by),
+        SortEvent::SetCursor(c) => SortEvent::set_cursor(&c.id, c.index.map(at), c.prev.map(at)),
+        SortEvent::EqualRange { lo: l, hi: h } => SortEvent::EqualRange { lo: at(*l), hi: at(*h) },
+        SortEvent::Snapshot(s) => {
+            let mut whole = array.to_vec();
+            whole[lo..lo + s.array.len()].copy_from_slice(&s.array);
+            SortEvent::snapshot(&whole)
+        }
+        // A gap is a distance, not an index
+        SortEvent::GapChange { .. } => event.clone(),
+        // Only reached for ranges starting at 0, where nothing shifts
+        SortEvent::Flip { .. } | SortEvent::Phase { .. } | SortEvent::Done => event.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patterns::{self, Pattern};
+
+    #[test]
+    fn test_sorts_only_the_range() {
+        let mut array = vec![9, 8, 7, 6, 5, 4, 3, 2, 1];
+        pregen_sort_range(Algorithm::MergeSort, &mut array, 2, 6).unwrap();
+
+        assert_eq!(array, vec![9, 8, 4, 5, 6, 7, 3, 2, 1]);
+    }
+
+    #[test]
+    fn test_events_replay_on_whole_array() {
+        let input = patterns::generate(Pattern::Uniform, 60, 3);
+        for &algorithm in Algorithm::all() {
+            let mut array = input.clone();
+            let events = pregen_sort_range(algorithm, &mut array, 10, 45).unwrap();
+
+            let mut replayed = input.clone();
+            for event in &events {
+                event.apply(&mut replayed);
+            }
+            assert_eq!(replayed, array, "{:?}", algorithm);
+            assert_eq!(array[..10], input[..10]);
+            assert_eq!(array[45..], input[45..]);
+        }
+    }
+
+    #[test]
+    fn test_indices_stay_in_range() {
+        let input = patterns::generate(Pattern::Random, 40, 5);
+        let events = pregen_sort_range(Algorithm::QuickSortLL, &mut input.clone(), 5, 25).unwrap();
+
+        for event in &events {
+            match *event {
+                SortEvent::Compare { i, j } | SortEvent::Swap { i, j } => {
+                    assert!((5..25).contains(&i) && (5..25).contains(&j), "{:?}", event);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    #[test]
+    fn test_snapshot_is_widened() {
+        let event = shift(&SortEvent::snapshot(&[1, 2]), 1, &[9, 5, 4, 9]);
+        assert_eq!(event, SortEvent::snapshot(&[9, 1, 2, 9]));
+    }
+
+    #[test]
+    fn test_empty_and_invalid_ranges() {
+        let mut array = vec![3, 1, 2];
+        let events = pregen_sort_range(Algorithm::Bubble, &mut array, 1, 1).unwrap();
+        assert_eq!(events, vec![SortEvent::Done]);
+        assert_eq!(array, vec![3, 1, 2]);
+
+        assert!(pregen_sort_range(Algorithm::Bubble, &mut array, 2, 1).is_err());
+        assert!(pregen_sort_range(Algorithm::Bubble, &mut array, 0, 4).is_err());
+    }
+}
@@ -0,0 +1,96 @@
+//! Downsampled array summaries for drawing huge arrays.
+//!
+//! A frontend can't draw a million bars per frame, but it only has a few
+//! thousand pixel columns anyway. `summarize` splits the array into one
+//! bucket per column and reduces each to its min, max and mean, which is
+//! enough to draw the column as a range with a marker.
+
+use serde::Serialize;
+use crate::prelude::*;
+
+/// Summary of one bucket of consecutive elements.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct BucketSummary {
+    pub min: i32,
+    pub max: i32,
+    pub mean: f64,
+}
+
+/// Split array into `buckets` nearly equal runs and summarize each. Bucket
+/// b covers `b * n / buckets .. (b + 1) * n / buckets`. Asking for more
+/// buckets than elements gives one bucket per element.
+pub fn summarize(array: &[i32], buckets: usize) -> Vec<BucketSummary> {
+    let n = array.len();
+    let buckets = buckets.min(n);
+
+    (0..buckets)
+        .map(|b| {
+            let bucket = &array[b * n / buckets..(b + 1) * n / buckets];
+            let (min, max, sum) = bucket.iter().fold(
+                (i32::MAX, i32::MIN, 0i64),
+                |(min, max, sum), &v| (min.min(v), max.max(v), sum + v as i64),
+            );
+            BucketSummary { min, max, mean: sum as f64 / bucket.len() as f64 }
+        })
+        .collect()
+}
+
+/// Flatten a summary to `[min, max, mean]` triples for a Float64Array.
+pub fn flatten_summary(summary: &[BucketSummary]) -> Vec<f64> {
+    summary
+        .iter()
+        .flat_map(|s| [s.min as f64, s.max as f64, s.mean])
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_even_buckets() {
+        let summary = summarize(&[1, 5, 3, 3, -2, 8], 3);
+        assert_eq!(
+            summary,
+            vec![
+                BucketSummary { min: 1, max: 5, mean: 3.0 },
+                BucketSummary { min: 3, max: 3, mean: 3.0 },
+                BucketSummary { min: -2, max: 8, mean: 3.0 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_uneven_buckets_cover_everything() {
+        let array: Vec<i32> = (0..1000).collect();
+        let summary = summarize(&array, 7);
+
+        assert_eq!(summary.len(), 7);
+        assert_eq!(summary[0].min, 0);
+        assert_eq!(summary[6].max, 999);
+        for w in summary.windows(2) {
+            assert_eq!(w[0].max + 1, w[1].min);
+        }
+    }
+
+    #[test]
+    fn test_more_buckets_than_elements() {
+        let summary = summarize(&[4, 2], 10);
+        assert_eq!(summary.len(), 2);
+        assert_eq!(summary[1], BucketSummary { min: 2, max: 2, mean: 2.0 });
+        assert!(summarize(&[], 10).is_empty());
+        assert!(summarize(&[1, 2], 0).is_empty());
+    }
+
+    #[test]
+    fn test_mean_does_not_overflow() {
+        let summary = summarize(&[i32::MAX, i32::MAX], 1);
+        assert_eq!(summary[0].mean, i32::MAX as f64);
+    }
+
+    #[test]
+    fn test_flat_layout() {
+        let flat = flatten_summary(&summarize(&[1, 3, 5, 7], 2));
+        assert_eq!(flat, vec![1.0, 3.0, 2.0, 5.0, 7.0, 6.0]);
+    }
+}
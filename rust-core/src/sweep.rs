@@ -0,0 +1,141 @@
+//! Final verification sweep.
+//!
+//! The classic visualizer ending: a left-to-right pass that compares each
+//! neighbouring pair and marks elements sorted, before Done. The sweep
+//! checks rather than assumes, so it stops marking at the first inversion.
+
+use serde::{Deserialize, Serialize};
+use crate::prelude::*;
+use crate::events::SortEvent;
+
+/// Incremental sweep over an array of `len` elements.
+#[derive(Serialize, Deserialize)]
+pub struct VerificationSweep {
+    next: usize,
+    len: usize,
+    done: bool,
+}
+
+impl VerificationSweep {
+    pub fn new(len: usize) -> Self {
+        Self {
+            next: 0,
+            len,
+            done: false,
+        }
+    }
+
+    /// Sweep up to `limit` elements, appending Compare/MarkSorted events and
+    /// a final Done. Each element counts as one step.
+    pub fn step(&mut self, arr: &[i32], limit: usize, events: &mut Vec<SortEvent>) {
+        for _ in 0..limit {
+            if self.done {
+                break;
+            }
+
+            let i = self.next;
+            if i >= self.len {
+                self.finish(events);
+                break;
+            }
+
+            if i > 0 {
+                events.push(SortEvent::Compare { i: i - 1, j: i });
+                if arr[i - 1] > arr[i] {
+                    self.finish(events);
+                    break;
+                }
+            }
+
+            events.push(SortEvent::MarkSorted { idx: i });
+            self.next += 1;
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+
+    fn finish(&mut self, events: &mut Vec<SortEvent>) {
+        self.done = true;
+        events.push(SortEvent::Done);
+    }
+}
+
+/// Append a full sweep to a finished trace, moving its Done to the end.
+pub fn append_sweep(arr: &[i32], events: &mut Vec<SortEvent>) {
+    if matches!(events.last(), Some(SortEvent::Done)) {
+        events.pop();
+    }
+
+    events.reserve(2 * arr.len() + 1);
+    VerificationSweep::new(arr.len()).step(arr, usize::MAX, events);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pregen::{pregen_sort, pregen_sort_with_sweep, Algorithm};
+
+    #[test]
+    fn test_append_sweep_sorted() {
+        let mut events = vec![SortEvent::Swap { i: 0, j: 1 }, SortEvent::Done];
+        append_sweep(&[1, 2, 3], &mut events);
+
+        assert_eq!(
+            events,
+            vec![
+                SortEvent::Swap { i: 0, j: 1 },
+                SortEvent::MarkSorted { idx: 0 },
+                SortEvent::Compare { i: 0, j: 1 },
+                SortEvent::MarkSorted { idx: 1 },
+                SortEvent::Compare { i: 1, j: 2 },
+                SortEvent::MarkSorted { idx: 2 },
+                SortEvent::Done,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sweep_stops_at_inversion() {
+        let mut events = Vec::new();
+        append_sweep(&[1, 3, 2, 4], &mut events);
+
+        let marked = events.iter().filter(|e| matches!(e, SortEvent::MarkSorted { .. })).count();
+        assert_eq!(marked, 2);
+        assert_eq!(events.last(), Some(&SortEvent::Done));
+    }
+
+    #[test]
+    fn test_sweep_empty() {
+        let mut events = vec![SortEvent::Done];
+        append_sweep(&[], &mut events);
+        assert_eq!(events, vec![SortEvent::Done]);
+    }
+
+    #[test]
+    fn test_sweep_in_batches_matches_full_sweep() {
+        let arr: Vec<i32> = (0..10).collect();
+        let mut expected = Vec::new();
+        append_sweep(&arr, &mut expected);
+
+        let mut sweep = VerificationSweep::new(arr.len());
+        let mut events = Vec::new();
+        while !sweep.is_done() {
+            sweep.step(&arr, 3, &mut events);
+        }
+        assert_eq!(events, expected);
+    }
+
+    #[test]
+    fn test_pregen_sort_with_sweep() {
+        let input = vec![4, 1, 3, 2];
+        let plain = pregen_sort(Algorithm::HeapSort, &mut input.clone());
+        let events = pregen_sort_with_sweep(Algorithm::HeapSort, &mut input.clone());
+
+        // Same sort, then one Compare/MarkSorted per element after the first
+        assert_eq!(events[..plain.len() - 1], plain[..plain.len() - 1]);
+        assert_eq!(events.len(), plain.len() + 2 * input.len() - 1);
+        assert_eq!(events.last(), Some(&SortEvent::Done));
+    }
+}
@@ -0,0 +1,105 @@
+//! Rayon thread pool on Web Workers.
+//!
+//! A threads-enabled wasm build has shared memory but no way to spawn
+//! threads itself. `init_thread_pool` builds the global rayon pool with a
+//! spawn handler that only queues each worker thread; the web UI then
+//! starts one Web Worker per thread, instantiates the module there with
+//! the same memory, and calls `start_pool_worker`, which takes a queued
+//! thread and runs it for the life of the worker.
+//!
+//! Blocking on the pool isn't allowed on the browser's main thread, so
+//! parallel work (`pregen_sort_batch`, `pregen_race`) must be called from
+//! a worker too. That keeps the main thread free while a batch runs.
+
+use std::io;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Mutex, OnceLock};
+use rayon::{ThreadBuilder, ThreadPoolBuilder};
+use wasm_bindgen::prelude::*;
+
+type Queue = (Mutex<Sender<ThreadBuilder>>, Mutex<Receiver<ThreadBuilder>>);
+
+/// Threads spawned by the pool, waiting for a worker to run them.
+fn queue() -> &'static Queue {
+    static QUEUE: OnceLock<Queue> = OnceLock::new();
+    QUEUE.get_or_init(|| {
+        let (sender, receiver) = mpsc::channel();
+        (Mutex::new(sender), Mutex::new(receiver))
+    })
+}
+
+/// Spawn handler that queues the thread instead of starting it.
+fn spawn_into_queue(thread: ThreadBuilder) -> io::Result<()> {
+    let sender = queue().0.lock().map_err(|e| io::Error::other(e.to_string()))?;
+    sender.send(thread).map_err(|e| io::Error::other(e.to_string()))
+}
+
+/// Take the next queued thread, waiting for one, and run it. Returns once
+/// the pool shuts the thread down.
+fn run_queued_thread() -> Result<(), String> {
+    let thread = {
+        let receiver = queue().1.lock().map_err(|e| e.to_string())?;
+        receiver.recv().map_err(|e| e.to_string())?
+    };
+    thread.run();
+    Ok(())
+}
+
+/// Build the global rayon pool with `num_threads` threads, to be started
+/// by as many workers calling `start_pool_worker`. Call once, before any
+/// parallel work.
+#[wasm_bindgen]
+pub fn init_thread_pool(num_threads: usize) -> Result<(), JsValue> {
+    if num_threads == 0 {
+        return Err(JsValue::from_str("A thread pool needs at least one thread"));
+    }
+    ThreadPoolBuilder::new()
+        .num_threads(num_threads)
+        .spawn_handler(spawn_into_queue)
+        .build_global()
+        .map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Run one pool thread in the calling Web Worker. Blocks the worker for
+/// as long as the pool lives.
+#[wasm_bindgen]
+pub fn start_pool_worker() -> Result<(), JsValue> {
+    run_queued_thread().map_err(|e| JsValue::from_str(&e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rayon::prelude::*;
+    use crate::batch::{run_all, TraceJob};
+    use crate::patterns::{self, Pattern};
+    use crate::pregen::{pregen_sort, Algorithm};
+
+    #[test]
+    fn test_queued_threads_run_the_pool() {
+        // A local pool, so the global one is left alone for other tests
+        let pool = ThreadPoolBuilder::new()
+            .num_threads(2)
+            .spawn_handler(spawn_into_queue)
+            .build()
+            .unwrap();
+        let workers: Vec<_> =
+            (0..2).map(|_| std::thread::spawn(|| run_queued_thread().unwrap())).collect();
+
+        let jobs: Vec<TraceJob> = [Algorithm::HeapSort, Algorithm::MergeSort, Algorithm::Shell]
+            .into_iter()
+            .map(|algorithm| TraceJob { algorithm, pattern: Pattern::Uniform, n: 64, seed: 3 })
+            .collect();
+        let runs = pool.install(|| run_all(&jobs));
+        for (job, run) in jobs.iter().zip(&runs) {
+            let mut input = patterns::generate(job.pattern, job.n, job.seed);
+            assert_eq!(run.events, pregen_sort(job.algorithm, &mut input));
+        }
+        assert_eq!(pool.install(|| (0..1000).into_par_iter().sum::<i32>()), 499500);
+
+        drop(pool);
+        for worker in workers {
+            worker.join().unwrap();
+        }
+    }
+}
@@ -0,0 +1,104 @@
+//! Per-frame event budgets for smooth playback.
+//!
+//! How many events a frame delivers depends on the playback speed, but how
+//! much of that batch is worth drawing depends on the algorithm: some
+//! emit a dozen compares per swap. `throttle_frame` cuts a frame's batch
+//! down to a budget by sampling the events that only highlight (Compare
+//! and Highlight). Everything else is kept, so the array and the range,
+//! phase and sorted state come out exactly as if every event was played.
+
+use crate::prelude::*;
+use crate::events::SortEvent;
+
+/// True for events that can be dropped without changing playback state.
+pub fn is_sampled(event: &SortEvent) -> bool {
+    matches!(event, SortEvent::Compare { .. } | SortEvent::Highlight(_))
+}
+
+/// Reduce a frame's events to at most `budget`, dropping evenly spaced
+/// Compare and Highlight events. Events that can't be dropped are always
+/// kept, so a frame with more of those than `budget` stays over it.
+pub fn throttle_frame(events: &[SortEvent], budget: usize) -> Vec<SortEvent> {
+    if events.len() <= budget {
+        return events.to_vec();
+    }
+
+    let sampled = events.iter().filter(|e| is_sampled(e)).count();
+    let slots = budget.saturating_sub(events.len() - sampled);
+
+    // Keep the s-th sampled event when s * slots / sampled steps up
+    let mut s = 0;
+    events
+        .iter()
+        .filter(|event| {
+            if !is_sampled(event) {
+                return true;
+            }
+            let keep = (s + 1) * slots / sampled > s * slots / sampled;
+            s += 1;
+            keep
+        })
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::EventCounts;
+    use crate::patterns::{self, Pattern};
+    use crate::pregen::{pregen_sort, Algorithm};
+
+    #[test]
+    fn test_under_budget_is_unchanged() {
+        let events = vec![SortEvent::Compare { i: 0, j: 1 }, SortEvent::Done];
+        assert_eq!(throttle_frame(&events, 2), events);
+    }
+
+    #[test]
+    fn test_compares_are_sampled_evenly() {
+        let events: Vec<SortEvent> = (0..10).map(|i| SortEvent::Compare { i, j: i + 1 }).collect();
+        let throttled = throttle_frame(&events, 5);
+
+        assert_eq!(throttled.len(), 5);
+        assert_eq!(throttled[0], SortEvent::Compare { i: 1, j: 2 });
+        assert_eq!(throttled[4], SortEvent::Compare { i: 9, j: 10 });
+    }
+
+    #[test]
+    fn test_mutations_are_kept() {
+        let input = patterns::generate(Pattern::Uniform, 100, 4);
+        let mut sorted = input.clone();
+        let events = pregen_sort(Algorithm::Shell, &mut sorted);
+
+        let mut array = input.clone();
+        for frame in events.chunks(97) {
+            let throttled = throttle_frame(frame, 10);
+            let counts = EventCounts::from_events(&throttled);
+            let expected = EventCounts::from_events(frame);
+            assert_eq!(counts.swaps + counts.overwrites, expected.swaps + expected.overwrites);
+            for event in &throttled {
+                event.apply(&mut array);
+            }
+        }
+        assert_eq!(array, sorted);
+    }
+
+    #[test]
+    fn test_budget_below_required_events() {
+        let events = vec![
+            SortEvent::Compare { i: 0, j: 1 },
+            SortEvent::Swap { i: 0, j: 1 },
+            SortEvent::Swap { i: 1, j: 2 },
+            SortEvent::Done,
+        ];
+        assert_eq!(throttle_frame(&events, 1), events[1..].to_vec());
+    }
+
+    #[test]
+    fn test_highlights_are_sampled() {
+        assert!(is_sampled(&SortEvent::highlight(vec![1], "pivot")));
+        assert!(!is_sampled(&SortEvent::MarkSorted { idx: 0 }));
+        assert!(!is_sampled(&SortEvent::EnterRange { lo: 0, hi: 3 }));
+    }
+}
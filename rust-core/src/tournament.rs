@@ -0,0 +1,337 @@
+//! Algorithm tournaments.
+//!
+//! A tournament enters a set of algorithms into rounds, one per (pattern,
+//! size, seed) input; every algorithm sorts the same input in a round. The
+//! fewest comparisons wins a round, and so do the fewest main-array writes
+//! and, in bench mode, the shortest wall-clock time; ties share the win.
+//! Each win is a point, and the standings rank algorithms by points.
+//!
+//! Runs are counted as their events are emitted and never keep a trace, so
+//! a whole tournament is one call from JavaScript. Rounds run on the batch
+//! thread pool, except in bench mode, where runs go one at a time so their
+//! clocks don't compete for cores.
+
+use serde::{Deserialize, Serialize};
+use crate::batch;
+use crate::events::{EventSink, SortEvent};
+use crate::patterns::{self, Pattern};
+use crate::pregen::{pregen_sort_into, Algorithm};
+use crate::profile;
+
+/// What to run. Missing fields take the defaults: every algorithm, random
+/// inputs of 100 elements, seed 0, no timing.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TournamentRequest {
+    /// Algorithm names; empty enters every algorithm.
+    pub algorithms: Vec<String>,
+    pub patterns: Vec<String>,
+    pub sizes: Vec<usize>,
+    pub seeds: Vec<u64>,
+    /// Time every run and award wins by wall-clock too.
+    pub bench: bool,
+}
+
+impl Default for TournamentRequest {
+    fn default() -> Self {
+        Self {
+            algorithms: Vec::new(),
+            patterns: vec!["random".to_string()],
+            sizes: vec![100],
+            seeds: vec![0],
+            bench: false,
+        }
+    }
+}
+
+/// One algorithm's run in a round.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Entry {
+    pub algorithm: &'static str,
+    pub comparisons: u64,
+    pub writes: u64,
+    /// Wall-clock time, in bench mode only.
+    pub millis: Option<f64>,
+}
+
+/// Every algorithm on one input.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Round {
+    pub pattern: &'static str,
+    pub n: usize,
+    pub seed: u64,
+    pub entries: Vec<Entry>,
+}
+
+/// One algorithm's record over the tournament.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Standing {
+    /// 1 for the most points; algorithms with equal points share a rank.
+    pub rank: usize,
+    pub algorithm: &'static str,
+    pub points: usize,
+    pub comparison_wins: usize,
+    pub write_wins: usize,
+    pub time_wins: usize,
+    /// Totals over every round.
+    pub comparisons: u64,
+    pub writes: u64,
+    pub millis: Option<f64>,
+}
+
+/// Result of `TournamentRequest::run`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Tournament {
+    pub rounds: Vec<Round>,
+    /// Best first; equal points are ordered by fewer comparisons, then
+    /// fewer writes.
+    pub standings: Vec<Standing>,
+}
+
+/// Operation counts of a run, taken as its events are emitted.
+#[derive(Debug, Default)]
+pub(crate) struct Tally {
+    pub comparisons: u64,
+    pub swaps: u64,
+    pub writes: u64,
+}
+
+impl EventSink for Tally {
+    fn push(&mut self, event: SortEvent) {
+        match event {
+            SortEvent::Compare { .. } => self.comparisons += 1,
+            SortEvent::Swap { .. } => self.swaps += 1,
+            _ => {}
+        }
+        self.writes += event.main_writes() as u64;
+    }
+}
+
+impl TournamentRequest {
+    /// Resolve the algorithm and pattern names, in request order.
+    fn resolve(&self) -> Result<(Vec<Algorithm>, Vec<Pattern>), String> {
+        let mut algorithms = Vec::new();
+        for name in &self.algorithms {
+            let algorithm = Algorithm::from_str(name)
+                .ok_or_else(|| format!("Unknown algorithm: {}", name))?;
+            if !algorithms.contains(&algorithm) {
+                algorithms.push(algorithm);
+            }
+        }
+        if algorithms.is_empty() {
+            algorithms = Algorithm::all().to_vec();
+        }
+
+        let patterns = self
+            .patterns
+            .iter()
+            .map(|name| Pattern::from_str(name).ok_or_else(|| format!("Unknown pattern: {}", name)))
+            .collect::<Result<Vec<_>, _>>()?;
+        if patterns.is_empty() || self.sizes.is_empty() || self.seeds.is_empty() {
+            return Err("A tournament needs at least one pattern, size and seed".to_string());
+        }
+
+        Ok((algorithms, patterns))
+    }
+
+    /// Play every round and rank the algorithms.
+    pub fn run(&self) -> Result<Tournament, String> {
+        let (algorithms, patterns) = self.resolve()?;
+
+        let mut inputs = Vec::new();
+        for &pattern in &patterns {
+            for &n in &self.sizes {
+                for &seed in &self.seeds {
+                    inputs.push((pattern, n, seed));
+                }
+            }
+        }
+
+        let play = |&(pattern, n, seed): &(Pattern, usize, u64)| {
+            let input = patterns::generate(pattern, n, seed);
+            let entries = algorithms.iter().map(|&a| run_entry(a, &input, self.bench)).collect();
+            Round { pattern: pattern.as_str(), n, seed, entries }
+        };
+        let rounds = if self.bench {
+            inputs.iter().map(play).collect()
+        } else {
+            batch::map_in_order(&inputs, play)
+        };
+
+        let standings = standings(&algorithms, &rounds, self.bench);
+        Ok(Tournament { rounds, standings })
+    }
+}
+
+fn run_entry(algorithm: Algorithm, input: &[i32], bench: bool) -> Entry {
+    let mut array = input.to_vec();
+    let mut tally = Tally::default();
+
+    let begin = profile::now_millis();
+    pregen_sort_into(algorithm, &mut array, &mut tally);
+    let millis = profile::now_millis() - begin;
+
+    Entry {
+        algorithm: algorithm.as_str(),
+        comparisons: tally.comparisons,
+        writes: tally.writes,
+        millis: bench.then_some(millis),
+    }
+}
+
+/// Indices of the entries with the smallest key.
+fn winners<K: PartialOrd + Copy>(entries: &[Entry], key: impl Fn(&Entry) -> K) -> Vec<usize> {
+    let Some(best) = entries.iter().map(&key).reduce(|a, b| if b < a { b } else { a }) else {
+        return Vec::new();
+    };
+    (0..entries.len()).filter(|&k| key(&entries[k]) == best).collect()
+}
+
+fn standings(algorithms: &[Algorithm], rounds: &[Round], bench: bool) -> Vec<Standing> {
+    let mut standings: Vec<Standing> = algorithms
+        .iter()
+        .map(|algorithm| Standing {
+            rank: 0,
+            algorithm: algorithm.as_str(),
+            points: 0,
+            comparison_wins: 0,
+            write_wins: 0,
+            time_wins: 0,
+            comparisons: 0,
+            writes: 0,
+            millis: bench.then_some(0.0),
+        })
+        .collect();
+
+    // Entries are in algorithm order in every round
+    for round in rounds {
+        for (standing, entry) in standings.iter_mut().zip(&round.entries) {
+            standing.comparisons += entry.comparisons;
+            standing.writes += entry.writes;
+            if let (Some(total), Some(millis)) = (&mut standing.millis, entry.millis) {
+                *total += millis;
+            }
+        }
+        for k in winners(&round.entries, |e| e.comparisons) {
+            standings[k].comparison_wins += 1;
+        }
+        for k in winners(&round.entries, |e| e.writes) {
+            standings[k].write_wins += 1;
+        }
+        if bench {
+            for k in winners(&round.entries, |e| e.millis.unwrap_or(f64::INFINITY)) {
+                standings[k].time_wins += 1;
+            }
+        }
+    }
+
+    for standing in &mut standings {
+        standing.points = standing.comparison_wins + standing.write_wins + standing.time_wins;
+    }
+    standings.sort_by(|a, b| {
+        b.points
+            .cmp(&a.points)
+            .then(a.comparisons.cmp(&b.comparisons))
+            .then(a.writes.cmp(&b.writes))
+    });
+    for k in 0..standings.len() {
+        standings[k].rank = match k {
+            0 => 1,
+            _ if standings[k].points == standings[k - 1].points => standings[k - 1].rank,
+            _ => k + 1,
+        };
+    }
+    standings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{main_writes, EventCounts};
+    use crate::pregen::pregen_sort;
+
+    fn request(algorithms: &[&str]) -> TournamentRequest {
+        TournamentRequest {
+            algorithms: algorithms.iter().map(|a| a.to_string()).collect(),
+            ..TournamentRequest::default()
+        }
+    }
+
+    #[test]
+    fn test_tally_matches_trace_counts() {
+        let input = patterns::generate(Pattern::Random, 50, 4);
+        let events = pregen_sort(Algorithm::HeapSort, &mut input.clone());
+        let entry = run_entry(Algorithm::HeapSort, &input, false);
+
+        assert_eq!(entry.comparisons, EventCounts::from_events(&events).compares as u64);
+        assert_eq!(entry.writes, main_writes(&events) as u64);
+        assert_eq!(entry.millis, None);
+    }
+
+    #[test]
+    fn test_rounds_cover_the_matrix() {
+        let mut request = request(&["insertion", "merge"]);
+        request.patterns = vec!["random".to_string(), "reversed".to_string()];
+        request.sizes = vec![10, 20];
+        request.seeds = vec![1, 2, 3];
+        let tournament = request.run().unwrap();
+
+        assert_eq!(tournament.rounds.len(), 2 * 2 * 3);
+        assert_eq!(tournament.rounds[0].entries.len(), 2);
+        let (last, first) = (tournament.rounds.last().unwrap(), &tournament.rounds[0]);
+        assert_eq!((first.pattern, first.n, first.seed), ("random", 10, 1));
+        assert_eq!((last.pattern, last.n, last.seed), ("reversed", 20, 3));
+    }
+
+    #[test]
+    fn test_ties_share_the_win() {
+        let mut request = request(&["bubble", "insertion", "merge"]);
+        request.patterns = vec!["sorted".to_string()];
+        let tournament = request.run().unwrap();
+
+        // On sorted input bubble and insertion both make n - 1 comparisons
+        // and no writes
+        let standing = |name| tournament.standings.iter().find(|s| s.algorithm == name).unwrap();
+        assert_eq!((standing("bubble").points, standing("insertion").points), (2, 2));
+        assert_eq!((standing("bubble").rank, standing("insertion").rank), (1, 1));
+        assert_eq!(standing("merge").rank, 3);
+    }
+
+    #[test]
+    fn test_standings_order() {
+        let mut request = request(&["bubble", "merge", "heap"]);
+        request.seeds = vec![0, 1, 2];
+        let tournament = request.run().unwrap();
+
+        let points: Vec<usize> = tournament.standings.iter().map(|s| s.points).collect();
+        assert!(points.windows(2).all(|w| w[0] >= w[1]));
+        assert_eq!(tournament.standings[0].rank, 1);
+        assert_eq!(tournament.standings.last().unwrap().algorithm, "bubble");
+        assert!(tournament.standings.iter().all(|s| s.time_wins == 0 && s.millis.is_none()));
+    }
+
+    #[test]
+    fn test_bench_awards_time_wins() {
+        let mut request = request(&["insertion", "shell"]);
+        request.bench = true;
+        let tournament = request.run().unwrap();
+
+        assert!(tournament.rounds[0].entries.iter().all(|e| e.millis.is_some()));
+        let time_wins: usize = tournament.standings.iter().map(|s| s.time_wins).sum();
+        assert!(time_wins >= 1);
+    }
+
+    #[test]
+    fn test_defaults_and_errors() {
+        let tournament = TournamentRequest::default().run().unwrap();
+        assert_eq!(tournament.standings.len(), Algorithm::all().len());
+
+        assert!(request(&["bogus"]).run().is_err());
+        let mut empty = request(&["heap"]);
+        empty.sizes.clear();
+        assert!(empty.run().is_err());
+        let mut bad_pattern = request(&["heap"]);
+        bad_pattern.patterns = vec!["zigzag-ish".to_string()];
+        assert!(bad_pattern.run().is_err());
+    }
+}
@@ -0,0 +1,131 @@
+//! Self-describing traces.
+//!
+//! A `Trace` bundles a run's events with the input they apply to and a
+//! `TraceHeader` saying how it was produced, so a saved trace file can be
+//! understood, replayed and compared without any outside context.
+
+use serde::{Deserialize, Serialize};
+use crate::events::{EventCounts, SortEvent};
+use crate::patterns::Pattern;
+use crate::pregen::Algorithm;
+
+/// Value of `TraceHeader::format`, identifying a sort-forge trace.
+pub const TRACE_FORMAT: &str = "sort-forge-trace";
+
+/// Bumped whenever the trace layout changes incompatibly.
+pub const TRACE_FORMAT_VERSION: u32 = 1;
+
+/// Metadata describing a trace.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TraceHeader {
+    pub format: String,
+    pub format_version: u32,
+    /// Version of the crate that generated the trace.
+    pub crate_version: String,
+    pub algorithm: String,
+    /// Type of the array elements ("i32").
+    pub element_type: String,
+    /// Input length.
+    pub n: usize,
+    /// Pattern and seed the input was generated from; None for inputs
+    /// given explicitly.
+    pub pattern: Option<String>,
+    pub seed: Option<u64>,
+    /// Whether a verification sweep was appended.
+    pub sweep: bool,
+    pub counts: EventCounts,
+}
+
+impl TraceHeader {
+    pub fn new(algorithm: Algorithm, input: &[i32], events: &[SortEvent]) -> Self {
+        Self {
+            format: TRACE_FORMAT.to_string(),
+            format_version: TRACE_FORMAT_VERSION,
+            crate_version: env!("CARGO_PKG_VERSION").to_string(),
+            algorithm: algorithm.as_str().to_string(),
+            element_type: "i32".to_string(),
+            n: input.len(),
+            pattern: None,
+            seed: None,
+            sweep: false,
+            counts: EventCounts::from_events(events),
+        }
+    }
+
+    /// Record the pattern and seed the input was generated from.
+    pub fn with_source(mut self, pattern: Pattern, seed: u64) -> Self {
+        self.pattern = Some(pattern.as_str().to_string());
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Record that a verification sweep was appended.
+    pub fn with_sweep(mut self, sweep: bool) -> Self {
+        self.sweep = sweep;
+        self
+    }
+}
+
+/// A complete trace: header, input array and events.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Trace {
+    pub header: TraceHeader,
+    pub input: Vec<i32>,
+    pub events: Vec<SortEvent>,
+}
+
+impl Trace {
+    /// Wrap the events of a run of algorithm on input.
+    pub fn new(algorithm: Algorithm, input: Vec<i32>, events: Vec<SortEvent>) -> Self {
+        Self {
+            header: TraceHeader::new(algorithm, &input, &events),
+            input,
+            events,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pregen::pregen_sort;
+
+    #[test]
+    fn test_header_describes_run() {
+        let input = vec![3, 1, 2];
+        let events = pregen_sort(Algorithm::Bubble, &mut input.clone());
+        let header = TraceHeader::new(Algorithm::Bubble, &input, &events);
+
+        assert_eq!(header.format, TRACE_FORMAT);
+        assert_eq!(header.format_version, TRACE_FORMAT_VERSION);
+        assert_eq!(header.crate_version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(header.algorithm, "bubble");
+        assert_eq!(header.element_type, "i32");
+        assert_eq!(header.n, 3);
+        assert_eq!(header.pattern, None);
+        assert_eq!(header.counts.total(), events.len());
+    }
+
+    #[test]
+    fn test_header_source_and_sweep() {
+        let header = TraceHeader::new(Algorithm::HeapSort, &[], &[])
+            .with_source(Pattern::Uniform, 42)
+            .with_sweep(true);
+
+        assert_eq!(header.pattern.as_deref(), Some("uniform"));
+        assert_eq!(header.seed, Some(42));
+        assert!(header.sweep);
+    }
+
+    #[cfg(feature = "native")]
+    #[test]
+    fn test_trace_json_round_trip() {
+        let input = vec![5, 4, 6];
+        let events = pregen_sort(Algorithm::MergeSort, &mut input.clone());
+        let trace = Trace::new(Algorithm::MergeSort, input, events);
+
+        let json = serde_json::to_string(&trace).unwrap();
+        assert!(json.starts_with(r#"{"header":{"format":"sort-forge-trace""#));
+        assert_eq!(serde_json::from_str::<Trace>(&json).unwrap(), trace);
+    }
+}
@@ -0,0 +1,230 @@
+//! Input validation before a run.
+//!
+//! `validate_input` checks an algorithm, an input array and run options
+//! together and returns every problem found as a `Diagnostic`, so the UI can
+//! explain what is wrong before running anything. Values are taken as f64,
+//! the way they arrive from JavaScript, so non-integers and NaN can be
+//! reported instead of failing the conversion to i32.
+
+use serde::{Deserialize, Serialize};
+use crate::engine::Engine;
+use crate::live::LIVE_ALGORITHMS;
+use crate::pregen::{self, estimate, Algorithm};
+#[cfg(feature = "esoteric")]
+use crate::pregen::gravity_sort;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// The run would fail or produce a wrong result.
+    Error,
+    /// The run works but may not behave as expected.
+    Warning,
+}
+
+/// One problem with the input.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// Stable identifier, e.g. "too_long" or "value_out_of_range".
+    pub code: &'static str,
+    pub message: String,
+    /// First offending array index, for value diagnostics.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index: Option<usize>,
+}
+
+impl Diagnostic {
+    fn error(code: &'static str, message: String) -> Self {
+        Self { severity: Severity::Error, code, message, index: None }
+    }
+
+    fn warning(code: &'static str, message: String) -> Self {
+        Self { severity: Severity::Warning, code, message, index: None }
+    }
+
+    fn at(mut self, index: usize) -> Self {
+        self.index = Some(index);
+        self
+    }
+}
+
+/// How the input is going to be run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidateOptions {
+    pub engine: Engine,
+    /// Append a verification sweep.
+    pub sweep: bool,
+}
+
+/// True if no diagnostic is an error.
+pub fn is_runnable(diagnostics: &[Diagnostic]) -> bool {
+    diagnostics.iter().all(|d| d.severity != Severity::Error)
+}
+
+/// Check algorithm, array and options, returning every problem found.
+/// An empty result means the run is safe.
+pub fn validate_input(
+    algorithm: &str,
+    array: &[f64],
+    options: &ValidateOptions,
+) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    let engine = options.engine;
+    if array.len() > engine.max_len() {
+        diagnostics.push(Diagnostic::error(
+            "too_long",
+            format!(
+                "{} elements exceeds the {} engine limit of {}",
+                array.len(),
+                engine.as_str(),
+                engine.max_len()
+            ),
+        ));
+    }
+
+    check_values(array, &mut diagnostics);
+
+    let Some(algo) = Algorithm::from_str(algorithm) else {
+        diagnostics.push(Diagnostic::error(
+            "unknown_algorithm",
+            format!("Unknown algorithm: {}", algorithm),
+        ));
+        return diagnostics;
+    };
+
+    if engine == Engine::Live && !LIVE_ALGORITHMS.contains(&algo.as_str()) {
+        diagnostics.push(Diagnostic::error(
+            "engine_unsupported",
+            format!("{} has no live implementation", algo.as_str()),
+        ));
+    }
+
+    if engine == Engine::Pregen {
+        let events = pregen::estimated_events(algo, array.len());
+        if events > estimate::MAX_RESERVED_EVENTS {
+            diagnostics.push(Diagnostic::warning(
+                "large_trace",
+                format!("{} is expected to emit about {} events", algo.as_str(), events),
+            ));
+        }
+    }
+
+    if let Some((min, max)) = value_range(algo) {
+        let outside = |v: &f64| v.is_finite() && (*v < min as f64 || *v > max as f64);
+        if let Some(index) = array.iter().position(outside) {
+            let diagnostic = Diagnostic::error(
+                "value_out_of_range",
+                format!("{} requires values in {}..={}", algo.as_str(), min, max),
+            );
+            diagnostics.push(diagnostic.at(index));
+        }
+    }
+
+    diagnostics
+}
+
+/// Values the algorithm can sort, for algorithms that can't take any i32.
+fn value_range(algorithm: Algorithm) -> Option<(i32, i32)> {
+    match algorithm {
+        #[cfg(feature = "distribution")]
+        Algorithm::RadixLsd | Algorithm::RadixMsd => Some((0, i32::MAX)),
+        #[cfg(feature = "esoteric")]
+        Algorithm::Gravity => Some((0, gravity_sort::MAX_VALUE)),
+        _ => None,
+    }
+}
+
+/// Every element must be an i32. Reports the first offender per kind.
+fn check_values(array: &[f64], diagnostics: &mut Vec<Diagnostic>) {
+    if let Some(index) = array.iter().position(|v| v.is_nan()) {
+        diagnostics.push(Diagnostic::error("nan", "Input contains NaN".to_string()).at(index));
+    }
+
+    let out_of_i32 = |v: &f64| v.is_infinite() || *v < i32::MIN as f64 || *v > i32::MAX as f64;
+    if let Some(index) = array.iter().position(out_of_i32) {
+        let message = "Values must fit in a 32-bit integer".to_string();
+        diagnostics.push(Diagnostic::error("not_i32", message).at(index));
+    }
+
+    if let Some(index) = array.iter().position(|v| v.is_finite() && v.fract() != 0.0) {
+        let message = "Values must be integers".to_string();
+        diagnostics.push(Diagnostic::error("not_integer", message).at(index));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn codes(diagnostics: &[Diagnostic]) -> Vec<&'static str> {
+        diagnostics.iter().map(|d| d.code).collect()
+    }
+
+    #[test]
+    fn test_valid_input() {
+        let diagnostics = validate_input("merge", &[3.0, -1.0, 2.0], &ValidateOptions::default());
+        assert!(diagnostics.is_empty());
+        assert!(is_runnable(&diagnostics));
+    }
+
+    #[test]
+    fn test_length_limit_per_engine() {
+        let array = vec![1.0; Engine::Pregen.max_len() + 1];
+        let pregen = validate_input("bubble", &array, &ValidateOptions::default());
+        assert_eq!(codes(&pregen), vec!["too_long"]);
+
+        let live = ValidateOptions { engine: Engine::Live, sweep: false };
+        assert!(validate_input("bubble", &array, &live).is_empty());
+    }
+
+    #[test]
+    fn test_value_diagnostics() {
+        let array = [1.0, f64::NAN, 2.5, 1e12, f64::INFINITY];
+        let diagnostics = validate_input("heap", &array, &ValidateOptions::default());
+
+        assert_eq!(codes(&diagnostics), vec!["nan", "not_i32", "not_integer"]);
+        assert_eq!(diagnostics[0].index, Some(1));
+        assert_eq!(diagnostics[1].index, Some(3));
+        assert_eq!(diagnostics[2].index, Some(2));
+        assert!(!is_runnable(&diagnostics));
+    }
+
+    #[cfg(all(feature = "distribution", feature = "esoteric"))]
+    #[test]
+    fn test_value_range_requirements() {
+        let options = ValidateOptions::default();
+        let diagnostics = validate_input("radix_lsd", &[4.0, -2.0], &options);
+        assert_eq!(codes(&diagnostics), vec!["value_out_of_range"]);
+        assert_eq!(diagnostics[0].index, Some(1));
+
+        let too_tall = [(gravity_sort::MAX_VALUE + 1) as f64];
+        assert_eq!(codes(&validate_input("gravity", &too_tall, &options)), vec!["value_out_of_range"]);
+        assert!(validate_input("radix_exchange", &[4.0, -2.0], &options).is_empty());
+    }
+
+    #[test]
+    fn test_option_compatibility() {
+        let live = ValidateOptions { engine: Engine::Live, sweep: true };
+        assert_eq!(codes(&validate_input("heap", &[1.0], &live)), vec!["engine_unsupported"]);
+        assert!(validate_input("quicksort_ll", &[1.0], &live).is_empty());
+    }
+
+    #[test]
+    fn test_unknown_algorithm() {
+        let diagnostics = validate_input("bogus", &[], &ValidateOptions::default());
+        assert_eq!(codes(&diagnostics), vec!["unknown_algorithm"]);
+    }
+
+    #[cfg(feature = "native")]
+    #[test]
+    fn test_diagnostic_json() {
+        let diagnostics = validate_input("radix_msd", &[-1.0], &ValidateOptions::default());
+        assert_eq!(
+            serde_json::to_string(&diagnostics[0]).unwrap(),
+            r#"{"severity":"error","code":"value_out_of_range","message":"radix_msd requires values in 0..=2147483647","index":0}"#
+        );
+    }
+}
@@ -0,0 +1,198 @@
+//! QA harness: run an algorithm over a battery of generated inputs and
+//! check every trace.
+//!
+//! Each case is one (pattern, size, seed) input. Its trace is checked for
+//! the properties the frontends rely on:
+//! - the output is the input sorted;
+//! - replaying the events on the input gives the output;
+//! - undoing them in reverse order from the output gives the input back;
+//! - EnterRange/ExitRange events nest and balance;
+//! - the trace ends with its only Done event.
+
+use serde::Serialize;
+use crate::events::SortEvent;
+use crate::patterns::{self, Pattern};
+use crate::pregen::{pregen_sort, Algorithm};
+
+/// Input sizes of every battery: the edge cases plus a couple of sizes
+/// large enough to reach the algorithms' recursive paths.
+pub const VERIFY_SIZES: &[usize] = &[0, 1, 2, 3, 16, 100];
+
+/// A property of a trace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Check {
+    Sorted,
+    Replay,
+    Inverse,
+    RangeBalance,
+    Done,
+}
+
+/// One case whose trace broke a check.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Failure {
+    pub pattern: &'static str,
+    pub n: usize,
+    pub seed: u64,
+    pub check: Check,
+    pub message: String,
+}
+
+/// Result of `verify_algorithm`.
+#[derive(Debug, Clone, Serialize)]
+pub struct VerifyReport {
+    pub algorithm: &'static str,
+    /// Number of inputs run.
+    pub cases: usize,
+    pub failures: Vec<Failure>,
+}
+
+impl VerifyReport {
+    pub fn passed(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+/// Run algorithm on every pattern and size in `VERIFY_SIZES` for each seed
+/// and check each trace.
+pub fn verify_algorithm(algorithm: Algorithm, seeds: &[u64]) -> VerifyReport {
+    let mut cases = 0;
+    let mut failures = Vec::new();
+    for &seed in seeds {
+        for &pattern in Pattern::all() {
+            for &n in VERIFY_SIZES {
+                let input = patterns::generate(pattern, n, seed);
+                let mut output = input.clone();
+                let events = pregen_sort(algorithm, &mut output);
+
+                cases += 1;
+                for (check, message) in check_trace(&input, &output, &events) {
+                    failures.push(Failure { pattern: pattern.as_str(), n, seed, check, message });
+                }
+            }
+        }
+    }
+
+    VerifyReport { algorithm: algorithm.as_str(), cases, failures }
+}
+
+/// Check a trace that turned `input` into `output`, returning each broken
+/// check with what went wrong.
+pub fn check_trace(input: &[i32], output: &[i32], events: &[SortEvent]) -> Vec<(Check, String)> {
+    let mut failures = Vec::new();
+
+    let mut expected = input.to_vec();
+    expected.sort_unstable();
+    if output != expected {
+        failures.push((Check::Sorted, format!("output {:?} is not the input sorted", output)));
+    }
+
+    let mut replayed = input.to_vec();
+    for event in events {
+        event.apply(&mut replayed);
+    }
+    if replayed != output {
+        failures.push((Check::Replay, format!("replay ends at {:?}", replayed)));
+    }
+
+    let mut undone = output.to_vec();
+    for event in events.iter().rev() {
+        event.inverse().apply(&mut undone);
+    }
+    if undone != input {
+        failures.push((Check::Inverse, format!("undoing ends at {:?}", undone)));
+    }
+
+    if let Err(message) = check_range_balance(events) {
+        failures.push((Check::RangeBalance, message));
+    }
+
+    let done = events.iter().filter(|e| matches!(e, SortEvent::Done)).count();
+    if done != 1 || !matches!(events.last(), Some(SortEvent::Done)) {
+        failures.push((Check::Done, format!("{} Done events, last event {:?}", done, events.last())));
+    }
+
+    failures
+}
+
+/// Every ExitRange must close the innermost open range, and every range
+/// must be closed by the end.
+fn check_range_balance(events: &[SortEvent]) -> Result<(), String> {
+    let mut open = Vec::new();
+    for (at, event) in events.iter().enumerate() {
+        match *event {
+            SortEvent::EnterRange { lo, hi } => open.push((lo, hi)),
+            SortEvent::ExitRange { lo, hi } => match open.pop() {
+                Some(range) if range == (lo, hi) => {}
+                Some((open_lo, open_hi)) => {
+                    return Err(format!(
+                        "event {} exits {}..={} inside {}..={}",
+                        at, lo, hi, open_lo, open_hi
+                    ));
+                }
+                None => return Err(format!("event {} exits {}..={} with no range open", at, lo, hi)),
+            },
+            _ => {}
+        }
+    }
+    match open.last() {
+        Some((lo, hi)) => Err(format!("{} ranges left open, innermost {}..={}", open.len(), lo, hi)),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_algorithm_passes() {
+        for &algorithm in Algorithm::all() {
+            let report = verify_algorithm(algorithm, &[1]);
+            assert!(report.passed(), "{:?}", report.failures);
+            assert_eq!(report.cases, Pattern::all().len() * VERIFY_SIZES.len());
+        }
+    }
+
+    #[test]
+    fn test_seeds_multiply_cases() {
+        let report = verify_algorithm(Algorithm::MergeSort, &[1, 2, 3]);
+        assert_eq!(report.cases, 3 * Pattern::all().len() * VERIFY_SIZES.len());
+        assert_eq!(report.algorithm, "merge");
+    }
+
+    #[test]
+    fn test_broken_trace_fails_replay_and_inverse() {
+        let input = vec![2, 1];
+        // Claims the right output but never swaps
+        let events = vec![SortEvent::Compare { i: 0, j: 1 }, SortEvent::Done];
+        let checks: Vec<Check> =
+            check_trace(&input, &[1, 2], &events).into_iter().map(|(check, _)| check).collect();
+        assert_eq!(checks, vec![Check::Replay, Check::Inverse]);
+    }
+
+    #[test]
+    fn test_unsorted_output_fails() {
+        let events = vec![SortEvent::Done];
+        let checks = check_trace(&[2, 1], &[2, 1], &events);
+        assert_eq!(checks.len(), 1);
+        assert_eq!(checks[0].0, Check::Sorted);
+    }
+
+    #[test]
+    fn test_range_balance() {
+        let enter = |lo, hi| SortEvent::EnterRange { lo, hi };
+        let exit = |lo, hi| SortEvent::ExitRange { lo, hi };
+        assert!(check_range_balance(&[enter(0, 3), enter(0, 1), exit(0, 1), exit(0, 3)]).is_ok());
+        assert!(check_range_balance(&[enter(0, 3), enter(0, 1), exit(0, 3)]).is_err());
+        assert!(check_range_balance(&[exit(0, 1)]).is_err());
+        assert!(check_range_balance(&[enter(0, 1)]).is_err());
+    }
+
+    #[test]
+    fn test_missing_done_fails() {
+        let checks = check_trace(&[1], &[1], &[]);
+        assert_eq!(checks.iter().map(|(check, _)| *check).collect::<Vec<_>>(), vec![Check::Done]);
+    }
+}
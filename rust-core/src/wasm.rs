@@ -0,0 +1,1317 @@
+//! WebAssembly bindings for the browser UI.
+//!
+//! Thin wrappers that convert between JS values and the native API.
+//! Only built with the `wasm` feature.
+
+use wasm_bindgen::prelude::*;
+use crate::argsort;
+use crate::audio::{self, AudioRange};
+use crate::batch::{self, BatchRequest, BatchResult};
+use crate::comparator::{self, ComparatorAlgorithm};
+use crate::cost::{self, CostModel};
+use crate::engine;
+use crate::float::{self, NanPolicy};
+use crate::handoff;
+use crate::heap_tree;
+use crate::key_transform::{self, KeyTransform, KeyTransformSpec};
+use crate::keyframes;
+use crate::narrate;
+#[cfg(feature = "networks")]
+use crate::networks::{self, NetworkKind};
+use crate::events::{self, SortEvent};
+use crate::packed;
+use crate::parity;
+use crate::permutation;
+use crate::precheck;
+use crate::profile;
+use crate::pregen::{self, context::PregenContext, Algorithm, Category};
+use crate::pregen::hybrid::{self, HybridConfig, HybridSpec};
+use crate::pregen::splay_sort;
+#[cfg(feature = "strings")]
+use crate::pregen::strings::StringAlgorithm;
+use crate::race::{self, RaceSchedule};
+use crate::rotation::{self, RotationAlgorithm};
+use crate::search::{self, SearchAlgorithm};
+use crate::seed_sweep::SeedSweepRequest;
+use crate::snapshot::{self, SnapshotPolicy};
+use crate::select::{self, SelectAlgorithm};
+use crate::session::SessionBundle;
+use crate::subrange;
+use crate::summary;
+use crate::throttle;
+use crate::tournament::TournamentRequest;
+use crate::trace::TraceHeader;
+use crate::validate::{self, ValidateOptions};
+use crate::verify;
+
+/// Initialize panic hook for better error messages in browser console
+#[wasm_bindgen(start)]
+pub fn init() {
+    #[cfg(feature = "console_error_panic_hook")]
+    console_error_panic_hook::set_once();
+}
+
+/// Run a pregeneration sort on the given array.
+///
+/// # Arguments
+/// * `algorithm` - Name of the sorting algorithm ("bubble", "quicksort")
+/// * `array` - JavaScript array of numbers to sort
+///
+/// # Returns
+/// Array of SortEvents describing all operations performed
+#[wasm_bindgen]
+pub fn pregen_sort(algorithm: &str, array: JsValue) -> Result<JsValue, JsValue> {
+    // Parse algorithm name
+    let algo = Algorithm::from_str(algorithm)
+        .ok_or_else(|| JsValue::from_str(&format!("Unknown algorithm: {}", algorithm)))?;
+
+    // Convert JS array to Rust Vec
+    let mut arr: Vec<i32> = events::js_to_array(array)?;
+
+    // Run the sort into compact storage; events are only expanded for JS
+    let events = pregen::pregen_sort_compact(algo, &mut arr);
+
+    // Convert events to JS
+    events.to_js()
+}
+
+/// Run a pregeneration sort followed by a verification sweep: a left-to-right
+/// pass of Compare/MarkSorted events before the final Done.
+#[wasm_bindgen]
+pub fn pregen_sort_with_sweep(algorithm: &str, array: JsValue) -> Result<JsValue, JsValue> {
+    let algo = Algorithm::from_str(algorithm)
+        .ok_or_else(|| JsValue::from_str(&format!("Unknown algorithm: {}", algorithm)))?;
+
+    let mut arr: Vec<i32> = events::js_to_array(array)?;
+    let events = pregen::pregen_sort_with_sweep(algo, &mut arr);
+    events::events_to_js(&events)
+}
+
+/// Run a pregeneration sort with insertion shifts coalesced: each chain of
+/// single-slot Overwrites becomes one BlockMove event.
+#[wasm_bindgen]
+pub fn pregen_sort_coalesced(algorithm: &str, array: JsValue) -> Result<JsValue, JsValue> {
+    let algo = Algorithm::from_str(algorithm)
+        .ok_or_else(|| JsValue::from_str(&format!("Unknown algorithm: {}", algorithm)))?;
+
+    let mut arr: Vec<i32> = events::js_to_array(array)?;
+    let events = pregen::pregen_sort_coalesced(algo, &mut arr);
+    events::events_to_js(&events)
+}
+
+/// Run a pregeneration sort after a left-to-right sortedness scan. If the
+/// scan finds no inversion, the trace ends with an "already_sorted" Phase
+/// and Done without running the sort.
+///
+/// # Returns
+/// `{ events, outcome }` where outcome is "already_sorted" or "sorted"
+#[wasm_bindgen]
+pub fn pregen_sort_prechecked(algorithm: &str, array: JsValue) -> Result<JsValue, JsValue> {
+    let algo = Algorithm::from_str(algorithm)
+        .ok_or_else(|| JsValue::from_str(&format!("Unknown algorithm: {}", algorithm)))?;
+
+    let mut arr: Vec<i32> = events::js_to_array(array)?;
+    let (events, outcome) = precheck::pregen_sort_prechecked(algo, &mut arr);
+
+    // Built field by field so the events can take the JSON fast path
+    let result = js_sys::Object::new();
+    js_sys::Reflect::set(&result, &"events".into(), &events::events_to_js(&events)?)?;
+    let outcome =
+        serde_wasm_bindgen::to_value(&outcome).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    js_sys::Reflect::set(&result, &"outcome".into(), &outcome)?;
+    Ok(result.into())
+}
+
+/// Sort the indices of the array by value, leaving the values untouched.
+/// Events refer to positions in the index array, which starts as 0..n;
+/// Overwrite values are indices. Ties keep index order.
+///
+/// # Returns
+/// `{ events, indices }` where indices is the sorted index array
+#[wasm_bindgen]
+pub fn pregen_argsort(algorithm: &str, array: JsValue) -> Result<JsValue, JsValue> {
+    let algo = Algorithm::from_str(algorithm)
+        .ok_or_else(|| JsValue::from_str(&format!("Unknown algorithm: {}", algorithm)))?;
+
+    let values: Vec<i32> = events::js_to_array(array)?;
+    let run = argsort::pregen_argsort(algo, &values);
+
+    // Built field by field so the events can take the JSON fast path
+    let result = js_sys::Object::new();
+    js_sys::Reflect::set(&result, &"events".into(), &events::events_to_js(&run.events)?)?;
+    let indices = serde_wasm_bindgen::to_value(&run.indices)
+        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+    js_sys::Reflect::set(&result, &"indices".into(), &indices)?;
+    Ok(result.into())
+}
+
+/// Sort an array of floats. NaN placement is fixed up front by
+/// `nan_policy` ("last", "first" or "error"; default "last"); numbers
+/// follow total order, so -0 sorts before 0. Overwrite values are ranks.
+///
+/// # Returns
+/// `{ events, sorted_array, values }` where `values[rank]` is the value a
+/// rank stands for
+#[wasm_bindgen]
+pub fn pregen_sort_f64(
+    algorithm: &str,
+    array: JsValue,
+    nan_policy: Option<String>,
+) -> Result<JsValue, JsValue> {
+    let algo = Algorithm::from_str(algorithm)
+        .ok_or_else(|| JsValue::from_str(&format!("Unknown algorithm: {}", algorithm)))?;
+    let policy = match nan_policy.as_deref() {
+        Some(name) => NanPolicy::from_str(name)
+            .ok_or_else(|| JsValue::from_str(&format!("Unknown NaN policy: {}", name)))?,
+        None => NanPolicy::default(),
+    };
+
+    let mut values: Vec<f64> =
+        serde_wasm_bindgen::from_value(array).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    let run = float::pregen_sort_f64(algo, &mut values, policy).map_err(|e| JsValue::from_str(&e))?;
+
+    // Built field by field so the events can take the JSON fast path
+    let result = js_sys::Object::new();
+    js_sys::Reflect::set(&result, &"events".into(), &events::events_to_js(&run.events)?)?;
+    let sorted = js_sys::Float64Array::from(values.as_slice());
+    js_sys::Reflect::set(&result, &"sorted_array".into(), &sorted)?;
+    let ranked = js_sys::Float64Array::from(run.values.as_slice());
+    js_sys::Reflect::set(&result, &"values".into(), &ranked)?;
+    Ok(result.into())
+}
+
+/// Sort the array while timing each chunk of `chunk_size` events, using
+/// the live engine when the algorithm has one. Pregen-only algorithms
+/// generate their whole trace in one call and report a single chunk.
+///
+/// # Returns
+/// `{ events, profile }` where profile is `{ engine, chunk_size, chunks,
+/// total_millis }` and each chunk is `{ start, len, millis, phase }`
+#[wasm_bindgen]
+pub fn profile_sort(algorithm: &str, array: JsValue, chunk_size: usize) -> Result<JsValue, JsValue> {
+    let arr: Vec<i32> = events::js_to_array(array)?;
+    let (events, profile) =
+        profile::profile_sort(algorithm, &arr, chunk_size).map_err(|e| JsValue::from_str(&e))?;
+
+    // Built field by field so the events can take the JSON fast path
+    let result = js_sys::Object::new();
+    js_sys::Reflect::set(&result, &"events".into(), &events::events_to_js(&events)?)?;
+    let profile =
+        serde_wasm_bindgen::to_value(&profile).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    js_sys::Reflect::set(&result, &"profile".into(), &profile)?;
+    Ok(result.into())
+}
+
+/// Run a pregeneration sort with Snapshot events of the whole array after
+/// every Phase event (if `at_phases`) and after every `every` events.
+///
+/// # Returns
+/// Array of SortEvents describing all operations performed
+#[wasm_bindgen]
+pub fn pregen_sort_with_snapshots(
+    algorithm: &str,
+    array: JsValue,
+    at_phases: bool,
+    every: Option<usize>,
+) -> Result<JsValue, JsValue> {
+    let algo = Algorithm::from_str(algorithm)
+        .ok_or_else(|| JsValue::from_str(&format!("Unknown algorithm: {}", algorithm)))?;
+
+    let mut arr: Vec<i32> = events::js_to_array(array)?;
+    let policy = SnapshotPolicy { at_phases, every };
+    let events = snapshot::pregen_sort_with_snapshots(algo, &mut arr, policy);
+    events::events_to_js(&events)
+}
+
+/// Run a pregeneration sort of `array[lo..hi]` only. Events use indices
+/// into the whole array, whose other elements are never touched.
+///
+/// # Returns
+/// Array of SortEvents describing all operations performed
+#[wasm_bindgen]
+pub fn pregen_sort_range(
+    algorithm: &str,
+    array: JsValue,
+    lo: usize,
+    hi: usize,
+) -> Result<JsValue, JsValue> {
+    let algo = Algorithm::from_str(algorithm)
+        .ok_or_else(|| JsValue::from_str(&format!("Unknown algorithm: {}", algorithm)))?;
+
+    let mut arr: Vec<i32> = events::js_to_array(array)?;
+    let events =
+        subrange::pregen_sort_range(algo, &mut arr, lo, hi).map_err(|e| JsValue::from_str(&e))?;
+    events::events_to_js(&events)
+}
+
+/// Run a hybrid sort described by `config`, a HybridSpec such as
+/// `{ split: "quicksort_lr", max_depth: 8, cutoff: 32, finish: "shell", gaps: "ciura" }`.
+/// Phase events mark every switch between the split and finish stages.
+#[wasm_bindgen]
+pub fn pregen_sort_hybrid(config: JsValue, array: JsValue) -> Result<JsValue, JsValue> {
+    let spec: HybridSpec =
+        serde_wasm_bindgen::from_value(config).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    let config = HybridConfig::from_spec(&spec).map_err(|e| JsValue::from_str(&e))?;
+
+    let mut arr: Vec<i32> = events::js_to_array(array)?;
+    let events = hybrid::pregen_sort_hybrid(&config, &mut arr);
+    events::events_to_js(&events)
+}
+
+/// Run a pregeneration sort ordering elements by a built-in key instead of
+/// their value.
+///
+/// # Arguments
+/// * `transform` - `{ transform: "abs" | "mod" | "digit_sum" | "bit_reverse", modulus? }`;
+///   modulus is required for "mod"
+///
+/// # Returns
+/// Array of SortEvents; elements with equal keys keep their input order
+#[wasm_bindgen]
+pub fn pregen_sort_by_key(
+    algorithm: &str,
+    transform: JsValue,
+    array: JsValue,
+) -> Result<JsValue, JsValue> {
+    let algo = Algorithm::from_str(algorithm)
+        .ok_or_else(|| JsValue::from_str(&format!("Unknown algorithm: {}", algorithm)))?;
+    let spec: KeyTransformSpec =
+        serde_wasm_bindgen::from_value(transform).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    let transform = KeyTransform::from_spec(&spec).map_err(|e| JsValue::from_str(&e))?;
+
+    let mut arr: Vec<i32> = events::js_to_array(array)?;
+    let events = key_transform::pregen_sort_by_key(algo, transform, &mut arr);
+    events::events_to_js(&events)
+}
+
+/// Get the sorted array after running pregen_sort.
+/// This is a convenience function to also get the final sorted result.
+#[wasm_bindgen]
+pub fn pregen_sort_with_result(algorithm: &str, array: JsValue) -> Result<JsValue, JsValue> {
+    let algo = Algorithm::from_str(algorithm)
+        .ok_or_else(|| JsValue::from_str(&format!("Unknown algorithm: {}", algorithm)))?;
+
+    let mut arr: Vec<i32> = events::js_to_array(array)?;
+    let events = pregen::pregen_sort(algo, &mut arr);
+
+    // Return both events and sorted array as { events, sorted_array }.
+    // Built field by field so the events can take the JSON fast path
+    let result = js_sys::Object::new();
+    js_sys::Reflect::set(&result, &"events".into(), &events::events_to_js(&events)?)?;
+    let sorted_array =
+        serde_wasm_bindgen::to_value(&arr).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    js_sys::Reflect::set(&result, &"sorted_array".into(), &sorted_array)?;
+
+    Ok(result.into())
+}
+
+/// Run a pregeneration sort writing into caller-owned typed arrays, so a
+/// long-lived session can reuse the same buffers for every run.
+///
+/// # Arguments
+/// * `events` - Int32Array that receives the packed events (see `packed`);
+///   events without a packed form (Phase, Highlight, SetCursor, Snapshot)
+///   are skipped
+/// * `output` - Int32Array that receives the sorted array
+///
+/// # Returns
+/// `{ events, skipped }`: packed records written and events skipped.
+/// Fails without writing anything if either buffer is too small.
+///
+/// Events are packed as the sort emits them, into a staging buffer that is
+/// reused by every call and keeps its largest size; `PregenContext` can
+/// release its buffers with `shrink`.
+#[wasm_bindgen]
+pub fn pregen_sort_into(
+    algorithm: &str,
+    array: JsValue,
+    events: js_sys::Int32Array,
+    output: js_sys::Int32Array,
+) -> Result<JsValue, JsValue> {
+    let algo = Algorithm::from_str(algorithm)
+        .ok_or_else(|| JsValue::from_str(&format!("Unknown algorithm: {}", algorithm)))?;
+
+    let mut arr: Vec<i32> = events::js_to_array(array)?;
+    PACKED_WORDS.with_borrow_mut(|words| {
+        words.clear();
+        let mut sink = packed::PackedSink::new(words);
+        pregen::pregen_sort_into(algo, &mut arr, &mut sink);
+        let skipped = sink.skipped();
+        write_packed(words, skipped, &arr, &events, &output)
+    })
+}
+
+thread_local! {
+    /// Staging for `pregen_sort_into`'s packed words, kept between calls.
+    static PACKED_WORDS: core::cell::RefCell<Vec<i32>> = const { core::cell::RefCell::new(Vec::new()) };
+}
+
+/// Copy a run's packed records `words` into `buffer` and its sorted array
+/// into `output`. Returns `{ events, skipped }`.
+fn write_packed(
+    words: &[i32],
+    skipped: usize,
+    sorted: &[i32],
+    buffer: &js_sys::Int32Array,
+    output: &js_sys::Int32Array,
+) -> Result<JsValue, JsValue> {
+    if (output.length() as usize) < sorted.len() {
+        return Err(JsValue::from_str(&format!(
+            "An output of {} can't hold {} elements",
+            output.length(),
+            sorted.len()
+        )));
+    }
+    let records = words.len() / packed::RECORD_WORDS;
+    if (buffer.length() as usize) < words.len() {
+        return Err(JsValue::from_str(&format!(
+            "An event buffer of {} words can't hold {} events",
+            buffer.length(),
+            records
+        )));
+    }
+
+    buffer.subarray(0, words.len() as u32).copy_from(words);
+    output.subarray(0, sorted.len() as u32).copy_from(sorted);
+
+    let result = js_sys::Object::new();
+    js_sys::Reflect::set(&result, &"events".into(), &records.into())?;
+    js_sys::Reflect::set(&result, &"skipped".into(), &skipped.into())?;
+    Ok(result.into())
+}
+
+/// Run a pregeneration sort and return a self-describing trace.
+///
+/// # Arguments
+/// * `algorithm` - Algorithm name
+/// * `array` - Input array
+/// * `sweep` - Append a verification sweep before Done
+///
+/// # Returns
+/// `{ header, input, events }`, the same layout as CLI trace files
+#[wasm_bindgen]
+pub fn pregen_sort_trace(
+    algorithm: &str,
+    array: JsValue,
+    sweep: bool,
+) -> Result<JsValue, JsValue> {
+    let algo = Algorithm::from_str(algorithm)
+        .ok_or_else(|| JsValue::from_str(&format!("Unknown algorithm: {}", algorithm)))?;
+
+    let input: Vec<i32> = events::js_to_array(array)?;
+    let mut arr = input.clone();
+    let events = if sweep {
+        pregen::pregen_sort_with_sweep(algo, &mut arr)
+    } else {
+        pregen::pregen_sort(algo, &mut arr)
+    };
+    let header = TraceHeader::new(algo, &input, &events).with_sweep(sweep);
+
+    // Built field by field so the events can take the JSON fast path
+    let result = js_sys::Object::new();
+    let header =
+        serde_wasm_bindgen::to_value(&header).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    js_sys::Reflect::set(&result, &"header".into(), &header)?;
+    let input =
+        serde_wasm_bindgen::to_value(&input).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    js_sys::Reflect::set(&result, &"input".into(), &input)?;
+    js_sys::Reflect::set(&result, &"events".into(), &events::events_to_js(&events)?)?;
+
+    Ok(result.into())
+}
+
+/// Get list of available algorithms.
+#[wasm_bindgen]
+pub fn get_available_algorithms() -> JsValue {
+    let algorithms = Algorithm::all()
+        .iter()
+        .map(Algorithm::as_str)
+        .collect::<Vec<_>>();
+    serde_wasm_bindgen::to_value(&algorithms).unwrap()
+}
+
+/// Canonical name and accepted aliases of an algorithm.
+#[derive(serde::Serialize)]
+struct AlgorithmAliases {
+    name: &'static str,
+    aliases: &'static [&'static str],
+}
+
+/// Get every algorithm with all names accepted for it (canonical first).
+#[wasm_bindgen]
+pub fn get_algorithm_aliases() -> JsValue {
+    let aliases = Algorithm::all()
+        .iter()
+        .map(|algorithm| AlgorithmAliases {
+            name: algorithm.as_str(),
+            aliases: algorithm.aliases(),
+        })
+        .collect::<Vec<_>>();
+    serde_wasm_bindgen::to_value(&aliases).unwrap()
+}
+
+/// Canonicalize an algorithm name. With `strict`, only canonical names are
+/// accepted, so stored presets can be validated; otherwise any alias in any
+/// case is. Returns undefined for names that aren't accepted.
+#[wasm_bindgen]
+pub fn canonical_algorithm_name(name: &str, strict: bool) -> Option<String> {
+    let algorithm = if strict {
+        Algorithm::from_str_strict(name)
+    } else {
+        Algorithm::from_str(name)
+    };
+    algorithm.map(|a| a.as_str().to_string())
+}
+
+/// Get the category of an algorithm ("exchange", "selection", "insertion",
+/// "merge", "distribution", "network", "hybrid" or "esoteric").
+#[wasm_bindgen]
+pub fn get_algorithm_category(algorithm: &str) -> Result<String, JsValue> {
+    let algo = Algorithm::from_str(algorithm)
+        .ok_or_else(|| JsValue::from_str(&format!("Unknown algorithm: {}", algorithm)))?;
+    Ok(algo.category().as_str().to_string())
+}
+
+/// Category of algorithms with its members, for grouped algorithm lists.
+#[derive(serde::Serialize)]
+struct CategoryGroup {
+    category: &'static str,
+    algorithms: Vec<&'static str>,
+}
+
+/// Get all algorithms grouped by category, in category order. Categories
+/// whose family is compiled out are left out.
+#[wasm_bindgen]
+pub fn get_algorithms_by_category() -> JsValue {
+    let groups = Category::all()
+        .iter()
+        .map(|category| CategoryGroup {
+            category: category.as_str(),
+            algorithms: category.algorithms().iter().map(Algorithm::as_str).collect(),
+        })
+        .filter(|group| !group.algorithms.is_empty())
+        .collect::<Vec<_>>();
+    serde_wasm_bindgen::to_value(&groups).unwrap()
+}
+
+/// Get metadata for an algorithm: complexity classes, space, stability,
+/// whether it is in-place and adaptive, and a short description.
+#[wasm_bindgen]
+pub fn get_algorithm_info(algorithm: &str) -> Result<JsValue, JsValue> {
+    let algo = Algorithm::from_str(algorithm)
+        .ok_or_else(|| JsValue::from_str(&format!("Unknown algorithm: {}", algorithm)))?;
+
+    serde_wasm_bindgen::to_value(&pregen::algorithm_info(algo))
+        .map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+#[cfg(feature = "strings")]
+/// Run a pregeneration string sort and return its events and sorted array.
+///
+/// # Arguments
+/// * `algorithm` - Name of the string sorting algorithm ("multikey_quicksort")
+/// * `array` - JavaScript array of strings to sort
+#[wasm_bindgen]
+pub fn pregen_sort_strings(algorithm: &str, array: JsValue) -> Result<JsValue, JsValue> {
+    let algo = StringAlgorithm::from_str(algorithm)
+        .ok_or_else(|| JsValue::from_str(&format!("Unknown string algorithm: {}", algorithm)))?;
+
+    let mut arr: Vec<String> =
+        serde_wasm_bindgen::from_value(array).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    let events = pregen::strings::pregen_sort_strings(algo, &mut arr);
+
+    let result = PregenStringResult {
+        events,
+        sorted_array: arr,
+    };
+
+    serde_wasm_bindgen::to_value(&result).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+#[cfg(feature = "strings")]
+/// Result of a pregeneration string sort, including events and final array.
+#[derive(serde::Serialize)]
+struct PregenStringResult {
+    events: Vec<SortEvent>,
+    sorted_array: Vec<String>,
+}
+
+#[cfg(feature = "strings")]
+/// Get list of available string sorting algorithms.
+#[wasm_bindgen]
+pub fn get_available_string_algorithms() -> JsValue {
+    let algorithms = StringAlgorithm::all()
+        .iter()
+        .map(StringAlgorithm::as_str)
+        .collect::<Vec<_>>();
+    serde_wasm_bindgen::to_value(&algorithms).unwrap()
+}
+
+/// Run a selection for the k-th smallest element (0-based).
+///
+/// # Returns
+/// Array of SortEvents, ending with a Found event at position k
+#[wasm_bindgen]
+pub fn pregen_select(algorithm: &str, array: JsValue, k: usize) -> Result<JsValue, JsValue> {
+    let algo = SelectAlgorithm::from_str(algorithm)
+        .ok_or_else(|| JsValue::from_str(&format!("Unknown select algorithm: {}", algorithm)))?;
+
+    let mut arr: Vec<i32> = events::js_to_array(array)?;
+    if k >= arr.len() {
+        return Err(JsValue::from_str(&format!(
+            "k = {} out of bounds for array of length {}",
+            k,
+            arr.len()
+        )));
+    }
+
+    let events = select::pregen_select(algo, &mut arr, k);
+    events::events_to_js(&events)
+}
+
+/// Sort only the k smallest elements into the first k positions.
+///
+/// # Returns
+/// Array of SortEvents describing all operations performed
+#[wasm_bindgen]
+pub fn pregen_partial_sort(array: JsValue, k: usize) -> Result<JsValue, JsValue> {
+    let mut arr: Vec<i32> = events::js_to_array(array)?;
+    let events = select::partial_sort::partial_sort(&mut arr, k);
+    events::events_to_js(&events)
+}
+
+/// Get list of available selection algorithms.
+#[wasm_bindgen]
+pub fn get_available_select_algorithms() -> JsValue {
+    let algorithms = SelectAlgorithm::all()
+        .iter()
+        .map(SelectAlgorithm::as_str)
+        .collect::<Vec<_>>();
+    serde_wasm_bindgen::to_value(&algorithms).unwrap()
+}
+
+/// Search a sorted array for target.
+///
+/// # Returns
+/// Array of SortEvents, ending with a Found event if target is present
+#[wasm_bindgen]
+pub fn pregen_search(algorithm: &str, array: JsValue, target: i32) -> Result<JsValue, JsValue> {
+    let algo = SearchAlgorithm::from_str(algorithm)
+        .ok_or_else(|| JsValue::from_str(&format!("Unknown search algorithm: {}", algorithm)))?;
+
+    let arr: Vec<i32> = events::js_to_array(array)?;
+    if arr.windows(2).any(|w| w[0] > w[1]) {
+        return Err(JsValue::from_str("Search requires a sorted array"));
+    }
+
+    let events = search::pregen_search(algo, &arr, target);
+    events::events_to_js(&events)
+}
+
+/// Get list of available search algorithms.
+#[wasm_bindgen]
+pub fn get_available_search_algorithms() -> JsValue {
+    let algorithms = SearchAlgorithm::all()
+        .iter()
+        .map(SearchAlgorithm::as_str)
+        .collect::<Vec<_>>();
+    serde_wasm_bindgen::to_value(&algorithms).unwrap()
+}
+
+/// Rotate the array left by k using the given rotation strategy.
+///
+/// # Returns
+/// Array of SortEvents describing all operations performed
+#[wasm_bindgen]
+pub fn pregen_rotate(algorithm: &str, array: JsValue, k: usize) -> Result<JsValue, JsValue> {
+    let algo = RotationAlgorithm::from_str(algorithm)
+        .ok_or_else(|| JsValue::from_str(&format!("Unknown rotation algorithm: {}", algorithm)))?;
+
+    let mut arr: Vec<i32> = events::js_to_array(array)?;
+    let events = rotation::pregen_rotate(algo, &mut arr, k);
+    events::events_to_js(&events)
+}
+
+/// Rearrange the array so that element i becomes the old element
+/// `permutation[i]`, with the fewest swaps (one cycle at a time).
+///
+/// # Returns
+/// Array of SortEvents; throws if permutation is not a permutation of the
+/// array's indices
+#[wasm_bindgen]
+pub fn pregen_permute(array: JsValue, permutation: JsValue) -> Result<JsValue, JsValue> {
+    let mut arr: Vec<i32> = events::js_to_array(array)?;
+    let perm: Vec<usize> = serde_wasm_bindgen::from_value(permutation)
+        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+    let events = permutation::pregen_permute(&mut arr, &perm).map_err(|e| JsValue::from_str(&e))?;
+    events::events_to_js(&events)
+}
+
+/// Get list of available rotation algorithms.
+#[wasm_bindgen]
+pub fn get_available_rotation_algorithms() -> JsValue {
+    let algorithms = RotationAlgorithm::all()
+        .iter()
+        .map(RotationAlgorithm::as_str)
+        .collect::<Vec<_>>();
+    serde_wasm_bindgen::to_value(&algorithms).unwrap()
+}
+
+#[cfg(feature = "networks")]
+/// Generate a comparator network for n wires without running a sort.
+///
+/// # Returns
+/// `{ n, comparators: [{ i, j, round }] }` ordered by round, where each
+/// comparator leaves the minimum on wire i
+#[wasm_bindgen]
+pub fn generate_network(kind: &str, n: usize) -> Result<JsValue, JsValue> {
+    let kind = NetworkKind::from_str(kind)
+        .ok_or_else(|| JsValue::from_str(&format!("Unknown network: {}", kind)))?;
+    let network = networks::generate(kind, n).map_err(|e| JsValue::from_str(&e))?;
+    serde_wasm_bindgen::to_value(&network).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+#[cfg(feature = "networks")]
+/// Check a comparator network against all 0/1 inputs on n wires.
+/// `comparators` is a list of `[i, j]` pairs, each leaving the minimum on i.
+///
+/// # Returns
+/// `{ sorts, counterexample }` where counterexample is `{ input, output }`
+/// for the first unsorted input, or null
+#[wasm_bindgen]
+pub fn check_network_zero_one(n: usize, comparators: JsValue) -> Result<JsValue, JsValue> {
+    let pairs: Vec<(usize, usize)> = serde_wasm_bindgen::from_value(comparators)
+        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+    let check = networks::check_zero_one(n, &pairs).map_err(|e| JsValue::from_str(&e))?;
+    serde_wasm_bindgen::to_value(&check).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+#[cfg(feature = "networks")]
+/// Run a user-provided network of `[i, j]` comparators on the array, each
+/// leaving the minimum on i.
+///
+/// # Returns
+/// `{ events, depth, sorted }`: the trace with a `Phase` event named
+/// "round <r>" before each round, the number of rounds, and whether the
+/// array ended up sorted
+#[wasm_bindgen]
+pub fn pregen_network(array: JsValue, comparators: JsValue) -> Result<JsValue, JsValue> {
+    let mut arr: Vec<i32> = events::js_to_array(array)?;
+    let pairs: Vec<(usize, usize)> = serde_wasm_bindgen::from_value(comparators)
+        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+    let run = networks::run_network(&mut arr, &pairs).map_err(|e| JsValue::from_str(&e))?;
+    network_run_to_js(&run)
+}
+
+#[cfg(feature = "networks")]
+/// Sort with a network algorithm ("bitonic", "odd_even_merge", "pairwise"),
+/// grouping its comparators into rounds that can be animated at once.
+///
+/// # Returns
+/// `{ events, depth, sorted }` like `pregen_network`
+#[wasm_bindgen]
+pub fn pregen_sort_rounds(algorithm: &str, array: JsValue) -> Result<JsValue, JsValue> {
+    let algo = Algorithm::from_str(algorithm)
+        .ok_or_else(|| JsValue::from_str(&format!("Unknown algorithm: {}", algorithm)))?;
+
+    let mut arr: Vec<i32> = events::js_to_array(array)?;
+    let run = networks::sort_in_rounds(algo, &mut arr).map_err(|e| JsValue::from_str(&e))?;
+    network_run_to_js(&run)
+}
+
+#[cfg(feature = "networks")]
+/// Get list of algorithms `pregen_sort_rounds` accepts.
+#[wasm_bindgen]
+pub fn get_round_algorithms() -> JsValue {
+    let algorithms = Algorithm::all()
+        .iter()
+        .filter(|&&algo| NetworkKind::for_algorithm(algo).is_some())
+        .map(Algorithm::as_str)
+        .collect::<Vec<_>>();
+    serde_wasm_bindgen::to_value(&algorithms).unwrap()
+}
+
+#[cfg(feature = "networks")]
+fn network_run_to_js(run: &networks::NetworkRun) -> Result<JsValue, JsValue> {
+    // Built field by field so the events can take the JSON fast path
+    let result = js_sys::Object::new();
+    js_sys::Reflect::set(&result, &"events".into(), &events::events_to_js(&run.events)?)?;
+    js_sys::Reflect::set(&result, &"depth".into(), &run.depth.into())?;
+    js_sys::Reflect::set(&result, &"sorted".into(), &run.sorted.into())?;
+    Ok(result.into())
+}
+
+#[cfg(feature = "networks")]
+/// Get list of network kinds `generate_network` accepts.
+#[wasm_bindgen]
+pub fn get_available_networks() -> JsValue {
+    let kinds = NetworkKind::all().iter().map(NetworkKind::as_str).collect::<Vec<_>>();
+    serde_wasm_bindgen::to_value(&kinds).unwrap()
+}
+
+/// Compute audio hints for a trace: the input array it was generated from
+/// and its events. Returns a Float32Array of `[freq_a, freq_b, gain]` per
+/// event; frequencies span min_freq..max_freq Hz and silent events are 0.
+#[wasm_bindgen]
+pub fn pregen_audio_hints(
+    array: JsValue,
+    events: JsValue,
+    min_freq: f32,
+    max_freq: f32,
+) -> Result<js_sys::Float32Array, JsValue> {
+    let arr: Vec<i32> = events::js_to_array(array)?;
+    let events: Vec<SortEvent> =
+        serde_wasm_bindgen::from_value(events).map_err(|e| JsValue::from_str(&e.to_string()))?;
+
+    let hints = audio::audio_hints(&arr, &events, AudioRange { min_freq, max_freq })
+        .map_err(|e| JsValue::from_str(&e))?;
+    Ok(js_sys::Float32Array::from(audio::flatten_hints(&hints).as_slice()))
+}
+
+/// Summarize an array in `buckets` screen columns, e.g. a frame of
+/// pregen playback. Returns a Float64Array of `[min, max, mean]` per bucket;
+/// asking for more buckets than elements gives one per element.
+#[wasm_bindgen]
+pub fn summarize_array(array: JsValue, buckets: usize) -> Result<js_sys::Float64Array, JsValue> {
+    let arr: Vec<i32> = events::js_to_array(array)?;
+    let summary = summary::summarize(&arr, buckets);
+    Ok(js_sys::Float64Array::from(summary::flatten_summary(&summary).as_slice()))
+}
+
+/// Cut a frame's events down to at most `budget` by sampling Compare and
+/// Highlight events. All other events are kept, so applying the result
+/// leaves the same array and visual state as applying every event.
+#[wasm_bindgen]
+pub fn throttle_events(events: JsValue, budget: usize) -> Result<JsValue, JsValue> {
+    let events: Vec<SortEvent> =
+        serde_wasm_bindgen::from_value(events).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    events::events_to_js(&throttle::throttle_frame(&events, budget))
+}
+
+/// Cumulative playback cost of a trace: entry i is the total cost of
+/// events 0..=i under `model`, a `{ swap?, compare?, ... }` object of
+/// per-type weights (missing weights keep their defaults, undefined uses
+/// the default model). Returns a Float64Array.
+#[wasm_bindgen]
+pub fn cumulative_costs(events: JsValue, model: JsValue) -> Result<js_sys::Float64Array, JsValue> {
+    let events: Vec<SortEvent> =
+        serde_wasm_bindgen::from_value(events).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    let model = cost_model_from_js(model)?;
+    Ok(js_sys::Float64Array::from(cost::cumulative_costs(&events, &model).as_slice()))
+}
+
+/// A validated CostModel from a JS weights object; undefined or null gives
+/// the default model.
+fn cost_model_from_js(model: JsValue) -> Result<CostModel, JsValue> {
+    let model: CostModel = if model.is_undefined() || model.is_null() {
+        CostModel::default()
+    } else {
+        serde_wasm_bindgen::from_value(model).map_err(|e| JsValue::from_str(&e.to_string()))?
+    };
+    model.validate().map_err(|e| JsValue::from_str(&e))?;
+    Ok(model)
+}
+
+/// Precompute per-element animation keyframes for a trace of `array`,
+/// timed by `model` like `cumulative_costs`. Element e is the bar that
+/// starts at index e; its keyframes are `offsets[e]..offsets[e + 1]` of the
+/// other arrays, and the frontend interpolates linearly between them.
+///
+/// # Returns
+/// `{ offsets: Uint32Array, times: Float32Array, positions: Float32Array,
+/// values: Int32Array, duration }`
+#[wasm_bindgen]
+pub fn animation_keyframes(
+    array: JsValue,
+    events: JsValue,
+    model: JsValue,
+) -> Result<JsValue, JsValue> {
+    let arr: Vec<i32> = events::js_to_array(array)?;
+    let events: Vec<SortEvent> =
+        serde_wasm_bindgen::from_value(events).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    let model = cost_model_from_js(model)?;
+    let frames = keyframes::keyframes(&arr, &events, &model).map_err(|e| JsValue::from_str(&e))?;
+
+    let result = js_sys::Object::new();
+    let offsets = js_sys::Uint32Array::from(frames.offsets.as_slice());
+    js_sys::Reflect::set(&result, &"offsets".into(), &offsets)?;
+    let times = js_sys::Float32Array::from(frames.times.as_slice());
+    js_sys::Reflect::set(&result, &"times".into(), &times)?;
+    let positions = js_sys::Float32Array::from(frames.positions.as_slice());
+    js_sys::Reflect::set(&result, &"positions".into(), &positions)?;
+    let values = js_sys::Int32Array::from(frames.values.as_slice());
+    js_sys::Reflect::set(&result, &"values".into(), &values)?;
+    js_sys::Reflect::set(&result, &"duration".into(), &frames.duration.into())?;
+    Ok(result.into())
+}
+
+/// Layout of the implicit tree of a heap of `n` elements with the given
+/// arity, optionally with the tree nodes each event of a trace touches.
+/// Node ids are offsets from `root`, the array index of the heap's root
+/// (0 unless given).
+///
+/// # Returns
+/// `{ nodes, event_nodes }` where nodes is `{ id, parent, children, level,
+/// position }` per node and event_nodes is one id array per event, or null
+/// without events
+#[wasm_bindgen]
+pub fn heap_tree_layout(
+    n: usize,
+    arity: usize,
+    events: JsValue,
+    root: Option<usize>,
+) -> Result<JsValue, JsValue> {
+    let nodes = heap_tree::heap_layout(n, arity).map_err(|e| JsValue::from_str(&e))?;
+    let event_nodes = if events.is_undefined() || events.is_null() {
+        None
+    } else {
+        let events: Vec<SortEvent> = serde_wasm_bindgen::from_value(events)
+            .map_err(|e| JsValue::from_str(&e.to_string()))?;
+        Some(heap_tree::annotate_heap_events(&events, root.unwrap_or(0), n))
+    };
+
+    let result = js_sys::Object::new();
+    let nodes =
+        serde_wasm_bindgen::to_value(&nodes).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    js_sys::Reflect::set(&result, &"nodes".into(), &nodes)?;
+    let event_nodes = match event_nodes {
+        Some(ids) => {
+            serde_wasm_bindgen::to_value(&ids).map_err(|e| JsValue::from_str(&e.to_string()))?
+        }
+        None => JsValue::NULL,
+    };
+    js_sys::Reflect::set(&result, &"event_nodes".into(), &event_nodes)?;
+    Ok(result.into())
+}
+
+/// Splaysort statistics: the number of comparisons each insertion into the
+/// splay tree needed, in input order.
+///
+/// # Returns
+/// Uint32Array with one depth per element
+#[wasm_bindgen]
+pub fn splay_insertion_depths(array: JsValue) -> Result<js_sys::Uint32Array, JsValue> {
+    let arr: Vec<i32> = events::js_to_array(array)?;
+    let depths: Vec<u32> =
+        splay_sort::insertion_depths(&arr).into_iter().map(|d| d as u32).collect();
+    Ok(js_sys::Uint32Array::from(depths.as_slice()))
+}
+
+/// Describe the events `start..end` of a trace of `algorithm` on `array`
+/// in plain language, one line per event (to the end of the trace unless
+/// `end` is given).
+///
+/// # Returns
+/// Array of strings, e.g. "compare a[3]=7 with pivot a[9]=4; 7 > 4 so leave it"
+#[wasm_bindgen]
+pub fn narrate_trace(
+    algorithm: &str,
+    array: JsValue,
+    events: JsValue,
+    start: usize,
+    end: Option<usize>,
+) -> Result<JsValue, JsValue> {
+    let algo = Algorithm::from_str(algorithm)
+        .ok_or_else(|| JsValue::from_str(&format!("Unknown algorithm: {}", algorithm)))?;
+    let arr: Vec<i32> = events::js_to_array(array)?;
+    let events: Vec<SortEvent> =
+        serde_wasm_bindgen::from_value(events).map_err(|e| JsValue::from_str(&e.to_string()))?;
+
+    let end = end.unwrap_or(events.len());
+    let lines =
+        narrate::narrate(algo, &arr, &events, start..end).map_err(|e| JsValue::from_str(&e))?;
+    serde_wasm_bindgen::to_value(&lines).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Replace every Flip event with the Swaps that reverse its prefix, for
+/// renderers that don't understand Flip.
+#[wasm_bindgen]
+pub fn expand_flips(events: JsValue) -> Result<JsValue, JsValue> {
+    let events: Vec<SortEvent> =
+        serde_wasm_bindgen::from_value(events).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    events::events_to_js(&events::expand_flips(&events))
+}
+
+/// Race two algorithms on copies of the same array.
+///
+/// # Returns
+/// `{ algorithms, input, events }`, where every event carries a `lane`
+/// (0 or 1, the index into algorithms). Schedule is "proportional" (both
+/// lanes finish together) or "lockstep" (one event per lane in turn).
+#[wasm_bindgen]
+pub fn pregen_race(
+    algorithm_a: &str,
+    algorithm_b: &str,
+    array: JsValue,
+    schedule: &str,
+) -> Result<JsValue, JsValue> {
+    let parse = |name: &str| {
+        Algorithm::from_str(name)
+            .ok_or_else(|| JsValue::from_str(&format!("Unknown algorithm: {}", name)))
+    };
+    let algorithms = [parse(algorithm_a)?, parse(algorithm_b)?];
+    let schedule = RaceSchedule::from_str(schedule)
+        .ok_or_else(|| JsValue::from_str(&format!("Unknown race schedule: {}", schedule)))?;
+
+    let arr: Vec<i32> = events::js_to_array(array)?;
+    let race = race::ghost_race(algorithms, &arr, schedule);
+
+    // Lane events are flattened through a serde map; emit plain objects, not Maps
+    let serializer = serde_wasm_bindgen::Serializer::new().serialize_maps_as_objects(true);
+    serde::Serialize::serialize(&race, &serializer).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Run an algorithm in both the pregen and live engines and compare the
+/// final arrays and event counts per type.
+///
+/// # Returns
+/// `{ algorithm, pregen_counts, live_counts, mismatches }`; mismatches is
+/// empty when the engines agree
+#[wasm_bindgen]
+pub fn check_engine_parity(algorithm: &str, array: JsValue) -> Result<JsValue, JsValue> {
+    let arr: Vec<i32> = events::js_to_array(array)?;
+    let report = parity::check_engine_parity(algorithm, &arr).map_err(|e| JsValue::from_str(&e))?;
+    serde_wasm_bindgen::to_value(&report).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Run an algorithm on generated inputs of every pattern and test size,
+/// once per seed, and check each trace.
+///
+/// # Arguments
+/// * `seeds` - Array of seeds for the generated inputs
+///
+/// # Returns
+/// `{ algorithm, cases, failures }`; each failure is
+/// `{ pattern, n, seed, check, message }`, none when every trace passed
+#[wasm_bindgen]
+pub fn verify_algorithm(algorithm: &str, seeds: JsValue) -> Result<JsValue, JsValue> {
+    let algo = Algorithm::from_str(algorithm)
+        .ok_or_else(|| JsValue::from_str(&format!("Unknown algorithm: {}", algorithm)))?;
+    let seeds: Vec<u64> =
+        serde_wasm_bindgen::from_value(seeds).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    let report = verify::verify_algorithm(algo, &seeds);
+    serde_wasm_bindgen::to_value(&report).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Run a tournament: every algorithm on every (pattern, size, seed) input,
+/// with wins for the fewest comparisons and writes (and, with `bench`, the
+/// shortest time).
+///
+/// # Arguments
+/// * `request` - `{ algorithms?, patterns?, sizes?, seeds?, bench? }`;
+///   no algorithms enters every algorithm
+///
+/// # Returns
+/// `{ rounds, standings }`; standings are best first
+#[wasm_bindgen]
+pub fn run_tournament(request: JsValue) -> Result<JsValue, JsValue> {
+    let request: TournamentRequest =
+        serde_wasm_bindgen::from_value(request).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    let tournament = request.run().map_err(|e| JsValue::from_str(&e))?;
+    serde_wasm_bindgen::to_value(&tournament).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Run one algorithm on many seeds of a pattern and report the spread of
+/// its counts.
+///
+/// # Arguments
+/// * `request` - `{ algorithm, pattern?, n?, seeds?, first_seed? }`
+///
+/// # Returns
+/// `{ algorithm, pattern, n, runs, comparisons, swaps, writes }`; each count
+/// is `{ min, median, p95, max, mean }`
+#[wasm_bindgen]
+pub fn run_seed_sweep(request: JsValue) -> Result<JsValue, JsValue> {
+    let request: SeedSweepRequest =
+        serde_wasm_bindgen::from_value(request).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    let sweep = request.run().map_err(|e| JsValue::from_str(&e))?;
+    serde_wasm_bindgen::to_value(&sweep).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Run many pregeneration sorts in one call.
+///
+/// # Arguments
+/// * `requests` - Array of `{ algorithm, array }` or
+///   `{ algorithm, pattern, n, seed? }`, each with optional
+///   `options: { events?: boolean, sweep?: boolean, coalesce?: boolean,
+///   precheck?: boolean, expand_flips?: boolean }`
+///
+/// # Returns
+/// One `{ algorithm, input, sorted_array, events, counts, main_writes,
+/// outcome }` per request, in order; events is null when the request's
+/// `events` option is false, outcome unless its `precheck` option is true
+#[wasm_bindgen]
+pub fn pregen_sort_batch(requests: JsValue) -> Result<JsValue, JsValue> {
+    let requests: Vec<BatchRequest> =
+        serde_wasm_bindgen::from_value(requests).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    let results = batch::run_requests(&requests).map_err(|e| JsValue::from_str(&e))?;
+
+    let output = js_sys::Array::new();
+    for result in &results {
+        output.push(&batch_result_to_js(result)?);
+    }
+
+    Ok(output.into())
+}
+
+/// One `pregen_sort_batch` result object. Built field by field so large
+/// traces take the events JSON fast path.
+fn batch_result_to_js(result: &BatchResult) -> Result<JsValue, JsValue> {
+    fn to_js<T: serde::Serialize + ?Sized>(value: &T) -> Result<JsValue, JsValue> {
+        serde_wasm_bindgen::to_value(value).map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    let events = match &result.events {
+        Some(events) => events::events_to_js(events)?,
+        None => JsValue::NULL,
+    };
+
+    let object = js_sys::Object::new();
+    js_sys::Reflect::set(&object, &"algorithm".into(), &result.algorithm.into())?;
+    js_sys::Reflect::set(&object, &"input".into(), &to_js(&result.input)?)?;
+    js_sys::Reflect::set(&object, &"sorted_array".into(), &to_js(&result.sorted_array)?)?;
+    js_sys::Reflect::set(&object, &"events".into(), &events)?;
+    js_sys::Reflect::set(&object, &"counts".into(), &to_js(&result.counts)?)?;
+    js_sys::Reflect::set(&object, &"main_writes".into(), &result.main_writes.into())?;
+    js_sys::Reflect::set(&object, &"outcome".into(), &to_js(&result.outcome)?)?;
+    Ok(object.into())
+}
+
+/// Export a run as a shareable session blob.
+///
+/// # Arguments
+/// * `request` - A `pregen_sort_batch` request: `{ algorithm, array }` or
+///   `{ algorithm, pattern, n, seed? }`, with optional `options`
+/// * `include_trace` - Run the request and store its events, so an import
+///   can check that the replay is identical
+///
+/// # Returns
+/// The session as a JSON string
+#[wasm_bindgen]
+pub fn export_session(request: JsValue, include_trace: bool) -> Result<String, JsValue> {
+    let request: BatchRequest =
+        serde_wasm_bindgen::from_value(request).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    let bundle = SessionBundle::export(request, include_trace).map_err(|e| JsValue::from_str(&e))?;
+
+    let value =
+        serde_wasm_bindgen::to_value(&bundle).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    js_sys::JSON::stringify(&value).map(String::from)
+}
+
+/// Import a session blob from `export_session` and replay it.
+///
+/// # Returns
+/// `{ request, crate_version, same_version, result, matches_trace,
+/// first_mismatch }`, where result is shaped like a `pregen_sort_batch`
+/// result; matches_trace and first_mismatch are null when the session
+/// carries no trace
+#[wasm_bindgen]
+pub fn import_session(blob: &str) -> Result<JsValue, JsValue> {
+    let value = js_sys::JSON::parse(blob)?;
+    let bundle: SessionBundle =
+        serde_wasm_bindgen::from_value(value).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    let replay = bundle.replay().map_err(|e| JsValue::from_str(&e))?;
+
+    let request = serde_wasm_bindgen::to_value(&bundle.request)
+        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+    let matches_trace = replay.matches_trace.map_or(JsValue::NULL, JsValue::from);
+    let first_mismatch = replay.first_mismatch.map_or(JsValue::NULL, JsValue::from);
+
+    let object = js_sys::Object::new();
+    js_sys::Reflect::set(&object, &"request".into(), &request)?;
+    js_sys::Reflect::set(&object, &"crate_version".into(), &bundle.crate_version.into())?;
+    js_sys::Reflect::set(&object, &"same_version".into(), &replay.same_version.into())?;
+    js_sys::Reflect::set(&object, &"result".into(), &batch_result_to_js(&replay.result)?)?;
+    js_sys::Reflect::set(&object, &"matches_trace".into(), &matches_trace)?;
+    js_sys::Reflect::set(&object, &"first_mismatch".into(), &first_mismatch)?;
+    Ok(object.into())
+}
+
+/// Wasm-exposed pregeneration context that keeps its buffers between runs,
+/// so repeated sorts don't allocate and free a fresh event vector, or the
+/// aux arrays of merge, timsort and distribution sorts, each time.
+#[wasm_bindgen(js_name = PregenContext)]
+pub struct WasmPregenContext {
+    inner: PregenContext,
+    /// Staging for `sort_into`'s packed words, kept between runs.
+    words: Vec<i32>,
+}
+
+#[wasm_bindgen(js_class = PregenContext)]
+impl WasmPregenContext {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> WasmPregenContext {
+        WasmPregenContext {
+            inner: PregenContext::new(),
+            words: Vec::new(),
+        }
+    }
+
+    /// Run a pregeneration sort on a copy of the array, return its events.
+    pub fn sort(&mut self, algorithm: &str, array: JsValue) -> Result<JsValue, JsValue> {
+        let algo = Algorithm::from_str(algorithm)
+            .ok_or_else(|| JsValue::from_str(&format!("Unknown algorithm: {}", algorithm)))?;
+
+        let arr: Vec<i32> = events::js_to_array(array)?;
+        events::events_to_js(self.inner.run(algo, &arr))
+    }
+
+    /// Run a pregeneration sort on a copy of the array, writing the packed
+    /// events and the sorted array into caller-owned buffers like
+    /// `pregen_sort_into`. Returns `{ events, skipped }`.
+    pub fn sort_into(
+        &mut self,
+        algorithm: &str,
+        array: JsValue,
+        events: js_sys::Int32Array,
+        output: js_sys::Int32Array,
+    ) -> Result<JsValue, JsValue> {
+        let algo = Algorithm::from_str(algorithm)
+            .ok_or_else(|| JsValue::from_str(&format!("Unknown algorithm: {}", algorithm)))?;
+
+        let arr: Vec<i32> = events::js_to_array(array)?;
+        let trace = self.inner.run(algo, &arr);
+        self.words.clear();
+        let records = packed::pack_events_skipping(trace, &mut self.words);
+        let skipped = trace.len() - records;
+        write_packed(&self.words, skipped, self.inner.array(), &events, &output)
+    }
+
+    /// Get the sorted array of the last run.
+    pub fn get_array(&self) -> Result<JsValue, JsValue> {
+        serde_wasm_bindgen::to_value(self.inner.array())
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Bytes currently held by the retained buffers.
+    pub fn retained_bytes(&self) -> usize {
+        self.inner.retained_bytes() + self.words.capacity() * core::mem::size_of::<i32>()
+    }
+
+    /// Release all retained memory. The context stays usable.
+    pub fn shrink(&mut self) {
+        self.inner.shrink();
+        self.words = Vec::new();
+    }
+}
+
+impl Default for WasmPregenContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Check an input before running it.
+///
+/// # Arguments
+/// * `algorithm` - Algorithm name
+/// * `array` - Input values as JS numbers
+/// * `options` - Optional `{ engine?: "pregen" | "live", sweep?: boolean }`
+///
+/// # Returns
+/// Array of `{ severity, code, message, index? }`; empty when the input is
+/// safe to run. Fails only if `options` is malformed
+#[wasm_bindgen]
+pub fn validate_input(
+    algorithm: &str,
+    array: JsValue,
+    options: JsValue,
+) -> Result<JsValue, JsValue> {
+    let values: Vec<f64> =
+        serde_wasm_bindgen::from_value(array).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    let options: ValidateOptions = if options.is_undefined() || options.is_null() {
+        ValidateOptions::default()
+    } else {
+        serde_wasm_bindgen::from_value(options).map_err(|e| JsValue::from_str(&e.to_string()))?
+    };
+
+    let diagnostics = validate::validate_input(algorithm, &values, &options);
+    serde_wasm_bindgen::to_value(&diagnostics).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Suggest an engine for running an algorithm on n elements.
+///
+/// # Returns
+/// `{ engine, expected_events, expected_memory, live_available, reason }`;
+/// fails for unknown algorithms and sizes neither engine can run
+#[wasm_bindgen]
+pub fn recommend_engine(algorithm: &str, n: usize) -> Result<JsValue, JsValue> {
+    let recommendation = engine::recommend_engine(algorithm, n).map_err(|e| JsValue::from_str(&e))?;
+    serde_wasm_bindgen::to_value(&recommendation).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Run a pregeneration sort that stops after at most `max_events` events.
+///
+/// # Returns
+/// `{ events, truncated, continuation }`. For algorithms with a live
+/// implementation, a truncated run's continuation resumes right after the
+/// last event via `LiveStepper.from_continuation`; it is null otherwise
+#[wasm_bindgen]
+pub fn pregen_sort_capped(
+    algorithm: &str,
+    array: JsValue,
+    max_events: usize,
+) -> Result<JsValue, JsValue> {
+    let algo = Algorithm::from_str(algorithm)
+        .ok_or_else(|| JsValue::from_str(&format!("Unknown algorithm: {}", algorithm)))?;
+
+    let arr: Vec<i32> = events::js_to_array(array)?;
+    let trace = handoff::pregen_sort_capped(algo, &arr, max_events);
+
+    // Built field by field so the events can take the JSON fast path
+    let result = js_sys::Object::new();
+    js_sys::Reflect::set(&result, &"events".into(), &events::events_to_js(&trace.events)?)?;
+    js_sys::Reflect::set(&result, &"truncated".into(), &trace.truncated.into())?;
+    let continuation = match &trace.continuation {
+        Some(live) => {
+            serde_wasm_bindgen::to_value(live).map_err(|e| JsValue::from_str(&e.to_string()))?
+        }
+        None => JsValue::NULL,
+    };
+    js_sys::Reflect::set(&result, &"continuation".into(), &continuation)?;
+
+    Ok(result.into())
+}
+
+/// Sort by a JS comparator, `(a, b) => number` like `Array.prototype.sort`.
+///
+/// The comparator may be called at most `max_ops` times (default
+/// `comparator::DEFAULT_OP_BUDGET`), so an inconsistent one can't hang the
+/// instance. Algorithms: "merge", "gnome".
+///
+/// # Returns
+/// The events; throws `{ reason, events }` with the partial trace if the
+/// budget runs out or the comparator throws
+#[wasm_bindgen]
+pub fn pregen_sort_by(
+    algorithm: &str,
+    array: JsValue,
+    compare: &js_sys::Function,
+    max_ops: Option<usize>,
+) -> Result<JsValue, JsValue> {
+    let algo = ComparatorAlgorithm::from_str(algorithm)
+        .ok_or_else(|| JsValue::from_str(&format!("Unknown algorithm: {}", algorithm)))?;
+
+    let mut arr: Vec<i32> = events::js_to_array(array)?;
+    let cmp = |a: i32, b: i32| {
+        let result = compare
+            .call2(&JsValue::NULL, &a.into(), &b.into())
+            .map_err(|e| e.as_string().unwrap_or_else(|| format!("{:?}", e)))?;
+        // NaN and non-numbers compare equal, as in Array.prototype.sort
+        let value = result.as_f64().unwrap_or(0.0);
+        Ok(if value < 0.0 {
+            std::cmp::Ordering::Less
+        } else if value > 0.0 {
+            std::cmp::Ordering::Greater
+        } else {
+            std::cmp::Ordering::Equal
+        })
+    };
+
+    let budget = max_ops.unwrap_or(comparator::DEFAULT_OP_BUDGET);
+    match comparator::sort_by(algo, &mut arr, cmp, budget) {
+        Ok(events) => events::events_to_js(&events),
+        Err(aborted) => Err(serde_wasm_bindgen::to_value(&aborted)
+            .map_err(|e| JsValue::from_str(&e.to_string()))?),
+    }
+}
@@ -83,6 +83,7 @@ function App() {
     totalSteps: 0,
     speed: SPEED_DEFAULT,
     array: [],
+    gap: null,
   });
 
   // Loading state
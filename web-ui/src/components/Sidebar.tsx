@@ -242,10 +242,18 @@ export function Sidebar({
 }
 
 const SPECIAL_ALGORITHM_NAMES: Record<string, string> = {
+  gnome_optimized: "Gnome Sort (Optimized)",
   quicksort_ll: "Quicksort (LL)",
   quicksort_lr: "Quicksort (LR)",
+  quicksort_stable: "Quicksort (Stable)",
+  pdq: "Pattern-Defeating Quicksort",
+  merge_bottom_up: "Merge Sort (Bottom-Up)",
+  merge_in_place: "Merge Sort (In-Place)",
+  merge_natural: "Natural Merge Sort",
+  heap_bottom_up: "Heap Sort (Bottom-Up)",
   radix_lsd: "Radix LSD Sort",
   radix_msd: "Radix MSD Sort",
+  odd_even_merge: "Batcher Odd-Even Merge Sort",
 };
 
 /**
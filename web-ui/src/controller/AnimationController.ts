@@ -25,6 +25,8 @@ export interface ControllerState {
   totalSteps: number;
   speed: number;
   array: number[];
+  /** Active gap of a gap-based sort (GapChange events), null if none */
+  gap: number | null;
 }
 
 type StateListener = (state: ControllerState) => void;
@@ -58,6 +60,8 @@ export class AnimationController {
   private highlights: Highlight[] = [];
   private activeRange: { lo: number; hi: number } | null = null;
   private rangeStack: { lo: number; hi: number }[] = [];
+  private cursors: Map<string, number> = new Map();
+  private gap: number | null = null;
   private isSorted = false;
 
   // Listeners
@@ -284,6 +288,7 @@ export class AnimationController {
       totalSteps: this.totalSteps,
       speed: this.speed,
       array: this.array,
+      gap: this.gap,
     };
   }
 
@@ -424,6 +429,30 @@ export class AnimationController {
         this.array[event.idx] = event.new_val;
         break;
       }
+      case "BlockMove": {
+        const block = this.array.splice(event.from, event.len);
+        this.array.splice(event.to, 0, ...block);
+        break;
+      }
+      case "Rotate": {
+        const moved = this.array.splice(event.lo, event.by);
+        this.array.splice(event.hi + 1 - event.by, 0, ...moved);
+        break;
+      }
+      case "Flip": {
+        for (let lo = 0, hi = event.k; lo < hi; lo++, hi--) {
+          const temp = this.array[lo];
+          this.array[lo] = this.array[hi];
+          this.array[hi] = temp;
+        }
+        break;
+      }
+      case "Snapshot": {
+        for (let k = 0; k < event.array.length; k++) {
+          this.array[k] = event.array[k];
+        }
+        break;
+      }
       case "EnterRange": {
         this.rangeStack.push({ lo: event.lo, hi: event.hi });
         this.activeRange = { lo: event.lo, hi: event.hi };
@@ -437,6 +466,18 @@ export class AnimationController {
             : null;
         break;
       }
+      case "SetCursor": {
+        if (event.index === null) {
+          this.cursors.delete(event.id);
+        } else {
+          this.cursors.set(event.id, event.index);
+        }
+        break;
+      }
+      case "GapChange": {
+        this.gap = event.gap > 0 ? event.gap : null;
+        break;
+      }
     }
   }
 
@@ -460,6 +501,50 @@ export class AnimationController {
           { kind: "writing", indices: [event.idx] },
         ];
         break;
+      case "BlockMove":
+        this.highlights = [
+          {
+            kind: "writing",
+            indices: Array.from({ length: event.len }, (_, k) => event.to + k),
+          },
+        ];
+        break;
+      case "Rotate":
+        this.highlights = [
+          {
+            kind: "writing",
+            indices: Array.from(
+              { length: event.hi - event.lo + 1 },
+              (_, k) => event.lo + k
+            ),
+          },
+        ];
+        break;
+      case "Flip":
+        this.highlights = [
+          {
+            kind: "writing",
+            indices: Array.from({ length: event.k + 1 }, (_, k) => k),
+          },
+        ];
+        break;
+      case "MarkSorted":
+        this.highlights = [{ kind: "emphasis", indices: [event.idx] }];
+        break;
+      case "Highlight":
+        this.highlights = [{ kind: "emphasis", indices: event.indices }];
+        break;
+      case "EqualRange":
+        this.highlights = [
+          {
+            kind: "equal",
+            indices: Array.from(
+              { length: event.hi - event.lo + 1 },
+              (_, k) => event.lo + k
+            ),
+          },
+        ];
+        break;
       case "Done":
         this.isSorted = true;
         break;
@@ -494,6 +579,7 @@ export class AnimationController {
       highlights: this.highlights,
       isSorted: this.isSorted,
       activeRange: this.activeRange,
+      cursors: [...this.cursors.values()],
     };
 
     this.renderer.render(state);
@@ -503,6 +589,8 @@ export class AnimationController {
     this.array = [...array];
     this.activeRange = null;
     this.rangeStack = [];
+    this.cursors.clear();
+    this.gap = null;
     this.isSorted = false;
     this.highlights = [];
   }
@@ -69,8 +69,15 @@ export class CanvasRenderer implements IRenderer {
     const ctx = this.ctx;
     const colors = this.colors;
 
-    const { array, activeRange, minValue, maxValue, isSorted, highlights } =
-      state;
+    const {
+      array,
+      activeRange,
+      minValue,
+      maxValue,
+      isSorted,
+      highlights,
+      cursors,
+    } = state;
     const width = this.width;
     const height = this.height;
 
@@ -141,6 +148,10 @@ export class CanvasRenderer implements IRenderer {
         comparing: colors.comparing,
         swapping: colors.swapping,
         writing: colors.writing,
+        // Highlight events have no palette entry of their own yet
+        emphasis: colors.writing,
+        // Equal-key ranges are flood-filled in the range color
+        equal: { ...colors.range, border: colors.range.fill },
       };
 
       for (const highlight of highlights) {
@@ -167,5 +178,16 @@ export class CanvasRenderer implements IRenderer {
       ctx.fillRect(lineX, lineY, lineWidth, ACTIVE_RANGE_LINE_HEIGHT);
       ctx.restore();
     }
+
+    // Cursors are ticks on the range line under their bar
+    if (!isSorted) {
+      const lineY = height - PADDING_BOTTOM - ACTIVE_RANGE_LINE_HEIGHT;
+      ctx.fillStyle = colors.comparing.fill;
+      for (const index of cursors) {
+        if (index < 0 || index >= array.length) continue;
+        const x = PADDING_LEFT + index * (barWidth + gap);
+        ctx.fillRect(x, lineY, barWidth, ACTIVE_RANGE_LINE_HEIGHT);
+      }
+    }
   }
 }
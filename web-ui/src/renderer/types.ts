@@ -3,7 +3,12 @@ import type { ThemeVizColors } from "@/themes/types";
 /**
  * Highlight types for overlay rendering.
  */
-export type HighlightKind = "comparing" | "swapping" | "writing";
+export type HighlightKind =
+  | "comparing"
+  | "swapping"
+  | "writing"
+  | "emphasis"
+  | "equal";
 
 export interface Highlight {
   kind: HighlightKind;
@@ -31,6 +36,9 @@ export interface RenderState {
 
   /** Currently active range (for highlighting subarrays), null if none */
   activeRange: { lo: number; hi: number } | null;
+
+  /** Indices of the algorithm's visible cursors (SetCursor events) */
+  cursors: number[];
 }
 
 /**
@@ -12,6 +12,9 @@ const EVENT_ENVELOPES: Record<string, EnvelopeParams> = {
   Compare: { attack: 0.005, decay: 0.05, sustain: 0, release: 0.01 },
   Swap: { attack: 0.01, decay: 0.08, sustain: 0, release: 0.02 },
   Overwrite: { attack: 0.02, decay: 0.06, sustain: 0, release: 0.02 },
+  BlockMove: { attack: 0.02, decay: 0.1, sustain: 0, release: 0.02 },
+  Rotate: { attack: 0.02, decay: 0.12, sustain: 0, release: 0.02 },
+  Flip: { attack: 0.02, decay: 0.12, sustain: 0, release: 0.02 },
 };
 
 /**
@@ -92,6 +95,15 @@ export class SoundEngine {
       case "Overwrite":
         this.playTone(event.new_val, envelope);
         break;
+      case "BlockMove":
+        this.playTone(array[event.from], envelope);
+        break;
+      case "Rotate":
+        this.playTone(array[event.lo + event.by], envelope);
+        break;
+      case "Flip":
+        this.playTone(array[0], envelope);
+        break;
     }
   }
 
@@ -37,6 +37,94 @@ export interface ExitRangeEvent {
   hi: number;
 }
 
+export interface PhaseEvent {
+  type: "Phase";
+  name: string;
+}
+
+export interface FoundEvent {
+  type: "Found";
+  idx: number;
+}
+
+/** The element at idx was verified to be in its final position */
+export interface MarkSortedEvent {
+  type: "MarkSorted";
+  idx: number;
+}
+
+/** Emphasis on some elements with no semantic meaning, e.g. a minimum candidate */
+export interface HighlightEvent {
+  type: "Highlight";
+  indices: number[];
+  tag: string;
+}
+
+/**
+ * The len elements starting at from now start at to; the elements in
+ * between shift over to fill the gap. Inverse moves them back.
+ */
+export interface BlockMoveEvent {
+  type: "BlockMove";
+  from: number;
+  to: number;
+  len: number;
+}
+
+/**
+ * The range lo..=hi was rotated left by `by`: array[lo + by] became its
+ * first element. Inverse rotates by the range length minus `by`.
+ */
+export interface RotateEvent {
+  type: "Rotate";
+  lo: number;
+  hi: number;
+  by: number;
+}
+
+/**
+ * A named pointer of the algorithm (e.g. "min") moved from prev to index;
+ * null means hidden. Inverse moves it back.
+ */
+export interface SetCursorEvent {
+  type: "SetCursor";
+  id: string;
+  index: number | null;
+  prev: number | null;
+}
+
+/** The range lo..=hi is known to hold equal keys, e.g. a three-way partition's equal block */
+export interface EqualRangeEvent {
+  type: "EqualRange";
+  lo: number;
+  hi: number;
+}
+
+/**
+ * The whole array at this point, a synchronization point. Applying it
+ * restores the array; on a correct replay it changes nothing.
+ */
+export interface SnapshotEvent {
+  type: "Snapshot";
+  array: number[];
+}
+
+/** The prefix 0..=k was reversed in one move (pancake sort's flip). Self-inverse */
+export interface FlipEvent {
+  type: "Flip";
+  k: number;
+}
+
+/**
+ * A gap-based sort (shell, comb) moved from gap prev to gap; 0 means no
+ * active gap. Inverse moves it back.
+ */
+export interface GapChangeEvent {
+  type: "GapChange";
+  gap: number;
+  prev: number;
+}
+
 export interface DoneEvent {
   type: "Done";
 }
@@ -48,17 +136,34 @@ export type SortEvent =
   | CompareEvent
   | EnterRangeEvent
   | ExitRangeEvent
+  | PhaseEvent
+  | FoundEvent
+  | MarkSortedEvent
+  | HighlightEvent
+  | BlockMoveEvent
+  | RotateEvent
+  | SetCursorEvent
+  | EqualRangeEvent
+  | SnapshotEvent
+  | FlipEvent
+  | GapChangeEvent
   | DoneEvent;
 
 /**
  * Returns the inverse of a sort event for rewinding.
- * Stateless events (Compare, Done) return themselves.
+ * Stateless events (Compare, Phase, Found, MarkSorted, Highlight, EqualRange, Done)
+ * return themselves. A Snapshot holds the array both before and after it, so
+ * it is its own inverse too.
  * EnterRange and ExitRange are inverses of each other.
+ * BlockMove is undone by moving the block back, Rotate by rotating the rest
+ * of the way around, SetCursor by moving the cursor back, GapChange by
+ * going back to the previous gap.
  */
 export function inverseEvent(event: SortEvent): SortEvent {
   switch (event.type) {
     case "Swap":
-      // Swap is self-inverse
+    case "Flip":
+      // Swap and Flip are self-inverse
       return event;
     case "Overwrite":
       // Swap old and new values
@@ -82,8 +187,38 @@ export function inverseEvent(event: SortEvent): SortEvent {
         lo: event.lo,
         hi: event.hi,
       };
+    case "BlockMove":
+      // Move the block back to where it came from
+      return {
+        type: "BlockMove",
+        from: event.to,
+        to: event.from,
+        len: event.len,
+      };
+    case "Rotate": {
+      // Rotate the rest of the way around
+      const len = event.hi - event.lo + 1;
+      return {
+        type: "Rotate",
+        lo: event.lo,
+        hi: event.hi,
+        by: (len - event.by) % len,
+      };
+    }
+    case "SetCursor":
+      // Move the cursor back to its previous index
+      return {
+        type: "SetCursor",
+        id: event.id,
+        index: event.prev,
+        prev: event.index,
+      };
+    case "GapChange":
+      // Go back to the previous gap
+      return { type: "GapChange", gap: event.prev, prev: event.gap };
     default:
-      // Stateless events (Compare, Done) are their own inverse
+      // Stateless events (Compare, Phase, Found, MarkSorted, Highlight, EqualRange,
+      // Snapshot, Done) are their own inverse
       return event;
   }
 }
@@ -92,5 +227,11 @@ export function inverseEvent(event: SortEvent): SortEvent {
  * Returns true if the event mutates the array.
  */
 export function isMutationEvent(event: SortEvent): boolean {
-  return event.type === "Swap" || event.type === "Overwrite";
+  return (
+    event.type === "Swap" ||
+    event.type === "Overwrite" ||
+    event.type === "BlockMove" ||
+    event.type === "Rotate" ||
+    event.type === "Flip"
+  );
 }
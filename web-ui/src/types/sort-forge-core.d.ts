@@ -8,12 +8,153 @@ declare module "sort-forge-core" {
   /** Get list of available algorithms */
   export function get_available_algorithms(): string[];
 
+  /** Every algorithm with all accepted names, canonical name first */
+  export function get_algorithm_aliases(): { name: string; aliases: string[] }[];
+
+  /** Canonical name for an algorithm name; strict accepts only canonical names */
+  export function canonical_algorithm_name(
+    name: string,
+    strict: boolean
+  ): string | undefined;
+
+  export type AlgorithmCategory =
+    | "exchange"
+    | "selection"
+    | "insertion"
+    | "merge"
+    | "distribution"
+    | "network"
+    | "hybrid"
+    | "esoteric";
+
+  /** Get the category of an algorithm; throws for unknown names */
+  export function get_algorithm_category(algorithm: string): AlgorithmCategory;
+
+  /** Get all algorithms grouped by category */
+  export function get_algorithms_by_category(): {
+    category: AlgorithmCategory;
+    algorithms: string[];
+  }[];
+
+  /** Complexity, stability and description of an algorithm */
+  export interface AlgorithmInfo {
+    display_name: string;
+    best: string;
+    average: string;
+    worst: string;
+    space: string;
+    stable: boolean;
+    in_place: boolean;
+    adaptive: boolean;
+    description: string;
+  }
+
+  /** Get metadata for an algorithm; throws for unknown names */
+  export function get_algorithm_info(algorithm: string): AlgorithmInfo;
+
   /** Initialize panic hook for better error messages */
   export function init(): void;
 
   /** Run a pregeneration sort on the given array */
   export function pregen_sort(algorithm: string, array: number[]): SortEvent[];
 
+  /** Run a pregeneration sort after a sortedness scan; sorted inputs end right after it */
+  export function pregen_sort_prechecked(
+    algorithm: string,
+    array: number[]
+  ): { events: SortEvent[]; outcome: PrecheckOutcome };
+
+  /**
+   * Sort the indices 0..n by value; values never move. Events refer to
+   * index-array positions and Overwrite values are indices. Ties keep index order
+   */
+  export function pregen_argsort(
+    algorithm: string,
+    array: number[]
+  ): { events: SortEvent[]; indices: number[] };
+
+  export type NanPolicy = "last" | "first" | "error";
+
+  /**
+   * Sort floats in total order (-0 before 0) with NaNs placed by the policy
+   * (default "last"; "error" rejects NaN inputs). Overwrite values are ranks
+   */
+  export function pregen_sort_f64(
+    algorithm: string,
+    array: number[] | Float64Array,
+    nan_policy?: NanPolicy
+  ): { events: SortEvent[]; sorted_array: Float64Array; values: Float64Array };
+
+  /** Run a pregeneration sort with Snapshot events after each Phase and/or every `every` events */
+  export function pregen_sort_with_snapshots(
+    algorithm: string,
+    array: number[],
+    at_phases: boolean,
+    every?: number
+  ): SortEvent[];
+
+  /** Run a pregeneration sort ending with a Compare/MarkSorted verification sweep */
+  export function pregen_sort_with_sweep(
+    algorithm: string,
+    array: number[]
+  ): SortEvent[];
+
+  /** Run a pregeneration sort with insertion shifts coalesced into BlockMove events */
+  export function pregen_sort_coalesced(
+    algorithm: string,
+    array: number[]
+  ): SortEvent[];
+
+  /**
+   * Run a pregeneration sort into caller-owned buffers: packed [code, a, b, c]
+   * records into events (events with no packed form are skipped) and the
+   * sorted array into output. Throws without writing if either is too small
+   */
+  export function pregen_sort_into(
+    algorithm: string,
+    array: number[],
+    events: Int32Array,
+    output: Int32Array
+  ): { events: number; skipped: number };
+
+  /** Sort only array[lo..hi); events use whole-array indices and the rest is untouched */
+  export function pregen_sort_range(
+    algorithm: string,
+    array: number[],
+    lo: number,
+    hi: number
+  ): SortEvent[];
+
+  /**
+   * A hybrid sort: split with quicksort_lr, quicksort_ll or merge until a
+   * range is at max_depth or has at most cutoff elements, then sort it with
+   * the finish algorithm. Omitted fields default to quicksort_ll, no depth
+   * limit, cutoff 16 and insertion. gaps ("shell", "knuth" or "ciura")
+   * only applies when finish is "shell"
+   */
+  export interface HybridConfig {
+    split?: 'quicksort_lr' | 'quicksort_ll' | 'merge';
+    max_depth?: number | null;
+    cutoff?: number;
+    finish?: string;
+    gaps?: 'shell' | 'knuth' | 'ciura' | null;
+  }
+
+  /** Run a hybrid sort; Phase events mark every switch between stages */
+  export function pregen_sort_hybrid(config: HybridConfig, array: number[]): SortEvent[];
+
+  /** A built-in sort key; modulus is required for "mod" and rejected otherwise */
+  export type KeyTransform =
+    | { transform: 'abs' | 'digit_sum' | 'bit_reverse' }
+    | { transform: 'mod'; modulus: number };
+
+  /** Run a pregeneration sort by key; equal keys keep their input order */
+  export function pregen_sort_by_key(
+    algorithm: string,
+    transform: KeyTransform,
+    array: number[]
+  ): SortEvent[];
+
   /** Run a pregeneration sort and return both events and sorted array */
   export function pregen_sort_with_result(
     algorithm: string,
@@ -23,6 +164,532 @@ declare module "sort-forge-core" {
     sorted_array: number[];
   };
 
+  /** Metadata describing how a trace was produced */
+  export interface TraceHeader {
+    format: "sort-forge-trace";
+    format_version: number;
+    crate_version: string;
+    algorithm: string;
+    element_type: "i32";
+    n: number;
+    /** Input pattern and seed; absent for explicit inputs */
+    pattern?: string;
+    seed?: number;
+    sweep: boolean;
+    counts: EventCounts;
+  }
+
+  /** Run a pregeneration sort and return a self-describing trace */
+  export function pregen_sort_trace(
+    algorithm: string,
+    array: number[],
+    sweep: boolean
+  ): {
+    header: TraceHeader;
+    input: number[];
+    events: SortEvent[];
+  };
+
+  export interface BatchOptions {
+    /** Return events (default true); false returns only counts */
+    events?: boolean;
+    /** Append a verification sweep (default false) */
+    sweep?: boolean;
+    /** Coalesce insertion shifts into BlockMove events (default false) */
+    coalesce?: boolean;
+    /** Scan for sortedness first and skip sorted inputs (default false) */
+    precheck?: boolean;
+    /** Replace Flip events with Swaps (default false) */
+    expand_flips?: boolean;
+  }
+
+  export type PrecheckOutcome = "already_sorted" | "sorted";
+
+  export type BatchRequest =
+    | { algorithm: string; array: number[]; options?: BatchOptions }
+    | {
+        algorithm: string;
+        pattern: string;
+        n: number;
+        seed?: number;
+        options?: BatchOptions;
+      };
+
+  /** Run many pregeneration sorts in one call; results are in request order */
+  export function pregen_sort_batch(requests: BatchRequest[]): {
+    algorithm: string;
+    input: number[];
+    sorted_array: number[];
+    events: SortEvent[] | null;
+    counts: EventCounts;
+    /** Element writes to the main array (a Swap is two) */
+    main_writes: number;
+    /** Null unless the precheck option is on */
+    outcome: PrecheckOutcome | null;
+  }[];
+
+  /** Export a run as a shareable JSON session blob, optionally with its trace */
+  export function export_session(request: BatchRequest, include_trace: boolean): string;
+
+  /** Import a session blob from export_session and replay it */
+  export function import_session(blob: string): {
+    request: BatchRequest;
+    crate_version: string;
+    /** Whether this build is the version that exported the session */
+    same_version: boolean;
+    result: ReturnType<typeof pregen_sort_batch>[number];
+    /** Null when the session carries no trace */
+    matches_trace: boolean | null;
+    /** Index of the first event differing from the stored trace */
+    first_mismatch: number | null;
+  };
+
+  /** Pregeneration context that keeps its buffers between runs */
+  export class PregenContext {
+    constructor();
+    sort(algorithm: string, array: number[]): SortEvent[];
+    /** Like pregen_sort_into, reusing the context's buffers */
+    sort_into(
+      algorithm: string,
+      array: number[],
+      events: Int32Array,
+      output: Int32Array
+    ): { events: number; skipped: number };
+    get_array(): number[];
+    retained_bytes(): number;
+    shrink(): void;
+    free(): void;
+  }
+
+  // String sorting exports exist only in builds with the `strings` feature
+
+  /** Get list of available string sorting algorithms */
+  export function get_available_string_algorithms(): string[];
+
+  /** Run a pregeneration string sort and return both events and sorted array */
+  export function pregen_sort_strings(
+    algorithm: string,
+    array: string[]
+  ): {
+    events: SortEvent[];
+    sorted_array: string[];
+  };
+
+  /** Get list of available selection algorithms */
+  export function get_available_select_algorithms(): string[];
+
+  /** Run a selection for the k-th smallest element, ending with a Found event */
+  export function pregen_select(
+    algorithm: string,
+    array: number[],
+    k: number
+  ): SortEvent[];
+
+  /** Sort only the k smallest elements into the first k positions */
+  export function pregen_partial_sort(array: number[], k: number): SortEvent[];
+
+  /** Get list of available search algorithms */
+  export function get_available_search_algorithms(): string[];
+
+  /** Search a sorted array, ending with a Found event if target is present */
+  export function pregen_search(
+    algorithm: string,
+    array: number[],
+    target: number
+  ): SortEvent[];
+
+  /** Get list of available rotation algorithms */
+  export function get_available_rotation_algorithms(): string[];
+
+  /** Rotate the array left by k */
+  export function pregen_rotate(
+    algorithm: string,
+    array: number[],
+    k: number
+  ): SortEvent[];
+
+  /**
+   * Make element i the old element permutation[i], swapping one cycle at a
+   * time (each highlighted with the "cycle" tag); throws for invalid permutations
+   */
+  export function pregen_permute(array: number[], permutation: number[]): SortEvent[];
+
+  /** Race two algorithms on the same input; each event carries its lane (0 or 1) */
+  export function pregen_race(
+    algorithm_a: string,
+    algorithm_b: string,
+    array: number[],
+    schedule: "proportional" | "lockstep"
+  ): {
+    algorithms: [string, string];
+    input: number[];
+    events: (SortEvent & { lane: 0 | 1 })[];
+  };
+
+  /** Number of events of each type in a trace */
+  export interface EventCounts {
+    swaps: number;
+    overwrites: number;
+    compares: number;
+    enter_ranges: number;
+    exit_ranges: number;
+    phases: number;
+    found: number;
+    marked_sorted: number;
+    highlights: number;
+    block_moves: number;
+    rotates: number;
+    cursors: number;
+    equal_ranges: number;
+    snapshots: number;
+    flips: number;
+    gap_changes: number;
+    done: number;
+  }
+
+  export type ParityMismatch =
+    | { kind: "FinalArray"; pregen: number[]; live: number[] }
+    | { kind: "EventCount"; event: string; pregen: number; live: number };
+
+  /** Run an algorithm in both engines and report where they disagree */
+  export function check_engine_parity(
+    algorithm: string,
+    array: number[]
+  ): {
+    algorithm: string;
+    pregen_counts: EventCounts;
+    live_counts: EventCounts;
+    mismatches: ParityMismatch[];
+  };
+
+  export type VerifyCheck = "sorted" | "replay" | "inverse" | "range_balance" | "done";
+
+  /** Check an algorithm's traces on generated inputs of every pattern and test size, per seed */
+  export function verify_algorithm(
+    algorithm: string,
+    seeds: number[]
+  ): {
+    algorithm: string;
+    cases: number;
+    /** Empty when every trace passed */
+    failures: {
+      pattern: string;
+      n: number;
+      seed: number;
+      check: VerifyCheck;
+      message: string;
+    }[];
+  };
+
+  /** Omitted fields default to every algorithm, random inputs of 100, seed 0 */
+  export interface TournamentRequest {
+    /** Empty or omitted enters every algorithm */
+    algorithms?: string[];
+    patterns?: string[];
+    sizes?: number[];
+    seeds?: number[];
+    /** Also time every run and award wins by wall-clock */
+    bench?: boolean;
+  }
+
+  export interface TournamentStanding {
+    /** Equal points share a rank */
+    rank: number;
+    algorithm: string;
+    points: number;
+    comparison_wins: number;
+    write_wins: number;
+    time_wins: number;
+    comparisons: number;
+    writes: number;
+    /** Bench mode only */
+    millis: number | null;
+  }
+
+  /** Every algorithm on every (pattern, size, seed) input; ties share a round's win */
+  export function run_tournament(request: TournamentRequest): {
+    rounds: {
+      pattern: string;
+      n: number;
+      seed: number;
+      entries: { algorithm: string; comparisons: number; writes: number; millis: number | null }[];
+    }[];
+    /** Best first */
+    standings: TournamentStanding[];
+  };
+
+  /** Omitted fields default to random inputs of 100, 32 seeds from 0 */
+  export interface SeedSweepRequest {
+    algorithm: string;
+    pattern?: string;
+    n?: number;
+    /** Number of seeds, run as first_seed, first_seed + 1, ... */
+    seeds?: number;
+    first_seed?: number;
+  }
+
+  /** Nearest-rank percentiles, so each is a count some run made */
+  export interface CountDistribution {
+    min: number;
+    median: number;
+    p95: number;
+    max: number;
+    mean: number;
+  }
+
+  /** One algorithm on many seeds of a pattern, with the spread of its counts */
+  export function run_seed_sweep(request: SeedSweepRequest): {
+    algorithm: string;
+    pattern: string;
+    n: number;
+    /** In seed order */
+    runs: { seed: number; comparisons: number; swaps: number; writes: number }[];
+    comparisons: CountDistribution;
+    swaps: CountDistribution;
+    writes: CountDistribution;
+  };
+
+  /**
+   * Audio hints per event: [freq_a, freq_b, gain] triples, 0 when silent.
+   * Throws if an event is out of bounds for the array
+   */
+  export function pregen_audio_hints(
+    array: number[],
+    events: SortEvent[],
+    min_freq: number,
+    max_freq: number
+  ): Float32Array;
+
+  export type Engine = "pregen" | "live";
+
+  /** One problem found by validate_input */
+  export interface Diagnostic {
+    severity: "error" | "warning";
+    /** Stable identifier, e.g. "too_long" or "value_out_of_range" */
+    code: string;
+    message: string;
+    /** First offending array index, for value diagnostics */
+    index?: number;
+  }
+
+  /** Check an input before running it; empty when it is safe to run */
+  export function validate_input(
+    algorithm: string,
+    array: number[],
+    options?: { engine?: Engine; sweep?: boolean }
+  ): Diagnostic[];
+
+  export interface EngineRecommendation {
+    engine: Engine;
+    expected_events: number;
+    /** Estimated memory held by the recommended engine, in bytes */
+    expected_memory: number;
+    live_available: boolean;
+    reason: string;
+  }
+
+  /** Suggest pregen or live; throws for unknown algorithms and sizes no engine can run */
+  export function recommend_engine(algorithm: string, n: number): EngineRecommendation;
+
+  export interface ChunkTiming {
+    /** Index of the chunk's first event */
+    start: number;
+    len: number;
+    millis: number;
+    /** Last Phase name up to the end of the chunk */
+    phase: string | null;
+  }
+
+  export interface Profile {
+    engine: Engine;
+    chunk_size: number;
+    chunks: ChunkTiming[];
+    total_millis: number;
+  }
+
+  /**
+   * Sort while timing each chunk of chunk_size events. Pregen-only
+   * algorithms generate in one call and report a single chunk
+   */
+  export function profile_sort(
+    algorithm: string,
+    array: number[],
+    chunk_size: number
+  ): { events: SortEvent[]; profile: Profile };
+
+  /** Opaque saved state of a live sort */
+  export type LiveContinuation = { readonly __brand: "LiveContinuation" };
+
+  /** Run a pregeneration sort that stops after at most max_events events */
+  export function pregen_sort_capped(
+    algorithm: string,
+    array: number[],
+    max_events: number
+  ): {
+    events: SortEvent[];
+    truncated: boolean;
+    /** Resumes right after the last event; null if complete or no live implementation */
+    continuation: LiveContinuation | null;
+  };
+
+  /** Why a comparator-driven sort stopped early */
+  export type AbortReason =
+    | { kind: "BudgetExceeded"; budget: number }
+    | { kind: "ComparatorError"; message: string };
+
+  /**
+   * Sort by a comparator ("merge" or "gnome"), calling it at most max_ops
+   * times. Throws { reason: AbortReason, events } with the partial trace on abort
+   */
+  export function pregen_sort_by(
+    algorithm: string,
+    array: number[],
+    compare: (a: number, b: number) => number,
+    max_ops?: number
+  ): SortEvent[];
+
+  /** A comparator leaving the minimum on wire i and the maximum on wire j */
+  export interface NetworkComparator {
+    i: number;
+    j: number;
+    round: number;
+  }
+
+  // Network exports exist only in builds with the `networks` feature
+
+  /** Generate a comparator network ("bitonic", "odd_even_merge", "pairwise", "insertion") */
+  export function generate_network(
+    kind: string,
+    n: number
+  ): { n: number; comparators: NetworkComparator[] };
+
+  export function get_available_networks(): string[];
+
+  /** Run [i, j] comparators (minimum to i) on the array, round by round */
+  export function pregen_network(
+    array: number[],
+    comparators: [number, number][]
+  ): {
+    /** Each round starts with a Phase event named "round <r>" */
+    events: SortEvent[];
+    depth: number;
+    sorted: boolean;
+  };
+
+  /** Sort with a network algorithm, its comparators grouped into rounds */
+  export function pregen_sort_rounds(
+    algorithm: string,
+    array: number[]
+  ): { events: SortEvent[]; depth: number; sorted: boolean };
+
+  /** Algorithms pregen_sort_rounds accepts */
+  export function get_round_algorithms(): string[];
+
+  /** Check a network of [i, j] comparators (minimum to i) against all 0/1 inputs */
+  export function check_network_zero_one(
+    n: number,
+    comparators: [number, number][]
+  ): {
+    sorts: boolean;
+    /** First 0/1 input left unsorted; null if the network sorts */
+    counterexample: { input: number[]; output: number[] } | null;
+  };
+
+  /** Summarize an array as [min, max, mean] per screen bucket */
+  export function summarize_array(array: number[], buckets: number): Float64Array;
+
+  /** Sample a frame's Compare/Highlight events down to budget; mutations are kept */
+  export function throttle_events(events: SortEvent[], budget: number): SortEvent[];
+
+  /** Plain-language line per event of trace[start..end] (end defaults to the trace length) */
+  export function narrate_trace(
+    algorithm: string,
+    array: number[],
+    events: SortEvent[],
+    start: number,
+    end?: number
+  ): string[];
+
+  /** Replace Flip events with the Swaps that reverse the same prefix */
+  export function expand_flips(events: SortEvent[]): SortEvent[];
+
+  /** Weight per event type for cost-driven playback */
+  export interface CostModel {
+    swap: number;
+    overwrite: number;
+    compare: number;
+    enter_range: number;
+    exit_range: number;
+    phase: number;
+    found: number;
+    mark_sorted: number;
+    highlight: number;
+    block_move: number;
+    rotate: number;
+    set_cursor: number;
+    equal_range: number;
+    snapshot: number;
+    flip: number;
+    gap_change: number;
+    done: number;
+  }
+
+  /** Running total of event costs; missing weights keep their defaults */
+  export function cumulative_costs(
+    events: SortEvent[],
+    model?: Partial<CostModel>
+  ): Float64Array;
+
+  /**
+   * Per-element animation keyframes timed by the cost model. Element e (the
+   * bar starting at index e) has keyframes offsets[e]..offsets[e + 1];
+   * interpolate linearly between consecutive ones
+   */
+  export function animation_keyframes(
+    array: number[],
+    events: SortEvent[],
+    model?: Partial<CostModel>
+  ): {
+    offsets: Uint32Array;
+    times: Float32Array;
+    positions: Float32Array;
+    values: Int32Array;
+    /** End time of the last event, in cost units */
+    duration: number;
+  };
+
+  export interface HeapNode {
+    id: number;
+    parent: number | null;
+    children: number[];
+    level: number;
+    /** Index within the level, counting the slots of a full tree */
+    position: number;
+  }
+
+  /**
+   * Implicit tree of an n-element heap; with events, also the node ids each
+   * event touches. Ids are offsets from root (default 0)
+   */
+  export function heap_tree_layout(
+    n: number,
+    arity: number,
+    events?: SortEvent[],
+    root?: number
+  ): { nodes: HeapNode[]; event_nodes: number[][] | null };
+
+  /** Comparisons each splaysort insertion needed, in input order */
+  export function splay_insertion_depths(array: number[]): Uint32Array;
+
+  /**
+   * Build the rayon pool of a wasm-threads build; then start one worker per
+   * thread calling start_pool_worker. Missing from builds without threads
+   */
+  export function init_thread_pool(num_threads: number): void;
+
+  /** Run one pool thread in the calling worker; blocks for the life of the pool */
+  export function start_pool_worker(): void;
+
   /** Initialize the wasm module */
   export default function init(): Promise<void>;
 
@@ -30,10 +697,95 @@ declare module "sort-forge-core" {
   export class LiveStepper {
     constructor(algorithm: string, array: number[]);
     step(limit: number): SortEvent[];
+    /** Step, then sample Compare/Highlight events down to budget; mutations are kept */
+    step_throttled(limit: number, budget: number): SortEvent[];
+    /** Enable step_auto with batches sized to take about budget_ms each; again to change it */
+    enable_auto_batch(budget_ms: number): void;
+    /** Step a batch of the tuned size; its measured time tunes the next one */
+    step_auto(): SortEvent[];
+    /** Batch size of the next step_auto; undefined until auto batching is enabled */
+    auto_batch_size(): number | undefined;
+    /** Smoothed events per millisecond measured by step_auto */
+    events_per_ms(): number | undefined;
+    /** Register a buffer that step_into_ring writes packed [code, a, b, c] records into */
+    set_ring_buffer(buffer: Int32Array): void;
+    /** Record slot where the next step_into_ring batch starts */
+    ring_head(): number | undefined;
+    /** Step and write the events into the ring; returns how many were written */
+    step_into_ring(limit: number): number;
+    /** Resume a sort from a pregen_sort_capped continuation */
+    static from_continuation(continuation: LiveContinuation): LiveStepper;
+    /** End with a verification sweep; call before the sort finishes */
+    finish_with_sweep(): void;
     is_done(): boolean;
+    /** Gap of the pass in progress for gap-based algorithms (comb) */
+    current_gap(): number | undefined;
     get_array(): number[];
+    /** [min, max, mean] per screen bucket of the current array */
+    get_summary(buckets: number): Float64Array;
+    free(): void;
+  }
+
+  /** Many live sorts with contiguous storage, e.g. a grid of small visualizations */
+  export class StepperArena {
+    constructor();
+    /** Add a live sort of a copy of array; returns its cell index */
+    push(algorithm: string, array: number[]): number;
+    len(): number;
+    is_empty(): boolean;
+    step(cell: number, limit: number): SortEvent[];
+    /** One events array per cell; finished cells get [] */
+    step_all(limit: number): SortEvent[][];
+    is_done(cell: number): boolean;
+    all_done(): boolean;
+    get_array(cell: number): Int32Array;
+    /** [min, max, mean] per screen bucket of one cell's array */
+    get_summary(cell: number, buckets: number): Float64Array;
+    free(): void;
+  }
+
+  export type Scale = "linear" | "rank";
+
+  /** Maps values to bar heights in 0..1 after scanning the input once */
+  export class ValueNormalizer {
+    constructor(array: Float64Array | number[], scale?: Scale);
+    /** Smallest and largest finite input values */
+    min(): number;
+    max(): number;
+    /** Height of one value, e.g. the new value of an Overwrite */
+    height(value: number): number;
+    heights(array: Float64Array | number[]): Float32Array;
+    free(): void;
+  }
+
+  /** Dense rank of each value; equal values share a rank */
+  export function value_ranks(array: Float64Array | number[]): Uint32Array;
+
+  /** Pregenerated trace kept in wasm memory, read in batches */
+  export class TraceCursor {
+    constructor(algorithm: string, array: number[]);
+    /** Next n events; fewer at the end of the trace */
+    next_batch(n: number): SortEvent[];
+    seek(position: number): void;
+    position(): number;
+    len(): number;
+    is_empty(): boolean;
+    is_done(): boolean;
+    /** Event at index without moving the read position */
+    get_event(index: number): SortEvent | undefined;
     free(): void;
   }
 
   export function get_live_algorithms(): string[];
+
+  export class LivePriorityQueue {
+    constructor(array: number[]);
+    heapify(): SortEvent[];
+    push(value: number): SortEvent[];
+    pop(): SortEvent[];
+    len(): number;
+    is_empty(): boolean;
+    get_array(): number[];
+    free(): void;
+  }
 }